@@ -7,6 +7,7 @@ use crate::cli::commands::buckle_mode::BuckleModeState;
 use crate::common::check_rotd_initialized;
 use crate::fs_ops::*;
 use crate::github;
+use crate::profile::Profile;
 use crate::pss;
 use crate::schema::*;
 
@@ -37,11 +38,18 @@ pub fn fix_common_json_errors(line: &str) -> String {
     fixed
 }
 
-pub fn init(force: bool, dry_run: bool) -> Result<()> {
+pub fn init(force: bool, git: bool, profile: Option<&Profile>, dry_run: bool) -> Result<()> {
+    let profile_name = profile.map(|p| p.name);
     if dry_run {
         println!(
-            "{{\"action\":\"init\",\"force\":{},\"dry_run\":true}}",
-            force
+            "{}",
+            json!({
+                "action": "init",
+                "force": force,
+                "git": git,
+                "profile": profile_name,
+                "dry_run": true,
+            })
         );
         return Ok(());
     }
@@ -60,6 +68,12 @@ pub fn init(force: bool, dry_run: bool) -> Result<()> {
 
     // Create directory structure
     std::fs::create_dir_all(&rotd_dir)?;
+    if git {
+        // Path helpers switch to the split layout once shared/ exists
+        std::fs::create_dir_all(rotd_dir.join(crate::common::SHARED_DIR))?;
+        std::fs::create_dir_all(rotd_dir.join(crate::common::LOCAL_DIR))?;
+        crate::git::write_rotd_gitignore()?;
+    }
     std::fs::create_dir_all(crate::common::test_summaries_path())?;
     std::fs::create_dir_all(crate::common::task_history_path())?;
 
@@ -79,6 +93,10 @@ pub fn init(force: bool, dry_run: bool) -> Result<()> {
         created: Some(Utc::now()),
         updated_at: Some(Utc::now()),
         completed: Some(Utc::now()),
+        pr_url: None,
+        assignee: None,
+        tags: Vec::new(),
+        schema: TASK_SCHEMA.to_string(),
     };
 
     append_jsonl(&crate::common::tasks_path(), &initial_task)?;
@@ -89,6 +107,7 @@ pub fn init(force: bool, dry_run: bool) -> Result<()> {
         current_task: Some("init".to_string()),
         status: "initialized".to_string(),
         deltas: None,
+        schema: SESSION_STATE_SCHEMA.to_string(),
     };
 
     write_json(&crate::common::session_state_path(), &session_state)?;
@@ -102,55 +121,177 @@ pub fn init(force: bool, dry_run: bool) -> Result<()> {
     write_json(&crate::common::coverage_history_path(), &coverage_history)?;
 
     // Create default config
-    let config = crate::schema::RotdConfig::default();
+    let mut config = crate::schema::RotdConfig::default();
+    if let Some(profile) = profile {
+        profile.apply(&mut config);
+    }
     crate::history::save_config(&config)?;
 
-    println!("{{\"status\":\"success\",\"action\":\"init\"}}");
+    println!(
+        "{}",
+        json!({
+            "status": "success",
+            "action": "init",
+            "layout": if git { "split" } else { "flat" },
+            "profile": profile_name,
+        })
+    );
     Ok(())
 }
 
-pub fn update_task(
-    file: Option<&str>,
-    strict: bool,
-    pss: bool,
-    timestamp: bool,
-    dry_run: bool,
-) -> Result<()> {
-    check_rotd_initialized()?;
+/// Flags for `rotd agent update-task`
+#[derive(Debug)]
+pub struct UpdateTaskOptions {
+    /// Enforce strict schema validation
+    pub strict: bool,
+    /// Reject fields the task schema does not define
+    pub strict_parse: bool,
+    /// Score the task after the update
+    pub pss: bool,
+    /// Stamp `updated_at`
+    pub timestamp: bool,
+    /// Note recorded with the history event
+    pub comment: Option<String>,
+    /// Apply a status change the transition rules reject
+    pub allow_transition: bool,
+    /// Treat the input as a merge patch or JSON Patch for this task
+    pub patch: Option<String>,
+}
 
-    let json_input = match file {
-        Some(f) => std::fs::read_to_string(f)?,
-        None => read_stdin()?,
-    };
+/// An agent task update checked against the latest entries, ready to append
+struct ResolvedUpdate<'a> {
+    prev: Option<&'a TaskEntry>,
+    task: TaskEntry,
+    /// The status change breaks the transition rules and was allowed anyway
+    forced: bool,
+}
 
-    let mut task: TaskEntry = serde_json::from_str(&json_input)
+/// Turn one input entry, or a patch with `--patch`, into the task to append.
+/// Errors carry a JSON body for the agent.
+fn resolve_update<'a>(
+    value: Value,
+    latest_tasks: &'a [TaskEntry],
+    options: &UpdateTaskOptions,
+) -> Result<ResolvedUpdate<'a>> {
+    let (latest, mut task) = if let Some(task_id) = &options.patch {
+        let prev = latest_tasks.iter().find(|t| &t.id == task_id).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{}",
+                json!({"error": "not_found", "message": format!("Task {} not found", task_id)})
+            )
+        })?;
+        let task = crate::task::apply_patch(prev, &value).map_err(|e| {
+            anyhow::anyhow!(
+                "{}",
+                json!({"error": "patch_failed", "message": e.to_string()})
+            )
+        })?;
+        if options.strict_parse {
+            crate::json_schema::deny_unknown_fields("tasks", &serde_json::to_value(&task)?)?;
+        }
+        (Some(prev), task)
+    } else {
+        if options.strict_parse {
+            crate::json_schema::deny_unknown_fields("tasks", &value)?;
+        }
+        // Fields sent for an existing task are applied over its latest entry,
+        // so `{"id":"6.2","status":"complete"}` is enough to change one field
+        let latest = value
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| latest_tasks.iter().find(|t| t.id == id));
+        let task: TaskEntry = match latest {
+            Some(prev) => crate::task::apply_fields(prev, &value),
+            None => serde_json::from_value(value).map_err(anyhow::Error::from),
+        }
         .map_err(|e| anyhow::anyhow!("{{\"error\":\"invalid_json\",\"message\":\"{}\"}}", e))?;
+        (latest, task)
+    };
 
-    if strict {
+    if options.strict {
         task.validate().map_err(|e| {
             anyhow::anyhow!("{{\"error\":\"validation_failed\",\"message\":\"{}\"}}", e)
         })?;
     }
 
-    if timestamp {
+    check_schema_versions(std::slice::from_ref(&task), "input", options.strict).map_err(|e| {
+        anyhow::anyhow!("{{\"error\":\"schema_version\",\"message\":\"{}\"}}", e)
+    })?;
+
+    if options.timestamp {
         task.update_timestamp();
     }
 
-    safe_update_task(&task, dry_run)?;
+    let forced = match crate::task::check_transition(latest, &task) {
+        Ok(()) => false,
+        Err(_) if options.allow_transition => true,
+        Err(e) => {
+            return Err(anyhow::anyhow!(
+                "{}",
+                serde_json::json!({
+                    "error": "invalid_transition",
+                    "message": e.to_string(),
+                    "hint": "pass --allow-transition to override",
+                })
+            ))
+        }
+    };
 
-    if !dry_run {
-        audit::log_info(
-            Some(&task.id),
-            "TASK_UPDATE",
-            &format!("Task {} updated via agent", task.id),
-        )?;
+    Ok(ResolvedUpdate {
+        prev: latest,
+        task,
+        forced,
+    })
+}
+
+pub fn update_task(file: Option<&str>, options: UpdateTaskOptions, dry_run: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let json_input = match file {
+        Some(f) => std::fs::read_to_string(f)?,
+        None => read_stdin()?,
+    };
+
+    let value: serde_json::Value = serde_json::from_str(&json_input)
+        .map_err(|e| anyhow::anyhow!("{{\"error\":\"invalid_json\",\"message\":\"{}\"}}", e))?;
+    let latest_tasks = latest_tasks()?;
+
+    // With --patch an array is a JSON Patch, not a batch of tasks
+    let value = match value {
+        Value::Array(items) if options.patch.is_none() => {
+            return update_tasks(items, &latest_tasks, &options, dry_run);
+        }
+        value => value,
+    };
+
+    let ResolvedUpdate { task, forced, .. } = resolve_update(value, &latest_tasks, &options)?;
+
+    let Some(prev_task) = append_task(&task, dry_run)? else {
+        return Ok(());
+    };
+    if forced {
+        if let Some(prev) = &prev_task {
+            crate::task::log_forced_transition(prev, &task)?;
+        }
     }
 
-    if pss && !dry_run {
+    audit::log_info(
+        Some(&task.id),
+        "TASK_UPDATE",
+        &format!("Task {} updated via agent", task.id),
+    )?;
+
+    // Scored before the history event so it can carry the change
+    let mut pss_delta = None;
+    if options.pss {
+        let previous = pss::latest_score(&task.id)?;
         let score = pss::score_task(&task.id)?;
         pss::save_score(&score, false)?;
+        pss_delta = previous.map(|p| score.score as f64 - p.score as f64);
     }
 
+    crate::history::record_task_history(&task, prev_task.as_ref(), options.comment, pss_delta)?;
+
     if !dry_run {
         println!(
             "{{\"status\":\"success\",\"action\":\"update_task\",\"task_id\":\"{}\"}}",
@@ -161,12 +302,113 @@ pub fn update_task(
     Ok(())
 }
 
-pub fn append_summary(file: &str, dry_run: bool) -> Result<()> {
+/// Apply an array of task entries: every entry is checked before anything is
+/// written, valid ones are appended in a single write, and the result of each
+/// is reported. In strict mode one bad entry rejects the whole batch.
+fn update_tasks(
+    items: Vec<Value>,
+    latest_tasks: &[TaskEntry],
+    options: &UpdateTaskOptions,
+    dry_run: bool,
+) -> Result<()> {
+    let mut results = Vec::new();
+    let mut accepted: Vec<ResolvedUpdate> = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let task_id = item.get("id").and_then(|id| id.as_str()).map(str::to_string);
+        let outcome = resolve_update(item, latest_tasks, options).and_then(|update| {
+            update.task.validate().map_err(|e| {
+                anyhow::anyhow!("{}", json!({"error": "validation_failed", "message": e.to_string()}))
+            })?;
+            if accepted.iter().any(|u| u.task.id == update.task.id) {
+                return Err(anyhow::anyhow!(
+                    "{}",
+                    json!({
+                        "error": "duplicate_id",
+                        "message": format!("Task {} appears more than once", update.task.id),
+                    })
+                ));
+            }
+            Ok(update)
+        });
+        match outcome {
+            Ok(update) => {
+                results.push(json!({"index": index, "task_id": task_id, "status": "ok"}));
+                accepted.push(update);
+            }
+            Err(e) => {
+                // Errors are JSON bodies; keep their fields in the result
+                let error: Value = serde_json::from_str(&e.to_string())
+                    .unwrap_or_else(|_| json!({"message": e.to_string()}));
+                results.push(json!({
+                    "index": index,
+                    "task_id": task_id,
+                    "status": "error",
+                    "error": error,
+                }));
+            }
+        }
+    }
+
+    let failed = results.len() - accepted.len();
+    if options.strict && failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{}",
+            json!({
+                "error": "batch_rejected",
+                "message": format!("{} of {} entries failed; nothing was written", failed, results.len()),
+                "results": results,
+            })
+        ));
+    }
+
+    if !dry_run && !accepted.is_empty() {
+        let tasks: Vec<TaskEntry> = accepted.iter().map(|u| u.task.clone()).collect();
+        append_tasks(&tasks, options.comment.as_deref())?;
+        for update in &accepted {
+            if let (true, Some(prev)) = (update.forced, update.prev) {
+                crate::task::log_forced_transition(prev, &update.task)?;
+            }
+            audit::log_info(
+                Some(&update.task.id),
+                "TASK_UPDATE",
+                &format!("Task {} updated via agent batch", update.task.id),
+            )?;
+            if options.pss {
+                let score = pss::score_task(&update.task.id)?;
+                pss::save_score(&score, false)?;
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        json!({
+            "status": if dry_run { "dry_run" } else { "success" },
+            "action": "update_tasks",
+            "updated": accepted.len(),
+            "failed": failed,
+            "results": results,
+        })
+    );
+
+    Ok(())
+}
+
+pub fn append_summary(file: Option<&str>, strict_parse: bool, dry_run: bool) -> Result<()> {
     check_rotd_initialized()?;
 
-    let summary: TestSummary = read_json(&std::path::Path::new(file))
+    let value: serde_json::Value = match file {
+        Some(f) => read_json(std::path::Path::new(f)),
+        None => read_stdin().and_then(|input| Ok(serde_json::from_str(&input)?)),
+    }
+    .map_err(|e| anyhow::anyhow!("{{\"error\":\"read_failed\",\"message\":\"{}\"}}", e))?;
+    if strict_parse {
+        crate::json_schema::deny_unknown_fields("test_summaries", &value)?;
+    }
+    let summary: TestSummary = serde_json::from_value(value)
         .map_err(|e| anyhow::anyhow!("{{\"error\":\"read_failed\",\"message\":\"{}\"}}", e))?;
 
+    check_schema_versions(std::slice::from_ref(&summary), file.unwrap_or("input"), false)?;
     safe_append_summary(&summary, dry_run)?;
 
     if !dry_run {
@@ -188,7 +430,7 @@ pub fn append_summary(file: &str, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn log_lesson(file: Option<&str>, dry_run: bool) -> Result<()> {
+pub fn log_lesson(file: Option<&str>, strict_parse: bool, dry_run: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     let json_input = match file {
@@ -196,13 +438,28 @@ pub fn log_lesson(file: Option<&str>, dry_run: bool) -> Result<()> {
         None => read_stdin()?,
     };
 
-    let mut lesson: LessonLearned = serde_json::from_str(&json_input)
+    let value: serde_json::Value = serde_json::from_str(&json_input)
+        .map_err(|e| anyhow::anyhow!("{{\"error\":\"invalid_json\",\"message\":\"{}\"}}", e))?;
+    if let Value::Array(items) = value {
+        return log_lessons(items, strict_parse, dry_run);
+    }
+    if strict_parse {
+        crate::json_schema::deny_unknown_fields("lessons", &value)?;
+    }
+    let mut lesson: LessonLearned = serde_json::from_value(value)
         .map_err(|e| anyhow::anyhow!("{{\"error\":\"invalid_json\",\"message\":\"{}\"}}", e))?;
 
     if lesson.timestamp.is_none() {
         lesson.timestamp = Some(Utc::now());
     }
+    if let Some(redactor) = crate::secrets::Redactor::load() {
+        redactor.redact_lesson(&mut lesson);
+    }
 
+    check_schema_versions(std::slice::from_ref(&lesson), "input", false)?;
+    // Read before appending so the new lesson doesn't match itself
+    let existing: Vec<LessonLearned> =
+        read_jsonl(&crate::common::lessons_path()).unwrap_or_default();
     safe_log_lesson(&lesson, dry_run)?;
 
     if !dry_run {
@@ -211,18 +468,139 @@ pub fn log_lesson(file: Option<&str>, dry_run: bool) -> Result<()> {
             "LESSON_LOGGED",
             &format!("Lesson logged: {}", lesson.id),
         )?;
+        let similar: Vec<serde_json::Value> = crate::lessons::similar(&lesson, &existing, 3, 0.2)
+            .into_iter()
+            .map(|(prior, score)| {
+                json!({
+                    "id": prior.id,
+                    "score": (score * 100.0).round() / 100.0,
+                    "diagnosis": prior.diagnosis,
+                    "remediation": prior.remediation,
+                    "tags": prior.tags,
+                })
+            })
+            .collect();
         println!(
-            "{{\"status\":\"success\",\"action\":\"log_lesson\",\"lesson_id\":\"{}\"}}",
-            lesson.id
+            "{}",
+            json!({
+                "status": "success",
+                "action": "log_lesson",
+                "lesson_id": lesson.id,
+                "redacted": lesson.redacted,
+                "similar": similar,
+            })
         );
     }
 
     Ok(())
 }
 
-pub fn ratchet_coverage(coverage: f64, task_id: Option<&str>, dry_run: bool) -> Result<()> {
+/// One lesson from a batch, timestamped `now` when it has no timestamp
+fn parse_lesson(value: Value, strict_parse: bool, now: chrono::DateTime<Utc>) -> Result<LessonLearned> {
+    if strict_parse {
+        let unknown = crate::json_schema::unknown_fields("lessons", &value);
+        if !unknown.is_empty() {
+            anyhow::bail!("Unknown field(s): {}", unknown.join(", "));
+        }
+    }
+    let mut lesson: LessonLearned = serde_json::from_value(value)?;
+    lesson.timestamp.get_or_insert(now);
+    lesson.validate()?;
+    Ok(lesson)
+}
+
+/// Log an array of lessons all-or-nothing: any invalid entry rejects the
+/// batch, listing the problem with each
+fn log_lessons(items: Vec<Value>, strict_parse: bool, dry_run: bool) -> Result<()> {
+    let now = Utc::now();
+    let mut lessons: Vec<LessonLearned> = Vec::new();
+    let mut errors = Vec::new();
+    for (index, value) in items.into_iter().enumerate() {
+        let lesson_id = value.get("id").and_then(|id| id.as_str()).map(str::to_string);
+        let parsed = parse_lesson(value, strict_parse, now).and_then(|lesson| {
+            if lessons.iter().any(|l| l.id == lesson.id) {
+                anyhow::bail!("Lesson {} appears more than once", lesson.id);
+            }
+            Ok(lesson)
+        });
+        match parsed {
+            Ok(lesson) => lessons.push(lesson),
+            Err(e) => errors.push(json!({
+                "index": index,
+                "lesson_id": lesson_id,
+                "message": e.to_string(),
+            })),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(anyhow::anyhow!(
+            "{}",
+            json!({
+                "error": "batch_rejected",
+                "message": format!("{} of {} lessons failed; nothing was written", errors.len(), errors.len() + lessons.len()),
+                "errors": errors,
+            })
+        ));
+    }
+
+    if let Some(redactor) = crate::secrets::Redactor::load() {
+        for lesson in &mut lessons {
+            redactor.redact_lesson(lesson);
+        }
+    }
+
+    check_schema_versions(&lessons, "input", false)?;
+    safe_log_lessons(&lessons, dry_run)?;
+
+    if !dry_run {
+        for lesson in &lessons {
+            audit::log_info(
+                None,
+                "LESSON_LOGGED",
+                &format!("Lesson logged: {}", lesson.id),
+            )?;
+        }
+        println!(
+            "{}",
+            json!({
+                "status": "success",
+                "action": "log_lessons",
+                "lesson_ids": lessons.iter().map(|l| &l.id).collect::<Vec<_>>(),
+                "redacted": lessons.iter().filter(|l| l.redacted).map(|l| &l.id).collect::<Vec<_>>(),
+            })
+        );
+    }
+
+    Ok(())
+}
+
+pub fn ratchet_coverage(
+    coverage: Option<f64>,
+    from_file: Option<&std::path::Path>,
+    run: bool,
+    task_id: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
+    let (coverage, tool) = match (coverage, from_file) {
+        (Some(coverage), _) => (coverage, None),
+        (None, Some(path)) => {
+            let reading = crate::coverage::read_report(path).map_err(|e| {
+                anyhow::anyhow!("{}", json!({"error": "coverage_report", "message": e.to_string()}))
+            })?;
+            (reading.percent, Some(reading.tool))
+        }
+        (None, None) if run => {
+            let reading = crate::coverage::run_configured().map_err(|e| {
+                anyhow::anyhow!("{}", json!({"error": "coverage_run", "message": e.to_string()}))
+            })?;
+            (reading.percent, Some(reading.tool))
+        }
+        (None, None) => anyhow::bail!("Pass a coverage percentage, --from-file, or --run"),
+    };
+
     let mut coverage_history: CoverageHistory = read_json(&crate::common::coverage_history_path())
         .unwrap_or_else(|_| CoverageHistory {
             floor: 70.0,
@@ -241,14 +619,31 @@ pub fn ratchet_coverage(coverage: f64, task_id: Option<&str>, dry_run: bool) ->
         coverage,
         timestamp: Utc::now(),
         triggered_ratchet,
+        tool: tool.clone(),
+        floor: Some(coverage_history.floor),
     };
 
     coverage_history.history.push(entry);
+    let config = crate::history::load_config().unwrap_or_default();
+    let pruned = crate::coverage::prune(
+        &mut coverage_history,
+        config.coverage_keep_entries,
+        config.coverage_keep_days,
+        Utc::now(),
+    );
 
     if dry_run {
         println!(
-            "{{\"action\":\"ratchet_coverage\",\"coverage\":{},\"triggered_ratchet\":{},\"new_floor\":{},\"dry_run\":true}}",
-            coverage, triggered_ratchet, coverage_history.floor
+            "{}",
+            json!({
+                "action": "ratchet_coverage",
+                "coverage": coverage,
+                "tool": tool,
+                "triggered_ratchet": triggered_ratchet,
+                "new_floor": coverage_history.floor,
+                "pruned": pruned,
+                "dry_run": true,
+            })
         );
         return Ok(());
     }
@@ -267,8 +662,16 @@ pub fn ratchet_coverage(coverage: f64, task_id: Option<&str>, dry_run: bool) ->
     }
 
     println!(
-        "{{\"status\":\"success\",\"action\":\"ratchet_coverage\",\"coverage\":{},\"triggered_ratchet\":{},\"new_floor\":{}}}",
-        coverage, triggered_ratchet, coverage_history.floor
+        "{}",
+        json!({
+            "status": "success",
+            "action": "ratchet_coverage",
+            "coverage": coverage,
+            "tool": tool,
+            "triggered_ratchet": triggered_ratchet,
+            "new_floor": coverage_history.floor,
+            "pruned": pruned,
+        })
     );
 
     Ok(())
@@ -296,190 +699,83 @@ pub fn score(task_id: &str, format: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn check(fix: bool) -> Result<()> {
+pub fn check(fix: bool, ci: Option<f64>, dry_run: bool) -> Result<()> {
     check_rotd_initialized()?;
 
-    let mut issues = Vec::new();
-    let mut score = 0;
-    let total_checks = 5;
-    let mut fixed = Vec::new();
-
-    // Check 1: Required files exist
-    let required_files = [
-        crate::common::tasks_path(),
-        crate::common::session_state_path(),
-        crate::common::coverage_history_path(),
-    ];
-
-    let files_exist = required_files.iter().all(|f| f.exists());
-    if files_exist {
-        score += 1;
-    } else {
-        issues.push("missing_required_files");
-    }
+    let run = crate::checks::run()?;
+    run.log_waivers()?;
 
-    // Check 2: JSONL files are valid
-    let jsonl_valid = read_jsonl::<TaskEntry>(&crate::common::tasks_path()).is_ok();
-    if jsonl_valid {
-        score += 1;
+    let score = run.passed();
+    let total_checks = run.results.len();
+    let issues: Vec<&str> = run.failed().map(|r| r.outcome.id).collect();
+
+    // Apply fixes if requested
+    let report = if fix && !issues.is_empty() {
+        crate::checks::fix(&issues, dry_run)?
     } else {
-        issues.push("invalid_jsonl");
-    }
+        crate::checks::FixReport::default()
+    };
+    let fixed: Vec<&str> = report.fixed.iter().map(|f| f.action).collect();
+
+    let health_percentage = run.health_percentage();
+    let verdict = ci.map(|threshold| run.ci_verdict(threshold));
 
-    // Check 3: Test summaries exist for completed tasks
-    let tasks: Vec<TaskEntry> = read_jsonl(&crate::common::tasks_path()).unwrap_or_default();
-    let completed_tasks: Vec<_> = tasks
+    let checks: Vec<_> = run
+        .results
         .iter()
-        .filter(|t| matches!(t.status, TaskStatus::Complete))
+        .map(|r| {
+            serde_json::json!({
+                "id": r.outcome.id,
+                "passed": r.outcome.passed(),
+                "severity": r.severity.as_str(),
+                "findings": r.outcome.findings.iter().map(|f| &f.message).collect::<Vec<_>>(),
+            })
+        })
         .collect();
-
-    let summaries_complete = completed_tasks
+    let waived: Vec<_> = run
+        .waived()
+        .map(|(check, w)| {
+            serde_json::json!({
+                "check": check,
+                "message": w.describe(),
+                "reason": w.reason,
+                "expires": w.expires.to_string(),
+            })
+        })
+        .collect();
+    let expired_waivers: Vec<_> = run
+        .expired_waivers
         .iter()
-        .all(|t| crate::common::test_summary_file(&t.id).exists());
-
-    if summaries_complete {
-        score += 1;
-    } else {
-        issues.push("missing_test_summaries");
-    }
-
-    // Check 4: No stubs remaining
-    let no_stubs = !pss::check_stubs_remaining();
-    if no_stubs {
-        score += 1;
-    } else {
-        issues.push("stubs_remaining");
-    }
-
-    // Check 5: Session state is valid JSON
-    let session_valid = read_json::<SessionState>(&crate::common::session_state_path()).is_ok();
-    if session_valid {
-        score += 1;
-    } else {
-        issues.push("invalid_session_state");
-    }
-
-    // Apply fixes if requested
-    if fix && !issues.is_empty() {
-        for issue in &issues {
-            match *issue {
-                "missing_required_files" => {
-                    // Create missing files
-                    for file_path in &required_files {
-                        if !file_path.exists() {
-                            match file_path.file_name().and_then(|f| f.to_str()) {
-                                Some("session_state.json") => {
-                                    let session_state = SessionState {
-                                        session_id: "fix".to_string(),
-                                        timestamp: chrono::Utc::now(),
-                                        current_task: None,
-                                        status: "initialized".to_string(),
-                                        deltas: None,
-                                    };
-                                    if write_json(file_path, &session_state).is_ok() {
-                                        fixed.push("created_session_state");
-                                    }
-                                }
-                                Some("coverage_history.json") => {
-                                    let coverage_history = CoverageHistory {
-                                        floor: 70.0,
-                                        ratchet_threshold: 3.0,
-                                        history: Vec::new(),
-                                    };
-                                    if write_json(file_path, &coverage_history).is_ok() {
-                                        fixed.push("created_coverage_history");
-                                    }
-                                }
-                                Some("tasks.jsonl") => {
-                                    // Create empty file
-                                    if std::fs::File::create(file_path).is_ok() {
-                                        fixed.push("created_tasks_file");
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-                "invalid_jsonl" => {
-                    // Attempt to fix invalid JSON in tasks.jsonl
-                    if let Ok(content) = std::fs::read_to_string(&crate::common::tasks_path()) {
-                        let mut fixed_lines = Vec::new();
-                        let mut has_errors = false;
-
-                        for (line_num, line) in content.lines().enumerate() {
-                            if line.trim().is_empty() {
-                                continue;
-                            }
-
-                            // Try to parse and re-serialize to fix formatting issues
-                            match serde_json::from_str::<serde_json::Value>(line) {
-                                Ok(value) => {
-                                    if let Ok(fixed_line) = serde_json::to_string(&value) {
-                                        fixed_lines.push(fixed_line);
-                                    } else {
-                                        has_errors = true;
-                                        fixed_lines.push(line.to_string());
-                                    }
-                                }
-                                Err(_) => {
-                                    // Try some basic fixes for common JSON errors
-                                    let mut fixed = fix_common_json_errors(line);
-                                    match serde_json::from_str::<serde_json::Value>(&fixed) {
-                                        Ok(value) => {
-                                            if let Ok(fixed_line) = serde_json::to_string(&value) {
-                                                fixed_lines.push(fixed_line);
-                                                fixed.push(
-                                                    format!("fixed_json_line_{}", line_num + 1)
-                                                        .chars()
-                                                        .next()
-                                                        .unwrap_or('_'),
-                                                );
-                                            } else {
-                                                has_errors = true;
-                                                fixed_lines.push(line.to_string());
-                                            }
-                                        }
-                                        Err(_) => {
-                                            has_errors = true;
-                                            fixed_lines.push(line.to_string());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        if !has_errors || fixed_lines.len() > 0 {
-                            // Create a backup first
-                            let backup_path = crate::common::rotd_path().join("tasks.jsonl.bak");
-                            if std::fs::copy(&crate::common::tasks_path(), &backup_path).is_ok() {
-                                // Write fixed content
-                                if std::fs::write(
-                                    &crate::common::tasks_path(),
-                                    fixed_lines.join("\n"),
-                                )
-                                .is_ok()
-                                {
-                                    fixed.push("fixed_jsonl_format");
-                                }
-                            }
-                        }
-                    }
-                }
-                _ => {
-                    // Other issues cannot be auto-fixed
-                }
-            }
-        }
-    }
+        .map(|w| serde_json::json!({"check": w.check, "expires": w.expires.to_string()}))
+        .collect();
 
-    let health_percentage = (score as f64 / total_checks as f64) * 100.0;
+    let workload = crate::task::workload(&crate::checks::readable_tasks());
 
     println!(
-        "{{\"passed\":{},\"total_checks\":{},\"issues\":{:?},\"fixed\":{:?},\"health_percentage\":{:.1}}}",
-        score, total_checks, issues, fixed, health_percentage
+        "{}",
+        serde_json::json!({
+            "passed": score,
+            "total_checks": total_checks,
+            "issues": issues,
+            "fixed": fixed,
+            "unfixed": report.unfixed,
+            "previews": report.previews,
+            "backup_dir": report.backup_dir.map(|d| d.display().to_string()),
+            "workload": workload,
+            "health_percentage": (health_percentage * 10.0).round() / 10.0,
+            "checks": checks,
+            "waived": waived,
+            "disabled": run.disabled,
+            "expired_waivers": expired_waivers,
+            "unknown_checks": run.unknown_checks,
+            "ci": verdict,
+        })
     );
 
+    if let Some(verdict) = verdict {
+        return verdict.into_result();
+    }
+
     Ok(())
 }
 
@@ -489,25 +785,29 @@ pub fn info() -> Result<()> {
             "version": "1.3.3",
             "agent_commands": {
                 "update_task": {
-                    "usage": "rotd agent update-task [--file FILE] [--strict] [--pss] [--timestamp]",
+                    "usage": "rotd agent update-task [--file FILE] [--strict] [--pss] [--timestamp] [--comment TEXT]",
                     "input": "JSON task entry via stdin or file",
                     "purpose": "Update task in tasks.jsonl with validation"
                 },
                 "append_summary": {
-                    "usage": "rotd agent append-summary --file FILE",
-                    "input": "Test summary JSON file",
+                    "usage": "rotd agent append-summary [--file FILE]",
+                    "input": "Test summary JSON via stdin or file",
                     "purpose": "Add test results to test_summaries/"
                 },
                 "log_lesson": {
                     "usage": "rotd agent log-lesson [--file FILE]",
                     "input": "Lesson learned JSON via stdin or file",
-                    "purpose": "Add lesson to lessons_learned.jsonl"
+                    "purpose": "Add lesson to lessons_learned.jsonl and list similar prior lessons"
                 },
                 "ratchet_coverage": {
-                    "usage": "rotd agent ratchet-coverage PERCENTAGE [--task-id ID]",
-                    "input": "Coverage percentage (float)",
+                    "usage": "rotd agent ratchet-coverage (PERCENTAGE | --from-file REPORT | --run) [--task-id ID]",
+                    "input": "Coverage percentage, a coverage report file, or the configured coverage_command",
                     "purpose": "Update coverage floor if threshold exceeded"
                 },
+                "resume": {
+                    "usage": "rotd agent resume [--history N]",
+                    "purpose": "Bundle session state, in-progress tasks, recent history, Buckle Mode state, primer summary, and unresolved criticals for a new session"
+                },
                 "info": {
                     "usage": "rotd agent info",
                     "purpose": "Show this command reference"
@@ -587,165 +887,159 @@ pub fn info() -> Result<()> {
     Ok(())
 }
 
-// Update-related agent functions
-pub fn update(check_only: bool, _skip_confirmation: bool) -> Result<()> {
+/// Print the context a fresh agent session needs to resume work: the
+/// current session, in-progress tasks, recent history, Buckle Mode state,
+/// the primer summary, and unresolved critical audit entries
+pub fn resume(history_limit: usize) -> Result<()> {
     check_rotd_initialized()?;
 
-    // Get current project version
-    let version_path = crate::common::rotd_path().join("version.json");
-    let current_version = if version_path.exists() {
-        let v: ProjectVersion = read_json(&version_path)?;
-        v.version
-    } else {
-        "1.3.3".to_string()
-    };
+    let tasks = latest_tasks()?;
+    let in_progress: Vec<&TaskEntry> = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TaskStatus::InProgress))
+        .collect();
 
-    // The latest methodology version available
-    let latest_methodology_version = "1.3.4";
-    
-    // Compare semantic versions
-    let needs_update = match (current_version.as_str(), latest_methodology_version) {
-        (current, latest) if current == latest => false,
-        (current, latest) => {
-            // Simple version comparison
-            let current_parts: Vec<u32> = current.trim_start_matches('v')
-                .split('.')
-                .filter_map(|s| s.parse().ok())
-                .collect();
-            let latest_parts: Vec<u32> = latest.trim_start_matches('v')
-                .split('.')
-                .filter_map(|s| s.parse().ok())
-                .collect();
-            
-            if current_parts.len() != 3 || latest_parts.len() != 3 {
-                true // Assume update needed if version format is unexpected
-            } else {
-                current_parts < latest_parts
-            }
+    let mut history = crate::history::read_all_history()?;
+    history.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+    history.truncate(history_limit);
+
+    let buckle_mode = std::fs::read_to_string(crate::common::buckle_state_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<BuckleModeState>(&content).ok())
+        .filter(|state| state.active);
+
+    let primer_path = crate::common::primer_path();
+    let primer = primer_path
+        .exists()
+        .then(|| crate::primer::load(&primer_path))
+        .transpose()?
+        .map(|primer| {
+            json!({
+                "name": primer.name,
+                "scope": primer.scope,
+                "status": primer.status,
+                "description": primer.description,
+                "known_issues": primer.known_issues,
+                "suggested_starting_points": primer.suggested_starting_points,
+            })
+        });
+
+    let criticals = audit::unresolved_criticals(audit::read_entries()?, &tasks);
+
+    let result = json!({
+        "status": "success",
+        "action": "resume",
+        "session": crate::session::current(),
+        "in_progress_tasks": in_progress,
+        "recent_history": history,
+        "buckle_mode": buckle_mode,
+        "primer": primer,
+        "unresolved_criticals": criticals,
+    });
+    println!("{}", serde_json::to_string(&result)?);
+    Ok(())
+}
+
+// Update-related agent functions
+pub fn update(
+    check_only: bool,
+    _skip_confirmation: bool,
+    force: bool,
+    skip_verify: bool,
+    channel: github::ReleaseChannel,
+    dry_run: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let check = match crate::methodology::check_for_update(channel) {
+        Ok(Some(check)) => check,
+        Ok(None) => {
+            let result = serde_json::json!({
+                "action": "check_updates",
+                "error": "No releases found on GitHub",
+                "update_available": false
+            });
+            println!("{}", serde_json::to_string(&result)?);
+            return Ok(());
+        }
+        Err(e) => {
+            let result = serde_json::json!({
+                "action": "check_updates",
+                "error": format!("Failed to fetch latest version: {}", e),
+                "latest_version": "unknown",
+                "update_available": false
+            });
+            println!("{}", serde_json::to_string(&result)?);
+            return Ok(());
         }
     };
 
+    let (breaking, non_breaking) = check.grouped_changes();
+    let changes = serde_json::json!({
+        "breaking": breaking,
+        "non_breaking": non_breaking
+    });
+
     if check_only {
         let result = serde_json::json!({
             "action": "check_updates",
-            "current_version": current_version,
-            "latest_version": latest_methodology_version,
-            "update_available": needs_update,
-            "update_type": "methodology"
+            "current_version": check.current_version,
+            "latest_version": check.latest_version,
+            "update_available": check.update_available,
+            "update_type": "methodology",
+            "changes": changes
         });
         println!("{}", serde_json::to_string(&result)?);
         return Ok(());
     }
 
     // Check if update is available
-    if !needs_update {
+    if !check.update_available && !force {
         let result = serde_json::json!({
             "status": "success",
             "action": "update",
             "message": "No updates available",
-            "current_version": current_version,
-            "latest_version": latest_methodology_version
+            "current_version": check.current_version,
+            "latest_version": check.latest_version
         });
         println!("{}", serde_json::to_string(&result)?);
         return Ok(());
     }
 
-    // Perform the update
-    let rotd_dir = crate::common::rotd_path();
-    
-    // Update version.json
-    let new_version = ProjectVersion {
-        version: latest_methodology_version.to_string(),
-        updated_at: Some(chrono::Utc::now()),
-        manifest_hash: None,
-    };
-    write_json(&version_path, &new_version)?;
-    
-    // Add primer strategy if missing
-    let primer_path = rotd_dir.join("primer.jsonc");
-    let primer_created = if !primer_path.exists() {
-        // Get project name from current directory
-        let current_dir = std::env::current_dir()?;
-        let project_name = current_dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Project")
-            .to_string();
-        
-        // Create basic primer structure
-        let primer = ProjectPrimer {
-            name: project_name.clone(),
-            scope: "root".to_string(),
-            description: "TODO: Add project description".to_string(),
-            status: "active".to_string(),
-            language: "TODO: Specify primary language".to_string(),
-            entry_points: vec!["TODO: Add entry points".to_string()],
-            test_dirs: vec!["tests/".to_string(), "test/".to_string()],
-            dependencies: vec!["TODO: List key dependencies".to_string()],
-            known_issues: vec!["TODO: Document any known issues".to_string()],
-            key_concepts: vec!["TODO: Add key concepts".to_string()],
-            preferred_agents: Some(vec!["Claude Sonnet".to_string(), "Claude Opus".to_string()]),
-            suggested_starting_points: vec![
-                "TODO: Add suggested starting points for new developers or agents".to_string()
-            ],
-            major_components: None,
-            update_triggers: Some(vec![
-                "Major architectural changes".to_string(),
-                "New features or significant functionality changes".to_string(),
-                "Documentation updates".to_string()
-            ]),
-        };
-        
-        // Write primer file with nice formatting
-        let primer_json = serde_json::to_string_pretty(&primer)?;
-        std::fs::write(&primer_path, primer_json)?;
-        true
-    } else {
-        false
-    };
-    
-    // Generate update manifest for tracking
-    let manifest = UpdateManifest {
-        version: latest_methodology_version.to_string(),
-        date: chrono::Utc::now().to_rfc3339(),
-        previous_version: current_version.clone(),
-        changes: vec![ChangeEntry {
-            change_type: "methodology_update".to_string(),
-            component: "rotd_project".to_string(),
-            description: format!("Updated ROTD methodology from {} to {}", current_version, latest_methodology_version),
-            breaking: false,
-            migration_required: false,
-        }],
-    };
-    
-    let manifest_path = rotd_dir.join("update_manifest.json");
-    write_json(&manifest_path, &manifest)?;
+    let outcome = crate::methodology::install(&check, force, skip_verify, dry_run)?;
+    let conflicts: Vec<&str> = outcome.conflicts().iter().map(|f| f.path.as_str()).collect();
 
-    let mut files_updated = vec!["version.json", "update_manifest.json"];
-    if primer_created {
-        files_updated.push("primer.jsonc");
-    }
-    
     let result = serde_json::json!({
-        "status": "success",
+        "status": if conflicts.is_empty() { "success" } else { "conflicts" },
         "action": "update",
-        "current_version": current_version,
-        "new_version": latest_methodology_version,
+        "dry_run": dry_run,
+        "current_version": outcome.previous_version,
+        "new_version": outcome.new_version,
         "update_type": "methodology",
-        "files_updated": files_updated,
-        "primer_created": primer_created
+        "bundle_sha256": outcome.bundle_sha256,
+        "checksum_verified": outcome.checksum_verified,
+        "signature_verified": outcome.signature_verified,
+        "files": outcome.files,
+        "conflicts": conflicts,
+        "changes": changes,
+        "primer_created": outcome.primer_created
     });
 
     println!("{}", serde_json::to_string(&result)?);
     Ok(())
 }
 
-pub fn upgrade(check_only: bool, _skip_confirmation: bool) -> Result<()> {
+pub fn upgrade(
+    check_only: bool,
+    _skip_confirmation: bool,
+    skip_verify: bool,
+    channel: github::ReleaseChannel,
+) -> Result<()> {
     // Get current binary version
     let current_version = env!("CARGO_PKG_VERSION");
 
     // Check for binary upgrades
-    let (upgrade_available, latest_release) = match github::check_update() {
+    let (upgrade_available, latest_release) = match github::check_update(channel) {
         Ok((available, release)) => (available, release),
         Err(e) => {
             let result = serde_json::json!({
@@ -824,8 +1118,8 @@ pub fn upgrade(check_only: bool, _skip_confirmation: bool) -> Result<()> {
     };
 
     // Download the binary
-    let binary_data = match github::download_binary(&asset.browser_download_url) {
-        Ok(data) => data,
+    let download = match github::download_binary(&latest, &asset, skip_verify) {
+        Ok(download) => download,
         Err(e) => {
             let result = serde_json::json!({
                 "status": "error",
@@ -839,37 +1133,12 @@ pub fn upgrade(check_only: bool, _skip_confirmation: bool) -> Result<()> {
         }
     };
 
-    // Create temporary file for new binary
-    let temp_path = current_exe.with_extension("new");
-    if let Err(e) = std::fs::write(&temp_path, binary_data) {
-        let result = serde_json::json!({
-            "status": "error",
-            "action": "upgrade",
-            "error": format!("Failed to write binary: {}", e),
-            "current_version": current_version,
-            "latest_version": latest.version
-        });
-        println!("{}", serde_json::to_string(&result)?);
-        return Ok(());
-    }
-
-    // Make it executable (Unix-like systems)
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        if let Ok(metadata) = std::fs::metadata(&temp_path) {
-            let mut perms = metadata.permissions();
-            perms.set_mode(0o755);
-            let _ = std::fs::set_permissions(&temp_path, perms);
-        }
-    }
-
     // Replace the current binary
-    if let Err(e) = std::fs::rename(&temp_path, &current_exe) {
+    if let Err(e) = crate::self_replace::replace_executable(&current_exe, &download.binary) {
         let result = serde_json::json!({
             "status": "error",
             "action": "upgrade",
-            "error": format!("Failed to install binary: {}", e),
+            "error": format!("Failed to install binary: {:#}", e),
             "current_version": current_version,
             "latest_version": latest.version
         });
@@ -886,16 +1155,19 @@ pub fn upgrade(check_only: bool, _skip_confirmation: bool) -> Result<()> {
         "current_version": current_version,
         "new_version": latest.version,
         "changes": changes,
-        "download_url": asset.browser_download_url
+        "download_url": asset.browser_download_url,
+        "sha256": download.sha256,
+        "checksum_verified": download.checksum_verified,
+        "signature_verified": download.signature_verified
     });
 
     println!("{}", serde_json::to_string(&result)?);
     Ok(())
 }
 
-pub fn version(project: bool, latest: bool) -> Result<()> {
+pub fn version(project: bool, latest: bool, channel: github::ReleaseChannel) -> Result<()> {
     if project {
-        let version_path = crate::common::rotd_path().join("version.json");
+        let version_path = crate::common::version_path();
         let version = if version_path.exists() {
             let v: ProjectVersion = read_json(&version_path)?;
             v.version
@@ -910,7 +1182,7 @@ pub fn version(project: bool, latest: bool) -> Result<()> {
         println!("{}", serde_json::to_string(&result)?);
     } else if latest {
         // Check GitHub for latest version
-        match github::fetch_latest_release()? {
+        match github::fetch_latest_release(channel)? {
             Some(latest) => {
                 let result = serde_json::json!({
                     "latest_version": latest.version
@@ -926,7 +1198,7 @@ pub fn version(project: bool, latest: bool) -> Result<()> {
             }
         }
     } else {
-        let version_path = crate::common::rotd_path().join("version.json");
+        let version_path = crate::common::version_path();
         let project_version = if version_path.exists() {
             let v: ProjectVersion = read_json(&version_path)?;
             v.version
@@ -935,7 +1207,7 @@ pub fn version(project: bool, latest: bool) -> Result<()> {
         };
 
         // Get latest version from GitHub
-        let (update_available, latest_version) = match github::check_update() {
+        let (update_available, latest_version) = match github::check_update(channel) {
             Ok((available, Some(release))) => (available, release.version),
             Ok((_, None)) => (false, "no-releases".to_string()),
             Err(_) => (false, "fetch-error".to_string()),
@@ -952,7 +1224,17 @@ pub fn version(project: bool, latest: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn validate(all: bool, schema_type: Option<&str>, strict: bool) -> Result<()> {
+pub fn build_info() -> Result<()> {
+    println!("{}", serde_json::to_string(&crate::build_info::BUILD_INFO)?);
+    Ok(())
+}
+
+pub fn validate(
+    all: bool,
+    schema_type: Option<&str>,
+    strict: bool,
+    strict_parse: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
     let mut report = ValidationReport {
@@ -961,134 +1243,85 @@ pub fn validate(all: bool, schema_type: Option<&str>, strict: bool) -> Result<()
         timestamp: Utc::now(),
     };
 
-    let mut total_errors = 0;
-
-    if all || schema_type.is_none() {
-        // Validate tasks.jsonl
-        match validate_tasks_jsonl(strict) {
-            Ok(result) => {
-                report.reports.insert("tasks".to_string(), result);
-            }
-            Err(_) => {
-                let result = ValidationResult {
-                    status: "failed".to_string(),
-                    errors: vec!["Failed to read tasks.jsonl".to_string()],
-                    warnings: vec![],
-                    items_checked: 0,
-                };
-                total_errors += 1;
-                report.reports.insert("tasks".to_string(), result);
+    match crate::validate::run(all, schema_type, strict, strict_parse) {
+        Ok(results) => {
+            for result in results {
+                report
+                    .reports
+                    .insert(result.outcome.id.to_string(), result.to_result());
             }
         }
-
-        // Validate other schemas if they exist
-        if crate::common::rotd_path().join("pss_scores.jsonl").exists() {
+        Err(e) => {
+            let schema = schema_type.unwrap_or_default().to_string();
             let result = ValidationResult {
-                status: "passed".to_string(),
-                errors: vec![],
+                status: "unknown".to_string(),
+                errors: vec![e.to_string()],
                 warnings: vec![],
-                items_checked: 1,
+                items_checked: 0,
             };
-            report.reports.insert("pss_scores".to_string(), result);
-        }
-    } else if let Some(schema) = schema_type {
-        match schema {
-            "tasks" => match validate_tasks_jsonl(strict) {
-                Ok(result) => {
-                    report.reports.insert("tasks".to_string(), result);
-                }
-                Err(_) => {
-                    let result = ValidationResult {
-                        status: "failed".to_string(),
-                        errors: vec!["Failed to read tasks.jsonl".to_string()],
-                        warnings: vec![],
-                        items_checked: 0,
-                    };
-                    total_errors += 1;
-                    report.reports.insert("tasks".to_string(), result);
-                }
-            },
-            _ => {
-                let result = ValidationResult {
-                    status: "unknown".to_string(),
-                    errors: vec![format!("Unknown schema type: {}", schema)],
-                    warnings: vec![],
-                    items_checked: 0,
-                };
-                total_errors += 1;
-                report.reports.insert(schema.to_string(), result);
-            }
+            report.reports.insert(schema, result);
         }
     }
 
     // Count total errors across all reports
-    for result in report.reports.values() {
-        total_errors += result.errors.len();
-    }
+    let total_errors: usize = report.reports.values().map(|r| r.errors.len()).sum();
 
     if total_errors > 0 {
         report.overall_status = "failed".to_string();
     }
 
     println!("{}", serde_json::to_string(&report)?);
+
+    if total_errors > 0 {
+        return Err(anyhow::anyhow!(
+            "Validation failed with {} error(s)",
+            total_errors
+        ));
+    }
+
     Ok(())
 }
 
-// Helper function for validation
-pub fn validate_tasks_jsonl(strict: bool) -> Result<ValidationResult> {
-    let tasks = read_jsonl::<TaskEntry>(&crate::common::tasks_path())?;
+/// Schema problems with a single task entry
+pub fn task_issues(task: &TaskEntry, strict: bool) -> Vec<String> {
+    let mut issues = Vec::new();
 
-    let mut errors = Vec::new();
-    let warnings = Vec::new();
+    if let Err(e) = task.validate() {
+        issues.push(e.to_string());
+    }
 
-    for (i, task) in tasks.iter().enumerate() {
-        if let Err(e) = task.validate() {
-            errors.push(format!("Line {}: {}", i + 1, e));
-        }
+    // Check for new priority field in strict mode
+    if strict && task.priority.is_none() {
+        issues.push("Missing priority field (required in v1.2.1+)".to_string());
+    }
 
-        // Check for new priority field in strict mode
-        if strict && task.priority.is_none() {
-            errors.push(format!(
-                "Line {}: Missing priority field (required in v1.2.1+)",
-                i + 1
+    // Check for priority_score validation
+    if let Some(score) = task.priority_score {
+        if !(0.0..=100.0).contains(&score) {
+            issues.push(format!(
+                "priority_score must be between 0-100, got {}",
+                score
             ));
         }
-
-        // Check for priority_score validation
-        if let Some(score) = task.priority_score {
-            if !(0.0..=100.0).contains(&score) {
-                errors.push(format!(
-                    "Line {}: priority_score must be between 0-100, got {}",
-                    i + 1,
-                    score
-                ));
-            }
-        }
     }
 
-    let status = if errors.is_empty() {
-        "passed"
-    } else {
-        "failed"
-    };
-
-    Ok(ValidationResult {
-        status: status.to_string(),
-        errors,
-        warnings,
-        items_checked: tasks.len() as u32,
-    })
+    issues
 }
 
 /// Check for Buckle Mode trigger conditions (agent mode)
 pub fn check_buckle_trigger() -> Result<()> {
     check_rotd_initialized()?;
 
-    let triggered = false;
-    let reasons: Vec<String> = Vec::new();
+    let mut reasons: Vec<String> = Vec::new();
 
     // Check for compilation errors
-    // Implementation would check cargo/npm output for error count
+    let config = crate::history::load_config().unwrap_or_default();
+    reasons.extend(
+        crate::compile::run(&config)
+            .iter()
+            .filter(|o| !o.success)
+            .map(|o| format!("Compilation failed: {}", o.describe())),
+    );
 
     // Check task.jsonl integrity
     // Implementation would verify task.jsonl status is consistent
@@ -1097,7 +1330,12 @@ pub fn check_buckle_trigger() -> Result<()> {
     // Implementation would verify test summaries exist for completed tasks
 
     // Check session state
-    // Implementation would verify session_state.json is up to date
+    reasons.extend(
+        crate::output::stale_session_check()
+            .into_iter()
+            .map(|f| f.message),
+    );
+    let triggered = !reasons.is_empty();
 
     // Return JSON result
     let result = json!({
@@ -1116,7 +1354,7 @@ pub fn enter_buckle_mode(task_id: &str) -> Result<()> {
     check_rotd_initialized()?;
 
     // Check if already in Buckle Mode
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if buckle_state_path.exists() {
         let state: BuckleModeState =
             serde_json::from_str(&std::fs::read_to_string(&buckle_state_path)?).map_err(|e| {
@@ -1174,7 +1412,7 @@ pub fn diagnose_buckle_mode_json() -> Result<Value> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         return Ok(json!({
             "status": "error",
@@ -1193,13 +1431,22 @@ pub fn diagnose_buckle_mode_json() -> Result<Value> {
 
     let task_id = state.task_id.unwrap_or_default();
 
-    // Implementation would collect diagnostics
+    let config = crate::history::load_config().unwrap_or_default();
+    let compile_outcomes = crate::compile::run(&config);
+    let compile_status = if compile_outcomes.is_empty() {
+        "unknown"
+    } else if crate::compile::compiles(&compile_outcomes) {
+        "passing"
+    } else {
+        "failing"
+    };
 
     let diagnostics = json!({
         "task_id": task_id,
         "compilation": {
-            "status": "unknown",
-            "errors": 0
+            "status": compile_status,
+            "errors": compile_outcomes.iter().map(|o| o.errors.len()).sum::<usize>(),
+            "checks": compile_outcomes
         },
         "tests": {
             "status": "unknown",
@@ -1237,7 +1484,7 @@ pub fn fix_compilation() -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         let result = json!({
             "status": "error",
@@ -1285,7 +1532,7 @@ pub fn fix_artifacts() -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         let result = json!({
             "status": "error",
@@ -1333,7 +1580,7 @@ pub fn check_exit_criteria() -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         let result = json!({
             "status": "error",
@@ -1382,7 +1629,7 @@ pub fn exit_buckle_mode() -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         let result = json!({
             "status": "error",
@@ -1444,7 +1691,7 @@ pub fn exit_buckle_mode() -> Result<()> {
 pub fn primer_init(force: bool) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = crate::common::rotd_path().join("primer.jsonc");
+    let primer_path = crate::common::primer_path();
     
     if primer_path.exists() && !force {
         let result = json!({
@@ -1463,46 +1710,27 @@ pub fn primer_init(force: bool) -> Result<()> {
         .unwrap_or("unknown")
         .to_string();
     
-    // Detect language based on files present
-    let language = if std::path::Path::new("Cargo.toml").exists() {
-        "Rust"
-    } else if std::path::Path::new("package.json").exists() {
-        "JavaScript/TypeScript"
-    } else if std::path::Path::new("requirements.txt").exists() || std::path::Path::new("setup.py").exists() {
-        "Python"
-    } else {
-        "Unknown"
-    };
-    
-    // Find entry points
-    let entry_points: Vec<String> = match language {
-        "Rust" => vec!["src/main.rs", "src/lib.rs"],
-        "JavaScript/TypeScript" => vec!["index.js", "src/index.js", "src/main.ts"],
-        "Python" => vec!["main.py", "app.py", "__main__.py"],
-        _ => vec!["main"],
-    }.into_iter().filter(|&path| std::path::Path::new(path).exists()).map(|s| s.to_string()).collect();
-    
-    // Find test directories
-    let test_dirs: Vec<String> = vec!["tests/", "test/", "spec/", "src/"]
-        .into_iter()
-        .filter(|&path| std::path::Path::new(path).exists())
-        .map(|s| s.to_string())
-        .collect();
+    // Language, entry points, and test directories from the init profile,
+    // or detected from the files present
+    let config = crate::history::load_config()?;
+    let (language, entry_points, test_dirs) = crate::profile::primer_defaults(&config);
+    // Components discovered from the source tree, for the developer to refine
+    let components = crate::primer::discover_components(&config, &entry_points);
     
     let primer = ProjectPrimer {
         name: project_name.clone(),
         scope: "root".to_string(),
         description: "TODO: Add project description".to_string(),
         status: "active".to_string(),
-        language: language.to_string(),
+        language: language.clone(),
         entry_points: entry_points.clone(),
         test_dirs: test_dirs.clone(),
-        dependencies: vec![],
+        dependencies: crate::primer::project_dependencies().unwrap_or_default(),
         known_issues: vec![],
         key_concepts: vec![],
         preferred_agents: Some(vec!["Claude Sonnet".to_string()]),
         suggested_starting_points: vec!["TODO: Add suggested starting points".to_string()],
-        major_components: None,
+        major_components: Some(components.clone()).filter(|c| !c.is_empty()),
         update_triggers: Some(vec![
             "Major architectural changes".to_string(),
             "New dependencies added".to_string(),
@@ -1510,9 +1738,8 @@ pub fn primer_init(force: bool) -> Result<()> {
         ]),
     };
     
-    // Convert to pretty JSON
-    let json_content = serde_json::to_string_pretty(&primer)?;
-    std::fs::write(&primer_path, json_content)?;
+    // Comments from a primer replaced with --force are kept
+    crate::primer::save(&primer_path, &primer)?;
     
     let result = json!({
         "status": "success",
@@ -1520,19 +1747,21 @@ pub fn primer_init(force: bool) -> Result<()> {
         "primer_path": primer_path.display().to_string(),
         "detected_language": language,
         "entry_points": entry_points,
-        "test_dirs": test_dirs
+        "test_dirs": test_dirs,
+        "components": components.keys().collect::<Vec<_>>()
     });
     
     println!("{}", serde_json::to_string(&result)?);
     Ok(())
 }
 
-pub fn primer_show(file: Option<&str>) -> Result<()> {
+pub fn primer_show(file: Option<&str>, scope: Option<&str>, format: &str) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = match file {
-        Some(f) => crate::common::rotd_path().join(f),
-        None => crate::common::rotd_path().join("primer.jsonc"),
+    let primer_path = match (file, scope) {
+        (Some(f), _) => crate::common::shared_path().join(f),
+        (None, Some(scope)) => crate::primer::find_scope(scope)?,
+        (None, None) => crate::common::primer_path(),
     };
     
     if !primer_path.exists() {
@@ -1546,13 +1775,16 @@ pub fn primer_show(file: Option<&str>) -> Result<()> {
     }
     
     let content = std::fs::read_to_string(&primer_path)?;
-    let primer: ProjectPrimer = serde_json::from_str(&content)?;
+    let primer: ProjectPrimer = crate::history::parse_jsonc(&content)?;
     
-    let result = json!({
+    let mut result = json!({
         "status": "success",
         "primer_path": primer_path.display().to_string(),
         "primer": primer
     });
+    if format == "md" {
+        result["markdown"] = json!(crate::primer::to_markdown(&primer));
+    }
     
     println!("{}", serde_json::to_string(&result)?);
     Ok(())
@@ -1561,7 +1793,7 @@ pub fn primer_show(file: Option<&str>) -> Result<()> {
 pub fn primer_check() -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = crate::common::rotd_path().join("primer.jsonc");
+    let primer_path = crate::common::primer_path();
     
     if !primer_path.exists() {
         let result = json!({
@@ -1575,7 +1807,7 @@ pub fn primer_check() -> Result<()> {
     
     // Try to parse the primer
     let content = std::fs::read_to_string(&primer_path)?;
-    let primer: ProjectPrimer = match serde_json::from_str(&content) {
+    let primer: ProjectPrimer = match crate::history::parse_jsonc(&content) {
         Ok(p) => p,
         Err(e) => {
             let result = json!({
@@ -1620,12 +1852,14 @@ pub fn primer_check() -> Result<()> {
     }
     
     let validation_passed = issues.is_empty();
+    let (_, drift) = crate::primer::project_drift(&primer, &[])?;
     
     let result = json!({
         "status": if validation_passed { "success" } else { "failed" },
         "validation_passed": validation_passed,
         "issues": issues,
         "warnings": warnings,
+        "drift": drift,
         "primer_summary": {
             "name": primer.name,
             "language": primer.language,
@@ -1639,12 +1873,63 @@ pub fn primer_check() -> Result<()> {
     Ok(())
 }
 
-pub fn primer_parse(format: &str) -> Result<()> {
+/// Without `--yes` the changes are only reported, so they can be reviewed
+/// before being applied
+pub fn primer_update(fields: &[String], yes: bool, dry_run: bool) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = crate::common::rotd_path().join("primer.jsonc");
-    
+    let primer_path = crate::common::primer_path();
     if !primer_path.exists() {
+        let result = json!({
+            "status": "error",
+            "message": "No primer.jsonc found",
+            "suggestion": "Run 'rotd primer init' to create one"
+        });
+        println!("{}", serde_json::to_string(&result)?);
+        return Ok(());
+    }
+    
+    let mut primer = crate::primer::load(&primer_path)?;
+    let (state, changes) = crate::primer::project_drift(&primer, fields)?;
+    
+    let status = if changes.is_empty() {
+        "up_to_date"
+    } else if dry_run {
+        "dry_run"
+    } else if !yes {
+        "review_required"
+    } else {
+        crate::primer::apply_changes(&mut primer, &state, &changes);
+        crate::primer::save(&primer_path, &primer)?;
+        audit::log_info(
+            None,
+            "PRIMER_UPDATE",
+            &format!("Applied {} primer change(s)", changes.len()),
+        )?;
+        "success"
+    };
+    
+    let mut result = json!({
+        "status": status,
+        "action": "primer_update",
+        "primer_path": primer_path.display().to_string(),
+        "changes": changes,
+    });
+    if status == "review_required" {
+        result["message"] = json!("Re-run with --yes to apply these changes");
+    }
+    
+    println!("{}", serde_json::to_string(&result)?);
+    Ok(())
+}
+
+pub fn primer_parse(format: &str, task_id: Option<&str>) -> Result<()> {
+    check_rotd_initialized()?;
+    
+    let mut primers: Vec<ProjectPrimer> =
+        crate::primer::load_all()?.into_iter().map(|(_, p)| p).collect();
+    
+    if primers.is_empty() {
         let result = json!({
             "status": "error",
             "message": "No primer.jsonc found"
@@ -1653,22 +1938,31 @@ pub fn primer_parse(format: &str) -> Result<()> {
         return Ok(());
     }
     
-    let content = std::fs::read_to_string(&primer_path)?;
-    let primer: ProjectPrimer = serde_json::from_str(&content)?;
+    // Only the scopes that bear on the task, so an agent's context stays small
+    if let Some(task_id) = task_id {
+        let task = latest_tasks()?
+            .into_iter()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+        primers.retain(|p| crate::primer::is_relevant(p, &task));
+    }
     
-    match format {
-        "json" => {
-            let result = json!({
-                "status": "success",
-                "format": "json",
-                "primer": primer
-            });
-            println!("{}", serde_json::to_string(&result)?);
-        }
+    let (root, scoped): (Vec<ProjectPrimer>, Vec<ProjectPrimer>) = primers
+        .into_iter()
+        .partition(|p| p.scope == crate::primer::ROOT_SCOPE);
+    let root = root.into_iter().next();
+    
+    let result = match format {
+        "json" => json!({
+            "status": "success",
+            "format": "json",
+            "task_id": task_id,
+            "primer": root,
+            "scopes": scoped
+        }),
         "summary" => {
-            let result = json!({
-                "status": "success",
-                "format": "summary",
+            let summary = |primer: &ProjectPrimer| json!({
+                "scope": primer.scope,
                 "name": primer.name,
                 "description": primer.description,
                 "language": primer.language,
@@ -1678,16 +1972,19 @@ pub fn primer_parse(format: &str) -> Result<()> {
                 "test_dirs": primer.test_dirs,
                 "known_issues": primer.known_issues
             });
-            println!("{}", serde_json::to_string(&result)?);
-        }
-        _ => {
-            let result = json!({
-                "status": "error",
-                "message": format!("Unknown format: {}", format)
-            });
-            println!("{}", serde_json::to_string(&result)?);
+            let mut result = root.as_ref().map(summary).unwrap_or_else(|| json!({}));
+            result["status"] = json!("success");
+            result["format"] = json!("summary");
+            result["task_id"] = json!(task_id);
+            result["scopes"] = scoped.iter().map(summary).collect();
+            result
         }
-    }
+        _ => json!({
+            "status": "error",
+            "message": format!("Unknown format: {}", format)
+        }),
+    };
     
+    println!("{}", serde_json::to_string(&result)?);
     Ok(())
 }
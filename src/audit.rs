@@ -1,8 +1,12 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 use crate::fs_ops::append_line;
-use crate::schema::AuditEntry;
+use crate::schema::{AuditEntry, TaskEntry, TaskStatus};
+
+/// Written after the task id on a line whose message had secrets redacted,
+/// so the flag survives a round trip without guessing from the message text
+const REDACTED_MARKER: &str = " [redacted]";
 
 pub fn log_violation(
     task_id: Option<&str>,
@@ -10,20 +14,23 @@ pub fn log_violation(
     severity: &str,
     message: &str,
 ) -> Result<()> {
+    let (message, redacted) = crate::secrets::redact_text(message);
     let entry = AuditEntry {
         timestamp: Utc::now(),
         task_id: task_id.map(|s| s.to_string()),
         rule: rule.to_string(),
         severity: severity.to_string(),
-        message: message.to_string(),
+        message,
+        redacted,
     };
 
     let log_line = format!(
-        "[{}] [{}] {} {} - {}",
+        "[{}] [{}] {} {}{} - {}",
         entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
         entry.severity.to_uppercase(),
         entry.rule,
         entry.task_id.as_deref().unwrap_or("GLOBAL"),
+        if entry.redacted { REDACTED_MARKER } else { "" },
         entry.message
     );
 
@@ -34,7 +41,6 @@ pub fn log_info(task_id: Option<&str>, rule: &str, message: &str) -> Result<()>
     log_violation(task_id, rule, "info", message)
 }
 
-#[allow(dead_code)]
 pub fn log_warning(task_id: Option<&str>, rule: &str, message: &str) -> Result<()> {
     log_violation(task_id, rule, "warning", message)
 }
@@ -48,6 +54,97 @@ pub fn log_entry(task_id: &str, rule: &str, severity: &str, message: &str) -> Re
     log_violation(Some(task_id), rule, severity, message)
 }
 
+/// Parse a line written by `log_violation` back into an entry
+pub fn parse_log_line(line: &str) -> Option<AuditEntry> {
+    let rest = crate::integrity::strip_text_link(line).strip_prefix('[')?;
+    let (timestamp, rest) = rest.split_once("] [")?;
+    let (severity, rest) = rest.split_once("] ")?;
+    let (head, message) = rest.split_once(" - ")?;
+    let (head, redacted) = match head.strip_suffix(REDACTED_MARKER) {
+        Some(head) => (head, true),
+        None => (head, false),
+    };
+    let (rule, task_id) = head.rsplit_once(' ')?;
+
+    let timestamp = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S UTC")
+        .ok()?
+        .and_utc();
+
+    Some(AuditEntry {
+        timestamp,
+        task_id: (task_id != "GLOBAL").then(|| task_id.to_string()),
+        rule: rule.to_string(),
+        severity: severity.to_lowercase(),
+        message: message.to_string(),
+        redacted,
+    })
+}
+
+/// Severities written to the audit log, least severe first
+pub const SEVERITIES: [&str; 4] = ["info", "warning", "error", "critical"];
+
+/// Which entries `show-audit` lists; empty fields match everything
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    pub severities: Vec<String>,
+    pub task_id: Option<String>,
+    pub rule: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        (self.severities.is_empty() || self.severities.contains(&entry.severity))
+            && self
+                .task_id
+                .as_ref()
+                .is_none_or(|id| entry.task_id.as_ref() == Some(id))
+            && self
+                .rule
+                .as_ref()
+                .is_none_or(|rule| entry.rule.eq_ignore_ascii_case(rule))
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+    }
+}
+
+/// Every entry in the audit log, newest first. Lines that don't parse are
+/// skipped.
+pub fn read_entries() -> Result<Vec<AuditEntry>> {
+    let audit_path = crate::common::audit_log_path();
+    if !audit_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&audit_path)?;
+    let mut entries: Vec<AuditEntry> = content
+        .lines()
+        .filter_map(|line| {
+            // Older tooling wrote JSON entries
+            parse_log_line(line).or_else(|| serde_json::from_str(line).ok())
+        })
+        .collect();
+    // Stable, so entries logged within the same second stay newest first
+    entries.reverse();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+    Ok(entries)
+}
+
+/// Critical entries still needing attention: those logged against a task
+/// that isn't complete yet, and every global one
+pub fn unresolved_criticals(entries: Vec<AuditEntry>, tasks: &[TaskEntry]) -> Vec<AuditEntry> {
+    entries
+        .into_iter()
+        .filter(|e| e.severity == "critical")
+        .filter(|e| {
+            e.task_id.as_ref().is_none_or(|id| {
+                !tasks
+                    .iter()
+                    .any(|t| &t.id == id && matches!(t.status, TaskStatus::Complete))
+            })
+        })
+        .collect()
+}
+
 #[allow(dead_code)]
 pub fn read_audit_log(limit: usize) -> Result<Vec<String>> {
     let audit_path = crate::common::audit_log_path();
@@ -66,3 +163,99 @@ pub fn read_audit_log(limit: usize) -> Result<Vec<String>> {
 
     Ok(lines)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_parsed_entries() {
+        let entry = parse_log_line(
+            "[2026-01-31 09:15:00 UTC] [WARNING] pss_low 6.1 - Score below threshold",
+        )
+        .unwrap();
+        assert_eq!(entry.severity, "warning");
+        assert_eq!(entry.task_id.as_deref(), Some("6.1"));
+
+        let since = |s: &str| Some(crate::common::parse_since(s).unwrap());
+        assert!(AuditFilter::default().matches(&entry));
+        assert!(AuditFilter {
+            severities: vec!["error".to_string(), "warning".to_string()],
+            task_id: Some("6.1".to_string()),
+            rule: Some("PSS_LOW".to_string()),
+            since: since("2026-01-31"),
+        }
+        .matches(&entry));
+        assert!(!AuditFilter {
+            severities: vec!["error".to_string()],
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!AuditFilter {
+            task_id: Some("6.2".to_string()),
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!AuditFilter {
+            since: since("2026-02-01"),
+            ..Default::default()
+        }
+        .matches(&entry));
+    }
+
+    #[test]
+    fn test_redaction_marker_round_trips() {
+        let entry = parse_log_line(
+            "[2026-01-31 09:15:00 UTC] [INFO] coord_msg GLOBAL [redacted] - token=[REDACTED]",
+        )
+        .unwrap();
+        assert!(entry.redacted);
+        assert_eq!(entry.task_id, None);
+        assert_eq!(entry.rule, "coord_msg");
+        assert_eq!(entry.message, "token=[REDACTED]");
+
+        // Text that merely looks redacted, without the marker, is not flagged
+        let entry = parse_log_line(
+            "[2026-01-31 09:15:00 UTC] [INFO] note 6.1 - Logs now show [REDACTED] for secrets",
+        )
+        .unwrap();
+        assert!(!entry.redacted);
+        assert_eq!(entry.task_id.as_deref(), Some("6.1"));
+    }
+
+    #[test]
+    fn test_unresolved_criticals() {
+        let entries: Vec<AuditEntry> = [
+            "[2026-01-31 09:20:00 UTC] [CRITICAL] audit.buckle.trigger.001 6.2 - Entered Buckle Mode",
+            "[2026-01-31 09:15:00 UTC] [CRITICAL] audit.buckle.trigger.001 6.1 - Entered Buckle Mode",
+            "[2026-01-31 09:10:00 UTC] [CRITICAL] coverage_drop GLOBAL - Coverage fell",
+            "[2026-01-31 09:05:00 UTC] [ERROR] pss_low 6.2 - Score below threshold",
+        ]
+        .iter()
+        .filter_map(|line| parse_log_line(line))
+        .collect();
+        let tasks: Vec<TaskEntry> = vec![
+            serde_json::from_value(serde_json::json!({
+                "id": "6.1", "title": "Done", "status": "complete"
+            }))
+            .unwrap(),
+            serde_json::from_value(serde_json::json!({
+                "id": "6.2", "title": "Open", "status": "in_progress"
+            }))
+            .unwrap(),
+        ];
+
+        let unresolved = unresolved_criticals(entries, &tasks);
+        let rules: Vec<(Option<&str>, &str)> = unresolved
+            .iter()
+            .map(|e| (e.task_id.as_deref(), e.rule.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (Some("6.2"), "audit.buckle.trigger.001"),
+                (None, "coverage_drop"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+/// Build metadata captured by `build.rs`, for bug reports
+#[derive(Debug, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub build_date: &'static str,
+    pub target: &'static str,
+    pub rustc_version: &'static str,
+    pub profile: &'static str,
+}
+
+pub const BUILD_INFO: BuildInfo = BuildInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    git_sha: env!("ROTD_GIT_SHA"),
+    build_date: env!("ROTD_BUILD_DATE"),
+    target: env!("ROTD_BUILD_TARGET"),
+    rustc_version: env!("ROTD_RUSTC_VERSION"),
+    profile: env!("ROTD_BUILD_PROFILE"),
+};
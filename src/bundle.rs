@@ -0,0 +1,466 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use crate::common::check_rotd_initialized;
+use crate::github::sha256_hex;
+use crate::schema::{
+    LESSON_SCHEMA, PSS_SCORE_SCHEMA, SESSION_LOG_SCHEMA, SESSION_STATE_SCHEMA, TASK_HISTORY_SCHEMA,
+    TASK_SCHEMA, TEST_SUMMARY_SCHEMA,
+};
+
+/// Format stamp of bundles this binary writes and reads
+pub const BUNDLE_FORMAT: &str = "rotd-bundle.v1";
+
+/// Name of the manifest at the root of a bundle
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Directory in the bundle holding the contents of `.rotd/`
+const BUNDLE_ROOT: &str = "rotd";
+
+/// Machine-local state left out of bundles: locks, heartbeats, private
+/// keys, backups, caches, and machine-local settings
+const EXCLUDED_DIRS: &[&str] = &[
+    ".lock",
+    "heartbeat",
+    "agent_locks",
+    "file_locks",
+    crate::common::KEYS_DIR,
+    crate::common::BACKUPS_DIR,
+    crate::common::SYNC_DIR,
+];
+const EXCLUDED_FILES: &[&str] = &[
+    crate::common::COMPILE_CACHE_FILE,
+    crate::common::GITHUB_TOKEN_FILE,
+    crate::common::LOCAL_CONFIG_FILE,
+];
+const EXCLUDED_EXTENSIONS: &[&str] = &["lock", "bak"];
+
+/// Schemas checked on import against what this binary understands
+const SUPPORTED_SCHEMAS: &[&str] = &[
+    TASK_SCHEMA,
+    TEST_SUMMARY_SCHEMA,
+    LESSON_SCHEMA,
+    PSS_SCORE_SCHEMA,
+    SESSION_STATE_SCHEMA,
+    TASK_HISTORY_SCHEMA,
+    SESSION_LOG_SCHEMA,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path relative to `.rotd/`
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// `manifest.json`: what a bundle holds and what wrote it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: String,
+    pub rotd_version: String,
+    pub created: DateTime<Utc>,
+    /// Whether `.rotd/` used the `shared/` + `local/` split
+    pub split_layout: bool,
+    pub files: Vec<BundleFile>,
+    /// Records per `_schema` stamp across the bundled artifacts
+    pub schema_versions: BTreeMap<String, usize>,
+}
+
+/// Machine-local files that never leave `.rotd/`
+pub fn excluded(relative: &Path) -> bool {
+    let name = relative.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    relative.parent().is_some_and(|parent| {
+        parent
+            .components()
+            .any(|c| EXCLUDED_DIRS.contains(&c.as_os_str().to_string_lossy().as_ref()))
+    }) || EXCLUDED_FILES.contains(&name)
+        || relative
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| EXCLUDED_EXTENSIONS.contains(&e))
+}
+
+/// Files under `.rotd/` that go into a bundle or `rotd sync`, relative to it
+pub fn bundled_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.path().strip_prefix(root).ok().map(Path::to_path_buf))
+        .filter(|relative| !excluded(relative))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Count the `_schema` stamps of the records in a JSON or JSONL artifact
+fn count_schemas(relative: &Path, data: &[u8], counts: &mut BTreeMap<String, usize>) {
+    let extension = relative.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let records: Vec<serde_json::Value> = match extension {
+        "jsonl" => content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect(),
+        "json" => serde_json::from_str(content).into_iter().collect(),
+        _ => Vec::new(),
+    };
+    for record in records {
+        if let Some(schema) = record.get("_schema").and_then(|s| s.as_str()) {
+            *counts.entry(schema.to_string()).or_default() += 1;
+        }
+    }
+}
+
+/// Write `.rotd/` as a gzipped tarball with a manifest. Returns the manifest.
+pub fn export(out: &Path, dry_run: bool) -> Result<BundleManifest> {
+    let root = crate::common::rotd_path();
+    let mut manifest = BundleManifest {
+        format: BUNDLE_FORMAT.to_string(),
+        rotd_version: env!("CARGO_PKG_VERSION").to_string(),
+        created: Utc::now(),
+        split_layout: crate::common::is_split_layout(),
+        files: Vec::new(),
+        schema_versions: BTreeMap::new(),
+    };
+    let mut contents = Vec::new();
+    for relative in bundled_files(&root) {
+        let data = std::fs::read(root.join(&relative))
+            .with_context(|| format!("Failed to read {}", root.join(&relative).display()))?;
+        count_schemas(&relative, &data, &mut manifest.schema_versions);
+        manifest.files.push(BundleFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            size: data.len() as u64,
+            sha256: sha256_hex(&data),
+        });
+        contents.push(data);
+    }
+    if dry_run {
+        return Ok(manifest);
+    }
+
+    let file = std::fs::File::create(out)
+        .with_context(|| format!("Failed to create {}", out.display()))?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let mut append = |path: &str, data: &[u8]| -> Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(manifest.created.timestamp().max(0) as u64);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, data)
+            .with_context(|| format!("Failed to add {} to the bundle", path))
+    };
+    append(MANIFEST_FILE, serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    for (entry, data) in manifest.files.iter().zip(&contents) {
+        append(&format!("{}/{}", BUNDLE_ROOT, entry.path), data)?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(manifest)
+}
+
+/// A bundle read into memory and checked against its manifest
+pub struct Bundle {
+    pub manifest: BundleManifest,
+    /// File contents by path relative to `.rotd/`
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// Reject paths that could land outside `.rotd/`
+pub fn safe_relative(path: &str) -> Result<PathBuf> {
+    let relative = PathBuf::from(path);
+    if relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(anyhow::anyhow!("Bundle contains an unsafe path: {}", path))
+    }
+}
+
+/// Read a bundle and check that its files match the manifest
+pub fn read(data: &[u8]) -> Result<Bundle> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(data));
+    let mut manifest: Option<BundleManifest> = None;
+    let mut files = BTreeMap::new();
+    for entry in archive.entries().context("Not a gzipped tar bundle")? {
+        let mut entry = entry.context("Not a gzipped tar bundle")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().replace('\\', "/");
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        if path == MANIFEST_FILE {
+            manifest = Some(serde_json::from_slice(&data).context("Invalid bundle manifest")?);
+        } else if let Some(relative) = path.strip_prefix(&format!("{}/", BUNDLE_ROOT)) {
+            safe_relative(relative)?;
+            files.insert(relative.to_string(), data);
+        }
+    }
+
+    let manifest = manifest.context("Bundle has no manifest.json")?;
+    if manifest.format != BUNDLE_FORMAT {
+        anyhow::bail!(
+            "Unsupported bundle format {} (this rotd reads {})",
+            manifest.format,
+            BUNDLE_FORMAT
+        );
+    }
+    for file in &manifest.files {
+        safe_relative(&file.path)?;
+        let data = files
+            .get(&file.path)
+            .with_context(|| format!("Bundle is missing {}", file.path))?;
+        if sha256_hex(data) != file.sha256 {
+            anyhow::bail!("{} does not match its checksum in the manifest", file.path);
+        }
+    }
+    files.retain(|path, _| manifest.files.iter().any(|f| &f.path == path));
+    Ok(Bundle { manifest, files })
+}
+
+/// Schema stamps in a manifest that this binary can't fully read
+pub fn schema_issues(manifest: &BundleManifest) -> Vec<String> {
+    manifest
+        .schema_versions
+        .keys()
+        .filter_map(|found| {
+            let name = found.rsplit_once(".v").map_or(found.as_str(), |(name, _)| name);
+            let supported = SUPPORTED_SCHEMAS
+                .iter()
+                .find(|s| s.rsplit_once(".v").is_some_and(|(n, _)| n == name))?;
+            crate::schema::schema_issue(found, supported)
+        })
+        .collect()
+}
+
+/// What importing a bundle did, or would do
+#[derive(Debug, Serialize)]
+pub struct ImportOutcome {
+    pub written: Vec<String>,
+    /// Existing files that were replaced
+    pub replaced: Vec<String>,
+    /// Where the replaced files were copied first
+    pub backup_dir: Option<PathBuf>,
+}
+
+/// Unpack a bundle into `.rotd/`. Existing files are only replaced with
+/// `force`, after being copied to `.rotd/backups/import-<timestamp>/`.
+pub fn import(bundle: &Bundle, force: bool, dry_run: bool) -> Result<ImportOutcome> {
+    let root = crate::common::rotd_path();
+    let replaced: Vec<String> = bundle
+        .files
+        .keys()
+        .filter(|path| root.join(path).exists())
+        .cloned()
+        .collect();
+    if !replaced.is_empty() && !force {
+        anyhow::bail!(
+            "{} file(s) in .rotd/ would be replaced, e.g. {} (use --force to replace them)",
+            replaced.len(),
+            replaced[0]
+        );
+    }
+
+    let mut outcome = ImportOutcome {
+        written: bundle.files.keys().cloned().collect(),
+        replaced,
+        backup_dir: None,
+    };
+    if dry_run {
+        return Ok(outcome);
+    }
+
+    if !outcome.replaced.is_empty() {
+        let backup_dir = crate::common::backups_path()
+            .join(format!("import-{}", Utc::now().format("%Y%m%d%H%M%S")));
+        for path in &outcome.replaced {
+            let target = backup_dir.join(path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(root.join(path), &target)
+                .with_context(|| format!("Failed to back up {}", path))?;
+        }
+        outcome.backup_dir = Some(backup_dir);
+    }
+    for (path, data) in &bundle.files {
+        let target = root.join(path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, data)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+    }
+    Ok(outcome)
+}
+
+/// `rotd export`
+pub fn cmd_export(out: &Path, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    let manifest = export(out, dry_run)?;
+    let bytes: u64 = manifest.files.iter().map(|f| f.size).sum();
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "export",
+                "out": out,
+                "files": manifest.files.len(),
+                "bytes": bytes,
+                "schema_versions": manifest.schema_versions,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} {} files ({} bytes) to {}",
+            if dry_run { "Would export" } else { "✓ Exported" },
+            manifest.files.len(),
+            bytes,
+            out.display()
+        );
+        if dry_run {
+            for file in &manifest.files {
+                println!("  {}", file.path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `rotd import bundle`
+pub fn cmd_import(file: &Path, force: bool, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    let data = std::fs::read(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let bundle = read(&data)?;
+
+    let issues = schema_issues(&bundle.manifest);
+    if !issues.is_empty() && !force {
+        anyhow::bail!(
+            "Bundle has records this rotd can't fully read (use --force to import anyway): {}",
+            issues.join("; ")
+        );
+    }
+    if bundle.manifest.split_layout != crate::common::is_split_layout()
+        && crate::common::rotd_path().exists()
+        && !force
+    {
+        anyhow::bail!(
+            "Bundle uses the {} layout but this project doesn't (use --force to import anyway)",
+            if bundle.manifest.split_layout { "shared/ + local/" } else { "single-directory" }
+        );
+    }
+    let outcome = import(&bundle, force, dry_run)?;
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "import_bundle",
+                "rotd_version": bundle.manifest.rotd_version,
+                "created": bundle.manifest.created,
+                "written": outcome.written.len(),
+                "replaced": outcome.replaced,
+                "backup_dir": outcome.backup_dir,
+                "schema_issues": issues,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} {} files from a bundle written by rotd {} on {}",
+            if dry_run { "Would import" } else { "✓ Imported" },
+            outcome.written.len(),
+            bundle.manifest.rotd_version,
+            bundle.manifest.created.format("%Y-%m-%d %H:%M UTC")
+        );
+        if !outcome.replaced.is_empty() {
+            println!(
+                "  {} {} existing file(s)",
+                if dry_run { "Would replace" } else { "Replaced" },
+                outcome.replaced.len()
+            );
+        }
+        if let Some(backup_dir) = &outcome.backup_dir {
+            println!("  Originals saved to {}", backup_dir.display());
+        }
+        for issue in &issues {
+            println!("  Warning: {}", issue);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excluded() {
+        for path in [
+            "coordination/heartbeat/agent-1.beat",
+            "coordination/.lock/coordination.lock",
+            "coordination/agent_locks/1.1.agent-1.lock",
+            "local/keys/agent-1.key",
+            "sync/base/tasks.jsonl",
+            "backups/fix-20260101000000/tasks.jsonl",
+            "compile_cache.json",
+            "tasks.jsonl.lock",
+            "tasks.jsonl.bak",
+        ] {
+            assert!(excluded(Path::new(path)), "{}", path);
+        }
+        for path in [
+            "tasks.jsonl",
+            "shared/tasks.jsonl",
+            "test_summaries/1.1.json",
+            "coordination/active_work_registry.json",
+            "audit.log",
+        ] {
+            assert!(!excluded(Path::new(path)), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_schema_issues_and_unsafe_paths() {
+        let mut counts = BTreeMap::new();
+        count_schemas(
+            Path::new("tasks.jsonl"),
+            b"{\"id\":\"1\",\"_schema\":\"task.v1\"}\n{\"id\":\"2\",\"_schema\":\"task.v9\"}\n",
+            &mut counts,
+        );
+        count_schemas(Path::new("session_state.json"), b"{\"_schema\":\"session_state.v1\"}", &mut counts);
+        assert_eq!(counts.get("task.v1"), Some(&1));
+        assert_eq!(counts.len(), 3);
+
+        let manifest = BundleManifest {
+            format: BUNDLE_FORMAT.to_string(),
+            rotd_version: "9.9.9".to_string(),
+            created: Utc::now(),
+            split_layout: false,
+            files: Vec::new(),
+            schema_versions: counts,
+        };
+        let issues = schema_issues(&manifest);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("task.v9"));
+
+        assert!(safe_relative("test_summaries/1.1.json").is_ok());
+        assert!(safe_relative("../outside").is_err());
+        assert!(safe_relative("/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,984 @@
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::fs_ops::{append_line, with_lock_result};
+use crate::output::{Finding, Level, Outcome};
+use crate::schema::{
+    CheckWaiver, CoverageHistory, RotdConfig, SESSION_STATE_SCHEMA, SessionLogEntry, SessionState,
+    TEST_SUMMARY_SCHEMA, TaskEntry, TaskStatus, TestSummary,
+};
+
+/// Built-in `rotd check` checks with their descriptions. Each can be
+/// disabled or given a different severity under `checks` in the config.
+pub const CHECKS: [(&str, &str); 11] = [
+    ("missing_required_files", "Missing required files"),
+    ("invalid_jsonl", "Invalid tasks.jsonl"),
+    (
+        "missing_test_summaries",
+        "Missing test summaries for completed tasks",
+    ),
+    (
+        "stubs_remaining",
+        "Stub code annotations remaining in project",
+    ),
+    ("invalid_session_state", "Invalid session state format"),
+    (
+        "stale_session",
+        "Stale session state while tasks are in progress",
+    ),
+    (
+        "dependency_integrity",
+        "Task dependencies that are unknown, archived, or incomplete",
+    ),
+    (
+        "orphaned_test_summaries",
+        "Test summaries for tasks missing from tasks.jsonl",
+    ),
+    (
+        "orphaned_pss_scores",
+        "PSS scores for tasks missing from tasks.jsonl",
+    ),
+    (
+        "completed_tasks_without_commits",
+        "Completed tasks without linked commits",
+    ),
+    ("milestone_at_risk", "Milestones at risk of missing their target date"),
+];
+
+/// Severity of a check the config doesn't override. Milestone risk is a
+/// forecast, so it warns rather than fails.
+fn default_severity(id: &str) -> Severity {
+    match id {
+        "milestone_at_risk" => Severity::Warning,
+        _ => Severity::Error,
+    }
+}
+
+pub fn description(id: &str) -> &str {
+    CHECKS
+        .iter()
+        .find(|(check, _)| *check == id)
+        .map(|(_, description)| *description)
+        .unwrap_or(id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "critical" => Ok(Self::Critical),
+            "error" => Ok(Self::Error),
+            "warning" => Ok(Self::Warning),
+            other => Err(anyhow::anyhow!(
+                "Unknown check severity '{}'. Expected critical, error, or warning",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// A finding suppressed by a waiver
+#[derive(Debug, Clone)]
+pub struct Waived {
+    pub finding: Finding,
+    pub reason: String,
+    pub expires: NaiveDate,
+}
+
+impl Waived {
+    /// The finding's message, prefixed with its location when it has one
+    pub fn describe(&self) -> String {
+        match (&self.finding.file, self.finding.line) {
+            (Some(file), Some(line)) => {
+                format!("{}:{}: {}", file.display(), line, self.finding.message)
+            }
+            (Some(file), None) => format!("{}: {}", file.display(), self.finding.message),
+            _ => self.finding.message.clone(),
+        }
+    }
+}
+
+/// One enabled check after config has been applied
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub outcome: Outcome,
+    pub severity: Severity,
+    pub waived: Vec<Waived>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CheckRun {
+    pub results: Vec<CheckResult>,
+    pub disabled: Vec<&'static str>,
+    /// Waivers past their expiry date, which no longer suppress anything
+    pub expired_waivers: Vec<CheckWaiver>,
+    /// Config entries naming checks that do not exist
+    pub unknown_checks: Vec<String>,
+}
+
+impl CheckRun {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.outcome.passed()).count()
+    }
+
+    pub fn health_percentage(&self) -> f64 {
+        if self.results.is_empty() {
+            100.0
+        } else {
+            self.passed() as f64 / self.results.len() as f64 * 100.0
+        }
+    }
+
+    /// Gate for `rotd check --ci`: health must reach the threshold and no
+    /// critical check may fail
+    pub fn ci_verdict(&self, threshold: f64) -> CiVerdict {
+        let health = self.health_percentage();
+        let critical: Vec<&'static str> = self
+            .failed()
+            .filter(|r| r.severity == Severity::Critical)
+            .map(|r| r.outcome.id)
+            .collect();
+        CiVerdict {
+            passed: health >= threshold && critical.is_empty(),
+            health: (health * 10.0).round() / 10.0,
+            threshold,
+            checks_passed: self.passed(),
+            total_checks: self.results.len(),
+            failed: self.failed().map(|r| r.outcome.id).collect(),
+            critical,
+        }
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results.iter().filter(|r| !r.outcome.passed())
+    }
+
+    pub fn outcomes(&self) -> Vec<Outcome> {
+        self.results.iter().map(|r| r.outcome.clone()).collect()
+    }
+
+    pub fn waived(&self) -> impl Iterator<Item = (&'static str, &Waived)> {
+        self.results
+            .iter()
+            .flat_map(|r| r.waived.iter().map(move |w| (r.outcome.id, w)))
+    }
+
+    /// Record waived findings and expired waivers in the audit log
+    pub fn log_waivers(&self) -> Result<()> {
+        for (check, waived) in self.waived() {
+            crate::audit::log_info(
+                None,
+                "CHECK_WAIVED",
+                &format!(
+                    "{}: {} (waived until {}: {})",
+                    check,
+                    waived.describe(),
+                    waived.expires,
+                    waived.reason
+                ),
+            )?;
+        }
+        for waiver in &self.expired_waivers {
+            crate::audit::log_info(
+                None,
+                "CHECK_WAIVER_EXPIRED",
+                &format!(
+                    "Waiver for {} expired on {}: {}",
+                    waiver.check, waiver.expires, waiver.reason
+                ),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CiVerdict {
+    pub passed: bool,
+    pub health: f64,
+    pub threshold: f64,
+    pub checks_passed: usize,
+    pub total_checks: usize,
+    pub failed: Vec<&'static str>,
+    pub critical: Vec<&'static str>,
+}
+
+impl CiVerdict {
+    /// One `key=value` line for CI logs
+    pub fn summary_line(&self) -> String {
+        let list = |ids: &[&str]| {
+            if ids.is_empty() {
+                "-".to_string()
+            } else {
+                ids.join(",")
+            }
+        };
+        format!(
+            "rotd-check status={} health={:.1} threshold={:.1} passed={}/{} critical={} failed={}",
+            if self.passed { "pass" } else { "fail" },
+            self.health,
+            self.threshold,
+            self.checks_passed,
+            self.total_checks,
+            list(&self.critical),
+            list(&self.failed)
+        )
+    }
+
+    pub fn into_result(self) -> Result<()> {
+        if self.passed {
+            return Ok(());
+        }
+        if !self.critical.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Health check failed: critical check(s) failed: {}",
+                self.critical.join(", ")
+            ));
+        }
+        Err(anyhow::anyhow!(
+            "Health check failed: {:.1}% is below the {:.1}% threshold ({}/{} checks passed)",
+            self.health,
+            self.threshold,
+            self.checks_passed,
+            self.total_checks
+        ))
+    }
+}
+
+/// Threshold for `rotd check --ci`: the flag, else `ci_threshold` in the
+/// config, else 100 (every check must pass)
+pub fn ci_threshold(flag: Option<f64>) -> Result<f64> {
+    let threshold = match flag {
+        Some(value) => value,
+        None => crate::history::load_config()?.ci_threshold.unwrap_or(100.0),
+    };
+    if !(0.0..=100.0).contains(&threshold) {
+        return Err(anyhow::anyhow!(
+            "CI threshold must be between 0 and 100, got {}",
+            threshold
+        ));
+    }
+    Ok(threshold)
+}
+
+fn waiver_applies(waiver: &CheckWaiver, finding: &Finding) -> bool {
+    match &waiver.matches {
+        None => true,
+        Some(text) => {
+            finding.message.contains(text.as_str())
+                || finding
+                    .file
+                    .as_ref()
+                    .is_some_and(|f| f.to_string_lossy().contains(text.as_str()))
+        }
+    }
+}
+
+/// Apply check settings and waivers from the config to raw check outcomes
+pub fn apply(outcomes: Vec<Outcome>, config: &RotdConfig, today: NaiveDate) -> Result<CheckRun> {
+    let mut run = CheckRun::default();
+
+    let mut severities = std::collections::HashMap::new();
+    for (id, settings) in &config.checks {
+        if let Some(value) = &settings.severity {
+            let severity =
+                Severity::parse(value).map_err(|e| anyhow::anyhow!("checks.{}: {}", id, e))?;
+            severities.insert(id.as_str(), severity);
+        }
+    }
+
+    let mut unknown: Vec<String> = config
+        .checks
+        .keys()
+        .chain(config.waivers.iter().map(|w| &w.check))
+        .filter(|id| !CHECKS.iter().any(|(check, _)| check == id))
+        .cloned()
+        .collect();
+    unknown.sort();
+    unknown.dedup();
+    run.unknown_checks = unknown;
+
+    let (active, expired): (Vec<&CheckWaiver>, Vec<&CheckWaiver>) =
+        config.waivers.iter().partition(|w| w.expires >= today);
+    run.expired_waivers = expired.into_iter().cloned().collect();
+
+    for mut outcome in outcomes {
+        let settings = config.checks.get(outcome.id);
+        if settings.is_some_and(|s| !s.enabled) {
+            run.disabled.push(outcome.id);
+            continue;
+        }
+        let severity = severities
+            .get(outcome.id)
+            .copied()
+            .unwrap_or_else(|| default_severity(outcome.id));
+
+        let mut waived = Vec::new();
+        let mut findings = Vec::new();
+        for mut finding in std::mem::take(&mut outcome.findings) {
+            let waiver = active
+                .iter()
+                .find(|w| w.check == outcome.id && waiver_applies(w, &finding));
+            if let Some(waiver) = waiver {
+                waived.push(Waived {
+                    finding,
+                    reason: waiver.reason.clone(),
+                    expires: waiver.expires,
+                });
+                continue;
+            }
+            if severity == Severity::Warning {
+                finding.level = Level::Warning;
+            }
+            findings.push(finding);
+        }
+        outcome.findings = findings;
+
+        run.results.push(CheckResult {
+            outcome,
+            severity,
+            waived,
+        });
+    }
+
+    Ok(run)
+}
+
+/// Run the built-in checks with the project's check config applied
+pub fn run() -> Result<CheckRun> {
+    let config = crate::history::load_config()?;
+    apply(
+        crate::output::check_outcomes(),
+        &config,
+        Utc::now().date_naive(),
+    )
+}
+
+/// Status of the test summaries `check --fix` writes for completed tasks
+/// that have none; PSS treats them as missing until real results replace them
+pub const UNVERIFIED: &str = "unverified";
+
+/// A repair made by `check --fix`
+#[derive(Debug, Serialize)]
+pub struct Fix {
+    pub issue: &'static str,
+    /// Machine-readable name, e.g. `created_session_state`
+    pub action: &'static str,
+    pub message: String,
+}
+
+/// A failing check `check --fix` left alone, and why
+#[derive(Debug, Serialize)]
+pub struct Unfixed {
+    pub issue: String,
+    pub reason: String,
+}
+
+/// A file `check --fix --dry-run` would change, as a unified diff
+#[derive(Debug, Serialize)]
+pub struct Preview {
+    pub path: String,
+    pub diff: String,
+}
+
+#[derive(Debug, Default)]
+pub struct FixReport {
+    /// Repairs made, or under `--dry-run` the repairs that would be made
+    pub fixed: Vec<Fix>,
+    pub unfixed: Vec<Unfixed>,
+    /// Changes `--dry-run` held back
+    pub previews: Vec<Preview>,
+    /// Where the files were copied before being changed, if any were
+    pub backup_dir: Option<PathBuf>,
+}
+
+impl FixReport {
+    fn fixed(&mut self, issue: &'static str, action: &'static str, message: impl Into<String>) {
+        self.fixed.push(Fix {
+            issue,
+            action,
+            message: message.into(),
+        });
+    }
+
+    fn unfixed(&mut self, issue: &str, reason: impl Into<String>) {
+        self.unfixed.push(Unfixed {
+            issue: issue.to_string(),
+            reason: reason.into(),
+        });
+    }
+
+    /// Whether anything was fixed or reported for `issue`
+    fn covers(&self, issue: &str) -> bool {
+        self.fixed.iter().any(|f| f.issue == issue) || self.unfixed.iter().any(|u| u.issue == issue)
+    }
+}
+
+/// Every file `check --fix` touches goes through here: under `--dry-run`
+/// the change is recorded as a diff instead, otherwise the file is copied
+/// into the backup directory before its first change
+struct Changes {
+    dry_run: bool,
+    backup_dir: PathBuf,
+    backed_up: HashSet<PathBuf>,
+    previews: Vec<Preview>,
+}
+
+impl Changes {
+    fn new(dry_run: bool) -> Self {
+        let stamp = Utc::now().format("%Y%m%d%H%M%S");
+        Changes {
+            dry_run,
+            backup_dir: crate::common::backups_path().join(format!("fix-{}", stamp)),
+            backed_up: HashSet::new(),
+            previews: Vec::new(),
+        }
+    }
+
+    /// Change `path` so it reads `after` (`None` once it is gone) by running `apply`
+    fn apply(
+        &mut self,
+        path: &Path,
+        after: Option<&str>,
+        apply: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        let before = std::fs::read_to_string(path).ok();
+        if self.dry_run {
+            self.previews.push(Preview {
+                path: path.display().to_string(),
+                diff: unified_diff(path, before.as_deref(), after),
+            });
+            return Ok(());
+        }
+
+        if before.is_some() && self.backed_up.insert(path.to_path_buf()) {
+            let relative = path.strip_prefix(crate::common::rotd_path()).unwrap_or(path);
+            let target = self.backup_dir.join(relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(path, &target)
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+        }
+        apply()
+    }
+
+    fn write(&mut self, path: &Path, content: &str) -> Result<()> {
+        self.apply(path, Some(content), || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+        })
+    }
+
+    fn write_json<T: Serialize>(&mut self, path: &Path, item: &T) -> Result<()> {
+        self.write(path, &serde_json::to_string_pretty(item)?)
+    }
+}
+
+/// Run `f` holding `path`'s lock, except in a dry run, where taking the
+/// lock would itself write to `.rotd`
+fn locked<T>(dry_run: bool, path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    if dry_run {
+        f()
+    } else {
+        with_lock_result(path, f)
+    }
+}
+
+/// Unified diff from `before` to `after`, either of which may be a missing file
+pub fn unified_diff(path: &Path, before: Option<&str>, after: Option<&str>) -> String {
+    let name = path.display().to_string();
+    similar::TextDiff::from_lines(before.unwrap_or_default(), after.unwrap_or_default())
+        .unified_diff()
+        .header(
+            if before.is_some() { &name } else { "/dev/null" },
+            if after.is_some() { &name } else { "/dev/null" },
+        )
+        .to_string()
+}
+
+/// Repair what can be repaired for the failing checks in `issues`,
+/// reporting the rest with the reason they need a person. With `dry_run`
+/// nothing is written and the report carries a diff per file instead.
+pub fn fix(issues: &[&str], dry_run: bool) -> Result<FixReport> {
+    let mut changes = Changes::new(dry_run);
+    let mut report = FixReport::default();
+    for &issue in issues {
+        if let Err(e) = fix_issue(issue, &mut changes, &mut report) {
+            report.unfixed(issue, format!("{:#}", e));
+        } else if !report.covers(issue) {
+            report.unfixed(issue, "No automatic fix applies");
+        }
+    }
+
+    report.previews = changes.previews;
+    report.backup_dir = Some(changes.backup_dir).filter(|_| !changes.backed_up.is_empty());
+    Ok(report)
+}
+
+fn fix_issue(issue: &str, changes: &mut Changes, report: &mut FixReport) -> Result<()> {
+    match issue {
+        "missing_required_files" => create_required_files(changes, report)?,
+        "invalid_jsonl" => repair_tasks_jsonl(changes, report)?,
+        "missing_test_summaries" => scaffold_test_summaries(changes, report)?,
+        "invalid_session_state" => regenerate_session_state(changes, report)?,
+        "dependency_integrity" => {
+            let rewritten = remove_dangling_dependencies(changes)?;
+            if rewritten.is_empty() {
+                report.unfixed(
+                    issue,
+                    "Dependencies on incomplete tasks clear once those tasks complete",
+                );
+            } else {
+                report.fixed(
+                    "dependency_integrity",
+                    "removed_dangling_dependencies",
+                    format!("Removed dangling dependencies from task(s) {}", rewritten.join(", ")),
+                );
+            }
+        }
+        "orphaned_test_summaries" => {
+            let archived = archive_orphaned_test_summaries(changes)?;
+            if !archived.is_empty() {
+                report.fixed(
+                    "orphaned_test_summaries",
+                    "archived_orphaned_test_summaries",
+                    format!("Archived test summaries for unknown task(s) {}", archived.join(", ")),
+                );
+            }
+        }
+        "orphaned_pss_scores" => {
+            let archived = archive_orphaned_pss_scores(changes)?;
+            if archived > 0 {
+                report.fixed(
+                    "orphaned_pss_scores",
+                    "archived_orphaned_pss_scores",
+                    format!("Archived {} orphaned PSS score(s)", archived),
+                );
+            }
+        }
+        "stubs_remaining" => report.unfixed(
+            issue,
+            "Stubs need real implementations; `rotd stubs` lists them",
+        ),
+        "stale_session" => report.unfixed(
+            issue,
+            "Only the agent knows whether the session is still going; run `rotd session start` or `rotd session end`",
+        ),
+        "completed_tasks_without_commits" => report.unfixed(
+            issue,
+            "Link each task's commit with `rotd git attach <task_id>`",
+        ),
+        "milestone_at_risk" => report.unfixed(
+            issue,
+            "A forecast rather than a defect; adjust the milestone's scope or target date",
+        ),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Drop `depends_on` entries that name neither a task nor an archived
+/// task, appending the corrected tasks. Returns the IDs of the tasks that
+/// were rewritten.
+fn remove_dangling_dependencies(changes: &mut Changes) -> Result<Vec<String>> {
+    let archived = crate::fs_ops::read_jsonl::<TaskEntry>(&crate::common::archived_tasks_path())
+        .unwrap_or_default();
+    let tasks = readable_tasks();
+    let known = |id: &String| tasks.iter().chain(&archived).any(|t| &t.id == id);
+
+    let mut updated = Vec::new();
+    for task in &tasks {
+        let Some(deps) = &task.depends_on else {
+            continue;
+        };
+        if deps.iter().all(known) {
+            continue;
+        }
+        let mut task = task.clone();
+        let kept: Vec<String> = deps.iter().filter(|d| known(d)).cloned().collect();
+        task.depends_on = (!kept.is_empty()).then_some(kept);
+        task.update_timestamp();
+        updated.push(task);
+    }
+    if updated.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let path = crate::common::tasks_path();
+    let mut after = std::fs::read_to_string(&path).unwrap_or_default();
+    for task in &updated {
+        after.push_str(&serde_json::to_string(task)?);
+        after.push('\n');
+    }
+    changes.apply(&path, Some(&after), || {
+        crate::fs_ops::append_tasks(&updated, Some("check --fix: removed dangling dependencies"))
+    })?;
+    Ok(updated.into_iter().map(|t| t.id).collect())
+}
+
+fn task_ids() -> HashSet<String> {
+    readable_tasks().into_iter().map(|t| t.id).collect()
+}
+
+/// Move summaries of tasks that are no longer in tasks.jsonl into the
+/// archive. Returns their task IDs.
+fn archive_orphaned_test_summaries(changes: &mut Changes) -> Result<Vec<String>> {
+    let known = task_ids();
+    let archive = crate::common::archived_test_summaries_path();
+
+    let mut archived = Vec::new();
+    for (path, task_id) in crate::output::test_summary_files() {
+        if known.contains(&task_id) {
+            continue;
+        }
+        let target = archive.join(path.file_name().unwrap_or_default());
+        let content = std::fs::read_to_string(&path)?;
+        changes.write(&target, &content)?;
+        changes.apply(&path, None, || {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))
+        })?;
+        archived.push(task_id);
+    }
+    Ok(archived)
+}
+
+/// Move score lines for tasks that are no longer in tasks.jsonl to the
+/// archive. Lines that don't parse are left for `invalid_jsonl`-style
+/// repair. Returns how many were moved.
+fn archive_orphaned_pss_scores(changes: &mut Changes) -> Result<usize> {
+    let known = task_ids();
+    let path = crate::common::pss_scores_path();
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    locked(changes.dry_run, &path, || {
+        let content = std::fs::read_to_string(&path)?;
+        let (orphaned, kept): (Vec<&str>, Vec<&str>) = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .partition(|line| {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .ok()
+                    .and_then(|v| v.get("task_id")?.as_str().map(str::to_string))
+                    .is_some_and(|id| !known.contains(&id))
+            });
+        if orphaned.is_empty() {
+            return Ok(0);
+        }
+
+        let archive_path = crate::common::archived_pss_scores_path();
+        let mut archive = std::fs::read_to_string(&archive_path).unwrap_or_default();
+        for line in &orphaned {
+            archive.push_str(line);
+            archive.push('\n');
+        }
+        changes.apply(&archive_path, Some(&archive), || {
+            orphaned
+                .iter()
+                .try_for_each(|line| append_line(&archive_path, line))
+        })?;
+
+        let mut rewritten = kept.join("\n");
+        if !rewritten.is_empty() {
+            rewritten.push('\n');
+        }
+        changes.write(&path, &rewritten)?;
+        Ok(orphaned.len())
+    })
+}
+
+/// Latest entry per task from the lines of tasks.jsonl that parse, for
+/// work that should carry on while other lines are broken
+pub fn readable_tasks() -> Vec<TaskEntry> {
+    let (entries, _) = crate::output::parse_jsonl_lines::<TaskEntry>(&crate::common::tasks_path());
+    let mut latest: Vec<TaskEntry> = Vec::new();
+    for (_, task) in entries {
+        match latest.iter_mut().find(|t| t.id == task.id) {
+            Some(slot) => *slot = task,
+            None => latest.push(task),
+        }
+    }
+    latest
+}
+
+fn create_required_files(changes: &mut Changes, report: &mut FixReport) -> Result<()> {
+    const ISSUE: &str = "missing_required_files";
+    let created = |path: &Path| {
+        format!("Created {}", path.file_name().unwrap_or_default().to_string_lossy())
+    };
+
+    let tasks_path = crate::common::tasks_path();
+    if !tasks_path.exists() {
+        changes.write(&tasks_path, "")?;
+        report.fixed(ISSUE, "created_tasks_file", created(&tasks_path));
+    }
+
+    let session_path = crate::common::session_state_path();
+    if !session_path.exists() {
+        let session_state = SessionState {
+            session_id: "fix".to_string(),
+            timestamp: Utc::now(),
+            current_task: None,
+            status: "initialized".to_string(),
+            deltas: None,
+            schema: SESSION_STATE_SCHEMA.to_string(),
+        };
+        changes.write_json(&session_path, &session_state)?;
+        report.fixed(ISSUE, "created_session_state", created(&session_path));
+    }
+
+    let coverage_path = crate::common::coverage_history_path();
+    if !coverage_path.exists() {
+        let coverage_history = CoverageHistory {
+            floor: 70.0,
+            ratchet_threshold: 3.0,
+            history: Vec::new(),
+        };
+        changes.write_json(&coverage_path, &coverage_history)?;
+        report.fixed(ISSUE, "created_coverage_history", created(&coverage_path));
+    }
+    Ok(())
+}
+
+/// Rewrite tasks.jsonl with the lines that common JSON slips broke. The
+/// rewrite happens under the file's lock, and the hash chain is re-linked
+/// over the result, since a repaired line no longer hashes to the link the
+/// line after it carries.
+fn repair_tasks_jsonl(changes: &mut Changes, report: &mut FixReport) -> Result<()> {
+    const ISSUE: &str = "invalid_jsonl";
+    let path = crate::common::tasks_path();
+
+    locked(changes.dry_run, &path, || {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let mut lines = Vec::new();
+        let mut repaired = 0;
+        let mut broken = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if serde_json::from_str::<TaskEntry>(line).is_ok() {
+                lines.push(line.to_string());
+                continue;
+            }
+            let candidate = crate::agent::fix_common_json_errors(line);
+            match serde_json::from_str::<serde_json::Value>(&candidate) {
+                Ok(value) if serde_json::from_value::<TaskEntry>(value.clone()).is_ok() => {
+                    lines.push(relinkable(value)?);
+                    repaired += 1;
+                }
+                _ => {
+                    lines.push(line.to_string());
+                    broken.push((line_num + 1).to_string());
+                }
+            }
+        }
+
+        if repaired > 0 {
+            let lines = crate::integrity::relink(lines);
+            changes.write(&path, &(lines.join("\n") + "\n"))?;
+            report.fixed(
+                ISSUE,
+                "fixed_jsonl_format",
+                format!("Fixed JSON format in tasks.jsonl (fixed {} lines)", repaired),
+            );
+        }
+        if !broken.is_empty() {
+            report.unfixed(
+                ISSUE,
+                format!(
+                    "Line(s) {} of tasks.jsonl are not valid tasks even after repair; edit them by hand",
+                    broken.join(", ")
+                ),
+            );
+        }
+        Ok(())
+    })
+}
+
+/// Serialize a repaired task line with its chain link, if it had one, moved
+/// back to the end where `integrity::relink` expects it
+fn relinkable(mut value: serde_json::Value) -> Result<String> {
+    use crate::integrity::{AGENT_FIELD, PREV_FIELD};
+    let link = value.as_object_mut().and_then(|map| {
+        let prev = map.remove(PREV_FIELD)?;
+        let agent = map.remove(AGENT_FIELD)?;
+        Some([(PREV_FIELD, prev.to_string()), (AGENT_FIELD, agent.to_string())])
+    });
+    let line = serde_json::to_string(&value)?;
+    match link {
+        Some(fields) => crate::integrity::append_fields(&line, &fields),
+        None => Ok(line),
+    }
+}
+
+/// Write an unverified placeholder summary for each completed task without
+/// one, signed like any other summary when the agent has a key
+fn scaffold_test_summaries(changes: &mut Changes, report: &mut FixReport) -> Result<()> {
+    let mut scaffolded = Vec::new();
+    for task in readable_tasks() {
+        let path = crate::common::test_summary_file(&task.id);
+        if !matches!(task.status, TaskStatus::Complete) || path.exists() {
+            continue;
+        }
+        let summary = TestSummary {
+            task_id: task.id.clone(),
+            status: UNVERIFIED.to_string(),
+            total_tests: 0,
+            passed: 0,
+            failed: 0,
+            warnings: None,
+            coverage: None,
+            verified_by: "rotd check --fix".to_string(),
+            timestamp: Utc::now(),
+            notes: Some("Placeholder; replace with the task's real test results".to_string()),
+            schema: TEST_SUMMARY_SCHEMA.to_string(),
+        };
+        let mut document = serde_json::to_value(&summary)?;
+        crate::signing::sign_document(&mut document)?;
+        changes.write_json(&path, &document)?;
+        scaffolded.push(task.id);
+    }
+    if !scaffolded.is_empty() {
+        report.fixed(
+            "missing_test_summaries",
+            "scaffolded_test_summaries",
+            format!(
+                "Wrote unverified placeholder test summaries for task(s) {}; replace them with real results",
+                scaffolded.join(", ")
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// Rebuild session_state.json from the session log and task history
+fn regenerate_session_state(changes: &mut Changes, report: &mut FixReport) -> Result<()> {
+    let path = crate::common::session_state_path();
+    let log: Vec<SessionLogEntry> =
+        crate::fs_ops::read_jsonl(&crate::common::sessions_path()).unwrap_or_default();
+    let history = crate::history::read_all_history().unwrap_or_default();
+    let state = crate::session::rebuild_state(&log, &history, &readable_tasks(), Utc::now());
+
+    changes.write_json(&path, &state)?;
+    report.fixed(
+        "invalid_session_state",
+        "regenerated_session_state",
+        format!(
+            "Regenerated session_state.json for session {}{}",
+            state.session_id,
+            if log.is_empty() { " from task history" } else { " from the session log" }
+        ),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::CheckSettings;
+
+    #[test]
+    fn test_apply_settings_and_waivers() {
+        let outcomes = vec![
+            Outcome {
+                id: "stubs_remaining",
+                findings: vec![
+                    Finding::new("stubs_remaining", "Stub marker TODO remains")
+                        .at(std::path::Path::new("src/legacy.rs"), Some(3)),
+                    Finding::new("stubs_remaining", "Stub marker FIXME remains")
+                        .at(std::path::Path::new("src/main.rs"), Some(9)),
+                ],
+            },
+            Outcome {
+                id: "missing_test_summaries",
+                findings: vec![Finding::new("missing_test_summaries", "Task 1.1")],
+            },
+            Outcome {
+                id: "invalid_session_state",
+                findings: vec![Finding::new("invalid_session_state", "Invalid")],
+            },
+        ];
+
+        let mut config = RotdConfig::default();
+        config.checks.insert(
+            "missing_test_summaries".to_string(),
+            CheckSettings {
+                enabled: true,
+                severity: Some("warning".to_string()),
+            },
+        );
+        config.checks.insert(
+            "invalid_session_state".to_string(),
+            CheckSettings {
+                enabled: false,
+                severity: None,
+            },
+        );
+        let waiver = |matches: &str, expires: &str| CheckWaiver {
+            check: "stubs_remaining".to_string(),
+            matches: Some(matches.to_string()),
+            reason: "Legacy module".to_string(),
+            expires: expires.parse().unwrap(),
+        };
+        config.waivers = vec![
+            waiver("legacy.rs", "2025-06-30"),
+            waiver("main.rs", "2025-01-01"),
+        ];
+
+        let today: NaiveDate = "2025-03-01".parse().unwrap();
+        let run = apply(outcomes, &config, today).unwrap();
+
+        assert_eq!(run.disabled, vec!["invalid_session_state"]);
+        assert_eq!(run.results.len(), 2);
+        assert_eq!(run.expired_waivers.len(), 1);
+
+        // The active waiver suppresses one finding; the expired one does not
+        let stubs = &run.results[0];
+        assert_eq!(stubs.waived.len(), 1);
+        assert_eq!(stubs.outcome.findings.len(), 1);
+        assert!(!stubs.outcome.passed());
+
+        // Downgraded to a warning, so the check passes
+        let summaries = &run.results[1];
+        assert_eq!(summaries.severity, Severity::Warning);
+        assert!(summaries.outcome.passed());
+        assert_eq!(run.passed(), 1);
+
+        // 1 of 2 checks passed
+        assert!(run.ci_verdict(50.0).passed);
+        assert!(!run.ci_verdict(60.0).passed);
+
+        config
+            .checks
+            .get_mut("missing_test_summaries")
+            .unwrap()
+            .severity = Some("loud".to_string());
+        assert!(apply(Vec::new(), &config, today).is_err());
+    }
+}
@@ -159,7 +159,7 @@ mod tests {
         assert!(json.contains("task_id"));
 
         let deserialized: BuckleModeState = serde_json::from_str(&json).unwrap();
-        assert_eq!(deserialized.active, true);
+        assert!(deserialized.active);
         assert_eq!(deserialized.task_id, Some("6.2".to_string()));
     }
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use std::path::{Path, PathBuf};
 
 pub const ROTD_DIR: &str = ".rotd";
@@ -5,53 +6,199 @@ pub const TASKS_FILE: &str = "tasks.jsonl";
 pub const LESSONS_FILE: &str = "lessons_learned.jsonl";
 pub const PSS_SCORES_FILE: &str = "pss_scores.jsonl";
 pub const SESSION_STATE_FILE: &str = "session_state.json";
+pub const SESSIONS_FILE: &str = "sessions.jsonl";
 pub const COVERAGE_HISTORY_FILE: &str = "coverage_history.json";
 pub const AUDIT_LOG_FILE: &str = "audit.log";
 pub const TEST_SUMMARIES_DIR: &str = "test_summaries";
-#[allow(dead_code)]
 pub const COORDINATION_DIR: &str = "coordination";
-#[allow(dead_code)]
 pub const ACTIVE_WORK_REGISTRY_FILE: &str = "active_work_registry.json";
 pub const TASK_HISTORY_DIR: &str = "task_history";
+pub const INTEGRATIONS_DIR: &str = "integrations";
 pub const CONFIG_FILE: &str = "config.jsonc";
+pub const BUCKLE_STATE_FILE: &str = "buckle_state.json";
+pub const COMPILE_CACHE_FILE: &str = "compile_cache.json";
+pub const MILESTONES_FILE: &str = "milestones.json";
+pub const TEMPLATES_DIR: &str = "templates";
+pub const BACKUPS_DIR: &str = "backups";
+pub const AGENTS_FILE: &str = "agents.json";
+pub const KEYS_DIR: &str = "keys";
+pub const SYNC_DIR: &str = "sync";
+pub const GITHUB_TOKEN_FILE: &str = "github_token";
+pub const LOCAL_CONFIG_FILE: &str = "config.local.jsonc";
+pub const PRIMER_FILE: &str = "primer.jsonc";
+pub const PRIMERS_DIR: &str = "primers";
+pub const VERSION_FILE: &str = "version.json";
+pub const UPDATE_MANIFEST_FILE: &str = "update_manifest.json";
+pub const SCHEMAS_DIR: &str = "schemas";
+pub const ARCHIVE_DIR: &str = "archive";
+pub const GITIGNORE_FILE: &str = ".gitignore";
+/// Version-controlled artifacts in the split layout
+pub const SHARED_DIR: &str = "shared";
+/// Machine-local state (session, locks, heartbeats) in the split layout
+pub const LOCAL_DIR: &str = "local";
 
 pub fn rotd_path() -> PathBuf {
     Path::new(ROTD_DIR).to_path_buf()
 }
 
+/// Whether `.rotd/` uses the `shared/` + `local/` split created by `rotd init --git`
+pub fn is_split_layout() -> bool {
+    rotd_path().join(SHARED_DIR).is_dir()
+}
+
+/// Directory for artifacts that belong in git.
+/// Projects using the original flat layout resolve to `.rotd/` itself.
+pub fn shared_path() -> PathBuf {
+    if is_split_layout() {
+        rotd_path().join(SHARED_DIR)
+    } else {
+        rotd_path()
+    }
+}
+
+/// Directory for machine-local state that should not be committed.
+/// Projects using the original flat layout resolve to `.rotd/` itself.
+pub fn local_path() -> PathBuf {
+    if is_split_layout() {
+        rotd_path().join(LOCAL_DIR)
+    } else {
+        rotd_path()
+    }
+}
+
 pub fn tasks_path() -> PathBuf {
-    rotd_path().join(TASKS_FILE)
+    shared_path().join(TASKS_FILE)
 }
 
 pub fn lessons_path() -> PathBuf {
-    rotd_path().join(LESSONS_FILE)
+    shared_path().join(LESSONS_FILE)
 }
 
 pub fn pss_scores_path() -> PathBuf {
-    rotd_path().join(PSS_SCORES_FILE)
+    shared_path().join(PSS_SCORES_FILE)
 }
 
 pub fn session_state_path() -> PathBuf {
-    rotd_path().join(SESSION_STATE_FILE)
+    local_path().join(SESSION_STATE_FILE)
+}
+
+/// Log of sessions starting and ending, shared so hand-offs between
+/// machines stay visible
+pub fn sessions_path() -> PathBuf {
+    shared_path().join(SESSIONS_FILE)
 }
 
 pub fn coverage_history_path() -> PathBuf {
-    rotd_path().join(COVERAGE_HISTORY_FILE)
+    shared_path().join(COVERAGE_HISTORY_FILE)
 }
 
 pub fn audit_log_path() -> PathBuf {
-    rotd_path().join(AUDIT_LOG_FILE)
+    local_path().join(AUDIT_LOG_FILE)
+}
+
+pub fn buckle_state_path() -> PathBuf {
+    local_path().join(BUCKLE_STATE_FILE)
+}
+
+/// Milestones with their target dates and assigned tasks
+pub fn milestones_path() -> PathBuf {
+    shared_path().join(MILESTONES_FILE)
+}
+
+/// Task templates used by `rotd task new --template`
+pub fn templates_path() -> PathBuf {
+    shared_path().join(TEMPLATES_DIR)
+}
+
+/// Copies of files taken before `rotd check --fix` changes them
+pub fn backups_path() -> PathBuf {
+    local_path().join(BACKUPS_DIR)
+}
+
+/// Agent profiles with the public keys their entries are signed with
+pub fn agents_path() -> PathBuf {
+    shared_path().join(AGENTS_FILE)
+}
+
+/// Private signing keys of the agents working on this machine
+pub fn keys_path() -> PathBuf {
+    local_path().join(KEYS_DIR)
+}
+
+/// What `rotd sync` last agreed with the remote, and its git checkout
+pub fn sync_path() -> PathBuf {
+    local_path().join(SYNC_DIR)
+}
+
+/// GitHub token for this machine, kept out of config and version control
+pub fn github_token_path() -> PathBuf {
+    local_path().join(GITHUB_TOKEN_FILE)
+}
+
+/// Settings for this machine only, kept out of the committed config
+pub fn local_config_path() -> PathBuf {
+    local_path().join(LOCAL_CONFIG_FILE)
+}
+
+/// Last compile check results, reused while sources are unchanged
+pub fn compile_cache_path() -> PathBuf {
+    local_path().join(COMPILE_CACHE_FILE)
+}
+
+pub fn primer_path() -> PathBuf {
+    shared_path().join(PRIMER_FILE)
+}
+
+/// Scoped primers for parts of a monorepo, one `<scope>.jsonc` per scope
+pub fn primers_path() -> PathBuf {
+    shared_path().join(PRIMERS_DIR)
+}
+
+pub fn version_path() -> PathBuf {
+    shared_path().join(VERSION_FILE)
+}
+
+pub fn update_manifest_path() -> PathBuf {
+    shared_path().join(UPDATE_MANIFEST_FILE)
+}
+
+pub fn github_integration_path() -> PathBuf {
+    shared_path().join(INTEGRATIONS_DIR).join("github.json")
+}
+
+pub fn coordination_path() -> PathBuf {
+    local_path().join(COORDINATION_DIR)
 }
 
-#[allow(dead_code)]
 pub fn active_work_registry_path() -> PathBuf {
-    rotd_path()
-        .join(COORDINATION_DIR)
-        .join(ACTIVE_WORK_REGISTRY_FILE)
+    coordination_path().join(ACTIVE_WORK_REGISTRY_FILE)
+}
+
+/// User-supplied `<artifact>.schema.json` overlays checked by `validate --strict`
+pub fn schemas_path() -> PathBuf {
+    shared_path().join(SCHEMAS_DIR)
+}
+
+/// Artifacts retired from the working set; archived tasks keep their IDs
+/// resolvable for dependency checks
+pub fn archive_path() -> PathBuf {
+    shared_path().join(ARCHIVE_DIR)
+}
+
+pub fn archived_tasks_path() -> PathBuf {
+    archive_path().join(TASKS_FILE)
+}
+
+pub fn archived_test_summaries_path() -> PathBuf {
+    archive_path().join(TEST_SUMMARIES_DIR)
+}
+
+pub fn archived_pss_scores_path() -> PathBuf {
+    archive_path().join(PSS_SCORES_FILE)
 }
 
 pub fn test_summaries_path() -> PathBuf {
-    rotd_path().join(TEST_SUMMARIES_DIR)
+    shared_path().join(TEST_SUMMARIES_DIR)
 }
 
 pub fn test_summary_file(task_id: &str) -> PathBuf {
@@ -59,7 +206,7 @@ pub fn test_summary_file(task_id: &str) -> PathBuf {
 }
 
 pub fn task_history_path() -> PathBuf {
-    rotd_path().join(TASK_HISTORY_DIR)
+    shared_path().join(TASK_HISTORY_DIR)
 }
 
 pub fn task_history_file(task_id: &str) -> PathBuf {
@@ -67,7 +214,7 @@ pub fn task_history_file(task_id: &str) -> PathBuf {
 }
 
 pub fn config_path() -> PathBuf {
-    rotd_path().join(CONFIG_FILE)
+    shared_path().join(CONFIG_FILE)
 }
 
 pub fn check_rotd_initialized() -> anyhow::Result<()> {
@@ -78,3 +225,74 @@ pub fn check_rotd_initialized() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// Parse a time bound given on the command line: a date (`2026-01-31`), an
+/// RFC 3339 time, or an age before now (`12h`, `7d`, `2w`). A bare date is
+/// the start of that day, or its last instant with `end_of_day`.
+pub fn parse_time_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        return Ok(if end_of_day {
+            start + Duration::days(1) - Duration::nanoseconds(1)
+        } else {
+            start
+        });
+    }
+
+    let unit_at = value.len().saturating_sub(1);
+    let age = value[..unit_at].parse::<i64>().ok().and_then(|n| {
+        match &value[unit_at..] {
+            "h" => Duration::try_hours(n),
+            "d" => Duration::try_days(n),
+            "w" => Duration::try_weeks(n),
+            _ => None,
+        }
+    });
+    match age {
+        Some(age) if !value.starts_with(['-', '+']) => Ok(Utc::now() - age),
+        _ => Err(format!(
+            "invalid time '{}': expected YYYY-MM-DD, an RFC 3339 time, or an age like 12h, 7d, 2w",
+            value
+        )),
+    }
+}
+
+/// Value parser for `--since` options
+pub fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_time_bound(value, false)
+}
+
+/// Value parser for `--until` options; a bare date includes the whole day
+pub fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_time_bound(value, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_bound() {
+        assert_eq!(
+            parse_since("2026-01-31").unwrap().to_rfc3339(),
+            "2026-01-31T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_until("2026-01-31").unwrap().to_rfc3339(),
+            "2026-01-31T23:59:59.999999999+00:00"
+        );
+        assert_eq!(
+            parse_until("2026-01-31T12:00:00+02:00").unwrap().to_rfc3339(),
+            "2026-01-31T10:00:00+00:00"
+        );
+        let week = Utc::now() - parse_since("7d").unwrap();
+        assert!((week - Duration::days(7)).num_seconds().abs() < 5);
+        assert!(parse_since("7x").is_err());
+        assert!(parse_since("-7d").is_err());
+        assert!(parse_since("").is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, mpsc};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::schema::{CompileCheck, RotdConfig};
+
+/// Seconds a compile check may run when the config sets no timeout
+pub const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+/// Seconds cached results are reused when the config sets no TTL
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 300;
+
+/// Build manifests whose changes invalidate cached results, alongside the
+/// files covered by stub detection
+const MANIFESTS: [&str; 9] = [
+    "Cargo.toml",
+    "Cargo.lock",
+    "package.json",
+    "package-lock.json",
+    "tsconfig.json",
+    "go.mod",
+    "go.sum",
+    "pyproject.toml",
+    "requirements.txt",
+];
+
+/// Results from earlier in this invocation, so `score --all` and Buckle
+/// Mode diagnostics compile once
+static MEMO: Mutex<Option<CompileCache>> = Mutex::new(None);
+
+/// Result of running one compile check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompileOutcome {
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    pub success: bool,
+    pub timed_out: bool,
+    /// Output lines reporting an error
+    pub errors: Vec<String>,
+}
+
+impl CompileOutcome {
+    /// One-line description for reports, e.g. `cargo check (3 errors)`
+    pub fn describe(&self) -> String {
+        let location = match &self.dir {
+            Some(dir) => format!("{} in {}", self.command, dir),
+            None => self.command.clone(),
+        };
+        if self.timed_out {
+            format!("{} (timed out)", location)
+        } else if self.success {
+            location
+        } else {
+            format!("{} ({} errors)", location, self.errors.len())
+        }
+    }
+}
+
+/// Compile checks for the project: `compile_checks` from the config, else
+/// `compile_command`, else `npm run typecheck` or `cargo check` depending on
+/// the manifest present. Projects with none of these have nothing to check.
+pub fn configured_checks(config: &RotdConfig) -> Vec<CompileCheck> {
+    if !config.compile_checks.is_empty() {
+        return config.compile_checks.clone();
+    }
+    let command = match &config.compile_command {
+        Some(command) => command.as_str(),
+        None if Path::new("package.json").exists() => "npm run typecheck",
+        None if Path::new("Cargo.toml").exists() => "cargo check",
+        None => return Vec::new(),
+    };
+    vec![CompileCheck {
+        command: command.to_string(),
+        dir: None,
+        timeout_secs: None,
+    }]
+}
+
+/// Compile check results keyed by the state of the sources they ran against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompileCache {
+    pub key: String,
+    pub checked_at: DateTime<Utc>,
+    pub outcomes: Vec<CompileOutcome>,
+}
+
+impl CompileCache {
+    /// Whether the results still apply to sources with `key`
+    pub fn is_fresh(&self, key: &str, ttl_secs: u64, now: DateTime<Utc>) -> bool {
+        self.key == key && (now - self.checked_at).num_seconds() < ttl_secs as i64
+    }
+}
+
+/// Hash of the checks, HEAD commit, and the paths, sizes, and modification
+/// times of the source files and build manifests
+pub fn cache_key(config: &RotdConfig, checks: &[CompileCheck]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(checks).unwrap_or_default());
+    hasher.update(crate::git::run_git(&["rev-parse", "HEAD"]).unwrap_or_default());
+
+    let mut files = crate::pss::source_files(config);
+    files.extend(MANIFESTS.iter().map(|m| Path::new(m).to_path_buf()));
+    files.sort();
+    for file in files {
+        let Ok(metadata) = std::fs::metadata(&file) else {
+            continue;
+        };
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        hasher.update(file.to_string_lossy().as_bytes());
+        hasher.update(metadata.len().to_le_bytes());
+        hasher.update(modified.as_nanos().to_le_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Run every configured compile check in order, reusing results from
+/// earlier in this invocation or from a recent run against the same sources
+pub fn run(config: &RotdConfig) -> Vec<CompileOutcome> {
+    let checks = configured_checks(config);
+    let ttl_secs = config.compile_cache_ttl_secs.unwrap_or(DEFAULT_CACHE_TTL_SECS);
+    if checks.is_empty() || ttl_secs == 0 {
+        return checks.iter().map(run_check).collect();
+    }
+
+    let key = cache_key(config, &checks);
+    let mut memo = MEMO.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(cache) = memo.as_ref().filter(|c| c.key == key) {
+        return cache.outcomes.clone();
+    }
+    let path = crate::common::compile_cache_path();
+    let stored: Option<CompileCache> = crate::fs_ops::read_json(&path).ok();
+    if let Some(cache) = stored.filter(|c| c.is_fresh(&key, ttl_secs, Utc::now())) {
+        let outcomes = cache.outcomes.clone();
+        *memo = Some(cache);
+        return outcomes;
+    }
+
+    let outcomes: Vec<CompileOutcome> = checks.iter().map(run_check).collect();
+    // A timeout says more about the machine than the code, so run again next time
+    if outcomes.iter().all(|o| !o.timed_out) {
+        let cache = CompileCache {
+            key,
+            checked_at: Utc::now(),
+            outcomes: outcomes.clone(),
+        };
+        let _ = crate::fs_ops::write_json(&path, &cache);
+        *memo = Some(cache);
+    }
+    outcomes
+}
+
+/// Whether every compile check passes
+pub fn compiles(outcomes: &[CompileOutcome]) -> bool {
+    outcomes.iter().all(|o| o.success)
+}
+
+fn run_check(check: &CompileCheck) -> CompileOutcome {
+    let mut outcome = CompileOutcome {
+        command: check.command.clone(),
+        dir: check.dir.clone(),
+        success: false,
+        timed_out: false,
+        errors: Vec::new(),
+    };
+
+    // Quoted arguments stay whole, as in a shell, but nothing else is
+    // interpreted: no pipes, globs, or variables
+    let parts = match shell_words::split(&check.command) {
+        Ok(parts) => parts,
+        Err(e) => {
+            outcome.errors.push(format!("Invalid command {}: {}", check.command, e));
+            return outcome;
+        }
+    };
+    let Some((program, args)) = parts.split_first() else {
+        outcome.success = true;
+        return outcome;
+    };
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(dir) = &check.dir {
+        command.current_dir(dir);
+    }
+    // Its own process group, so a timeout also stops what it spawned
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            outcome.errors.push(format!("Failed to run {}: {}", program, e));
+            return outcome;
+        }
+    };
+
+    // Drain both pipes while waiting so a chatty compiler can't block on a
+    // full pipe; type checkers like tsc report errors on stdout
+    let (sender, received) = mpsc::channel();
+    let readers = [
+        child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .enumerate()
+    .map(|(index, mut pipe)| {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            let mut output = String::new();
+            let _ = pipe.read_to_string(&mut output);
+            let _ = sender.send((index, output));
+        })
+    })
+    .count();
+    drop(sender);
+
+    let timeout = Duration::from_secs(check.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(50))
+            }
+            _ => {
+                kill_process_group(&mut child);
+                break None;
+            }
+        }
+    };
+
+    // A process that escaped the group, such as a daemonized build server,
+    // can hold the pipes open indefinitely, so stop waiting for output
+    // shortly after the check ends
+    let grace = Instant::now().max(deadline) + Duration::from_secs(1);
+    let mut outputs: Vec<(usize, String)> = Vec::new();
+    for _ in 0..readers {
+        match received.recv_timeout(grace.saturating_duration_since(Instant::now())) {
+            Ok(output) => outputs.push(output),
+            Err(_) => break,
+        }
+    }
+    outputs.sort();
+    let output: String = outputs
+        .into_iter()
+        .map(|(_, output)| output)
+        .collect::<Vec<_>>()
+        .join("\n");
+    outcome.errors = error_lines(&output);
+    match status {
+        Some(status) => outcome.success = status.success(),
+        None => outcome.timed_out = true,
+    }
+    outcome
+}
+
+/// Kill a timed-out check and everything in its process group
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Lines of compiler output that report an error, for the common Rust,
+/// TypeScript, C-family, Go, and Python formats. Summary lines such as
+/// cargo's `error: could not compile` are not counted.
+pub fn error_lines(output: &str) -> Vec<String> {
+    let error = Regex::new(
+        r"^(error(\[\w+\])?:|\S+\.go:\d+:\d+: |\*\*\* Error compiling|\s*\w*Error: )|\berror TS\d+:|:\d+(:\d+)?: (fatal )?error:",
+    )
+    .unwrap();
+    let summary = Regex::new(r"^error: (could not compile|aborting due to)").unwrap();
+    output
+        .lines()
+        .filter(|line| error.is_match(line) && !summary.is_match(line))
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_lines() {
+        let output = "\
+    Checking rotd v1.3.1
+error[E0425]: cannot find value `x` in this scope
+ --> src/main.rs:3:5
+warning: unused variable: `y`
+error: expected `;`, found `}`
+error: could not compile `rotd` (bin \"rotd\") due to 2 previous errors
+src/index.ts(4,7): error TS2322: Type 'string' is not assignable to type 'number'.
+./main.go:5:2: undefined: foo
+lib.c:10:3: error: use of undeclared identifier 'n'
+  File \"app.py\", line 2
+SyntaxError: invalid syntax
+";
+        let errors = error_lines(output);
+        assert_eq!(errors.len(), 6);
+        assert!(errors[0].starts_with("error[E0425]"));
+        assert!(errors.iter().all(|e| !e.contains("could not compile")));
+    }
+
+    #[test]
+    fn test_cache_freshness() {
+        let now = Utc::now();
+        let cache = CompileCache {
+            key: "abc".to_string(),
+            checked_at: now - chrono::Duration::seconds(60),
+            outcomes: Vec::new(),
+        };
+
+        assert!(cache.is_fresh("abc", 300, now));
+        assert!(!cache.is_fresh("abd", 300, now));
+        assert!(!cache.is_fresh("abc", 60, now));
+    }
+
+    #[test]
+    fn test_run_check() {
+        let check = |command: &str, timeout_secs| {
+            run_check(&CompileCheck {
+                command: command.to_string(),
+                dir: None,
+                timeout_secs,
+            })
+        };
+
+        assert!(check("true", None).success);
+        let failed = check("false", None);
+        assert!(!failed.success && !failed.timed_out);
+        let slow = check("sleep 5", Some(0));
+        assert!(slow.timed_out && !slow.success);
+        assert_eq!(slow.describe(), "sleep 5 (timed out)");
+        let missing = check("rotd-no-such-compiler", None);
+        assert_eq!(missing.errors.len(), 1);
+
+        // Quoting works as in a shell
+        assert!(check("sh -c 'exit 0'", None).success);
+        assert!(!check("sh -c 'exit 3'", None).success);
+        let unbalanced = check("sh -c 'exit 0", None);
+        assert!(!unbalanced.success);
+        assert!(unbalanced.errors[0].starts_with("Invalid command"));
+
+        // A timeout stops the whole group, including a grandchild holding
+        // the output pipe
+        let started = Instant::now();
+        let spawner = check("sh -c 'sleep 30 & sleep 30'", Some(1));
+        assert!(spawner.timed_out);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
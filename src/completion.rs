@@ -0,0 +1,240 @@
+use anyhow::Result;
+use clap::{Arg, Command};
+use std::collections::BTreeSet;
+
+use crate::fs_ops::{latest_tasks, read_jsonl};
+
+/// Complete the last of `words` (the command line after `rotd`, with the word
+/// under the cursor last, possibly empty). `values` supplies candidates for
+/// an argument by its id, so project data is only read when needed.
+pub fn candidates(
+    cmd: &mut Command,
+    words: &[String],
+    values: impl Fn(&str) -> Vec<String>,
+) -> Vec<String> {
+    // Propagate global flags (--agent, --verbose, --dry-run) to subcommands
+    cmd.build();
+
+    let (current, preceding) = match words.split_last() {
+        Some((current, preceding)) => (current.as_str(), preceding),
+        None => ("", &[][..]),
+    };
+
+    let mut command: &Command = cmd;
+    let mut positional = 0;
+    let mut pending: Option<&Arg> = None;
+    for word in preceding {
+        if pending.take().is_some() {
+            continue;
+        }
+        if let Some(name) = word.strip_prefix("--") {
+            let (name, inline) = match name.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (name, false),
+            };
+            pending = command
+                .get_arguments()
+                .find(|a| a.get_long() == Some(name))
+                .filter(|a| takes_value(a) && !inline);
+        } else if let Some(short) = word.strip_prefix('-').filter(|s| s.chars().count() == 1) {
+            let short = short.chars().next();
+            pending = command
+                .get_arguments()
+                .find(|a| a.get_short() == short)
+                .filter(|a| takes_value(a));
+        } else if let Some(sub) = command.find_subcommand(word) {
+            command = sub;
+            positional = 0;
+        } else {
+            positional += 1;
+        }
+    }
+
+    let mut found = BTreeSet::new();
+    if let Some(arg) = pending {
+        found.extend(arg_values(arg, &values));
+    } else if current.starts_with('-') {
+        for arg in command.get_arguments().filter(|a| !a.is_hide_set()) {
+            if let Some(long) = arg.get_long() {
+                found.insert(format!("--{}", long));
+            }
+        }
+    } else {
+        found.extend(
+            command
+                .get_subcommands()
+                .filter(|s| !s.is_hide_set())
+                .map(|s| s.get_name().to_string()),
+        );
+        if let Some(arg) = command.get_positionals().nth(positional) {
+            found.extend(arg_values(arg, &values));
+        }
+    }
+
+    found
+        .into_iter()
+        .filter(|c| c.starts_with(current))
+        .collect()
+}
+
+fn takes_value(arg: &Arg) -> bool {
+    arg.get_num_args().is_some_and(|n| n.takes_values())
+}
+
+fn arg_values(arg: &Arg, values: &impl Fn(&str) -> Vec<String>) -> Vec<String> {
+    let declared: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .map(|v| v.get_name().to_string())
+        .collect();
+    if declared.is_empty() {
+        values(arg.get_id().as_str())
+    } else {
+        declared
+    }
+}
+
+/// Project data offered for arguments with a well-known id
+pub fn project_values(id: &str) -> Vec<String> {
+    match id {
+        "task_id" => latest_tasks()
+            .map(|tasks| tasks.into_iter().map(|t| t.id).collect())
+            .unwrap_or_default(),
+        "task_tag" => latest_tasks()
+            .map(|tasks| tasks.into_iter().flat_map(|t| t.tags).collect())
+            .unwrap_or_default(),
+        "tag" | "from" | "to" => read_jsonl::<serde_json::Value>(&crate::common::lessons_path())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|lesson| lesson.get("tags")?.as_array().cloned())
+            .flatten()
+            .filter_map(|tag| tag.as_str().map(str::to_string))
+            .collect(),
+        "schema" => crate::validate::SCHEMA_TYPES
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        "shell" => SHELLS.iter().map(|s| s.to_string()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Entry point for the hidden `rotd __complete -- <words>` helper the shell
+/// scripts call; prints one candidate per line, or a JSON array in agent mode
+pub fn complete(mut cmd: Command, words: &[String], is_agent_mode: bool) -> Result<()> {
+    let found = candidates(&mut cmd, words, project_values);
+    if is_agent_mode {
+        println!("{}", serde_json::to_string(&found)?);
+    } else {
+        for candidate in found {
+            println!("{}", candidate);
+        }
+    }
+    Ok(())
+}
+
+pub const SHELLS: [&str; 4] = ["bash", "zsh", "fish", "powershell"];
+
+const BASH_SCRIPT: &str = r#"# rotd completion for bash; add to ~/.bashrc:
+#   eval "$(rotd completions bash)"
+_rotd() {
+    local IFS=$'\n'
+    COMPREPLY=($(rotd __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null))
+}
+complete -o default -F _rotd rotd
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef rotd
+# rotd completion for zsh; add to ~/.zshrc after compinit:
+#   eval "$(rotd completions zsh)"
+_rotd() {
+    local -a candidates
+    candidates=("${(@f)$(rotd __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+    if [[ -n "${candidates[1]}" ]]; then
+        compadd -a candidates
+    else
+        _files
+    fi
+}
+compdef _rotd rotd
+"#;
+
+const FISH_SCRIPT: &str = r#"# rotd completion for fish; save as ~/.config/fish/completions/rotd.fish
+complete -c rotd -f -a '(rotd __complete -- (commandline -opc)[2..-1] (commandline -ct))'
+"#;
+
+const POWERSHELL_SCRIPT: &str = r#"# rotd completion for PowerShell; add to $PROFILE:
+#   rotd completions powershell | Out-String | Invoke-Expression
+Register-ArgumentCompleter -Native -CommandName rotd -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() })
+    if ($wordToComplete -eq '') { $words += '' }
+    rotd __complete -- @words 2>$null | ForEach-Object {
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }
+}
+"#;
+
+/// Shell integration that delegates to `rotd __complete`
+pub fn script(shell: &str) -> Result<&'static str> {
+    match shell.to_lowercase().as_str() {
+        "bash" => Ok(BASH_SCRIPT),
+        "zsh" => Ok(ZSH_SCRIPT),
+        "fish" => Ok(FISH_SCRIPT),
+        "powershell" | "pwsh" => Ok(POWERSHELL_SCRIPT),
+        other => Err(anyhow::anyhow!(
+            "Unsupported shell '{}'. Expected one of: {}",
+            other,
+            SHELLS.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn complete_words(line: &[&str]) -> Vec<String> {
+        let words: Vec<String> = line.iter().map(|w| w.to_string()).collect();
+        candidates(&mut crate::Cli::command(), &words, |id| match id {
+            "task_id" => vec!["1.1".to_string(), "1.2".to_string(), "2.1".to_string()],
+            "tag" | "from" | "to" => vec!["async".to_string(), "parser".to_string()],
+            "task_tag" => vec!["backend".to_string(), "perf".to_string()],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(complete_words(&["show-task", "1."]), vec!["1.1", "1.2"]);
+        assert_eq!(
+            complete_words(&["show-lessons", "--tag", ""]),
+            vec!["async", "parser"]
+        );
+        assert_eq!(
+            complete_words(&["lessons", "retag", "--from", "pa"]),
+            vec!["parser"]
+        );
+        // Task tags come from tasks, not lessons
+        assert_eq!(
+            complete_words(&["show-task", "--tag", ""]),
+            vec!["backend", "perf"]
+        );
+        assert_eq!(
+            complete_words(&["coord", "claim", "--tag", "p"]),
+            vec!["perf"]
+        );
+        assert_eq!(
+            complete_words(&["task", "tag", "1.1", "b"]),
+            vec!["backend"]
+        );
+        assert_eq!(complete_words(&["--agent", "show-ta"]), vec!["show-task"]);
+        assert!(complete_words(&["update", "--"]).contains(&"--skip-verify".to_string()));
+        // Global flags are offered on subcommands, hidden commands never are
+        assert!(complete_words(&["score", "1.1", "--d"]).contains(&"--dry-run".to_string()));
+        assert!(!complete_words(&["__"]).contains(&"__complete".to_string()));
+        // The task ID was already given, so nothing more is positional
+        assert!(complete_words(&["show-task", "1.1", ""]).is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use chrono::{DateTime, Timelike, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
@@ -8,7 +10,7 @@ use uuid::Uuid;
 
 use crate::fs_ops::{read_json, with_lock, with_lock_result, write_json};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct WorkRegistryTask {
     pub id: String,
     pub title: String,
@@ -21,9 +23,15 @@ pub struct WorkRegistryTask {
     pub reviewer_id: Option<String>,
     pub capability: Option<String>,
     pub skill_level: Option<String>,
+    /// Score from `rotd prioritize`, used to order claims within a priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_score: Option<f64>,
+    /// When the task was added to the registry; older tasks are claimed first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum WorkStatus {
     Unclaimed,
@@ -33,7 +41,29 @@ pub enum WorkStatus {
     Done,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+impl WorkStatus {
+    /// Registry statuses in board column order
+    pub const ALL: [WorkStatus; 5] = [
+        WorkStatus::Unclaimed,
+        WorkStatus::Claimed,
+        WorkStatus::Blocked,
+        WorkStatus::Review,
+        WorkStatus::Done,
+    ];
+
+    /// Name as written in the registry
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkStatus::Unclaimed => "unclaimed",
+            WorkStatus::Claimed => "claimed",
+            WorkStatus::Blocked => "blocked",
+            WorkStatus::Review => "review",
+            WorkStatus::Done => "done",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskPriority {
     Urgent,
@@ -42,7 +72,87 @@ pub enum TaskPriority {
     Low,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl TaskPriority {
+    /// Claim order: urgent first
+    fn rank(&self) -> u8 {
+        match self {
+            TaskPriority::Urgent => 0,
+            TaskPriority::High => 1,
+            TaskPriority::Medium => 2,
+            TaskPriority::Low => 3,
+        }
+    }
+}
+
+/// Orderings `coord claim --strategy` can use
+pub const CLAIM_STRATEGIES: [&str; 3] = ["fifo", "priority", "score"];
+
+/// Sort registry tasks into the order `coord claim` tries them:
+/// `fifo` by age alone, `priority` by priority then score then age, and
+/// `score` by score then priority then age. Higher scores go first, and
+/// tasks without a score or creation time go after those with one. The
+/// sort is stable, so remaining ties keep registry order.
+pub fn sort_for_claim(tasks: &mut [WorkRegistryTask], strategy: &str) {
+    use std::cmp::Ordering;
+
+    let by_priority = |a: &WorkRegistryTask, b: &WorkRegistryTask| {
+        a.priority.rank().cmp(&b.priority.rank())
+    };
+    let by_score = |a: &WorkRegistryTask, b: &WorkRegistryTask| {
+        let score = |t: &WorkRegistryTask| t.priority_score.unwrap_or(f64::NEG_INFINITY);
+        score(b).total_cmp(&score(a))
+    };
+    let by_age = |a: &WorkRegistryTask, b: &WorkRegistryTask| match (a.created_at, b.created_at) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+
+    match strategy {
+        "fifo" => tasks.sort_by(by_age),
+        "score" => tasks.sort_by(|a, b| {
+            by_score(a, b)
+                .then_with(|| by_priority(a, b))
+                .then_with(|| by_age(a, b))
+        }),
+        _ => tasks.sort_by(|a, b| {
+            by_priority(a, b)
+                .then_with(|| by_score(a, b))
+                .then_with(|| by_age(a, b))
+        }),
+    }
+}
+
+/// Copy `priority_score`s onto matching registry tasks. Returns how many
+/// registry tasks changed; projects without a registry have none.
+pub fn set_priority_scores(scores: &std::collections::HashMap<String, f64>) -> Result<usize> {
+    let registry_path = crate::common::active_work_registry_path();
+    if !registry_path.exists() {
+        return Ok(0);
+    }
+    let lock_dir = crate::common::coordination_path().join(".lock");
+    fs::create_dir_all(&lock_dir)?;
+    let lock_path = lock_dir.join("registry.lock");
+
+    with_lock_result(&lock_path, || -> Result<usize> {
+        let mut registry: WorkRegistry = read_json(&registry_path)?;
+        let mut changed = 0;
+        for task in &mut registry.tasks {
+            let score = scores.get(&task.id).copied();
+            if score.is_some() && task.priority_score != score {
+                task.priority_score = score;
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            write_json(&registry_path, &registry)?;
+        }
+        Ok(changed)
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct WorkRegistry {
     pub tasks: Vec<WorkRegistryTask>,
 }
@@ -79,7 +189,7 @@ pub fn get_agent_id() -> Result<String> {
 
 pub fn touch_heartbeat(agent_id: &str) -> Result<()> {
     let heartbeat_path =
-        PathBuf::from(".rotd/coordination/heartbeat").join(format!("{}.beat", agent_id));
+        crate::common::coordination_path().join("heartbeat").join(format!("{}.beat", agent_id));
 
     // Create parent directory if it doesn't exist
     if let Some(parent) = heartbeat_path.parent() {
@@ -89,6 +199,7 @@ pub fn touch_heartbeat(agent_id: &str) -> Result<()> {
     // Touch the file
     OpenOptions::new()
         .create(true)
+        .truncate(false)
         .write(true)
         .open(&heartbeat_path)?;
 
@@ -97,7 +208,7 @@ pub fn touch_heartbeat(agent_id: &str) -> Result<()> {
 
 pub fn check_heartbeat(agent_id: &str) -> Result<Option<std::time::SystemTime>> {
     let heartbeat_path =
-        PathBuf::from(".rotd/coordination/heartbeat").join(format!("{}.beat", agent_id));
+        crate::common::coordination_path().join("heartbeat").join(format!("{}.beat", agent_id));
 
     if heartbeat_path.exists() {
         let metadata = fs::metadata(&heartbeat_path)?;
@@ -107,67 +218,83 @@ pub fn check_heartbeat(agent_id: &str) -> Result<Option<std::time::SystemTime>>
     }
 }
 
-pub fn clean_stale_locks(timeout_secs: u64) -> Result<Vec<String>> {
-    let mut cleaned = Vec::new();
-    let lock_dir = PathBuf::from(".rotd/coordination/agent_locks");
+/// Agent lock files whose holder's heartbeat is older than `timeout_secs`,
+/// with the holding agent's ID
+pub fn stale_locks(timeout_secs: u64) -> Result<Vec<(PathBuf, String)>> {
+    let mut stale = Vec::new();
+    let lock_dir = crate::common::coordination_path().join("agent_locks");
 
     if !lock_dir.exists() {
-        return Ok(cleaned);
+        return Ok(stale);
     }
 
     let now = std::time::SystemTime::now();
 
     for entry in fs::read_dir(&lock_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("lock") {
+            continue;
+        }
+        // Parse lock filename: <task_id>.<agent_id>.lock
+        let Some(agent_id) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|stem| stem.split('.').nth(1))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let Some(last_beat) = check_heartbeat(&agent_id)? else {
+            continue;
+        };
+        if now
+            .duration_since(last_beat)
+            .is_ok_and(|elapsed| elapsed.as_secs() > timeout_secs)
+        {
+            stale.push((path, agent_id));
+        }
+    }
 
-        if path.extension().and_then(|s| s.to_str()) == Some("lock") {
-            // Parse lock filename: <task_id>.<agent_id>.lock
-            if let Some(filename) = path.file_stem().and_then(|s| s.to_str()) {
-                if let Some(agent_id) = filename.split('.').nth(1) {
-                    // Check heartbeat
-                    if let Some(last_beat) = check_heartbeat(agent_id)? {
-                        if let Ok(elapsed) = now.duration_since(last_beat) {
-                            if elapsed.as_secs() > timeout_secs {
-                                // Stale lock, remove it
-                                fs::remove_file(&path)?;
-                                cleaned.push(filename.to_string());
-
-                                // Update registry
-                                let registry_path =
-                                    PathBuf::from(".rotd/coordination/active_work_registry.json");
-                                let lock_path =
-                                    PathBuf::from(".rotd/coordination/.lock/registry.lock");
-
-                                with_lock(&lock_path, || {
-                                    let mut registry: WorkRegistry = read_json(&registry_path)?;
-
-                                    // Find task and reset to unclaimed
-                                    for task in &mut registry.tasks {
-                                        if task.claimed_by.as_ref() == Some(&agent_id.to_string()) {
-                                            task.status = WorkStatus::Unclaimed;
-                                            task.claimed_by = None;
-                                            task.claimed_at = None;
-                                        }
-                                    }
-
-                                    write_json(&registry_path, &registry)?;
-                                    Ok(())
-                                })?;
-                            }
-                        }
-                    }
+    Ok(stale)
+}
+
+pub fn clean_stale_locks(timeout_secs: u64) -> Result<Vec<String>> {
+    let mut cleaned = Vec::new();
+
+    for (path, agent_id) in stale_locks(timeout_secs)? {
+        // Stale lock, remove it
+        fs::remove_file(&path)?;
+        if let Some(filename) = path.file_stem().and_then(|s| s.to_str()) {
+            cleaned.push(filename.to_string());
+        }
+
+        // Update registry
+        let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+        let lock_path = crate::common::coordination_path().join(".lock/registry.lock");
+
+        with_lock(&lock_path, || {
+            let mut registry: WorkRegistry = read_json(&registry_path)?;
+
+            // Find task and reset to unclaimed
+            for task in &mut registry.tasks {
+                if task.claimed_by.as_ref() == Some(&agent_id) {
+                    task.status = WorkStatus::Unclaimed;
+                    task.claimed_by = None;
+                    task.claimed_at = None;
                 }
             }
-        }
+
+            write_json(&registry_path, &registry)?;
+            Ok(())
+        })?;
     }
 
     Ok(cleaned)
 }
 
 pub fn append_coordination_log(message: &str) -> Result<()> {
-    let log_path = PathBuf::from(".rotd/coordination/coordination.log");
-    let lock_path = PathBuf::from(".rotd/coordination/.lock/coordination.lock");
+    let log_path = crate::common::coordination_path().join("coordination.log");
+    let lock_path = crate::common::coordination_path().join(".lock/coordination.lock");
 
     with_lock(&lock_path, || {
         let mut file = OpenOptions::new()
@@ -181,12 +308,12 @@ pub fn append_coordination_log(message: &str) -> Result<()> {
 }
 
 pub fn rotate_coordination_log() -> Result<()> {
-    let log_path = PathBuf::from(".rotd/coordination/coordination.log");
+    let log_path = crate::common::coordination_path().join("coordination.log");
 
     if log_path.exists() {
         let today = Utc::now().format("%Y-%m-%d");
         let archive_path =
-            PathBuf::from(".rotd/coordination").join(format!("coordination-{}.log", today));
+            crate::common::coordination_path().join(format!("coordination-{}.log", today));
 
         fs::rename(&log_path, &archive_path)?;
     }
@@ -196,13 +323,116 @@ pub fn rotate_coordination_log() -> Result<()> {
 
 use crate::CoordCommands;
 
+/// Claim a specific registry task for `agent_id`.
+/// Returns `Ok(None)` when there is no registry or the task is not listed in it.
+pub fn claim_task(task_id: &str, agent_id: &str) -> Result<Option<WorkRegistryTask>> {
+    let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+    if !registry_path.exists() {
+        return Ok(None);
+    }
+    let lock_dir = crate::common::coordination_path().join(".lock");
+    fs::create_dir_all(&lock_dir)?;
+    let lock_path = lock_dir.join("registry.lock");
+
+    let claimed = with_lock_result(&lock_path, || -> Result<Option<WorkRegistryTask>> {
+        let mut registry: WorkRegistry = read_json(&registry_path)?;
+        let Some(task) = registry.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return Ok(None);
+        };
+
+        match (&task.status, task.claimed_by.as_deref()) {
+            // Already ours; nothing to do
+            (WorkStatus::Claimed, Some(holder)) if holder == agent_id => {
+                return Ok(Some(task.clone()))
+            }
+            (WorkStatus::Unclaimed, _) => {}
+            (status, holder) => {
+                return Err(anyhow::anyhow!(
+                    "Task {} cannot be claimed (status: {:?}, claimed by: {})",
+                    task_id,
+                    status,
+                    holder.unwrap_or("nobody")
+                ))
+            }
+        }
+
+        let lock_dir = crate::common::coordination_path().join("agent_locks");
+        fs::create_dir_all(&lock_dir)?;
+        let lock_file = lock_dir.join(format!("{}.{}.lock", task.id, agent_id));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock_file)
+            .map_err(|_| anyhow::anyhow!("Task {} is already locked", task_id))?;
+        serde_json::to_writer(
+            &file,
+            &LockMetadata {
+                holder: agent_id.to_string(),
+                since: Utc::now(),
+            },
+        )?;
+
+        task.status = WorkStatus::Claimed;
+        task.claimed_by = Some(agent_id.to_string());
+        task.claimed_at = Some(Utc::now());
+        let claimed = task.clone();
+
+        write_json(&registry_path, &registry)?;
+        Ok(Some(claimed))
+    })?;
+
+    if claimed.is_some() {
+        append_coordination_log(&format!("{} ▶ claimed task {}", agent_id, task_id))?;
+    }
+
+    Ok(claimed)
+}
+
+/// Return a task claimed with `claim_task` to the unclaimed pool
+pub fn unclaim_task(task_id: &str, agent_id: &str) -> Result<()> {
+    let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+    let lock_path = crate::common::coordination_path().join(".lock/registry.lock");
+
+    with_lock(&lock_path, || {
+        let mut registry: WorkRegistry = read_json(&registry_path)?;
+        if let Some(task) = registry
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == task_id && t.claimed_by.as_deref() == Some(agent_id))
+        {
+            task.status = WorkStatus::Unclaimed;
+            task.claimed_by = None;
+            task.claimed_at = None;
+        }
+        write_json(&registry_path, &registry)?;
+
+        let lock_file = crate::common::coordination_path().join("agent_locks")
+            .join(format!("{}.{}.lock", task_id, agent_id));
+        if lock_file.exists() {
+            fs::remove_file(&lock_file)?;
+        }
+        Ok(())
+    })
+}
+
 pub fn handle_command(cmd: CoordCommands, is_agent_mode: bool, verbose: bool) -> Result<()> {
     match cmd {
         CoordCommands::Claim {
             capability,
             skill_level,
             any,
-        } => cmd_claim(capability, skill_level, any, is_agent_mode),
+            strategy,
+            tags,
+        } => cmd_claim(
+            &ClaimFilter {
+                capability,
+                skill_level,
+                any,
+                strategy,
+                tags,
+            },
+            is_agent_mode,
+        ),
         CoordCommands::Release { task_id } => cmd_release(&task_id, is_agent_mode),
         CoordCommands::Approve { task_id } => cmd_approve(&task_id, is_agent_mode),
         CoordCommands::Msg { message } => cmd_msg(&message, is_agent_mode),
@@ -210,23 +440,51 @@ pub fn handle_command(cmd: CoordCommands, is_agent_mode: bool, verbose: bool) ->
         CoordCommands::CleanStale { timeout } => cmd_clean_stale(timeout, is_agent_mode),
         CoordCommands::Quota { add } => cmd_quota(add, is_agent_mode),
         CoordCommands::Ls => cmd_ls(is_agent_mode, verbose),
+        CoordCommands::Board { watch, interval } => cmd_board(watch, interval, is_agent_mode),
         CoordCommands::History { task_id, format } => cmd_history(&task_id, &format, is_agent_mode),
         CoordCommands::PruneHistory { dry_run } => cmd_prune_history(dry_run, is_agent_mode),
     }
 }
 
-fn cmd_claim(
-    capability: Option<String>,
-    skill_level: Option<String>,
-    any: bool,
-    is_agent_mode: bool,
-) -> Result<()> {
-    let agent_id = get_agent_id()?;
-    let registry_path = PathBuf::from(".rotd/coordination/active_work_registry.json");
-    let lock_dir = PathBuf::from(".rotd/coordination/.lock");
+/// Filters `coord claim` picks the next task with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimFilter {
+    pub capability: Option<String>,
+    pub skill_level: Option<String>,
+    /// Claim any task regardless of priority
+    #[serde(default)]
+    pub any: bool,
+    pub strategy: String,
+    /// Only claim tasks carrying all of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Claim the next eligible registry task for `agent_id`.
+/// Returns `Ok(None)` when no task matches the filter.
+pub fn claim_next(agent_id: &str, filter: &ClaimFilter) -> Result<Option<WorkRegistryTask>> {
+    let ClaimFilter {
+        capability,
+        skill_level,
+        any,
+        strategy,
+        tags,
+    } = filter;
+    // Tags live on the task entries, not in the registry
+    let tagged: HashSet<String> = if tags.is_empty() {
+        HashSet::new()
+    } else {
+        crate::fs_ops::latest_tasks()?
+            .into_iter()
+            .filter(|t| crate::task::has_tags(t, tags))
+            .map(|t| t.id)
+            .collect()
+    };
+    let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+    let lock_dir = crate::common::coordination_path().join(".lock");
     fs::create_dir_all(&lock_dir)?;
     let lock_path = lock_dir.join("registry.lock");
-    let deps_path = PathBuf::from(".rotd/coordination/dependency_map.json");
+    let deps_path = crate::common::coordination_path().join("dependency_map.json");
 
     let result = with_lock_result(&lock_path, || -> Result<Option<WorkRegistryTask>> {
         let mut registry: WorkRegistry = read_json(&registry_path)?;
@@ -241,21 +499,9 @@ fn cmd_claim(
         // Find first unclaimed task matching filters
         let mut claimed_task = None;
 
-        // Sort tasks by priority if not using --any
+        // Try tasks in strategy order unless using --any
         if !any {
-            registry
-                .tasks
-                .sort_by(|a, b| match (&a.priority, &b.priority) {
-                    (TaskPriority::Urgent, TaskPriority::Urgent) => std::cmp::Ordering::Equal,
-                    (TaskPriority::Urgent, _) => std::cmp::Ordering::Less,
-                    (_, TaskPriority::Urgent) => std::cmp::Ordering::Greater,
-                    (TaskPriority::High, TaskPriority::High) => std::cmp::Ordering::Equal,
-                    (TaskPriority::High, _) => std::cmp::Ordering::Less,
-                    (_, TaskPriority::High) => std::cmp::Ordering::Greater,
-                    (TaskPriority::Medium, TaskPriority::Low) => std::cmp::Ordering::Less,
-                    (TaskPriority::Low, TaskPriority::Medium) => std::cmp::Ordering::Greater,
-                    _ => std::cmp::Ordering::Equal,
-                });
+            sort_for_claim(&mut registry.tasks, strategy);
         }
 
         // Create a list of task statuses to avoid borrowing issues
@@ -277,6 +523,11 @@ fn cmd_claim(
                 }
             }
 
+            // Check tag filter
+            if !tags.is_empty() && !tagged.contains(&task.id) {
+                continue;
+            }
+
             // Check skill level filter
             if let Some(ref _skill) = skill_level {
                 // TODO: Implement skill level comparison logic
@@ -296,7 +547,7 @@ fn cmd_claim(
             }
 
             // Check if task has no existing lock
-            let lock_dir = PathBuf::from(".rotd/coordination/agent_locks");
+            let lock_dir = crate::common::coordination_path().join("agent_locks");
             fs::create_dir_all(&lock_dir)?;
             let lock_file = lock_dir.join(format!("{}.{}.lock", task.id, agent_id));
 
@@ -309,14 +560,14 @@ fn cmd_claim(
                 {
                     // Write lock metadata
                     let metadata = LockMetadata {
-                        holder: agent_id.clone(),
+                        holder: agent_id.to_string(),
                         since: Utc::now(),
                     };
                     serde_json::to_writer(&file, &metadata)?;
 
                     // Update task status
                     task.status = WorkStatus::Claimed;
-                    task.claimed_by = Some(agent_id.clone());
+                    task.claimed_by = Some(agent_id.to_string());
                     task.claimed_at = Some(Utc::now());
 
                     claimed_task = Some(task.clone());
@@ -332,11 +583,27 @@ fn cmd_claim(
         Ok(claimed_task)
     })?;
 
+    // Log the claim
+    if let Some(ref task) = result {
+        let msg = format!("{} ▶ claimed task {}", agent_id, task.id);
+        append_coordination_log(&msg)?;
+    }
+
+    Ok(result)
+}
+
+fn cmd_claim(filter: &ClaimFilter, is_agent_mode: bool) -> Result<()> {
+    let agent_id = get_agent_id()?;
+    let result = match crate::coord_server::client()? {
+        Some(client) => client.claim(&agent_id, filter)?,
+        None => claim_next(&agent_id, filter)?,
+    };
+
     if is_agent_mode {
         if let Some(ref task) = result {
             println!("{}", serde_json::to_string(&task)?);
         } else {
-            println!("{}", r#"{"status":"no_eligible_task"}"#);
+            println!("{{\"status\":\"no_eligible_task\"}}");
         }
     } else {
         if let Some(ref task) = result {
@@ -346,19 +613,36 @@ fn cmd_claim(
         }
     }
 
-    // Log the claim
-    if let Some(ref task) = result {
-        let msg = format!("{} ▶ claimed task {}", agent_id, task.id);
-        append_coordination_log(&msg)?;
-    }
-
     Ok(())
 }
 
 fn cmd_release(task_id: &str, is_agent_mode: bool) -> Result<()> {
     let agent_id = get_agent_id()?;
-    let registry_path = PathBuf::from(".rotd/coordination/active_work_registry.json");
-    let lock_path = PathBuf::from(".rotd/coordination/.lock/registry.lock");
+    match crate::coord_server::client()? {
+        Some(client) => client.release(task_id, &agent_id)?,
+        None => release_task(task_id, &agent_id)?,
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "release",
+                "task_id": task_id
+            })
+        );
+    } else {
+        println!("Released task {}", task_id);
+    }
+
+    Ok(())
+}
+
+/// Mark a task `agent_id` has claimed as done and drop its lock
+pub fn release_task(task_id: &str, agent_id: &str) -> Result<()> {
+    let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+    let lock_path = crate::common::coordination_path().join(".lock/registry.lock");
 
     with_lock(&lock_path, || {
         let mut registry: WorkRegistry = read_json(&registry_path)?;
@@ -366,7 +650,7 @@ fn cmd_release(task_id: &str, is_agent_mode: bool) -> Result<()> {
         // Find and update task
         let mut found = false;
         for task in &mut registry.tasks {
-            if task.id == task_id && task.claimed_by.as_ref() == Some(&agent_id) {
+            if task.id == task_id && task.claimed_by.as_deref() == Some(agent_id) {
                 task.status = WorkStatus::Done;
                 task.completed_at = Some(Utc::now());
                 found = true;
@@ -383,7 +667,7 @@ fn cmd_release(task_id: &str, is_agent_mode: bool) -> Result<()> {
         write_json(&registry_path, &registry)?;
 
         // Remove lock file
-        let lock_file = PathBuf::from(".rotd/coordination/agent_locks")
+        let lock_file = crate::common::coordination_path().join("agent_locks")
             .join(format!("{}.{}.lock", task_id, agent_id));
         if lock_file.exists() {
             fs::remove_file(&lock_file)?;
@@ -394,28 +678,32 @@ fn cmd_release(task_id: &str, is_agent_mode: bool) -> Result<()> {
 
     // Log the release
     let msg = format!("{} ▶ completed task {}", agent_id, task_id);
-    append_coordination_log(&msg)?;
+    append_coordination_log(&msg)
+}
+
+fn cmd_approve(task_id: &str, is_agent_mode: bool) -> Result<()> {
+    approve_task(task_id, &get_agent_id()?)?;
 
     if is_agent_mode {
         println!(
             "{}",
             serde_json::json!({
                 "status": "success",
-                "action": "release",
+                "action": "approve",
                 "task_id": task_id
             })
         );
     } else {
-        println!("Released task {}", task_id);
+        println!("Approved task {}", task_id);
     }
 
     Ok(())
 }
 
-fn cmd_approve(task_id: &str, is_agent_mode: bool) -> Result<()> {
-    let agent_id = get_agent_id()?;
-    let registry_path = PathBuf::from(".rotd/coordination/active_work_registry.json");
-    let lock_path = PathBuf::from(".rotd/coordination/.lock/registry.lock");
+/// Approve a task in review as done, with `agent_id` as the reviewer
+pub fn approve_task(task_id: &str, agent_id: &str) -> Result<()> {
+    let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+    let lock_path = crate::common::coordination_path().join(".lock/registry.lock");
 
     with_lock(&lock_path, || {
         let mut registry: WorkRegistry = read_json(&registry_path)?;
@@ -425,7 +713,7 @@ fn cmd_approve(task_id: &str, is_agent_mode: bool) -> Result<()> {
         for task in &mut registry.tasks {
             if task.id == task_id && task.status == WorkStatus::Review {
                 task.status = WorkStatus::Done;
-                task.reviewer_id = Some(agent_id.clone());
+                task.reviewer_id = Some(agent_id.to_string());
                 task.completed_at = Some(Utc::now());
                 found = true;
                 break;
@@ -438,31 +726,26 @@ fn cmd_approve(task_id: &str, is_agent_mode: bool) -> Result<()> {
 
         write_json(&registry_path, &registry)?;
         Ok(())
-    })?;
-
-    if is_agent_mode {
-        println!(
-            "{}",
-            serde_json::json!({
-                "status": "success",
-                "action": "approve",
-                "task_id": task_id
-            })
-        );
-    } else {
-        println!("Approved task {}", task_id);
-    }
-
-    Ok(())
+    })
 }
 
 fn cmd_msg(message: &str, is_agent_mode: bool) -> Result<()> {
     let agent_id = get_agent_id()?;
+    let (message, redacted) = crate::secrets::redact_text(message);
     let full_msg = format!("{} ▶ {}", agent_id, message);
     append_coordination_log(&full_msg)?;
 
     if is_agent_mode {
-        println!("{}", r#"{"status":"success","action":"msg"}"#);
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "msg",
+                "redacted": redacted,
+            })
+        );
+    } else if redacted {
+        println!("Message logged (secrets redacted)");
     } else {
         println!("Message logged");
     }
@@ -472,7 +755,10 @@ fn cmd_msg(message: &str, is_agent_mode: bool) -> Result<()> {
 
 fn cmd_beat(is_agent_mode: bool) -> Result<()> {
     let agent_id = get_agent_id()?;
-    touch_heartbeat(&agent_id)?;
+    match crate::coord_server::client()? {
+        Some(client) => client.beat(&agent_id)?,
+        None => touch_heartbeat(&agent_id)?,
+    }
 
     if is_agent_mode {
         println!(
@@ -522,11 +808,17 @@ fn cmd_clean_stale(timeout: u64, is_agent_mode: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_quota(add: Option<u64>, is_agent_mode: bool) -> Result<()> {
-    let quota_path = PathBuf::from(".rotd/coordination/quota.json");
-    let lock_path = PathBuf::from(".rotd/coordination/.lock/quota.lock");
+/// The quota tracker, if one has been written
+pub fn read_quota() -> Option<QuotaTracker> {
+    read_json(&crate::common::coordination_path().join("quota.json")).ok()
+}
+
+/// Add `add` tokens to the quota tracker, if given, and return it
+pub fn update_quota(add: Option<u64>) -> Result<QuotaTracker> {
+    let quota_path = crate::common::coordination_path().join("quota.json");
+    let lock_path = crate::common::coordination_path().join(".lock/quota.lock");
 
-    let result = with_lock_result(&lock_path, || -> Result<QuotaTracker> {
+    with_lock_result(&lock_path, || -> Result<QuotaTracker> {
         let mut quota: QuotaTracker = if quota_path.exists() {
             read_json(&quota_path)?
         } else {
@@ -544,7 +836,14 @@ fn cmd_quota(add: Option<u64>, is_agent_mode: bool) -> Result<()> {
         }
 
         Ok(quota)
-    })?;
+    })
+}
+
+fn cmd_quota(add: Option<u64>, is_agent_mode: bool) -> Result<()> {
+    let result = match crate::coord_server::client()? {
+        Some(client) => client.quota(add)?,
+        None => update_quota(add)?,
+    };
 
     if is_agent_mode {
         println!("{}", serde_json::to_string(&result)?);
@@ -558,9 +857,16 @@ fn cmd_quota(add: Option<u64>, is_agent_mode: bool) -> Result<()> {
     Ok(())
 }
 
+/// The work registry, from the coordination server when one is configured
+fn read_registry() -> Result<WorkRegistry> {
+    match crate::coord_server::client()? {
+        Some(client) => client.registry(),
+        None => read_json(&crate::common::active_work_registry_path()),
+    }
+}
+
 fn cmd_ls(is_agent_mode: bool, verbose: bool) -> Result<()> {
-    let registry_path = PathBuf::from(".rotd/coordination/active_work_registry.json");
-    let registry: WorkRegistry = read_json(&registry_path)?;
+    let registry = read_registry()?;
 
     if is_agent_mode {
         println!("{}", serde_json::to_string(&registry)?);
@@ -599,6 +905,122 @@ fn cmd_ls(is_agent_mode: bool, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Registry tasks in each board column, in `WorkStatus::ALL` order
+pub fn board_columns(registry: &WorkRegistry) -> Vec<(&WorkStatus, Vec<&WorkRegistryTask>)> {
+    WorkStatus::ALL
+        .iter()
+        .map(|status| {
+            let tasks = registry.tasks.iter().filter(|t| &t.status == status).collect();
+            (status, tasks)
+        })
+        .collect()
+}
+
+/// Card lines for a task: its ID and title, then who holds or reviewed it
+/// and why it is blocked
+fn board_card(task: &WorkRegistryTask) -> Vec<String> {
+    let mut card = vec![format!("{} {}", task.id, task.title)];
+    let agent = match task.status {
+        WorkStatus::Done => task.reviewer_id.as_ref().or(task.claimed_by.as_ref()),
+        _ => task.claimed_by.as_ref(),
+    };
+    if let Some(agent) = agent {
+        card.push(format!("  @{}", agent));
+    }
+    if let Some(reason) = &task.blocked_reason {
+        card.push(format!("  ! {}", reason));
+    }
+    card
+}
+
+/// The registry as side-by-side columns filling `width`; columns are as
+/// tall as the longest unless `height` is given
+pub fn render_board(registry: &WorkRegistry, width: usize, height: Option<usize>) -> Vec<String> {
+    let columns = board_columns(registry);
+    let cards: Vec<Vec<String>> = columns
+        .iter()
+        .map(|(_, tasks)| tasks.iter().flat_map(|t| board_card(t)).collect())
+        .collect();
+    let height = height.unwrap_or_else(|| cards.iter().map(Vec::len).max().unwrap_or(0) + 2);
+
+    let boxes = columns
+        .iter()
+        .zip(&cards)
+        .enumerate()
+        .map(|(i, ((status, tasks), lines))| {
+            // Spread the remainder over the first columns
+            let column_width = width / columns.len() + usize::from(i < width % columns.len());
+            let title = format!("{} ({})", status.as_str().to_uppercase(), tasks.len());
+            crate::tui::boxed(&title, lines, None, false, column_width, height)
+        })
+        .collect();
+    crate::tui::side_by_side(boxes)
+}
+
+fn cmd_board(watch: bool, interval: u64, is_agent_mode: bool) -> Result<()> {
+    if is_agent_mode {
+        let registry = read_registry()?;
+        let mut counts = serde_json::Map::new();
+        let mut columns = serde_json::Map::new();
+        for (status, tasks) in board_columns(&registry) {
+            let cards: Vec<serde_json::Value> = tasks
+                .iter()
+                .map(|t| {
+                    serde_json::json!({
+                        "id": t.id,
+                        "title": t.title,
+                        "claimed_by": t.claimed_by,
+                        "reviewer_id": t.reviewer_id,
+                        "blocked_reason": t.blocked_reason,
+                    })
+                })
+                .collect();
+            counts.insert(status.as_str().to_string(), cards.len().into());
+            columns.insert(status.as_str().to_string(), cards.into());
+        }
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "board",
+                "counts": counts,
+                "columns": columns,
+            })
+        );
+        return Ok(());
+    }
+
+    let term = console::Term::stdout();
+    let width = term.size_checked().map_or(120, |(_, cols)| cols as usize);
+    if !watch {
+        let registry = read_registry()?;
+        for line in render_board(&registry, width, None) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    loop {
+        let (rows, cols) = term.size();
+        // Leave a row for the footer
+        let board = read_registry().map(|registry| {
+            render_board(&registry, cols as usize, Some((rows as usize).saturating_sub(1)))
+        });
+        term.clear_screen()?;
+        match board {
+            Ok(lines) => println!("{}", lines.join("\n")),
+            Err(e) => println!("Could not read the work registry: {}", e),
+        }
+        print!(
+            "Updated {}; refreshing every {}s, Ctrl-C to stop",
+            Utc::now().format("%H:%M:%S"),
+            interval.max(1)
+        );
+        std::io::stdout().flush()?;
+        std::thread::sleep(std::time::Duration::from_secs(interval.max(1)));
+    }
+}
+
 fn cmd_history(task_id: &str, format: &str, is_agent_mode: bool) -> Result<()> {
     use crate::history;
     use colored::Colorize;
@@ -768,3 +1190,75 @@ fn cmd_prune_history(dry_run: bool, is_agent_mode: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_board() {
+        let registry: WorkRegistry = serde_json::from_value(serde_json::json!({"tasks": [
+            {"id": "1.1", "title": "Parser", "status": "claimed", "priority": "high",
+             "claimed_by": "agent-a", "claimed_at": null, "completed_at": null,
+             "blocked_reason": null, "reviewer_id": null, "capability": null, "skill_level": null},
+            {"id": "1.2", "title": "Lexer", "status": "blocked", "priority": "low",
+             "claimed_by": "agent-b", "claimed_at": null, "completed_at": null,
+             "blocked_reason": "waiting on 1.1", "reviewer_id": null, "capability": null, "skill_level": null},
+            {"id": "1.3", "title": "Docs", "status": "unclaimed", "priority": "low",
+             "claimed_by": null, "claimed_at": null, "completed_at": null,
+             "blocked_reason": null, "reviewer_id": null, "capability": null, "skill_level": null}
+        ]}))
+        .unwrap();
+
+        let counts: Vec<(&str, usize)> = board_columns(&registry)
+            .iter()
+            .map(|(status, tasks)| (status.as_str(), tasks.len()))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![("unclaimed", 1), ("claimed", 1), ("blocked", 1), ("review", 0), ("done", 0)]
+        );
+
+        colored::control::set_override(false);
+        let lines = render_board(&registry, 112, None);
+        // Tallest column (blocked: title, agent, reason) plus borders
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().all(|l| console::measure_text_width(l) == 112));
+        assert!(lines[0].starts_with("┌ UNCLAIMED (1) ─"));
+        assert!(lines[0].contains("┌ REVIEW (0) ─"));
+        assert!(lines[1].contains("│1.1 Parser"));
+        assert!(lines[2].contains("│  @agent-b"));
+        assert!(lines[3].contains("│  ! waiting on 1.1"));
+    }
+
+    #[test]
+    fn test_sort_for_claim() {
+        let now = Utc::now();
+        let task = |id: &str, priority: &str, score: Option<f64>, age_days: i64| -> WorkRegistryTask {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "title": id, "status": "unclaimed", "priority": priority,
+                "claimed_by": null, "claimed_at": null, "completed_at": null,
+                "blocked_reason": null, "reviewer_id": null, "capability": null, "skill_level": null,
+                "priority_score": score, "created_at": now - chrono::Duration::days(age_days),
+            }))
+            .unwrap()
+        };
+        let mut tasks = vec![
+            task("1.1", "medium", Some(40.0), 1),
+            task("1.2", "high", Some(20.0), 2),
+            task("1.3", "medium", Some(90.0), 3),
+            task("1.4", "high", None, 4),
+            task("1.5", "medium", Some(40.0), 5),
+        ];
+        let order = |tasks: &[WorkRegistryTask]| -> Vec<String> {
+            tasks.iter().map(|t| t.id.clone()).collect()
+        };
+
+        sort_for_claim(&mut tasks, "priority");
+        assert_eq!(order(&tasks), vec!["1.2", "1.4", "1.3", "1.5", "1.1"]);
+        sort_for_claim(&mut tasks, "score");
+        assert_eq!(order(&tasks), vec!["1.3", "1.5", "1.1", "1.2", "1.4"]);
+        sort_for_claim(&mut tasks, "fifo");
+        assert_eq!(order(&tasks), vec!["1.5", "1.4", "1.3", "1.2", "1.1"]);
+    }
+}
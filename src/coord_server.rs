@@ -0,0 +1,389 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Read;
+use std::time::Duration;
+
+use crate::common::check_rotd_initialized;
+use crate::coord::{ClaimFilter, QuotaTracker, WorkRegistry, WorkRegistryTask};
+
+/// Address `rotd serve --coord` listens on by default
+pub const DEFAULT_BIND: &str = "127.0.0.1:7420";
+
+/// Environment variable holding the shared token the server requires and
+/// clients send; unset means no authentication, which is only allowed on a
+/// loopback address
+const TOKEN_ENV: &str = "ROTD_COORD_TOKEN";
+
+/// Largest request body the server reads; coordination requests are tiny
+const MAX_BODY_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ClaimRequest {
+    agent_id: String,
+    #[serde(flatten)]
+    filter: ClaimFilter,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ClaimResponse {
+    task: Option<WorkRegistryTask>,
+}
+
+/// Body of requests about one task: claiming it by ID, handing it back, or
+/// releasing it
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskRequest {
+    agent_id: String,
+    task_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BeatRequest {
+    agent_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct QuotaRequest {
+    add: Option<u64>,
+}
+
+fn token() -> Option<String> {
+    std::env::var(TOKEN_ENV).ok().filter(|t| !t.trim().is_empty())
+}
+
+/// Whether an `Authorization` header value carries `token`, compared in
+/// constant time
+fn authorized(header: Option<&str>, token: Option<&str>) -> bool {
+    match token {
+        None => true,
+        Some(token) => header
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .is_some_and(|sent| constant_time_eq(sent.as_bytes(), token.as_bytes())),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Refuse to serve beyond this machine without a token
+fn check_exposure(bind: &str, token: Option<&str>) -> Result<()> {
+    if token.is_some() {
+        return Ok(());
+    }
+    let addrs: Vec<std::net::SocketAddr> = std::net::ToSocketAddrs::to_socket_addrs(bind)
+        .with_context(|| format!("Invalid bind address {}", bind))?
+        .collect();
+    if addrs.is_empty() || addrs.iter().any(|addr| !addr.ip().is_loopback()) {
+        anyhow::bail!(
+            "Refusing to serve on {} without {}; set it or bind to a loopback address such as {}",
+            bind,
+            TOKEN_ENV,
+            DEFAULT_BIND
+        );
+    }
+    Ok(())
+}
+
+/// A request body, or why it was refused
+fn read_body(request: &mut tiny_http::Request) -> Result<Vec<u8>, (u16, String)> {
+    let too_large = || (413, format!("Request body exceeds {} bytes", MAX_BODY_BYTES));
+    if request.body_length().is_some_and(|len| len > MAX_BODY_BYTES) {
+        return Err(too_large());
+    }
+    // Chunked bodies have no declared length, so cap what is read as well
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| (400, e.to_string()))?;
+    if body.len() > MAX_BODY_BYTES {
+        return Err(too_large());
+    }
+    Ok(body)
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, (u16, String)> {
+    serde_json::from_slice(body).map_err(|e| (400, format!("Invalid request body: {}", e)))
+}
+
+/// Run one request against the local `.rotd/`, as a status code and JSON body
+fn route(method: &str, path: &str, body: &[u8]) -> (u16, Value) {
+    // Operation failures, such as releasing someone else's task, are conflicts
+    let conflict = |e: anyhow::Error| (409, e.to_string());
+    let result: Result<Value, (u16, String)> = match (method, path) {
+        ("GET", "/health") => Ok(serde_json::json!({
+            "status": "ok",
+            "version": env!("CARGO_PKG_VERSION"),
+        })),
+        ("GET", "/coord/registry") => crate::fs_ops::read_json::<WorkRegistry>(
+            &crate::common::active_work_registry_path(),
+        )
+        .map(|registry| serde_json::json!(registry))
+        .map_err(conflict),
+        ("POST", "/coord/claim") => parse::<ClaimRequest>(body).and_then(|req| {
+            crate::coord::claim_next(&req.agent_id, &req.filter)
+                .map(|task| serde_json::json!(ClaimResponse { task }))
+                .map_err(conflict)
+        }),
+        ("POST", "/coord/claim_task") => parse::<TaskRequest>(body).and_then(|req| {
+            crate::coord::claim_task(&req.task_id, &req.agent_id)
+                .map(|task| serde_json::json!(ClaimResponse { task }))
+                .map_err(conflict)
+        }),
+        ("POST", "/coord/unclaim") => parse::<TaskRequest>(body).and_then(|req| {
+            crate::coord::unclaim_task(&req.task_id, &req.agent_id)
+                .map(|_| serde_json::json!({ "status": "success" }))
+                .map_err(conflict)
+        }),
+        ("POST", "/coord/release") => parse::<TaskRequest>(body).and_then(|req| {
+            crate::coord::release_task(&req.task_id, &req.agent_id)
+                .map(|_| serde_json::json!({ "status": "success" }))
+                .map_err(conflict)
+        }),
+        ("POST", "/coord/beat") => parse::<BeatRequest>(body).and_then(|req| {
+            crate::coord::touch_heartbeat(&req.agent_id)
+                .map(|_| serde_json::json!({ "status": "success" }))
+                .map_err(conflict)
+        }),
+        ("POST", "/coord/quota") => parse::<QuotaRequest>(body).and_then(|req| {
+            crate::coord::update_quota(req.add)
+                .map(|quota| serde_json::json!(quota))
+                .map_err(conflict)
+        }),
+        _ => Err((404, format!("No route for {} {}", method, path))),
+    };
+    match result {
+        Ok(value) => (200, value),
+        Err((status, error)) => (status, serde_json::json!({ "error": error })),
+    }
+}
+
+/// `rotd serve --coord`: answer coordination requests from other machines
+/// against this project's `.rotd/`, one at a time, until interrupted
+pub fn cmd_serve(bind: &str, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    let token = token();
+    check_exposure(bind, token.as_deref())?;
+    let server = tiny_http::Server::http(bind)
+        .map_err(|e| anyhow::anyhow!("Failed to listen on {}: {}", bind, e))?;
+    let addr = server
+        .server_addr()
+        .to_ip()
+        .map_or_else(|| bind.to_string(), |addr| addr.to_string());
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "listening",
+                "action": "serve",
+                "bind": addr,
+                "auth": token.is_some(),
+            })
+        );
+    } else {
+        println!("✓ Coordination server listening on http://{}", addr);
+        if token.is_none() {
+            println!("  {} is not set; only clients on this machine can connect", TOKEN_ENV);
+        }
+        println!("  Press Ctrl-C to stop");
+    }
+
+    for mut request in server.incoming_requests() {
+        let method = request.method().as_str().to_string();
+        let path = request.url().split('?').next().unwrap_or_default().to_string();
+        let header = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Authorization"))
+            .map(|h| h.value.as_str().to_string());
+
+        let (status, body) = if !authorized(header.as_deref(), token.as_deref()) {
+            (401, serde_json::json!({ "error": format!("Missing or wrong {}", TOKEN_ENV) }))
+        } else {
+            match read_body(&mut request) {
+                Ok(body) => route(&method, &path, &body),
+                Err((status, error)) => (status, serde_json::json!({ "error": error })),
+            }
+        };
+        if !is_agent_mode {
+            println!("{} {} {}", method, path, status);
+        }
+
+        let response = tiny_http::Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                "Content-Type: application/json"
+                    .parse::<tiny_http::Header>()
+                    .expect("static header is valid"),
+            );
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to answer {} {}: {}", method, path, e);
+        }
+    }
+    Ok(())
+}
+
+/// Client for a `rotd serve --coord` instance
+pub struct CoordClient {
+    base_url: String,
+    client: reqwest::blocking::Client,
+    token: Option<String>,
+}
+
+/// The coordination server this machine should use, from
+/// `ROTD_COORD_SERVER` or `coord_server` in the machine-local config; `None`
+/// means coordinate through local files. The shared config is never
+/// consulted, so a cloned project can't send the token elsewhere.
+pub fn client() -> Result<Option<CoordClient>> {
+    let url = std::env::var("ROTD_COORD_SERVER")
+        .ok()
+        .or_else(|| crate::history::load_local_config().ok()?.coord_server)
+        .filter(|url| !url.trim().is_empty());
+    let Some(url) = url else {
+        return Ok(None);
+    };
+    Ok(Some(CoordClient {
+        base_url: url.trim_end_matches('/').to_string(),
+        client: crate::github::client_builder(Duration::from_secs(30))?
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?,
+        token: token(),
+    }))
+}
+
+impl CoordClient {
+    fn post<B: Serialize, T: DeserializeOwned>(&self, route: &str, body: &B) -> Result<T> {
+        let url = format!("{}{}", self.base_url, route);
+        self.send(self.client.post(&url).json(body), &url)
+    }
+
+    fn get<T: DeserializeOwned>(&self, route: &str) -> Result<T> {
+        let url = format!("{}{}", self.base_url, route);
+        self.send(self.client.get(&url), &url)
+    }
+
+    fn send<T: DeserializeOwned>(
+        &self,
+        mut request: reqwest::blocking::RequestBuilder,
+        url: &str,
+    ) -> Result<T> {
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().map_err(|e| {
+            anyhow::anyhow!("Coordination server {} is unreachable: {}", self.base_url, e)
+        })?;
+        let status = response.status();
+        let value: Value = response
+            .json()
+            .with_context(|| format!("Invalid response from {}", url))?;
+        if !status.is_success() {
+            let error = value
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("no error message");
+            anyhow::bail!("Coordination server returned {}: {}", status, error);
+        }
+        serde_json::from_value(value).with_context(|| format!("Invalid response from {}", url))
+    }
+
+    pub fn claim(&self, agent_id: &str, filter: &ClaimFilter) -> Result<Option<WorkRegistryTask>> {
+        let request = ClaimRequest {
+            agent_id: agent_id.to_string(),
+            filter: filter.clone(),
+        };
+        self.post::<_, ClaimResponse>("/coord/claim", &request)
+            .map(|response| response.task)
+    }
+
+    pub fn claim_task(&self, task_id: &str, agent_id: &str) -> Result<Option<WorkRegistryTask>> {
+        let request = TaskRequest {
+            agent_id: agent_id.to_string(),
+            task_id: task_id.to_string(),
+        };
+        self.post::<_, ClaimResponse>("/coord/claim_task", &request)
+            .map(|response| response.task)
+    }
+
+    /// Return a task claimed with `claim_task` to the unclaimed pool
+    pub fn unclaim(&self, task_id: &str, agent_id: &str) -> Result<()> {
+        let request = TaskRequest {
+            agent_id: agent_id.to_string(),
+            task_id: task_id.to_string(),
+        };
+        self.post::<_, Value>("/coord/unclaim", &request).map(|_| ())
+    }
+
+    pub fn release(&self, task_id: &str, agent_id: &str) -> Result<()> {
+        let request = TaskRequest {
+            agent_id: agent_id.to_string(),
+            task_id: task_id.to_string(),
+        };
+        self.post::<_, Value>("/coord/release", &request).map(|_| ())
+    }
+
+    pub fn beat(&self, agent_id: &str) -> Result<()> {
+        let request = BeatRequest {
+            agent_id: agent_id.to_string(),
+        };
+        self.post::<_, Value>("/coord/beat", &request).map(|_| ())
+    }
+
+    pub fn quota(&self, add: Option<u64>) -> Result<QuotaTracker> {
+        self.post("/coord/quota", &QuotaRequest { add })
+    }
+
+    pub fn registry(&self) -> Result<WorkRegistry> {
+        self.get("/coord/registry")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorized() {
+        assert!(authorized(None, None));
+        assert!(authorized(Some("Bearer anything"), None));
+        assert!(authorized(Some("Bearer s3cret"), Some("s3cret")));
+        assert!(!authorized(Some("Bearer wrong"), Some("s3cret")));
+        assert!(!authorized(Some("s3cret"), Some("s3cret")));
+        assert!(!authorized(None, Some("s3cret")));
+        assert!(!authorized(Some("Bearer s3cre"), Some("s3cret")));
+        assert!(!authorized(Some("Bearer s3cret2"), Some("s3cret")));
+    }
+
+    #[test]
+    fn test_check_exposure() {
+        assert!(check_exposure("127.0.0.1:7420", None).is_ok());
+        assert!(check_exposure("[::1]:7420", None).is_ok());
+        assert!(check_exposure("0.0.0.0:7420", Some("s3cret")).is_ok());
+        let err = check_exposure("0.0.0.0:7420", None).unwrap_err().to_string();
+        assert!(err.contains("Refusing to serve on 0.0.0.0:7420 without ROTD_COORD_TOKEN"));
+        assert!(check_exposure("192.168.1.10:7420", None).is_err());
+    }
+
+    #[test]
+    fn test_route_rejects_bad_requests() {
+        let (status, body) = route("GET", "/health", b"");
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+
+        let (status, body) = route("POST", "/coord/release", b"{\"task_id\":\"1.1\"}");
+        assert_eq!(status, 400);
+        assert!(body["error"].as_str().unwrap().contains("agent_id"));
+
+        let (status, body) = route("POST", "/coord/claim_task", b"{\"agent_id\":\"a\"}");
+        assert_eq!(status, 400);
+        assert!(body["error"].as_str().unwrap().contains("task_id"));
+
+        let (status, _) = route("DELETE", "/coord/claim", b"");
+        assert_eq!(status, 404);
+    }
+}
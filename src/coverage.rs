@@ -0,0 +1,482 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::CoverageCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{read_json, write_json};
+use crate::schema::CoverageHistory;
+
+/// Output formats for `coverage chart`
+pub const CHART_FORMATS: [&str; 2] = ["ascii", "json"];
+
+/// Rows in the ASCII chart
+const CHART_HEIGHT: usize = 11;
+
+pub fn handle_command(cmd: CoverageCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        CoverageCommands::Chart { last, format } => cmd_chart(last, &format, is_agent_mode),
+        CoverageCommands::Prune { keep, days } => cmd_prune(keep, days, dry_run, is_agent_mode),
+    }
+}
+
+/// Report files the common coverage tools write, checked after `--run` when
+/// `coverage_report` is not configured
+const KNOWN_REPORTS: &[&str] = &[
+    "tarpaulin-report.json",
+    "coverage/coverage-summary.json",
+    "coverage.json",
+    "coverage.out",
+    "lcov.info",
+    "coverage/lcov.info",
+    "cobertura.xml",
+    "coverage.xml",
+];
+
+/// A coverage percentage and the tool that produced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    pub percent: f64,
+    pub tool: String,
+}
+
+/// Line coverage from a report, recognising tarpaulin JSON, istanbul/c8
+/// json-summary, coverage.py JSON, Go cover profiles, lcov, and Cobertura XML
+pub fn parse_report(content: &str) -> Option<Reading> {
+    let reading = |percent: f64, tool: &str| Reading {
+        percent: (percent * 100.0).round() / 100.0,
+        tool: tool.to_string(),
+    };
+    let ratio = |covered: f64, total: f64| if total > 0.0 { covered / total * 100.0 } else { 0.0 };
+
+    if let Ok(json) = serde_json::from_str::<Value>(content) {
+        if let Some(pct) = json.pointer("/total/lines/pct").and_then(Value::as_f64) {
+            return Some(reading(pct, "istanbul"));
+        }
+        if let Some(pct) = json.pointer("/totals/percent_covered").and_then(Value::as_f64) {
+            return Some(reading(pct, "coverage.py"));
+        }
+        if let Some(files) = json.get("files").and_then(Value::as_array) {
+            // Older tarpaulin reports only have per-file counts
+            let percent = json.get("coverage").and_then(Value::as_f64).unwrap_or_else(|| {
+                let sum = |key: &str| -> f64 {
+                    files.iter().filter_map(|f| f.get(key)?.as_f64()).sum()
+                };
+                ratio(sum("covered"), sum("coverable"))
+            });
+            return Some(reading(percent, "tarpaulin"));
+        }
+        return None;
+    }
+
+    if content.starts_with("mode: ") {
+        let (mut covered, mut total) = (0.0, 0.0);
+        for line in content.lines().skip(1) {
+            let mut fields = line.rsplit(' ');
+            let (Some(count), Some(statements)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let (Ok(count), Ok(statements)) = (count.parse::<u64>(), statements.parse::<f64>())
+            else {
+                continue;
+            };
+            total += statements;
+            if count > 0 {
+                covered += statements;
+            }
+        }
+        return Some(reading(ratio(covered, total), "go"));
+    }
+
+    if content.lines().any(|l| l.starts_with("LF:")) {
+        let sum = |prefix: &str| -> f64 {
+            content
+                .lines()
+                .filter_map(|l| l.strip_prefix(prefix)?.trim().parse::<f64>().ok())
+                .sum()
+        };
+        return Some(reading(ratio(sum("LH:"), sum("LF:")), "lcov"));
+    }
+
+    let cobertura = Regex::new(r#"<coverage\b[^>]*\bline-rate="([0-9.]+)""#).unwrap();
+    if let Some(rate) = cobertura.captures(content).and_then(|c| c[1].parse::<f64>().ok()) {
+        return Some(reading(rate * 100.0, "cobertura"));
+    }
+    None
+}
+
+/// Read and parse a coverage report
+pub fn read_report(path: &Path) -> Result<Reading> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_report(&content)
+        .ok_or_else(|| anyhow::anyhow!("Unrecognised coverage report: {}", path.display()))
+}
+
+/// Run the configured `coverage_command` and read the report it writes:
+/// `coverage_report` when configured, otherwise the first well-known report
+/// updated by the run, otherwise a `NN.NN% coverage` line in its output
+pub fn run_configured() -> Result<Reading> {
+    let config = crate::history::load_config().unwrap_or_default();
+    let command_line = config
+        .coverage_command
+        .filter(|c| !c.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("No coverage_command configured in config.jsonc"))?;
+
+    let started = SystemTime::now();
+    let mut parts = command_line.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+    let output = Command::new(program)
+        .args(parts)
+        .output()
+        .with_context(|| format!("Failed to run {}", command_line))?;
+    if !output.status.success() {
+        anyhow::bail!("{} exited with {}", command_line, output.status);
+    }
+
+    if let Some(report) = config.coverage_report {
+        return read_report(Path::new(&report));
+    }
+    let fresh = |path: &PathBuf| {
+        std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified >= started)
+    };
+    if let Some(path) = KNOWN_REPORTS.iter().map(PathBuf::from).find(fresh) {
+        return read_report(&path);
+    }
+
+    let summary = Regex::new(r"(\d+(?:\.\d+)?)% coverage").unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let percent = summary
+        .captures_iter(&stdout)
+        .last()
+        .and_then(|c| c[1].parse::<f64>().ok())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} wrote no coverage report; set coverage_report in config.jsonc",
+                command_line
+            )
+        })?;
+    Ok(Reading {
+        percent,
+        tool: program.to_string(),
+    })
+}
+
+/// Drop entries older than `days` and all but the last `keep`, returning
+/// how many went. The newest entry is always kept.
+pub fn prune(
+    history: &mut CoverageHistory,
+    keep: Option<usize>,
+    days: Option<u64>,
+    now: DateTime<Utc>,
+) -> usize {
+    let before = history.history.len();
+    if let Some(days) = days {
+        let cutoff = now - Duration::days(days as i64);
+        let mut index = 0;
+        history.history.retain(|entry| {
+            index += 1;
+            index == before || entry.timestamp >= cutoff
+        });
+    }
+    if let Some(keep) = keep {
+        let excess = history.history.len().saturating_sub(keep.max(1));
+        history.history.drain(..excess);
+    }
+    before - history.history.len()
+}
+
+/// The floor after each entry. Entries from before floors were recorded
+/// take it from the last ratchet; ahead of the first ratchet it is known
+/// only if the floor never moved.
+fn floors(history: &CoverageHistory) -> Vec<Option<f64>> {
+    let mut known = None;
+    let mut floors: Vec<Option<f64>> = history
+        .history
+        .iter()
+        .map(|entry| {
+            if let Some(floor) = entry.floor {
+                known = Some(floor);
+            } else if entry.triggered_ratchet {
+                known = Some(entry.coverage - 1.0);
+            }
+            known
+        })
+        .collect();
+
+    let fill = match floors.iter().position(Option::is_some) {
+        Some(first) if !history.history[first].triggered_ratchet => floors[first],
+        Some(_) => None,
+        None => Some(history.floor),
+    };
+    for floor in floors.iter_mut().take_while(|f| f.is_none()) {
+        *floor = fill;
+    }
+    floors
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ChartPoint {
+    pub timestamp: DateTime<Utc>,
+    pub task_id: String,
+    pub coverage: f64,
+    pub floor: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+/// Coverage and floor for the last `last` entries
+#[derive(Debug, Serialize)]
+pub struct Chart {
+    pub floor: f64,
+    pub ratchet_threshold: f64,
+    pub points: Vec<ChartPoint>,
+}
+
+pub fn chart(history: &CoverageHistory, last: usize) -> Chart {
+    let skip = history.history.len().saturating_sub(last);
+    let points = history
+        .history
+        .iter()
+        .zip(floors(history))
+        .skip(skip)
+        .map(|(entry, floor)| ChartPoint {
+            timestamp: entry.timestamp,
+            task_id: entry.task_id.clone(),
+            coverage: entry.coverage,
+            floor,
+            tool: entry.tool.clone(),
+        })
+        .collect();
+    Chart {
+        floor: history.floor,
+        ratchet_threshold: history.ratchet_threshold,
+        points,
+    }
+}
+
+/// One column per point: `*` for coverage and `-` for the floor, on a
+/// scale snapped to 5% steps around the values shown
+pub fn render_ascii(chart: &Chart) -> Vec<String> {
+    if chart.points.is_empty() {
+        return vec!["No coverage history recorded".to_string()];
+    }
+    let values = chart
+        .points
+        .iter()
+        .flat_map(|p| std::iter::once(p.coverage).chain(p.floor));
+    let (min, max) = values.fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let low = (min / 5.0).floor() * 5.0;
+    let high = ((max / 5.0).ceil() * 5.0).max(low + 5.0);
+    let step = (high - low) / (CHART_HEIGHT - 1) as f64;
+    let row = |value: f64| ((high - value) / step).round() as usize;
+
+    let mut grid = vec![vec![' '; chart.points.len()]; CHART_HEIGHT];
+    for (column, point) in chart.points.iter().enumerate() {
+        if let Some(floor) = point.floor {
+            grid[row(floor)][column] = '-';
+        }
+        grid[row(point.coverage)][column] = '*';
+    }
+
+    let mut lines: Vec<String> = grid
+        .into_iter()
+        .enumerate()
+        .map(|(i, cells)| {
+            let label = high - step * i as f64;
+            format!("{:>6.1}% |{}", label, cells.into_iter().collect::<String>())
+        })
+        .collect();
+    lines.push(format!("{:>8}+{}", "", "-".repeat(chart.points.len())));
+    let first = chart.points[0].timestamp.format("%Y-%m-%d").to_string();
+    let last = chart.points[chart.points.len() - 1].timestamp.format("%Y-%m-%d").to_string();
+    let gap = chart.points.len().saturating_sub(first.len() + last.len()).max(1);
+    lines.push(if first == last {
+        format!("{:>9}{}", "", first)
+    } else {
+        format!("{:>9}{}{}{}", "", first, " ".repeat(gap), last)
+    });
+    lines
+}
+
+fn load_history() -> Result<CoverageHistory> {
+    check_rotd_initialized()?;
+    let path = crate::common::coverage_history_path();
+    if !path.exists() {
+        anyhow::bail!("No coverage history recorded; run `rotd agent ratchet-coverage` first");
+    }
+    read_json(&path)
+}
+
+fn cmd_chart(last: usize, format: &str, is_agent_mode: bool) -> Result<()> {
+    let history = load_history()?;
+    let chart = chart(&history, last);
+
+    // Agent mode gets JSON
+    if format == "json" || is_agent_mode {
+        println!("{}", serde_json::to_string_pretty(&chart)?);
+        return Ok(());
+    }
+
+    println!("{}", "Coverage".cyan().bold());
+    println!(
+        "Floor {:.1}%, ratchet at +{:.1} points  (* coverage, - floor)\n",
+        chart.floor, chart.ratchet_threshold
+    );
+    for line in render_ascii(&chart) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn cmd_prune(
+    keep: Option<usize>,
+    days: Option<u64>,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    let mut history = load_history()?;
+    let config = crate::history::load_config().unwrap_or_default();
+    let keep = keep.or(config.coverage_keep_entries);
+    let days = days.or(config.coverage_keep_days);
+    if keep.is_none() && days.is_none() {
+        anyhow::bail!(
+            "Pass --keep or --days, or set coverage_keep_entries or coverage_keep_days in config.jsonc"
+        );
+    }
+
+    let removed = prune(&mut history, keep, days, Utc::now());
+    if !dry_run && removed > 0 {
+        write_json(&crate::common::coverage_history_path(), &history)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "coverage_prune",
+                "removed": removed,
+                "kept": history.history.len(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} {} coverage entr{} ({} kept)",
+            if dry_run { "Would prune" } else { "✓ Pruned" },
+            removed,
+            if removed == 1 { "y" } else { "ies" },
+            history.history.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::CoverageEntry;
+
+    #[test]
+    fn test_parse_report() {
+        let parsed = |content: &str| parse_report(content).map(|r| (r.percent, r.tool));
+        let tool = |percent: f64, tool: &str| Some((percent, tool.to_string()));
+
+        assert_eq!(
+            parsed(r#"{"files":[{"covered":3,"coverable":4},{"covered":1,"coverable":4}]}"#),
+            tool(50.0, "tarpaulin")
+        );
+        assert_eq!(
+            parsed(r#"{"files":[],"coverage":81.25}"#),
+            tool(81.25, "tarpaulin")
+        );
+        assert_eq!(
+            parsed(r#"{"total":{"lines":{"total":80,"covered":70,"pct":87.5}}}"#),
+            tool(87.5, "istanbul")
+        );
+        assert_eq!(
+            parsed(r#"{"meta":{},"totals":{"percent_covered":66.666666}}"#),
+            tool(66.67, "coverage.py")
+        );
+        assert_eq!(
+            parsed("mode: set\na.go:1.1,2.2 3 1\na.go:3.1,4.2 1 0\n"),
+            tool(75.0, "go")
+        );
+        assert_eq!(
+            parsed("SF:a.rs\nLF:10\nLH:9\nend_of_record\nSF:b.rs\nLF:10\nLH:7\nend_of_record\n"),
+            tool(80.0, "lcov")
+        );
+        assert_eq!(
+            parsed(r#"<?xml version="1.0"?><coverage line-rate="0.913" branch-rate="0">"#),
+            tool(91.3, "cobertura")
+        );
+        assert_eq!(parsed(r#"{"unrelated":true}"#), None);
+        assert_eq!(parsed("plain text"), None);
+    }
+
+    fn entry(day: i64, coverage: f64, triggered_ratchet: bool, floor: Option<f64>) -> CoverageEntry {
+        CoverageEntry {
+            task_id: format!("{}.1", day),
+            coverage,
+            timestamp: DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().to_utc()
+                + Duration::days(day),
+            triggered_ratchet,
+            tool: None,
+            floor,
+        }
+    }
+
+    #[test]
+    fn test_prune_and_chart() {
+        let mut history = CoverageHistory {
+            floor: 79.0,
+            ratchet_threshold: 3.0,
+            history: vec![
+                entry(0, 71.0, false, None),
+                entry(1, 76.0, true, None),
+                entry(2, 74.0, false, None),
+                entry(3, 80.0, true, Some(79.0)),
+            ],
+        };
+
+        // Nothing before the first ratchet says what the floor was
+        let series = chart(&history, 10);
+        let series_floors: Vec<_> = series.points.iter().map(|p| p.floor).collect();
+        assert_eq!(series_floors, vec![None, Some(75.0), Some(75.0), Some(79.0)]);
+        assert_eq!(chart(&history, 2).points[0].task_id, "2.1");
+
+        let lines = render_ascii(&series);
+        assert_eq!(lines[0], "  80.0% |   *");
+        assert_eq!(lines[5], "  75.0% | -- ");
+        assert_eq!(lines[10], "  70.0% |    ");
+        assert!(lines[9].ends_with("|*   "));
+        assert!(lines[11].ends_with("+----"));
+
+        // A floor that never moved applies throughout
+        let steady = CoverageHistory {
+            floor: 70.0,
+            ratchet_threshold: 3.0,
+            history: vec![entry(0, 71.0, false, None), entry(1, 72.0, false, None)],
+        };
+        assert_eq!(floors(&steady), vec![Some(70.0), Some(70.0)]);
+
+        let now = history.history[3].timestamp;
+        assert_eq!(prune(&mut history, None, Some(2), now), 1);
+        assert_eq!(prune(&mut history, Some(2), None, now), 1);
+        let ids: Vec<_> = history.history.iter().map(|e| e.task_id.as_str()).collect();
+        assert_eq!(ids, vec!["2.1", "3.1"]);
+
+        // The newest entry survives any limit
+        assert_eq!(prune(&mut history, Some(0), Some(0), now + Duration::days(30)), 1);
+        assert_eq!(history.history.len(), 1);
+    }
+}
@@ -1,8 +1,9 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::fs::{self, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use crate::schema::*;
@@ -12,24 +13,7 @@ where
     F: FnOnce() -> Result<()>,
     P: AsRef<Path>,
 {
-    use fs2::FileExt;
-    let lock_path = Path::new(path.as_ref());
-    std::fs::create_dir_all(lock_path.parent().unwrap())?;
-    let file = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .open(lock_path)?;
-    let start = Instant::now();
-    while file.try_lock_exclusive().is_err() {
-        if start.elapsed() > Duration::from_secs(30) {
-            return Err(anyhow::anyhow!("E_LOCK_TIMEOUT"));
-        }
-        std::thread::sleep(Duration::from_millis(250));
-    }
-    let res = f();
-    fs2::FileExt::unlock(&file)?;
-    res
+    with_lock_result(path, f)
 }
 
 pub fn with_lock_result<F, P, T>(path: P, f: F) -> Result<T>
@@ -37,24 +21,170 @@ where
     F: FnOnce() -> Result<T>,
     P: AsRef<Path>,
 {
+    let file = acquire_lock(path.as_ref())?;
+    let res = f();
+    fs2::FileExt::unlock(&file)?;
+    res
+}
+
+/// Lock file guarding `path`. Paths that already end in `.lock` are used as-is;
+/// data files get `.lock/<name>.lock` next to them (mirroring
+/// `coordination/.lock/`) so holder metadata never touches the data itself.
+pub fn lock_file_path(path: &Path) -> PathBuf {
+    if path.extension().and_then(|e| e.to_str()) == Some("lock") {
+        return path.to_path_buf();
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    path.parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(".lock")
+        .join(name)
+}
+
+fn acquire_lock(path: &Path) -> Result<File> {
     use fs2::FileExt;
-    let lock_path = Path::new(path.as_ref());
-    std::fs::create_dir_all(lock_path.parent().unwrap())?;
-    let file = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .open(lock_path)?;
+    let lock_path = lock_file_path(path);
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     let start = Instant::now();
-    while file.try_lock_exclusive().is_err() {
+    loop {
+        // Reopen on every attempt so a lock file replaced by a stale-lock break
+        // is picked up instead of waiting on the orphaned inode.
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)?;
+
+        if file.try_lock_exclusive().is_ok() {
+            if !same_file(&file, &lock_path) {
+                // Another process broke and recreated the lock underneath us
+                continue;
+            }
+            write_lock_holder(&mut file)?;
+            return Ok(file);
+        }
+
+        if let Some(holder) = read_lock_holder(&lock_path) {
+            if holder_is_dead(&holder) {
+                drop(file);
+                break_stale_lock(&lock_path, &holder)?;
+                continue;
+            }
+        }
+
         if start.elapsed() > Duration::from_secs(30) {
             return Err(anyhow::anyhow!("E_LOCK_TIMEOUT"));
         }
         std::thread::sleep(Duration::from_millis(250));
     }
-    let res = f();
-    fs2::FileExt::unlock(&file)?;
-    res
+}
+
+fn write_lock_holder(file: &mut File) -> Result<()> {
+    use std::io::{Seek, SeekFrom};
+    let holder = LockHolder {
+        pid: std::process::id(),
+        hostname: hostname(),
+        since: Utc::now(),
+    };
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    serde_json::to_writer(&mut *file, &holder)?;
+    file.flush()?;
+    Ok(())
+}
+
+pub fn read_lock_holder(lock_path: &Path) -> Option<LockHolder> {
+    let content = fs::read_to_string(lock_path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// A holder is only considered dead when it ran on this host and its pid is gone;
+/// locks held from other machines are never broken.
+pub fn holder_is_dead(holder: &LockHolder) -> bool {
+    holder.hostname == hostname() && holder.pid != std::process::id() && !process_alive(holder.pid)
+}
+
+fn break_stale_lock(lock_path: &Path, holder: &LockHolder) -> Result<()> {
+    // Re-read right before removal so we never delete a lock that a live
+    // process re-acquired in the meantime.
+    match read_lock_holder(lock_path) {
+        Some(current) if current.pid == holder.pid && current.since == holder.since => {}
+        _ => return Ok(()),
+    }
+
+    match fs::remove_file(lock_path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+
+    if crate::common::rotd_path().exists() {
+        let _ = crate::audit::log_warning(
+            None,
+            "LOCK_BROKEN",
+            &format!(
+                "Broke stale lock {} held by dead process {} on {} since {}",
+                lock_path.display(),
+                holder.pid,
+                holder.hostname,
+                holder.since.to_rfc3339()
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_file(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // Signal 0 only performs the permission/existence check
+    let rc = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    // Without a portable liveness probe, assume the holder is alive
+    true
+}
+
+pub fn hostname() -> String {
+    #[cfg(unix)]
+    {
+        let mut buf = [0u8; 256];
+        let rc = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+        if rc == 0 {
+            let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+            if let Ok(name) = std::str::from_utf8(&buf[..len]) {
+                if !name.is_empty() {
+                    return name.to_string();
+                }
+            }
+        }
+    }
+
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
 }
 
 pub fn read_jsonl<T>(file_path: &Path) -> Result<Vec<T>>
@@ -97,6 +227,7 @@ where
         }
 
         let json_line = serde_json::to_string(item).context("Failed to serialize item")?;
+        let json_line = seal(file_path, vec![json_line])?.concat();
 
         let mut file = fs::OpenOptions::new()
             .create(true)
@@ -110,6 +241,47 @@ where
     })
 }
 
+/// Sign and hash-chain lines about to be appended to `file_path`, for the
+/// files that take either
+fn seal(file_path: &Path, lines: Vec<String>) -> Result<Vec<String>> {
+    let lines = crate::signing::sign_lines(file_path, lines)?;
+    crate::integrity::link(file_path, lines)
+}
+
+/// Append several items with one locked write, so readers see all of
+/// them or none
+pub fn append_jsonl_all<T>(file_path: &Path, items: &[T]) -> Result<()>
+where
+    T: Serialize,
+{
+    let lines = items
+        .iter()
+        .map(|item| serde_json::to_string(item).context("Failed to serialize item"))
+        .collect::<Result<Vec<_>>>()?;
+
+    with_lock(file_path, || {
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create parent directory")?;
+        }
+
+        let lines: String = seal(file_path, lines)?
+            .into_iter()
+            .map(|line| line + "\n")
+            .collect();
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file_path)
+            .context("Failed to open file for appending")?;
+
+        file.write_all(lines.as_bytes())
+            .context("Failed to write to file")?;
+
+        Ok(())
+    })
+}
+
 pub fn write_json<T>(file_path: &Path, item: &T) -> Result<()>
 where
     T: Serialize,
@@ -147,6 +319,8 @@ pub fn append_line(file_path: &Path, line: &str) -> Result<()> {
             fs::create_dir_all(parent).context("Failed to create parent directory")?;
         }
 
+        let line = crate::integrity::link(file_path, vec![line.to_string()])?.concat();
+
         let mut file = fs::OpenOptions::new()
             .create(true)
             .append(true)
@@ -168,25 +342,125 @@ pub fn read_stdin() -> Result<String> {
     Ok(buffer.trim().to_string())
 }
 
+/// Warn on stderr about records stamped with a `_schema` this binary does
+/// not understand, or fail in strict mode. Each distinct problem is reported
+/// once per call.
+pub fn check_schema_versions<T: Versioned>(records: &[T], source: &str, strict: bool) -> Result<()> {
+    let mut issues: Vec<String> = records.iter().filter_map(|r| r.schema_issue()).collect();
+    issues.dedup();
+    if strict {
+        if let Some(issue) = issues.into_iter().next() {
+            return Err(anyhow::anyhow!("{}: {}", source, issue));
+        }
+        return Ok(());
+    }
+    for issue in issues {
+        eprintln!("Warning: {}: {}", source, issue);
+    }
+    Ok(())
+}
+
+/// Collapse the append-only task log to the latest entry per task ID,
+/// keeping tasks in the order they first appeared
+pub fn latest_tasks() -> Result<Vec<TaskEntry>> {
+    let tasks_path = crate::common::tasks_path();
+    let entries = read_jsonl::<TaskEntry>(&tasks_path)?;
+    check_schema_versions(&entries, &tasks_path.display().to_string(), false)?;
+    let mut order: Vec<String> = Vec::new();
+    let mut latest: std::collections::HashMap<String, TaskEntry> =
+        std::collections::HashMap::new();
+
+    for entry in entries {
+        if !latest.contains_key(&entry.id) {
+            order.push(entry.id.clone());
+        }
+        latest.insert(entry.id.clone(), entry);
+    }
+
+    Ok(order
+        .into_iter()
+        .filter_map(|id| latest.remove(&id))
+        .collect())
+}
+
 // Safe file operations with validation
 pub fn safe_update_task(task: &TaskEntry, dry_run: bool) -> Result<()> {
+    if let Some(prev_task) = append_task(task, dry_run)? {
+        crate::history::record_task_history(task, prev_task.as_ref(), None, None)?;
+    }
+    Ok(())
+}
+
+/// Validate and append a task entry without recording history. Returns
+/// the entry it supersedes (`Some(None)` for a new task), or `None` on a
+/// dry run.
+pub fn append_task(task: &TaskEntry, dry_run: bool) -> Result<Option<Option<TaskEntry>>> {
     task.validate()?;
 
+    // Get previous task state for history
+    let tasks = read_jsonl::<TaskEntry>(&crate::common::tasks_path())?;
+    let prev_task = tasks.into_iter().rev().find(|t| t.id == task.id);
+
+    let mut task = task.clone();
+    if timestamps_managed() {
+        stamp_timestamps(&mut task, prev_task.as_ref(), Utc::now());
+    }
+
     if dry_run {
-        println!("Would update task: {}", serde_json::to_string_pretty(task)?);
-        return Ok(());
+        println!("Would update task: {}", serde_json::to_string_pretty(&task)?);
+        return Ok(None);
     }
 
-    // Get previous task state for history
-    let tasks = read_jsonl::<TaskEntry>(&crate::common::tasks_path())?;
-    let prev_task = tasks.iter().rev().find(|t| t.id == task.id);
+    append_jsonl(&crate::common::tasks_path(), &task)?;
 
-    // Append to main tasks file
-    append_jsonl(&crate::common::tasks_path(), task)?;
+    Ok(Some(prev_task))
+}
 
-    // Append to task history
-    crate::history::append_task_history(task, prev_task, None, None)?;
+fn timestamps_managed() -> bool {
+    crate::history::load_config().map_or(true, |config| config.manage_timestamps)
+}
+
+/// Set `updated_at` to `now`, and `completed` when a task is complete: kept
+/// if given or already recorded, otherwise `now`. Any other status clears it.
+pub fn stamp_timestamps(task: &mut TaskEntry, prev: Option<&TaskEntry>, now: DateTime<Utc>) {
+    task.updated_at = Some(now);
+    task.completed = match task.status {
+        TaskStatus::Complete => task
+            .completed
+            .or_else(|| {
+                prev.filter(|p| p.status == TaskStatus::Complete)
+                    .and_then(|p| p.completed)
+            })
+            .or(Some(now)),
+        _ => None,
+    };
+}
+
+/// Validate and append several task entries in a single write, so either
+/// all of them land in tasks.jsonl or none do. History is recorded per task,
+/// each event carrying `comment`.
+pub fn append_tasks(tasks: &[TaskEntry], comment: Option<&str>) -> Result<()> {
+    for task in tasks {
+        task.validate()?;
+    }
 
+    let tasks_path = crate::common::tasks_path();
+    let previous = read_jsonl::<TaskEntry>(&tasks_path)?;
+    let mut tasks = tasks.to_vec();
+    if timestamps_managed() {
+        let now = Utc::now();
+        for task in &mut tasks {
+            let prev_task = previous.iter().rev().find(|t| t.id == task.id);
+            stamp_timestamps(task, prev_task, now);
+        }
+    }
+
+    append_jsonl_all(&tasks_path, &tasks)?;
+
+    for task in &tasks {
+        let prev_task = previous.iter().rev().find(|t| t.id == task.id);
+        crate::history::record_task_history(task, prev_task, comment.map(str::to_string), None)?;
+    }
     Ok(())
 }
 
@@ -201,7 +475,9 @@ pub fn safe_append_summary(summary: &TestSummary, dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    write_json(&file_path, summary)
+    let mut document = serde_json::to_value(summary)?;
+    crate::signing::sign_document(&mut document)?;
+    write_json(&file_path, &document)
 }
 
 pub fn safe_log_lesson(lesson: &LessonLearned, dry_run: bool) -> Result<()> {
@@ -218,6 +494,23 @@ pub fn safe_log_lesson(lesson: &LessonLearned, dry_run: bool) -> Result<()> {
     append_jsonl(&crate::common::lessons_path(), lesson)
 }
 
+/// Validate and append several lessons in a single write
+pub fn safe_log_lessons(lessons: &[LessonLearned], dry_run: bool) -> Result<()> {
+    for lesson in lessons {
+        lesson.validate()?;
+    }
+
+    if dry_run {
+        println!(
+            "Would append lessons: {}",
+            serde_json::to_string_pretty(lessons)?
+        );
+        return Ok(());
+    }
+
+    append_jsonl_all(&crate::common::lessons_path(), lessons)
+}
+
 #[allow(dead_code)]
 pub fn read_active_work_registry() -> Result<ActiveWorkRegistry> {
     let path = crate::common::active_work_registry_path();
@@ -232,3 +525,76 @@ pub fn write_active_work_registry(registry: &ActiveWorkRegistry) -> Result<()> {
     let path = crate::common::active_work_registry_path();
     write_json(&path, registry)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_file_path() {
+        assert_eq!(
+            lock_file_path(Path::new(".rotd/tasks.jsonl")),
+            PathBuf::from(".rotd/.lock/tasks.jsonl.lock")
+        );
+        assert_eq!(
+            lock_file_path(Path::new(".rotd/coordination/.lock/registry.lock")),
+            PathBuf::from(".rotd/coordination/.lock/registry.lock")
+        );
+    }
+
+    #[test]
+    fn test_dead_holder_detection() {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+
+        let dead = LockHolder {
+            pid,
+            hostname: hostname(),
+            since: Utc::now(),
+        };
+        assert!(holder_is_dead(&dead));
+
+        let remote = LockHolder {
+            pid,
+            hostname: "some-other-host".to_string(),
+            since: Utc::now(),
+        };
+        assert!(!holder_is_dead(&remote));
+
+        let me = LockHolder {
+            pid: std::process::id(),
+            hostname: hostname(),
+            since: Utc::now(),
+        };
+        assert!(!holder_is_dead(&me));
+    }
+
+    #[test]
+    fn test_stamp_timestamps() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let task = |status: &str, completed: Option<&str>| -> TaskEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": "6.1",
+                "title": "Parser",
+                "status": status,
+                "completed": completed,
+            }))
+            .unwrap()
+        };
+        let now = at("2026-02-01T10:00:00Z");
+
+        let mut done = task("complete", None);
+        stamp_timestamps(&mut done, Some(&task("in_progress", None)), now);
+        assert_eq!((done.updated_at, done.completed), (Some(now), Some(now)));
+
+        // An earlier completion survives re-saving a complete task
+        let mut again = task("complete", None);
+        stamp_timestamps(&mut again, Some(&task("complete", Some("2026-01-20T09:00:00Z"))), now);
+        assert_eq!(again.completed, Some(at("2026-01-20T09:00:00Z")));
+
+        let mut reopened = task("in_progress", Some("2026-01-20T09:00:00Z"));
+        stamp_timestamps(&mut reopened, None, now);
+        assert_eq!(reopened.completed, None);
+    }
+}
@@ -0,0 +1,480 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::GitCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, read_jsonl};
+use crate::merge::MERGE_DRIVER;
+use crate::schema::TaskEntry;
+
+/// Trailer key linking a commit to a ROTD task
+pub const TASK_TRAILER: &str = "ROTD-Task";
+
+/// `git log` placeholder for the values of a commit's `ROTD-Task:`
+/// trailers, comma-separated. git reads only the final trailer block and
+/// matches the key case-insensitively.
+const TASK_TRAILER_FORMAT: &str = "%(trailers:key=ROTD-Task,valueonly,separator=%x2c)";
+
+/// Commits by task, built on first use and kept for the invocation
+static COMMIT_INDEX: OnceLock<HashMap<String, Vec<TaskCommit>>> = OnceLock::new();
+
+/// A commit referencing a task through a `ROTD-Task:` trailer
+#[derive(Debug, Serialize, Clone)]
+pub struct TaskCommit {
+    pub sha: String,
+    pub subject: String,
+    pub author: String,
+    pub date: DateTime<Utc>,
+}
+
+pub fn handle_command(cmd: GitCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        GitCommands::Attach { task_id } => cmd_attach(&task_id, dry_run, is_agent_mode),
+        GitCommands::Message { task_id, install } => {
+            cmd_message(&task_id, install, dry_run, is_agent_mode)
+        }
+        GitCommands::ConfigureMerge => cmd_configure_merge(dry_run, is_agent_mode),
+    }
+}
+
+/// Run git with `args` and return its trimmed stdout
+pub fn run_git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git. Is git installed?")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Task IDs in comma-separated trailer values, in order and without
+/// duplicates
+fn split_task_ids(values: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for id in values.split([',', '\n']).map(str::trim).filter(|s| !s.is_empty()) {
+        if !ids.iter().any(|existing| existing == id) {
+            ids.push(id.to_string());
+        }
+    }
+    ids
+}
+
+/// Extract task IDs from the `ROTD-Task:` trailers of a commit message, as
+/// `git interpret-trailers` parses them: only the final trailer block
+/// counts, the key is matched case-insensitively, and values may be
+/// comma-separated.
+pub fn parse_task_trailers(message: &str) -> Result<Vec<String>> {
+    let trailers = interpret_trailers(message, &["--parse"])?;
+    let values: Vec<&str> = trailers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(key, _)| key.trim().eq_ignore_ascii_case(TASK_TRAILER))
+        .map(|(_, value)| value)
+        .collect();
+    Ok(split_task_ids(&values.join(",")))
+}
+
+/// Index commits reachable from HEAD by the task IDs in their trailers.
+/// Built once per invocation; empty outside a git repository or before the
+/// first commit.
+pub fn task_commit_index() -> &'static HashMap<String, Vec<TaskCommit>> {
+    COMMIT_INDEX.get_or_init(build_commit_index)
+}
+
+fn build_commit_index() -> HashMap<String, Vec<TaskCommit>> {
+    let mut index: HashMap<String, Vec<TaskCommit>> = HashMap::new();
+
+    // Fields are separated by US (0x1f) and records by RS (0x1e)
+    let format = format!("--format=%H%x1f%s%x1f%an%x1f%aI%x1f{}%x1e", TASK_TRAILER_FORMAT);
+    let Ok(log) = run_git(&["log", "HEAD", &format]) else {
+        return index;
+    };
+
+    for record in log.split('\x1e') {
+        let fields: Vec<&str> = record.trim_start().splitn(5, '\x1f').collect();
+        let [sha, subject, author, date, trailers] = fields.as_slice() else {
+            continue;
+        };
+        let ids = split_task_ids(trailers);
+        if ids.is_empty() {
+            continue;
+        }
+        let commit = TaskCommit {
+            sha: sha.to_string(),
+            subject: subject.to_string(),
+            author: author.to_string(),
+            date: DateTime::parse_from_rfc3339(date)
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        };
+        for id in ids {
+            index.entry(id).or_default().push(commit.clone());
+        }
+    }
+
+    index
+}
+
+pub fn commits_for_task(task_id: &str) -> Vec<TaskCommit> {
+    task_commit_index().get(task_id).cloned().unwrap_or_default()
+}
+
+fn cmd_attach(task_id: &str, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks: Vec<TaskEntry> = read_jsonl(&crate::common::tasks_path())?;
+    if !tasks.iter().any(|t| t.id == task_id) {
+        return Err(anyhow::anyhow!("Task {} not found", task_id));
+    }
+
+    let message = run_git(&["log", "-1", "--format=%B", "HEAD"])
+        .context("No commit to attach to. Create a commit first.")?;
+    let sha = run_git(&["rev-parse", "--short", "HEAD"])?;
+
+    if parse_task_trailers(&message)?.iter().any(|id| id == task_id) {
+        if is_agent_mode {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "unchanged",
+                    "task_id": task_id,
+                    "commit": sha,
+                })
+            );
+        } else {
+            println!("Commit {} is already linked to task {}", sha, task_id);
+        }
+        return Ok(());
+    }
+
+    let trailer = format!("{}: {}", TASK_TRAILER, task_id);
+    let new_message = add_trailer(&format!("{}\n", message), &trailer)?;
+
+    let new_sha = if dry_run {
+        sha.clone()
+    } else {
+        // --only with no paths rewrites the message without picking up staged changes
+        let mut child = Command::new("git")
+            .args([
+                "commit",
+                "--amend",
+                "--only",
+                "--no-verify",
+                "--quiet",
+                "-F",
+                "-",
+            ])
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Failed to run git commit --amend")?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(new_message.as_bytes())?;
+        if !child.wait()?.success() {
+            return Err(anyhow::anyhow!("git commit --amend failed"));
+        }
+        run_git(&["rev-parse", "--short", "HEAD"])?
+    };
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "task_id": task_id,
+                "previous_commit": sha,
+                "commit": new_sha,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+            println!("{}", new_message);
+        }
+        println!(
+            "{} Linked commit {} to task {}",
+            "✓".green(),
+            new_sha,
+            task_id
+        );
+    }
+
+    Ok(())
+}
+
+/// Trailer key naming a PSS criterion a commit addresses
+pub const CRITERIA_TRAILER: &str = "ROTD-Criteria";
+
+/// PSS criteria a commit typically moves; listed as a checklist in messages
+const COMMIT_CRITERIA: [&str; 5] = [
+    "core_impl",
+    "tests_written",
+    "tests_pass",
+    "doc_maintained",
+    "stub_free",
+];
+
+/// Build a commit message skeleton for a task.
+/// Lines starting with `#` are removed when git opens the message in an editor.
+pub fn commit_message(task: &TaskEntry) -> String {
+    let mut msg = format!("{}\n\n", task.title);
+
+    if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        let first_paragraph = description.split("\n\n").next().unwrap_or(description);
+        msg.push_str(first_paragraph.trim());
+        msg.push_str("\n\n");
+    }
+
+    if let Some(tests) = task.tests.as_ref().filter(|t| !t.is_empty()) {
+        msg.push_str("Tests:\n");
+        for test in tests {
+            msg.push_str(&format!("- {}\n", test));
+        }
+        msg.push('\n');
+    }
+
+    // Commented trailers sit in the trailer block so uncommenting one keeps it there
+    msg.push_str("# Uncomment the PSS criteria this commit addresses:\n");
+    for criterion in COMMIT_CRITERIA {
+        msg.push_str(&format!("# {}: {}\n", CRITERIA_TRAILER, criterion));
+    }
+
+    msg.push_str(&format!("{}: {}\n", TASK_TRAILER, task.id));
+    msg
+}
+
+fn cmd_message(task_id: &str, install: bool, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks = latest_tasks()?;
+    let task = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+    let message = commit_message(task);
+
+    let template_path = if install {
+        let path = run_git(&["rev-parse", "--git-path", "rotd_commit_template.txt"])
+            .context("Not a git repository (or git is not installed)")?;
+        // Store an absolute path so the template resolves from any subdirectory
+        let path = std::env::current_dir()?.join(path);
+        if !dry_run {
+            std::fs::write(&path, &message)?;
+            run_git(&["config", "commit.template", &path.display().to_string()])?;
+        }
+        Some(path)
+    } else {
+        None
+    };
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "task_id": task_id,
+                "message": message,
+                "template": template_path.as_ref().map(|p| p.display().to_string()),
+                "dry_run": dry_run,
+            })
+        );
+    } else if let Some(path) = template_path {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+        }
+        println!(
+            "{} Installed commit template for task {} at {}",
+            "✓".green(),
+            task_id,
+            path.display()
+        );
+        println!("  `git commit` will now open with this message pre-filled.");
+    } else {
+        print!("{}", message);
+    }
+
+    Ok(())
+}
+
+/// Write `.rotd/.gitignore` keeping machine-local state out of git
+pub fn write_rotd_gitignore() -> Result<std::path::PathBuf> {
+    let path = crate::common::rotd_path().join(crate::common::GITIGNORE_FILE);
+    let content = format!(
+        "# Written by `rotd init --git`: shared/ is committed, local/ stays on this machine\n\
+         {}/\n\
+         .lock/\n\
+         *.bak\n",
+        crate::common::LOCAL_DIR
+    );
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+/// `.gitattributes` patterns routed through the JSONL merge driver
+fn merge_attribute_lines() -> Vec<String> {
+    [crate::common::tasks_path(), crate::common::lessons_path()]
+        .iter()
+        .map(|p| {
+            format!(
+                "{} merge={}",
+                p.display().to_string().replace('\\', "/"),
+                MERGE_DRIVER
+            )
+        })
+        .collect()
+}
+
+fn cmd_configure_merge(dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    run_git(&["rev-parse", "--git-dir"])
+        .context("Not a git repository (or git is not installed)")?;
+
+    let attributes_path = std::path::Path::new(".gitattributes");
+    let existing = std::fs::read_to_string(attributes_path).unwrap_or_default();
+    let missing: Vec<String> = merge_attribute_lines()
+        .into_iter()
+        .filter(|line| !existing.lines().any(|l| l.trim() == line))
+        .collect();
+
+    let driver_cmd = "rotd merge-jsonl %O %A %B";
+    if !dry_run {
+        if !missing.is_empty() {
+            let mut content = existing.clone();
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            for line in &missing {
+                content.push_str(line);
+                content.push('\n');
+            }
+            std::fs::write(attributes_path, content)?;
+        }
+        run_git(&[
+            "config",
+            &format!("merge.{}.name", MERGE_DRIVER),
+            "ROTD JSONL union merge",
+        ])?;
+        run_git(&[
+            "config",
+            &format!("merge.{}.driver", MERGE_DRIVER),
+            driver_cmd,
+        ])?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "driver": MERGE_DRIVER,
+                "command": driver_cmd,
+                "gitattributes_added": missing,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+        }
+        for line in &missing {
+            println!("  {} .gitattributes: {}", "+".green(), line);
+        }
+        println!(
+            "{} Merge driver {} configured ({})",
+            "✓".green(),
+            MERGE_DRIVER,
+            driver_cmd
+        );
+        println!(
+            "  Commit .gitattributes; each clone needs `rotd git configure-merge` for the driver."
+        );
+    }
+
+    Ok(())
+}
+
+/// Append a trailer to a commit message using git's own trailer rules
+fn add_trailer(message: &str, trailer: &str) -> Result<String> {
+    interpret_trailers(message, &["--trailer", trailer])
+}
+
+/// Run `git interpret-trailers` with `args` over `message`
+fn interpret_trailers(message: &str, args: &[&str]) -> Result<String> {
+    let mut child = Command::new("git")
+        .arg("interpret-trailers")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to run git interpret-trailers")?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(message.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("git interpret-trailers failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_task_trailers() {
+        let message = "Add parser\n\nLonger body mentioning ROTD-Task in prose.\n\n\
+                       ROTD-Task: 6.2\nrotd-task: 6.3, 6.2\nSigned-off-by: Dev <dev@example.com>\n";
+        assert_eq!(parse_task_trailers(message).unwrap(), vec!["6.2", "6.3"]);
+        assert!(parse_task_trailers("No trailers here").unwrap().is_empty());
+
+        // Only the final trailer block counts, not trailer-like body lines
+        let quoted = "Revert parser\n\nROTD-Task: 6.1\nwas reverted here.\n\nROTD-Task: 6.4\n";
+        assert_eq!(parse_task_trailers(quoted).unwrap(), vec!["6.4"]);
+        let body_only = "Fix build\n\nSee ROTD-Task: 6.1 for context,\nwhich is unrelated.\n";
+        assert!(parse_task_trailers(body_only).unwrap().is_empty());
+        assert_eq!(split_task_ids("6.2, 6.3,6.2"), vec!["6.2", "6.3"]);
+    }
+
+    #[test]
+    fn test_commit_message_round_trips_trailer() {
+        let task: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "6.2",
+            "title": "Add parser cache",
+            "status": "in_progress",
+            "tests": ["test_cache_hit"],
+        }))
+        .unwrap();
+        let message = commit_message(&task);
+        assert!(message.starts_with("Add parser cache\n\n"));
+        assert!(message.contains("- test_cache_hit"));
+        assert_eq!(parse_task_trailers(&message).unwrap(), vec!["6.2"]);
+    }
+}
@@ -1,10 +1,17 @@
 use anyhow::Result;
+use colored::Colorize;
 use reqwest::blocking::Client;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use std::time::Duration;
 
+use crate::GithubCommands;
+use crate::audit;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, read_json, safe_update_task, write_json};
+use crate::schema::{PSSScore, Priority, TASK_SCHEMA, TaskEntry, TaskStatus, TestSummary};
+
 /// GitHub repository owner and name
 const GITHUB_REPO_OWNER: &str = "jmfigueroa";
 const GITHUB_REPO_NAME: &str = "rotd";
@@ -21,11 +28,16 @@ fn github_releases_url() -> String {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GitHubRelease {
     pub tag_name: String,
-    pub name: String,
-    pub published_at: String,
-    pub body: String,
+    pub name: Option<String>,
+    /// Unset for drafts
+    pub published_at: Option<String>,
+    pub body: Option<String>,
     pub html_url: String,
     pub assets: Vec<GitHubAsset>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
 }
 
 /// GitHub Release Asset
@@ -47,30 +59,81 @@ pub struct ReleaseInfo {
     pub description: String,
     pub download_url: String,
     pub html_url: String,
+    #[serde(skip_serializing)]
+    pub assets: Vec<GitHubAsset>,
 }
 
-/// Fetch latest release information from GitHub
-pub fn fetch_latest_release() -> Result<Option<ReleaseInfo>> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(10))
-        .user_agent("rotd-cli")
-        .build()
-        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?;
+/// Which releases `upgrade` and `version --latest` consider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseChannel {
+    /// Published, non-prerelease versions only
+    Stable,
+    /// Also prereleases and drafts
+    Beta,
+}
 
-    // Try to get the latest release
-    let releases_url = github_releases_url();
-    let response = client.get(&releases_url).send().map_err(|e| {
-        if e.is_timeout() {
-            anyhow::anyhow!("Request timed out after 10 seconds. Check your internet connection.")
-        } else if e.is_connect() {
-            anyhow::anyhow!(
-                "Failed to connect to GitHub API. Check your internet connection and DNS."
-            )
-        } else {
-            anyhow::anyhow!("Network error: {}", e)
+impl ReleaseChannel {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            other => Err(anyhow::anyhow!(
+                "Unknown release channel '{}'. Expected stable or beta",
+                other
+            )),
         }
-    })?;
+    }
+
+    /// The `--channel` flag, else `release_channel` from config, else stable
+    pub fn resolve(flag: Option<&str>) -> Result<Self> {
+        match flag {
+            Some(value) => Self::parse(value),
+            None => match crate::history::load_config()
+                .ok()
+                .and_then(|c| c.release_channel)
+            {
+                Some(value) => Self::parse(&value),
+                None => Ok(Self::Stable),
+            },
+        }
+    }
+
+    pub fn accepts(self, release: &GitHubRelease, version: &Version) -> bool {
+        match self {
+            Self::Stable => !release.draft && !release.prerelease && version.pre.is_empty(),
+            Self::Beta => true,
+        }
+    }
+}
 
+/// Highest-versioned release on a channel; tags that are not semver are skipped
+pub fn select_release(
+    releases: &[GitHubRelease],
+    channel: ReleaseChannel,
+) -> Option<(&GitHubRelease, Version)> {
+    releases
+        .iter()
+        .filter_map(|r| {
+            let version = Version::parse(r.tag_name.trim_start_matches('v')).ok()?;
+            channel.accepts(r, &version).then_some((r, version))
+        })
+        .max_by(|a, b| a.1.cmp(&b.1))
+}
+
+/// Fetch the most recent releases (up to 100) from GitHub
+pub fn fetch_releases() -> Result<Vec<GitHubRelease>> {
+    let client = api_client_with_timeout(Duration::from_secs(10))?;
+
+    let releases_url = github_releases_url();
+    let response = client
+        .get(&releases_url)
+        .query(&[("per_page", "100")])
+        .send()
+        .map_err(request_error)?;
+
+    if let Some(e) = rate_limit_error(&response) {
+        return Err(e);
+    }
     if !response.status().is_success() {
         return Err(anyhow::anyhow!(
             "GitHub API returned error {}: {}. This might be due to rate limiting or service issues.",
@@ -82,53 +145,47 @@ pub fn fetch_latest_release() -> Result<Option<ReleaseInfo>> {
         ));
     }
 
-    let releases: Vec<GitHubRelease> = response
+    response
         .json()
-        .map_err(|e| anyhow::anyhow!("Failed to parse GitHub API response: {}", e))?;
-
-    if releases.is_empty() {
-        return Ok(None);
-    }
-
-    // Get the most recent release
-    let latest_release = &releases[0];
-
-    // Parse semver version from tag_name (removing 'v' prefix if present)
-    let version_str = latest_release.tag_name.trim_start_matches('v');
-    let semver = Version::parse(version_str).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to parse version '{}' from release tag: {}",
-            version_str,
-            e
-        )
-    })?;
+        .map_err(|e| anyhow::anyhow!("Failed to parse GitHub API response: {}", e))
+}
 
+/// Summarize a release for the update and upgrade commands
+pub fn release_info(release: &GitHubRelease, semver: Version) -> ReleaseInfo {
     // Find suitable download asset (if any)
-    let download_url = if let Some(asset) = latest_release
+    let download_url = if let Some(asset) = release
         .assets
         .iter()
         .find(|a| a.name.ends_with(".tar.gz") || a.name.ends_with(".zip"))
     {
         asset.browser_download_url.clone()
     } else {
-        latest_release.html_url.clone()
+        release.html_url.clone()
     };
 
-    let release_info = ReleaseInfo {
-        version: latest_release.tag_name.clone(),
+    ReleaseInfo {
+        version: release.tag_name.clone(),
         semver,
-        published_at: latest_release.published_at.clone(),
-        name: latest_release.name.clone(),
-        description: latest_release.body.clone(),
+        published_at: release.published_at.clone().unwrap_or_default(),
+        name: release
+            .name
+            .clone()
+            .unwrap_or_else(|| release.tag_name.clone()),
+        description: release.body.clone().unwrap_or_default(),
         download_url,
-        html_url: latest_release.html_url.clone(),
-    };
+        html_url: release.html_url.clone(),
+        assets: release.assets.clone(),
+    }
+}
 
-    Ok(Some(release_info))
+/// Fetch latest release information from GitHub
+pub fn fetch_latest_release(channel: ReleaseChannel) -> Result<Option<ReleaseInfo>> {
+    let releases = fetch_releases()?;
+    Ok(select_release(&releases, channel).map(|(release, semver)| release_info(release, semver)))
 }
 
 /// Check if update is available
-pub fn check_update() -> Result<(bool, Option<ReleaseInfo>)> {
+pub fn check_update(channel: ReleaseChannel) -> Result<(bool, Option<ReleaseInfo>)> {
     // Get current version from Cargo.toml
     let current_version = env!("CARGO_PKG_VERSION");
     let current_semver = Version::parse(current_version).map_err(|e| {
@@ -140,7 +197,7 @@ pub fn check_update() -> Result<(bool, Option<ReleaseInfo>)> {
     })?;
 
     // Fetch latest release
-    match fetch_latest_release()? {
+    match fetch_latest_release(channel)? {
         Some(latest) => {
             let update_available = latest.semver > current_semver;
             Ok((update_available, Some(latest)))
@@ -161,97 +218,372 @@ pub fn extract_changes(body: &str) -> Vec<String> {
         .collect()
 }
 
-/// Find the appropriate release asset for the current platform
-pub fn find_platform_asset(release: &ReleaseInfo) -> Result<GitHubAsset> {
-    // Get the current platform
-    let os = std::env::consts::OS;
-    let arch = std::env::consts::ARCH;
-
-    // Map platform to expected asset name patterns
-    let patterns = match (os, arch) {
-        ("linux", "x86_64") => vec!["x86_64-unknown-linux-gnu", "linux-x86_64", "linux-amd64"],
-        ("linux", "aarch64") => vec!["aarch64-unknown-linux-gnu", "linux-aarch64", "linux-arm64"],
-        ("macos", "x86_64") => vec!["x86_64-apple-darwin", "macos-x86_64", "darwin-x86_64"],
-        ("macos", "aarch64") => vec!["aarch64-apple-darwin", "macos-aarch64", "darwin-arm64"],
-        ("windows", "x86_64") => vec!["x86_64-pc-windows-msvc", "windows-x86_64", "windows-amd64"],
-        _ => return Err(anyhow::anyhow!("Unsupported platform: {}-{}", os, arch)),
-    };
+/// Target platform used to pick a release asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    pub os: &'static str,
+    pub arch: &'static str,
+    /// C library / ABI: `gnu`, `musl`, or `msvc`; empty when not applicable
+    pub env: &'static str,
+}
 
-    // First try to fetch the actual release with assets
-    let release_detail = fetch_release_detail(&release.version)?;
+impl Platform {
+    pub fn current() -> Self {
+        let env = if cfg!(target_env = "musl") {
+            "musl"
+        } else if cfg!(target_env = "msvc") {
+            "msvc"
+        } else if cfg!(target_env = "gnu") {
+            "gnu"
+        } else {
+            ""
+        };
+        Self {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            env,
+        }
+    }
 
-    // Look for assets matching our platform
-    for pattern in patterns {
-        if let Some(asset) = release_detail.assets.iter().find(|a| {
-            a.name.contains(pattern) && (a.name.ends_with(".tar.gz") || a.name.ends_with(".zip"))
-        }) {
-            return Ok(asset.clone());
+    /// Rust target triple, e.g. `aarch64-unknown-linux-musl`
+    pub fn triple(&self) -> String {
+        match self.os {
+            "macos" => format!("{}-apple-darwin", self.arch),
+            "windows" => format!("{}-pc-windows-{}", self.arch, self.env),
+            os => format!("{}-unknown-{}-{}", self.arch, os, self.env),
         }
     }
 
-    // If no platform-specific asset found, try to find any binary asset
-    if let Some(asset) = release_detail.assets.iter().find(|a| {
-        a.name.contains("rotd") && (a.name.ends_with(".tar.gz") || a.name.ends_with(".zip"))
-    }) {
-        return Ok(asset.clone());
+    fn os_aliases(&self) -> &'static [&'static str] {
+        match self.os {
+            "linux" => &["linux"],
+            "macos" => &["darwin", "macos", "osx", "apple"],
+            "windows" => &["windows", "win64", "win"],
+            _ => &[],
+        }
     }
 
-    Err(anyhow::anyhow!(
-        "No suitable binary asset found for platform {}-{}",
-        os,
-        arch
-    ))
+    fn arch_aliases(&self) -> &'static [&'static str] {
+        match (self.os, self.arch) {
+            ("macos", "x86_64") => &["x86_64", "amd64", "x64", "universal"],
+            ("macos", "aarch64") => &["aarch64", "arm64", "universal"],
+            (_, "x86_64") => &["x86_64", "amd64", "x64"],
+            (_, "aarch64") => &["aarch64", "arm64"],
+            _ => &[],
+        }
+    }
 }
 
-/// Fetch detailed release information including assets
-fn fetch_release_detail(version: &str) -> Result<GitHubRelease> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(30))
-        .user_agent("rotd-cli")
-        .build()?;
+/// Score how well an asset name fits the platform; `None` means unusable
+fn asset_score(name: &str, platform: &Platform) -> Option<u32> {
+    let lower = name.to_lowercase();
+    let tokens: Vec<&str> = lower.split(['-', '.']).collect();
+    let has = |aliases: &[&str]| aliases.iter().any(|a| tokens.contains(a));
 
-    let release_url = format!(
-        "https://api.github.com/repos/{}/{}/releases/tags/{}",
-        GITHUB_REPO_OWNER, GITHUB_REPO_NAME, version
-    );
+    // Checksums, signatures, and other metadata are never installable
+    const METADATA_SUFFIXES: [&str; 8] = [
+        ".sig", ".minisig", ".asc", ".sha256", ".pem", ".txt", ".json", ".sbom",
+    ];
+    if lower.contains("sha256sums") || METADATA_SUFFIXES.iter().any(|s| lower.ends_with(s)) {
+        return None;
+    }
+    let is_archive =
+        lower.ends_with(".tar.gz") || lower.ends_with(".tgz") || lower.ends_with(".zip");
+    // Bare executables are only usable when they match the platform's format
+    if !is_archive && lower.ends_with(".exe") != (platform.os == "windows") {
+        return None;
+    }
+
+    if !has(platform.os_aliases()) || !has(platform.arch_aliases()) {
+        return None;
+    }
+
+    let mut score = 1;
+    if lower.contains(&platform.triple()) {
+        score += 4;
+    }
+
+    if platform.os == "linux" {
+        let musl = tokens.contains(&"musl");
+        let gnu = tokens.contains(&"gnu");
+        match (platform.env, musl, gnu) {
+            // Static musl builds run anywhere but glibc builds need glibc
+            ("musl", _, true) => return None,
+            ("musl", true, _) | ("gnu", _, true) => score += 2,
+            ("gnu", true, _) => {}
+            _ => score += 1,
+        }
+    }
+
+    if is_archive {
+        score += 1;
+    }
+
+    Some(score)
+}
+
+/// Pick the best asset for a platform, listing what is available otherwise
+pub fn select_platform_asset(assets: &[GitHubAsset], platform: &Platform) -> Result<GitHubAsset> {
+    assets
+        .iter()
+        .filter_map(|a| asset_score(&a.name, platform).map(|score| (score, a)))
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, a)| a.clone())
+        .ok_or_else(|| {
+            let names: Vec<&str> = assets.iter().map(|a| a.name.as_str()).collect();
+            anyhow::anyhow!(
+                "No release asset matches platform {}. Available assets: {}",
+                platform.triple(),
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            )
+        })
+}
+
+/// Find the appropriate release asset for the current platform
+pub fn find_platform_asset(release: &ReleaseInfo) -> Result<GitHubAsset> {
+    select_platform_asset(&release.assets, &Platform::current())
+}
+
+/// Name of the release asset listing SHA-256 digests of the other assets
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
 
-    let response = client.get(&release_url).send()?;
+/// Download a release asset as raw bytes
+fn download_asset(url: &str) -> Result<Vec<u8>> {
+    let client = client_builder(Duration::from_secs(300))? // 5 minutes for download
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?;
+
+    let response = client.get(url).send().map_err(request_error)?;
     if !response.status().is_success() {
         return Err(anyhow::anyhow!(
-            "Failed to fetch release details: HTTP {}",
+            "Failed to download {}: HTTP {}",
+            url,
             response.status()
         ));
     }
 
-    let release: GitHubRelease = response.json()?;
-    Ok(release)
+    Ok(response.bytes()?.to_vec())
 }
 
-/// Download binary from URL
-pub fn download_binary(url: &str) -> Result<Vec<u8>> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(300)) // 5 minutes for download
-        .user_agent("rotd-cli")
-        .build()?;
+/// Parse `sha256sum` output (`<digest>  <name>` or `<digest> *<name>`)
+pub fn parse_checksums(content: &str) -> std::collections::HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim_start().trim_start_matches('*');
+            Some((name.to_string(), digest.to_lowercase()))
+        })
+        .collect()
+}
 
-    let response = client.get(url).send()?;
-    if !response.status().is_success() {
+pub fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Check an asset against the release's checksum list, returning its digest
+pub fn verify_checksum(asset_name: &str, data: &[u8], checksums: &str) -> Result<String> {
+    let expected = parse_checksums(checksums)
+        .remove(asset_name)
+        .ok_or_else(|| anyhow::anyhow!("{} has no entry for {}", CHECKSUMS_ASSET, asset_name))?;
+    let actual = sha256_hex(data);
+    if actual != expected {
         return Err(anyhow::anyhow!(
-            "Failed to download binary: HTTP {}",
-            response.status()
+            "Checksum mismatch for {}: expected {}, got {}. Refusing to install.",
+            asset_name,
+            expected,
+            actual
         ));
     }
+    Ok(actual)
+}
+
+/// Release signing key baked in at build time (`ROTD_RELEASE_PUBLIC_KEY`)
+const PINNED_RELEASE_PUBLIC_KEY: Option<&str> = option_env!("ROTD_RELEASE_PUBLIC_KEY");
 
-    let bytes = response.bytes()?;
+/// Suffix of the detached minisign signature published next to each archive
+pub const SIGNATURE_SUFFIX: &str = ".minisig";
+
+/// Key used to verify release signatures: the key pinned in the binary, or
+/// `release_public_key` from the machine-local config when nothing is pinned.
+/// The committed config.jsonc is never consulted, so a cloned project cannot
+/// supply the key its own releases are checked against.
+pub fn release_public_key() -> Option<String> {
+    choose_public_key(PINNED_RELEASE_PUBLIC_KEY, || {
+        crate::history::load_local_config().ok()?.release_public_key
+    })
+}
+
+fn choose_public_key(
+    pinned: Option<&str>,
+    configured: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let usable = |k: &String| !k.trim().is_empty();
+    match pinned.map(str::to_string).filter(usable) {
+        Some(key) => Some(key),
+        None => configured().filter(usable),
+    }
+}
 
-    // If it's a compressed file, extract it
-    if url.ends_with(".tar.gz") {
-        extract_tar_gz(&bytes)
-    } else if url.ends_with(".zip") {
-        extract_zip(&bytes)
+/// Verify a detached minisign signature over `data`
+pub fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let key = minisign_verify::PublicKey::from_base64(public_key.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid release public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(signature)
+        .map_err(|e| anyhow::anyhow!("Invalid release signature: {}", e))?;
+    key.verify(data, &signature, false)
+        .map_err(|e| anyhow::anyhow!("Release signature verification failed: {}", e))
+}
+
+/// A downloaded release asset and how it was verified
+pub struct VerifiedAsset {
+    pub data: Vec<u8>,
+    pub sha256: String,
+    pub checksum_verified: bool,
+    pub signature_verified: bool,
+}
+
+/// A downloaded binary and how it was verified
+pub struct VerifiedDownload {
+    pub binary: Vec<u8>,
+    pub sha256: String,
+    pub checksum_verified: bool,
+    pub signature_verified: bool,
+}
+
+/// The checksum list and the signature that verify `asset`
+fn verification_assets<'a>(
+    release: &'a ReleaseInfo,
+    asset: &GitHubAsset,
+) -> Result<(&'a GitHubAsset, &'a GitHubAsset)> {
+    let checksums = release
+        .assets
+        .iter()
+        .find(|a| a.name == CHECKSUMS_ASSET)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Release {} has no {} asset, so the download cannot be verified",
+                release.version,
+                CHECKSUMS_ASSET
+            )
+        })?;
+    let signature_name = format!("{}{}", asset.name, SIGNATURE_SUFFIX);
+    let signature = release
+        .assets
+        .iter()
+        .find(|a| a.name == signature_name)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Release {} has no {} signature. Refusing to install; pass --skip-verify to override.",
+                release.version,
+                signature_name
+            )
+        })?;
+    Ok((checksums, signature))
+}
+
+/// Download an asset and verify it against `SHA256SUMS` and its minisign
+/// signature. Without a signing key nothing is installed; `skip_verify`
+/// bypasses both checks.
+pub fn download_verified(
+    release: &ReleaseInfo,
+    asset: &GitHubAsset,
+    skip_verify: bool,
+) -> Result<VerifiedAsset> {
+    if skip_verify {
+        let data = download_asset(&asset.browser_download_url)?;
+        return Ok(VerifiedAsset {
+            sha256: sha256_hex(&data),
+            data,
+            checksum_verified: false,
+            signature_verified: false,
+        });
+    }
+
+    let public_key = release_public_key().ok_or_else(|| {
+        anyhow::anyhow!(
+            "No release signing key: this build pins no ROTD_RELEASE_PUBLIC_KEY and {} sets no release_public_key. Refusing to install unverified; pass --skip-verify to override.",
+            crate::common::LOCAL_CONFIG_FILE
+        )
+    })?;
+    let (checksums_asset, signature_asset) = verification_assets(release, asset)?;
+    let checksums = String::from_utf8(download_asset(&checksums_asset.browser_download_url)?)
+        .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", CHECKSUMS_ASSET))?;
+
+    let data = download_asset(&asset.browser_download_url)?;
+    let sha256 = verify_checksum(&asset.name, &data, &checksums)?;
+
+    let signature = String::from_utf8(download_asset(&signature_asset.browser_download_url)?)
+        .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", signature_asset.name))?;
+    verify_signature(&data, &signature, &public_key)?;
+
+    Ok(VerifiedAsset {
+        data,
+        sha256,
+        checksum_verified: true,
+        signature_verified: true,
+    })
+}
+
+/// Download and verify an asset, then extract the binary
+pub fn download_binary(
+    release: &ReleaseInfo,
+    asset: &GitHubAsset,
+    skip_verify: bool,
+) -> Result<VerifiedDownload> {
+    let download = download_verified(release, asset, skip_verify)?;
+    Ok(VerifiedDownload {
+        binary: extract_binary(&asset.name, &download.data)?,
+        sha256: download.sha256,
+        checksum_verified: download.checksum_verified,
+        signature_verified: download.signature_verified,
+    })
+}
+
+/// Pull the rotd executable out of a downloaded asset.
+/// The archive type is detected from the content (gzip or zip magic bytes)
+/// so renamed or query-suffixed URLs still extract; bare binaries pass
+/// through unchanged.
+pub fn extract_binary(asset_name: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let binary = if data.starts_with(&[0x1f, 0x8b]) {
+        extract_tar_gz(data)?
+    } else if data.starts_with(b"PK\x03\x04") {
+        extract_zip(data)?
+    } else if asset_name.ends_with(".tar.gz")
+        || asset_name.ends_with(".tgz")
+        || asset_name.ends_with(".zip")
+    {
+        return Err(anyhow::anyhow!(
+            "Downloaded {} is not a valid archive",
+            asset_name
+        ));
     } else {
-        Ok(bytes.to_vec())
+        data.to_vec()
+    };
+
+    if binary.is_empty() {
+        return Err(anyhow::anyhow!(
+            "The rotd binary in {} is empty",
+            asset_name
+        ));
     }
+
+    Ok(binary)
+}
+
+fn is_binary_entry(path: &std::path::Path) -> bool {
+    matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("rotd") | Some("rotd.exe")
+    )
 }
 
 /// Extract binary from tar.gz archive
@@ -264,10 +596,12 @@ fn extract_tar_gz(data: &[u8]) -> Result<Vec<u8>> {
 
     for entry in archive.entries()? {
         let mut entry = entry?;
-        let path = entry.path()?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
 
         // Look for the rotd binary
-        if path.file_name().and_then(|n| n.to_str()) == Some("rotd") {
+        if is_binary_entry(&entry.path()?) {
             let mut buffer = Vec::new();
             entry.read_to_end(&mut buffer)?;
             return Ok(buffer);
@@ -287,9 +621,12 @@ fn extract_zip(data: &[u8]) -> Result<Vec<u8>> {
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
+        if !file.is_file() {
+            continue;
+        }
 
         // Look for the rotd binary
-        if file.name().ends_with("rotd") || file.name().ends_with("rotd.exe") {
+        if is_binary_entry(std::path::Path::new(file.name())) {
             let mut buffer = Vec::new();
             std::io::copy(&mut file, &mut buffer)?;
             return Ok(buffer);
@@ -299,10 +636,1490 @@ fn extract_zip(data: &[u8]) -> Result<Vec<u8>> {
     Err(anyhow::anyhow!("No rotd binary found in zip archive"))
 }
 
+/// Base HTTP client settings shared by API calls and downloads.
+/// `HTTP(S)_PROXY` and `NO_PROXY` are honored by reqwest itself; an explicit
+/// proxy (`ROTD_PROXY` or `proxy` in config.local.jsonc) and extra root
+/// certificates (`ROTD_CA_BUNDLE` or `ca_bundle` there) are added here. Both
+/// are machine-local: a committed config must not reroute or re-trust the
+/// requests that carry the GitHub token.
+pub fn client_builder(timeout: Duration) -> Result<reqwest::blocking::ClientBuilder> {
+    let config = crate::history::load_local_config().unwrap_or_default();
+    let builder = Client::builder().timeout(timeout).user_agent("rotd-cli");
+    with_network_settings(
+        builder,
+        setting(std::env::var("ROTD_PROXY").ok(), config.proxy),
+        setting(std::env::var("ROTD_CA_BUNDLE").ok(), config.ca_bundle),
+    )
+}
+
+/// A network setting from its environment variable, falling back to the
+/// machine-local config.
+/// A blank value counts as unset.
+fn setting(env: Option<String>, config: Option<String>) -> Option<String> {
+    env.or(config).filter(|v| !v.trim().is_empty())
+}
+
+fn with_network_settings(
+    mut builder: reqwest::blocking::ClientBuilder,
+    proxy: Option<String>,
+    ca_bundle: Option<String>,
+) -> Result<reqwest::blocking::ClientBuilder> {
+    if let Some(url) = proxy {
+        let proxy = reqwest::Proxy::all(&url)
+            .map_err(|e| anyhow::anyhow!("Invalid proxy URL '{}': {}", url, e))?
+            .no_proxy(reqwest::NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = ca_bundle {
+        let pem = std::fs::read(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read CA bundle {}: {}", path, e))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| anyhow::anyhow!("Invalid CA bundle {}: {}", path, e))?;
+        if certs.is_empty() {
+            return Err(anyhow::anyhow!(
+                "CA bundle {} contains no PEM certificates",
+                path
+            ));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    Ok(builder)
+}
+
+/// Turn a transport failure into an actionable message
+fn request_error(e: reqwest::Error) -> anyhow::Error {
+    let mut chain = e.to_string();
+    let mut source = std::error::Error::source(&e);
+    while let Some(inner) = source {
+        // Transport errors often repeat their cause in their own message
+        let text = inner.to_string();
+        if !chain.contains(&text) {
+            chain.push_str(": ");
+            chain.push_str(&text);
+        }
+        source = inner.source();
+    }
+    let lower = chain.to_lowercase();
+
+    if e.is_timeout() {
+        anyhow::anyhow!("Request timed out. Check your internet connection.")
+    } else if lower.contains("certificate") || lower.contains("tls") || lower.contains("ssl") {
+        anyhow::anyhow!(
+            "TLS handshake with GitHub failed ({}). If you are behind an intercepting proxy, \
+             point ROTD_CA_BUNDLE or `ca_bundle` in config.local.jsonc at its root certificate.",
+            chain
+        )
+    } else if e.is_connect() {
+        anyhow::anyhow!(
+            "Failed to connect to GitHub ({}). Check your internet connection, DNS, and proxy \
+             settings (HTTPS_PROXY, ROTD_PROXY).",
+            chain
+        )
+    } else {
+        anyhow::anyhow!("Network error: {}", chain)
+    }
+}
+
+/// Build an API client, authenticating with the GitHub token when one is set
+fn api_client() -> Result<Client> {
+    api_client_with_timeout(Duration::from_secs(30))
+}
+
+fn api_client_with_timeout(timeout: Duration) -> Result<Client> {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        reqwest::header::ACCEPT,
+        reqwest::header::HeaderValue::from_static("application/vnd.github+json"),
+    );
+    if let Some(token) = github_token() {
+        let value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| anyhow::anyhow!("GitHub token contains invalid characters"))?;
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+
+    client_builder(timeout)?
+        .default_headers(headers)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))
+}
+
+/// GitHub token from `ROTD_GITHUB_TOKEN`, `GITHUB_TOKEN`, or the
+/// machine-local `github_token` file, in that order
+pub fn github_token() -> Option<String> {
+    std::env::var("ROTD_GITHUB_TOKEN")
+        .or_else(|_| std::env::var("GITHUB_TOKEN"))
+        .ok()
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| token_from_file(&crate::common::github_token_path()))
+}
+
+/// The token stored in `path`, if the file exists and is not blank
+fn token_from_file(path: &std::path::Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// Explain a rate-limit response, including when the limit resets
+fn rate_limit_message(
+    status: u16,
+    remaining: Option<&str>,
+    reset: Option<&str>,
+    now: i64,
+    authenticated: bool,
+) -> Option<String> {
+    if !(status == 429 || (status == 403 && remaining == Some("0"))) {
+        return None;
+    }
+
+    let mut message = "GitHub API rate limit exceeded".to_string();
+    if let Some(reset) = reset.and_then(|r| r.parse::<i64>().ok()) {
+        if let Some(at) = chrono::DateTime::from_timestamp(reset, 0) {
+            let minutes = ((reset - now).max(0) + 59) / 60;
+            message.push_str(&format!(
+                "; resets at {} (in {} min)",
+                at.format("%Y-%m-%d %H:%M:%S UTC"),
+                minutes
+            ));
+        }
+    }
+    if !authenticated {
+        message.push_str(
+            ". Set GITHUB_TOKEN (or ROTD_GITHUB_TOKEN) to use the higher authenticated limit",
+        );
+    }
+    Some(message)
+}
+
+fn rate_limit_error(response: &reqwest::blocking::Response) -> Option<anyhow::Error> {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    rate_limit_message(
+        response.status().as_u16(),
+        header("x-ratelimit-remaining").as_deref(),
+        header("x-ratelimit-reset").as_deref(),
+        chrono::Utc::now().timestamp(),
+        github_token().is_some(),
+    )
+    .map(|m| anyhow::anyhow!(m))
+}
+
+/// Split `owner/name` into its parts
+pub fn parse_repo(repo: &str) -> Result<(String, String)> {
+    match repo.trim().split_once('/') {
+        Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+            Ok((owner.to_string(), name.to_string()))
+        }
+        _ => Err(anyhow::anyhow!(
+            "Invalid repository '{}'. Expected owner/name",
+            repo
+        )),
+    }
+}
+
+/// GitHub issue as returned by the issues API
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubIssue {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: String,
+    pub html_url: String,
+    pub labels: Vec<GitHubLabel>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Present when the "issue" is actually a pull request
+    pub pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubLabel {
+    pub name: String,
+}
+
+/// Fetch all issues (not pull requests) carrying `label`, following pagination
+pub fn fetch_issues(owner: &str, name: &str, label: &str, state: &str) -> Result<Vec<GitHubIssue>> {
+    let client = api_client()?;
+    let mut issues = Vec::new();
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!("https://api.github.com/repos/{}/{}/issues", owner, name);
+        let response = client
+            .get(&url)
+            .query(&[
+                ("labels", label),
+                ("state", state),
+                ("per_page", &per_page.to_string()),
+                ("page", &page.to_string()),
+            ])
+            .send()
+            .map_err(request_error)?;
+
+        if let Some(e) = rate_limit_error(&response) {
+            return Err(e);
+        }
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "GitHub API returned error {} for {}/{}. Set GITHUB_TOKEN for private repositories.",
+                response.status().as_u16(),
+                owner,
+                name
+            ));
+        }
+
+        let batch: Vec<GitHubIssue> = response
+            .json()
+            .map_err(|e| anyhow::anyhow!("Failed to parse GitHub API response: {}", e))?;
+        let done = batch.len() < per_page;
+        issues.extend(batch.into_iter().filter(|i| i.pull_request.is_none()));
+        if done {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Task ID used for an imported issue
+pub fn issue_task_id(number: u64) -> String {
+    format!("gh-{}", number)
+}
+
+/// Map `priority:<level>`, bare level names, or `P0`-`P3` labels to a task priority
+fn label_priority(label: &str) -> Option<Priority> {
+    let label = label.trim().to_lowercase();
+    let level = label.strip_prefix("priority:").unwrap_or(&label).trim();
+    match level {
+        "urgent" | "critical" | "p0" => Some(Priority::Urgent),
+        "high" | "p1" => Some(Priority::High),
+        "medium" | "p2" => Some(Priority::Medium),
+        "low" | "p3" => Some(Priority::Low),
+        "deferred" | "someday" => Some(Priority::Deferred),
+        _ => None,
+    }
+}
+
+/// Convert an issue into a task entry: labels supply priority and `phase:<name>`,
+/// and the issue URL is kept in `origin` for dedupe and later sync
+pub fn issue_to_task(issue: &GitHubIssue) -> TaskEntry {
+    let priority = issue.labels.iter().find_map(|l| label_priority(&l.name));
+    let phase = issue.labels.iter().find_map(|l| {
+        l.name
+            .strip_prefix("phase:")
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+    });
+    let closed = issue.state == "closed";
+    let now = chrono::Utc::now();
+
+    TaskEntry {
+        id: issue_task_id(issue.number),
+        title: issue.title.clone(),
+        status: if closed {
+            TaskStatus::Complete
+        } else {
+            TaskStatus::Pending
+        },
+        tests: None,
+        description: issue.body.clone().filter(|b| !b.trim().is_empty()),
+        summary_file: None,
+        origin: Some(issue.html_url.clone()),
+        phase,
+        depends_on: None,
+        priority,
+        priority_score: None,
+        created: issue.created_at.or(Some(now)),
+        updated_at: Some(now),
+        completed: if closed {
+            issue.closed_at.or(Some(now))
+        } else {
+            None
+        },
+        pr_url: None,
+        assignee: None,
+        tags: Vec::new(),
+        schema: TASK_SCHEMA.to_string(),
+    }
+}
+
+/// Link between a task and the GitHub issue it tracks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueLink {
+    pub number: u64,
+    pub url: String,
+    /// Task status last pushed to (or imported from) the issue
+    pub last_status: Option<String>,
+    pub synced_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Contents of `.rotd/integrations/github.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GithubIntegration {
+    pub repo: Option<String>,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub issues: std::collections::BTreeMap<String, IssueLink>,
+}
+
+pub fn load_integration() -> Result<GithubIntegration> {
+    let path = crate::common::github_integration_path();
+    if !path.exists() {
+        return Ok(GithubIntegration::default());
+    }
+    read_json(&path)
+}
+
+fn save_integration(integration: &GithubIntegration) -> Result<()> {
+    let path = crate::common::github_integration_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_json(&path, integration)
+}
+
+/// Parse `https://github.com/owner/name/issues/N` into its parts
+pub fn parse_issue_url(url: &str) -> Option<(String, String, u64)> {
+    let rest = url.strip_prefix("https://github.com/")?;
+    let parts: Vec<&str> = rest.trim_end_matches('/').split('/').collect();
+    match parts.as_slice() {
+        [owner, name, "issues", number] => {
+            Some((owner.to_string(), name.to_string(), number.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+fn status_str(status: &TaskStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Label applied to an issue to mirror the task status
+fn status_label(status: &str) -> String {
+    format!("rotd:{}", status)
+}
+
+pub fn handle_command(cmd: GithubCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        GithubCommands::Import { repo, label, state } => {
+            cmd_import(&repo, &label, &state, dry_run, is_agent_mode)
+        }
+        GithubCommands::Sync { repo, no_pull } => {
+            cmd_sync(repo.as_deref(), !no_pull, dry_run, is_agent_mode)
+        }
+        GithubCommands::Pr {
+            task_id,
+            repo,
+            base,
+            draft,
+            push,
+        } => cmd_pr(
+            &task_id,
+            repo.as_deref(),
+            base.as_deref(),
+            draft,
+            push,
+            dry_run,
+            is_agent_mode,
+        ),
+    }
+}
+
+/// Create tasks for issues not yet tracked and record their links.
+/// Returns the imported tasks and the IDs of tasks that already existed.
+fn import_issues(
+    issues: &[GitHubIssue],
+    integration: &mut GithubIntegration,
+    dry_run: bool,
+) -> Result<(Vec<TaskEntry>, Vec<String>)> {
+    let existing = latest_tasks()?;
+    let mut imported = Vec::new();
+    let mut skipped = Vec::new();
+
+    for issue in issues {
+        let task = issue_to_task(issue);
+        // Re-imports match on the issue URL first, then on the generated ID
+        let found = existing
+            .iter()
+            .find(|t| t.origin.as_deref() == Some(issue.html_url.as_str()))
+            .or_else(|| existing.iter().find(|t| t.id == task.id));
+
+        if let Some(found) = found {
+            integration
+                .issues
+                .entry(found.id.clone())
+                .or_insert_with(|| IssueLink {
+                    number: issue.number,
+                    url: issue.html_url.clone(),
+                    last_status: None,
+                    synced_at: None,
+                });
+            skipped.push(found.id.clone());
+            continue;
+        }
+
+        if !dry_run {
+            safe_update_task(&task, false)?;
+        }
+        integration.issues.insert(
+            task.id.clone(),
+            IssueLink {
+                number: issue.number,
+                url: issue.html_url.clone(),
+                last_status: Some(status_str(&task.status)),
+                synced_at: Some(chrono::Utc::now()),
+            },
+        );
+        imported.push(task);
+    }
+
+    Ok((imported, skipped))
+}
+
+fn cmd_import(
+    repo: &str,
+    label: &str,
+    state: &str,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+    let (owner, name) = parse_repo(repo)?;
+
+    let issues = fetch_issues(&owner, &name, label, state)?;
+    let mut integration = load_integration()?;
+    integration.repo = Some(repo.to_string());
+    integration.label = Some(label.to_string());
+
+    let (imported, skipped) = import_issues(&issues, &mut integration, dry_run)?;
+
+    if !dry_run {
+        save_integration(&integration)?;
+        if !imported.is_empty() {
+            audit::log_info(
+                None,
+                "GITHUB_IMPORT",
+                &format!("Imported {} issue(s) from {}", imported.len(), repo),
+            )?;
+        }
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "github_import",
+                "repo": repo,
+                "dry_run": dry_run,
+                "fetched": issues.len(),
+                "imported": imported.iter().map(|t| &t.id).collect::<Vec<_>>(),
+                "skipped": skipped,
+            })
+        );
+    } else {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+        }
+        println!(
+            "Fetched {} issue(s) labeled '{}' from {}",
+            issues.len(),
+            label,
+            repo
+        );
+        for task in &imported {
+            println!("  {} {} {}", "+".green(), task.id.bold(), task.title);
+        }
+        if !skipped.is_empty() {
+            println!(
+                "  {} {} already imported: {}",
+                "•".yellow(),
+                skipped.len(),
+                skipped.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn check_response(response: reqwest::blocking::Response, what: &str) -> Result<()> {
+    if let Some(e) = rate_limit_error(&response) {
+        Err(e.context(format!("Failed to {}", what)))
+    } else if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "GitHub API returned error {} while trying to {}",
+            response.status().as_u16(),
+            what
+        ))
+    }
+}
+
+/// Push one status change to an issue: comment, swap the status label, and
+/// close the issue when the task is complete
+fn push_status(
+    client: &Client,
+    repo: &str,
+    link: &IssueLink,
+    task: &TaskEntry,
+    status: &str,
+) -> Result<()> {
+    let base = format!(
+        "https://api.github.com/repos/{}/issues/{}",
+        repo, link.number
+    );
+
+    let comment = match &link.last_status {
+        Some(prev) => format!(
+            "ROTD task `{}` status changed: `{}` → `{}`",
+            task.id, prev, status
+        ),
+        None => format!("ROTD task `{}` status: `{}`", task.id, status),
+    };
+    let response = client
+        .post(format!("{}/comments", base))
+        .json(&serde_json::json!({ "body": comment }))
+        .send()
+        .map_err(request_error)?;
+    check_response(response, "post a comment")?;
+
+    if let Some(prev) = &link.last_status {
+        // A missing label is not an error worth failing the sync over
+        let _ = client
+            .delete(format!("{}/labels/{}", base, status_label(prev)))
+            .send();
+    }
+    let response = client
+        .post(format!("{}/labels", base))
+        .json(&serde_json::json!({ "labels": [status_label(status)] }))
+        .send()
+        .map_err(request_error)?;
+    check_response(response, "add a label")?;
+
+    if matches!(task.status, TaskStatus::Complete) {
+        let response = client
+            .patch(&base)
+            .json(&serde_json::json!({ "state": "closed" }))
+            .send()
+            .map_err(request_error)?;
+        check_response(response, "close the issue")?;
+    }
+
+    Ok(())
+}
+
+fn cmd_sync(repo: Option<&str>, pull: bool, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let mut integration = load_integration()?;
+    let repo = repo
+        .map(str::to_string)
+        .or_else(|| integration.repo.clone())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No repository configured. Pass --repo or run `rotd github import` first."
+            )
+        })?;
+    let (owner, name) = parse_repo(&repo)?;
+    integration.repo = Some(repo.clone());
+
+    if !dry_run && github_token().is_none() {
+        return Err(anyhow::anyhow!(
+            "GITHUB_TOKEN is required to update issues (use --dry-run to preview)"
+        ));
+    }
+
+    // Tasks whose origin points at an issue in this repo are tracked too
+    let tasks = latest_tasks()?;
+    for task in &tasks {
+        if let Some((o, n, number)) = task.origin.as_deref().and_then(parse_issue_url) {
+            if o == owner && n == name {
+                integration
+                    .issues
+                    .entry(task.id.clone())
+                    .or_insert_with(|| IssueLink {
+                        number,
+                        url: task.origin.clone().unwrap_or_default(),
+                        last_status: None,
+                        synced_at: None,
+                    });
+            }
+        }
+    }
+
+    // Pull: import newly labeled issues and note issues closed on GitHub
+    let mut imported = Vec::new();
+    let mut closed_remotely = Vec::new();
+    if pull {
+        let label = integration
+            .label
+            .clone()
+            .unwrap_or_else(|| "rotd".to_string());
+        let issues = fetch_issues(&owner, &name, &label, "all")?;
+        let open: Vec<GitHubIssue> = issues
+            .iter()
+            .filter(|i| i.state == "open")
+            .cloned()
+            .collect();
+        imported = import_issues(&open, &mut integration, dry_run)?.0;
+
+        for issue in issues.iter().filter(|i| i.state == "closed") {
+            if let Some((task_id, _)) = integration
+                .issues
+                .iter()
+                .find(|(_, l)| l.number == issue.number)
+            {
+                let open_locally = tasks
+                    .iter()
+                    .any(|t| &t.id == task_id && !matches!(t.status, TaskStatus::Complete));
+                if open_locally {
+                    closed_remotely.push(task_id.clone());
+                }
+            }
+        }
+    }
+
+    // Push: report status changes since the last sync
+    let client = api_client()?;
+    let mut pushed = Vec::new();
+    for (task_id, link) in integration.issues.iter_mut() {
+        let Some(task) = tasks.iter().find(|t| &t.id == task_id) else {
+            continue;
+        };
+        let status = status_str(&task.status);
+        if link.last_status.as_deref() == Some(status.as_str()) {
+            continue;
+        }
+
+        if !dry_run {
+            push_status(&client, &repo, link, task, &status)?;
+            link.last_status = Some(status.clone());
+            link.synced_at = Some(chrono::Utc::now());
+        }
+        pushed.push(serde_json::json!({
+            "task_id": task_id,
+            "issue": link.number,
+            "status": status,
+            "closed": matches!(task.status, TaskStatus::Complete),
+        }));
+    }
+
+    if !dry_run {
+        save_integration(&integration)?;
+        if !pushed.is_empty() || !imported.is_empty() {
+            audit::log_info(
+                None,
+                "GITHUB_SYNC",
+                &format!(
+                    "Synced {}: {} status update(s), {} new issue(s)",
+                    repo,
+                    pushed.len(),
+                    imported.len()
+                ),
+            )?;
+        }
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "github_sync",
+                "repo": repo,
+                "dry_run": dry_run,
+                "pushed": pushed,
+                "imported": imported.iter().map(|t| &t.id).collect::<Vec<_>>(),
+                "closed_remotely": closed_remotely,
+            })
+        );
+    } else {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+        }
+        println!("Syncing tasks with {}", repo.cyan());
+        for p in &pushed {
+            println!(
+                "  {} {} → #{} {}{}",
+                "↑".green(),
+                p["task_id"].as_str().unwrap_or_default(),
+                p["issue"],
+                p["status"].as_str().unwrap_or_default(),
+                if p["closed"].as_bool().unwrap_or(false) {
+                    " (closing issue)"
+                } else {
+                    ""
+                }
+            );
+        }
+        for task in &imported {
+            println!("  {} {} {}", "↓".green(), task.id.bold(), task.title);
+        }
+        for task_id in &closed_remotely {
+            println!(
+                "  {} {} is closed on GitHub but not complete locally",
+                "!".yellow(),
+                task_id
+            );
+        }
+        if pushed.is_empty() && imported.is_empty() && closed_remotely.is_empty() {
+            println!("  {}", "Already in sync".green());
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract `owner/name` from a GitHub remote URL (https or ssh form)
+pub fn parse_remote_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("http://github.com/"))
+        .or_else(|| url.strip_prefix("git@github.com:"))
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    parse_repo(path)
+        .ok()
+        .map(|(owner, name)| format!("{}/{}", owner, name))
+}
+
+/// Markdown PR body with the task description, PSS score, and test summary
+pub fn pr_body(
+    task: &TaskEntry,
+    score: Option<&PSSScore>,
+    summary: Option<&TestSummary>,
+    issue: Option<u64>,
+) -> String {
+    let mut body = String::from("## Summary\n\n");
+    match task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(description) => body.push_str(description.trim()),
+        None => body.push_str("_No description provided._"),
+    }
+    body.push_str("\n\n## PSS Score\n\n");
+    match score {
+        Some(score) => {
+            body.push_str(&format!("**{}/10**\n\n", score.score));
+            body.push_str("| Criterion | Score | Rationale |\n|---|---|---|\n");
+            let mut criteria: Vec<_> = score.criteria.iter().collect();
+            criteria.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, criterion) in criteria {
+                body.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    name,
+                    if criterion.score > 0 { "✅" } else { "❌" },
+                    criterion.rationale.replace('|', "\\|")
+                ));
+            }
+        }
+        None => body.push_str("_Not scored yet._\n"),
+    }
+    body.push_str("\n## Tests\n\n");
+    match summary {
+        Some(summary) => {
+            body.push_str(&format!(
+                "{} passed, {} failed of {} ({})",
+                summary.passed, summary.failed, summary.total_tests, summary.status
+            ));
+            if let Some(coverage) = summary.coverage {
+                body.push_str(&format!(", coverage {:.1}%", coverage));
+            }
+            body.push('\n');
+        }
+        None => body.push_str("_No test summary recorded._\n"),
+    }
+    body.push('\n');
+    if let Some(number) = issue {
+        body.push_str(&format!("Closes #{}\n\n", number));
+    }
+    body.push_str(&format!("{}: {}\n", crate::git::TASK_TRAILER, task.id));
+    body
+}
+
+fn cmd_pr(
+    task_id: &str,
+    repo: Option<&str>,
+    base: Option<&str>,
+    draft: bool,
+    push: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks = latest_tasks()?;
+    let task = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+    if let Some(url) = &task.pr_url {
+        return Err(anyhow::anyhow!(
+            "Task {} already has a pull request: {}",
+            task_id,
+            url
+        ));
+    }
+
+    let integration = load_integration()?;
+    let repo = match repo
+        .map(str::to_string)
+        .or_else(|| integration.repo.clone())
+    {
+        Some(repo) => repo,
+        None => crate::git::run_git(&["remote", "get-url", "origin"])
+            .ok()
+            .and_then(|url| parse_remote_url(&url))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not determine the GitHub repository. Pass --repo owner/name."
+                )
+            })?,
+    };
+    parse_repo(&repo)?;
+
+    let branch = crate::git::run_git(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+    if branch == "HEAD" {
+        return Err(anyhow::anyhow!(
+            "HEAD is detached. Check out a branch first."
+        ));
+    }
+
+    // Prefer the recorded score; fall back to scoring now without saving
+    let score = match crate::pss::latest_score(task_id)? {
+        Some(score) => Some(score),
+        None => crate::pss::score_task(task_id).ok(),
+    };
+    let summary = crate::pss::load_test_summary(task_id).ok();
+    let issue = integration.issues.get(task_id).map(|l| l.number);
+    let body = pr_body(task, score.as_ref(), summary.as_ref(), issue);
+
+    if dry_run {
+        if is_agent_mode {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "dry_run",
+                    "repo": repo,
+                    "head": branch,
+                    "base": base,
+                    "title": task.title,
+                    "body": body,
+                    "draft": draft,
+                })
+            );
+        } else {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+            println!("Would open PR on {} from {}:\n", repo, branch);
+            println!("{}\n\n{}", task.title.bold(), body);
+        }
+        return Ok(());
+    }
+
+    if github_token().is_none() {
+        return Err(anyhow::anyhow!(
+            "GITHUB_TOKEN is required to open a pull request"
+        ));
+    }
+
+    if push {
+        crate::git::run_git(&["push", "-u", "origin", &branch])?;
+    }
+
+    let client = api_client()?;
+    let base = match base {
+        Some(base) => base.to_string(),
+        None => {
+            let response = client
+                .get(format!("https://api.github.com/repos/{}", repo))
+                .send()
+                .map_err(request_error)?;
+            if !response.status().is_success() {
+                return Err(anyhow::anyhow!(
+                    "GitHub API returned error {} while looking up {}",
+                    response.status().as_u16(),
+                    repo
+                ));
+            }
+            let info: serde_json::Value = response.json()?;
+            info["default_branch"]
+                .as_str()
+                .unwrap_or("main")
+                .to_string()
+        }
+    };
+
+    let response = client
+        .post(format!("https://api.github.com/repos/{}/pulls", repo))
+        .json(&serde_json::json!({
+            "title": task.title,
+            "head": branch,
+            "base": base,
+            "body": body,
+            "draft": draft,
+        }))
+        .send()
+        .map_err(request_error)?;
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let detail: serde_json::Value = response.json().unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "GitHub API returned error {} while creating the pull request: {}. Is the branch pushed (--push)?",
+            status,
+            detail["message"].as_str().unwrap_or("unknown error")
+        ));
+    }
+    let pr: serde_json::Value = response.json()?;
+    let pr_url = pr["html_url"].as_str().unwrap_or_default().to_string();
+
+    let mut updated = task.clone();
+    updated.pr_url = Some(pr_url.clone());
+    updated.updated_at = Some(chrono::Utc::now());
+    safe_update_task(&updated, false)?;
+    audit::log_info(
+        Some(task_id),
+        "GITHUB_PR",
+        &format!("Opened pull request {}", pr_url),
+    )?;
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "github_pr",
+                "task_id": task_id,
+                "pr_url": pr_url,
+            })
+        );
+    } else {
+        println!("{} Opened pull request {}", "✓".green(), pr_url.cyan());
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn tar_gz_fixture(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn zip_fixture(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .add_directory("rotd/", zip::write::FileOptions::default())
+            .unwrap();
+        for (path, data) in entries {
+            writer
+                .start_file(*path, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_binary_from_archives() {
+        let entries: [(&str, &[u8]); 3] = [
+            ("rotd-v1.4.0/README.md", b"readme"),
+            ("rotd-v1.4.0/rotd.1", b"manpage"),
+            ("rotd-v1.4.0/rotd", b"\x7fELF binary"),
+        ];
+
+        let tar_gz = tar_gz_fixture(&entries);
+        assert_eq!(
+            extract_binary("rotd-x86_64-unknown-linux-gnu.tar.gz", &tar_gz).unwrap(),
+            b"\x7fELF binary"
+        );
+
+        let zip = zip_fixture(&[("rotd/rotd.exe", b"MZ binary"), ("rotd/LICENSE", b"MIT")]);
+        assert_eq!(
+            extract_binary("download?asset=1", &zip).unwrap(),
+            b"MZ binary"
+        );
+
+        let no_binary = tar_gz_fixture(&entries[..2]);
+        assert!(extract_binary("rotd.tar.gz", &no_binary).is_err());
+        assert!(extract_binary("rotd.tar.gz", b"<html>not found</html>").is_err());
+        assert_eq!(extract_binary("rotd", b"raw").unwrap(), b"raw");
+    }
+
+    #[test]
+    fn test_select_release_by_channel() {
+        let release = |tag: &str, draft: bool, prerelease: bool| GitHubRelease {
+            tag_name: tag.to_string(),
+            name: None,
+            published_at: None,
+            body: None,
+            html_url: String::new(),
+            assets: Vec::new(),
+            draft,
+            prerelease,
+        };
+        let releases = vec![
+            release("v1.5.0", true, false),
+            release("v1.4.1-rc.1", false, true),
+            release("nightly", false, true),
+            release("v1.4.2-beta.1", false, false),
+            release("v1.4.0", false, false),
+            release("v1.3.9", false, false),
+        ];
+        let pick = |channel| select_release(&releases, channel).map(|(r, _)| r.tag_name.as_str());
+
+        assert_eq!(pick(ReleaseChannel::Stable), Some("v1.4.0"));
+        assert_eq!(pick(ReleaseChannel::Beta), Some("v1.5.0"));
+        assert!(select_release(&releases[..3], ReleaseChannel::Stable).is_none());
+        assert!(ReleaseChannel::parse("nightly").is_err());
+    }
+
+    #[test]
+    fn test_pinned_public_key_wins() {
+        // `configured` stands for config.local.jsonc; config.jsonc is never read
+        let configured = || Some("RWconfigured".to_string());
+        assert_eq!(
+            choose_public_key(Some("RWpinned"), configured).as_deref(),
+            Some("RWpinned")
+        );
+        assert_eq!(
+            choose_public_key(None, configured).as_deref(),
+            Some("RWconfigured")
+        );
+        // An empty build-time variable counts as nothing pinned
+        assert_eq!(
+            choose_public_key(Some(""), configured).as_deref(),
+            Some("RWconfigured")
+        );
+        assert_eq!(choose_public_key(None, || None), None);
+        assert_eq!(choose_public_key(None, || Some(" ".to_string())), None);
+    }
+
+    #[test]
+    fn test_token_from_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(crate::common::GITHUB_TOKEN_FILE);
+        assert_eq!(token_from_file(&path), None);
+        std::fs::write(&path, "  \n").unwrap();
+        assert_eq!(token_from_file(&path), None);
+        std::fs::write(&path, "ghp_local\n").unwrap();
+        assert_eq!(token_from_file(&path).as_deref(), Some("ghp_local"));
+    }
+
+    #[test]
+    fn test_proxy_setting_precedence() {
+        let some = |v: &str| Some(v.to_string());
+        assert_eq!(
+            setting(some("http://env:3128"), some("http://config:3128")),
+            some("http://env:3128")
+        );
+        assert_eq!(setting(None, some("http://config:3128")), some("http://config:3128"));
+        assert_eq!(setting(None, some("  ")), None);
+        assert_eq!(setting(None, None), None);
+
+        // The chosen proxy carries plain HTTP requests
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let proxy = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let handle = std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let url = request.url().to_string();
+            request
+                .respond(tiny_http::Response::from_string("via proxy"))
+                .unwrap();
+            url
+        });
+        let client = with_network_settings(Client::builder(), Some(proxy), None)
+            .unwrap()
+            .build()
+            .unwrap();
+        let body = client
+            .get("http://rotd-proxy-test.invalid/releases")
+            .send()
+            .unwrap()
+            .text()
+            .unwrap();
+        assert_eq!(body, "via proxy");
+        assert_eq!(
+            handle.join().unwrap(),
+            "http://rotd-proxy-test.invalid/releases"
+        );
+
+        let err = with_network_settings(Client::builder(), some("http://[::1"), None).unwrap_err();
+        assert!(err.to_string().contains("Invalid proxy URL"));
+    }
+
+    #[test]
+    fn test_ca_bundle_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let bundle = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            Some(path.display().to_string())
+        };
+        let error = |ca_bundle: Option<String>| {
+            with_network_settings(Client::builder(), None, ca_bundle)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let missing = dir.path().join("missing.pem").display().to_string();
+        assert!(error(Some(missing)).starts_with("Failed to read CA bundle"));
+        assert!(error(bundle("empty.pem", "no certificates here\n"))
+            .contains("contains no PEM certificates"));
+        assert!(error(bundle(
+            "corrupt.pem",
+            "-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n",
+        ))
+        .starts_with("Invalid CA bundle"));
+    }
+
+    #[test]
+    fn test_rate_limit_message() {
+        let now = 1_700_000_000;
+        let reset = (now + 600).to_string();
+
+        let message = rate_limit_message(403, Some("0"), Some(&reset), now, false).unwrap();
+        assert!(message.contains("resets at 2023-11-14 22:23:20 UTC (in 10 min)"));
+        assert!(message.contains("GITHUB_TOKEN"));
+        assert!(
+            !rate_limit_message(429, None, None, now, true)
+                .unwrap()
+                .contains("GITHUB_TOKEN")
+        );
+
+        // A plain permission error is not a rate limit
+        assert!(rate_limit_message(403, Some("42"), Some(&reset), now, true).is_none());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let keypair = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
+        let data = b"archive bytes";
+        let signature = minisign::sign(
+            Some(&keypair.pk),
+            &keypair.sk,
+            std::io::Cursor::new(data),
+            None,
+            None,
+        )
+        .unwrap()
+        .to_string();
+        let key = keypair.pk.to_base64();
+
+        verify_signature(data, &signature, &key).unwrap();
+        assert!(verify_signature(b"tampered bytes", &signature, &key).is_err());
+
+        let other = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
+        assert!(verify_signature(data, &signature, &other.pk.to_base64()).is_err());
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let data = b"archive bytes";
+        let digest = sha256_hex(data);
+        let sums = format!(
+            "{}  rotd-x86_64-unknown-linux-gnu.tar.gz\n{} *rotd.zip\n",
+            digest.to_uppercase(),
+            "0".repeat(64)
+        );
+
+        assert_eq!(
+            verify_checksum("rotd-x86_64-unknown-linux-gnu.tar.gz", data, &sums).unwrap(),
+            digest
+        );
+        assert!(
+            verify_checksum("rotd.zip", data, &sums)
+                .unwrap_err()
+                .to_string()
+                .contains("Checksum mismatch")
+        );
+        assert!(verify_checksum("missing.tar.gz", data, &sums).is_err());
+    }
+
+    #[test]
+    fn test_select_platform_asset() {
+        let assets: Vec<GitHubAsset> = [
+            "SHA256SUMS",
+            "rotd-v1.4.0-x86_64-unknown-linux-gnu.tar.gz",
+            "rotd-v1.4.0-x86_64-unknown-linux-gnu.tar.gz.sig",
+            "rotd-v1.4.0-x86_64-unknown-linux-musl.tar.gz",
+            "rotd-v1.4.0-aarch64-unknown-linux-gnu.tar.gz",
+            "rotd-v1.4.0-universal-apple-darwin.tar.gz",
+            "rotd-v1.4.0-x86_64-pc-windows-msvc.zip",
+        ]
+        .iter()
+        .map(|name| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            size: 1,
+        })
+        .collect();
+        let pick = |os, arch, env| {
+            select_platform_asset(&assets, &Platform { os, arch, env }).map(|a| a.name)
+        };
+
+        assert_eq!(
+            pick("linux", "x86_64", "gnu").unwrap(),
+            "rotd-v1.4.0-x86_64-unknown-linux-gnu.tar.gz"
+        );
+        assert_eq!(
+            pick("linux", "x86_64", "musl").unwrap(),
+            "rotd-v1.4.0-x86_64-unknown-linux-musl.tar.gz"
+        );
+        assert_eq!(
+            pick("linux", "aarch64", "gnu").unwrap(),
+            "rotd-v1.4.0-aarch64-unknown-linux-gnu.tar.gz"
+        );
+        assert_eq!(
+            pick("macos", "aarch64", "").unwrap(),
+            "rotd-v1.4.0-universal-apple-darwin.tar.gz"
+        );
+        assert_eq!(
+            pick("windows", "x86_64", "msvc").unwrap(),
+            "rotd-v1.4.0-x86_64-pc-windows-msvc.zip"
+        );
+
+        // A musl host must not get the glibc build
+        let err = pick("linux", "aarch64", "musl").unwrap_err().to_string();
+        assert!(err.contains("aarch64-unknown-linux-musl"));
+        assert!(err.contains("Available assets: SHA256SUMS, "));
+    }
+
+    /// Asset names `.github/workflows/release.yml` uploads for `version`
+    fn release_workflow_assets(version: &str) -> Vec<GitHubAsset> {
+        let workflow: serde_yaml::Value =
+            serde_yaml::from_str(include_str!("../.github/workflows/release.yml")).unwrap();
+        let jobs = &workflow["jobs"];
+        let mut names: Vec<String> = jobs["build-release"]["strategy"]["matrix"]["include"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["asset_name"].as_str().unwrap().to_string())
+            .collect();
+        names.push("rotd-macos-installer.pkg".to_string());
+        let package: String = jobs["package-methodology"]["steps"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|step| step["run"].as_str())
+            .collect();
+        assert!(package.contains("BUNDLE=rotd-methodology-${TAG#v}"));
+        assert!(package.contains("tar czf \"$BUNDLE.tar.gz\""));
+        names.push(format!("rotd-methodology-{}.tar.gz", version));
+
+        // The publish job checksums and signs every rotd-* asset
+        let script: String = jobs["publish-checksums"]["steps"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|step| step["run"].as_str())
+            .collect();
+        assert!(script.contains("sha256sum rotd-* > SHA256SUMS"));
+        assert!(script.contains("-x \"$asset.minisig\""));
+        let signatures: Vec<String> = names
+            .iter()
+            .map(|name| format!("{}{}", name, SIGNATURE_SUFFIX))
+            .collect();
+        names.push(CHECKSUMS_ASSET.to_string());
+        names.extend(signatures);
+
+        names
+            .into_iter()
+            .map(|name| GitHubAsset {
+                browser_download_url: format!("https://example.com/{}", name),
+                name,
+                size: 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_release_workflow_publishes_verification_assets() {
+        let release = ReleaseInfo {
+            version: "1.4.0".to_string(),
+            semver: Version::parse("1.4.0").unwrap(),
+            published_at: String::new(),
+            name: "v1.4.0".to_string(),
+            description: String::new(),
+            download_url: String::new(),
+            html_url: String::new(),
+            assets: release_workflow_assets("1.4.0"),
+        };
+        let platforms = [
+            ("linux", "x86_64", "gnu", "rotd-linux-x86_64.tar.gz"),
+            ("macos", "x86_64", "", "rotd-macos-x86_64.tar.gz"),
+            ("macos", "aarch64", "", "rotd-macos-aarch64.tar.gz"),
+            ("windows", "x86_64", "msvc", "rotd-windows-x86_64.zip"),
+        ];
+        for (os, arch, env, expected) in platforms {
+            let asset = select_platform_asset(&release.assets, &Platform { os, arch, env }).unwrap();
+            assert_eq!(asset.name, expected);
+            let (checksums, signature) = verification_assets(&release, &asset).unwrap();
+            assert_eq!(checksums.name, CHECKSUMS_ASSET);
+            assert_eq!(signature.name, format!("{}.minisig", expected));
+        }
+
+        // `rotd update` finds the methodology bundle and verifies it the same way
+        let bundle = crate::methodology::find_bundle_asset(&release).unwrap();
+        assert_eq!(bundle.name, "rotd-methodology-1.4.0.tar.gz");
+        let (_, signature) = verification_assets(&release, &bundle).unwrap();
+        assert_eq!(signature.name, "rotd-methodology-1.4.0.tar.gz.minisig");
+
+        // Without the publish job's output, upgrades refuse to install
+        let unverified = ReleaseInfo {
+            assets: release
+                .assets
+                .iter()
+                .filter(|a| a.name != CHECKSUMS_ASSET && !a.name.ends_with(SIGNATURE_SUFFIX))
+                .cloned()
+                .collect(),
+            ..release.clone()
+        };
+        let asset = select_platform_asset(&unverified.assets, &Platform {
+            os: "linux",
+            arch: "x86_64",
+            env: "gnu",
+        })
+        .unwrap();
+        let err = verification_assets(&unverified, &asset).unwrap_err();
+        assert!(err.to_string().contains("has no SHA256SUMS asset"));
+    }
+
+    #[test]
+    fn test_issue_to_task_maps_labels() {
+        let issue: GitHubIssue = serde_json::from_value(serde_json::json!({
+            "number": 42,
+            "title": "Cache parsed primers",
+            "body": "Parsing is slow",
+            "state": "open",
+            "html_url": "https://github.com/acme/app/issues/42",
+            "labels": [{"name": "rotd"}, {"name": "priority:high"}, {"name": "phase:2"}],
+            "created_at": "2025-07-01T10:00:00Z",
+            "closed_at": null
+        }))
+        .unwrap();
+
+        let task = issue_to_task(&issue);
+        assert_eq!(task.id, "gh-42");
+        assert_eq!(task.priority, Some(Priority::High));
+        assert_eq!(task.phase.as_deref(), Some("2"));
+        assert_eq!(
+            task.origin.as_deref(),
+            Some("https://github.com/acme/app/issues/42")
+        );
+        assert!(matches!(task.status, TaskStatus::Pending));
+        assert_eq!(label_priority("P0"), Some(Priority::Urgent));
+        assert!(parse_repo("acme").is_err());
+        assert_eq!(
+            parse_remote_url("git@github.com:acme/app.git").as_deref(),
+            Some("acme/app")
+        );
+        assert_eq!(
+            parse_issue_url(&issue.html_url),
+            Some(("acme".to_string(), "app".to_string(), 42))
+        );
+    }
+
+    #[test]
+    fn test_pr_body_from_task_score_and_summary() {
+        let task: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "4.2",
+            "title": "Cache parsed primers",
+            "status": "complete",
+            "description": "  Keep parsed primers in memory  ",
+        }))
+        .unwrap();
+        let score: PSSScore = serde_json::from_value(serde_json::json!({
+            "task_id": "4.2",
+            "score": 7,
+            "timestamp": "2025-07-01T10:00:00Z",
+            "criteria": {
+                "tests_pass": {"score": 1, "rationale": "12 passed"},
+                "coverage_ok": {"score": 0, "rationale": "61% | floor 70%"},
+            },
+        }))
+        .unwrap();
+        let summary: TestSummary = serde_json::from_value(serde_json::json!({
+            "task_id": "4.2",
+            "status": "passing",
+            "total_tests": 12,
+            "passed": 12,
+            "failed": 0,
+            "warnings": null,
+            "coverage": 61.25,
+            "verified_by": "cargo test",
+            "timestamp": "2025-07-01T10:00:00Z",
+            "notes": null,
+        }))
+        .unwrap();
+
+        let body = pr_body(&task, Some(&score), Some(&summary), Some(42));
+        assert!(body.starts_with("## Summary\n\nKeep parsed primers in memory\n"));
+        assert!(body.contains("**7/10**"));
+        // Criteria are sorted and pipes in rationales are escaped
+        let coverage = body.find("| coverage_ok | ❌ | 61% \\| floor 70% |").unwrap();
+        let tests = body.find("| tests_pass | ✅ | 12 passed |").unwrap();
+        assert!(coverage < tests);
+        assert!(body.contains("12 passed, 0 failed of 12 (passing), coverage 61.2%"));
+        assert!(body.contains("Closes #42\n"));
+        assert!(body.ends_with("ROTD-Task: 4.2\n"));
+
+        let bare = pr_body(&task, None, None, None);
+        assert!(bare.contains("_Not scored yet._"));
+        assert!(bare.contains("_No test summary recorded._"));
+        assert!(!bare.contains("Closes #"));
+    }
+
+    #[test]
+    fn test_parse_remote_url() {
+        for url in [
+            "https://github.com/acme/app.git",
+            "https://github.com/acme/app/",
+            "git@github.com:acme/app.git",
+            "ssh://git@github.com/acme/app\n",
+        ] {
+            assert_eq!(parse_remote_url(url).as_deref(), Some("acme/app"), "{}", url);
+        }
+        assert_eq!(parse_remote_url("git@gitlab.com:acme/app.git"), None);
+        assert_eq!(parse_remote_url("https://github.com/acme"), None);
+    }
+
     #[test]
     fn test_extract_changes() {
         let body = r#"
@@ -0,0 +1,432 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::latest_tasks;
+use crate::schema::{TaskEntry, TaskStatus};
+
+/// Output formats for `rotd graph`
+pub const GRAPH_FORMATS: [&str; 2] = ["dot", "mermaid"];
+
+/// Node fill color for each status
+fn status_color(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "#e5e7eb",
+        TaskStatus::InProgress => "#bfdbfe",
+        TaskStatus::Blocked => "#fecaca",
+        TaskStatus::Scaffolded => "#fde68a",
+        TaskStatus::Complete => "#bbf7d0",
+    }
+}
+
+/// Dependency edges as `(dependency, dependent)` pairs, in task order, plus
+/// the referenced IDs that aren't in the task list
+fn edges(tasks: &[TaskEntry]) -> (Vec<(&str, &str)>, Vec<&str>) {
+    let mut edges = Vec::new();
+    let mut missing: Vec<&str> = Vec::new();
+    for task in tasks {
+        for dep in task.depends_on.iter().flatten() {
+            if !tasks.iter().any(|t| &t.id == dep) && !missing.contains(&dep.as_str()) {
+                missing.push(dep);
+            }
+            edges.push((dep.as_str(), task.id.as_str()));
+        }
+    }
+    (edges, missing)
+}
+
+fn label(task: &TaskEntry) -> String {
+    format!("{}\n{}", task.id, task.title)
+}
+
+/// Graphviz DOT with one node per task, filled by status, and an edge from
+/// each dependency to the tasks waiting on it. Unknown dependencies are
+/// drawn dashed.
+pub fn to_dot(tasks: &[TaskEntry]) -> String {
+    let quote = |s: &str| {
+        format!(
+            "\"{}\"",
+            s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+        )
+    };
+    let (edges, missing) = edges(tasks);
+
+    let mut dot = String::from(
+        "digraph rotd {\n  rankdir=LR;\n  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n",
+    );
+    for task in tasks {
+        dot.push_str(&format!(
+            "  {} [label={}, fillcolor=\"{}\", tooltip={}];\n",
+            quote(&task.id),
+            quote(&label(task)),
+            status_color(&task.status),
+            quote(task.status.as_str())
+        ));
+    }
+    for id in &missing {
+        dot.push_str(&format!(
+            "  {} [style=dashed, fillcolor=white, tooltip=\"missing\"];\n",
+            quote(id)
+        ));
+    }
+    for (from, to) in &edges {
+        dot.push_str(&format!("  {} -> {};\n", quote(from), quote(to)));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Mermaid flowchart equivalent of [`to_dot`]. Task IDs contain dots, so
+/// nodes get positional IDs (`t0`, `t1`, ...) and show the task ID in the
+/// label.
+pub fn to_mermaid(tasks: &[TaskEntry]) -> String {
+    let escape = |s: &str| s.replace('"', "#quot;").replace('\n', "<br/>");
+    let (edges, missing) = edges(tasks);
+    let node_id = |id: &str| match tasks.iter().position(|t| t.id == id) {
+        Some(index) => format!("t{}", index),
+        None => format!("m{}", missing.iter().position(|m| *m == id).unwrap_or_default()),
+    };
+
+    let mut mermaid = String::from("flowchart LR\n");
+    for (index, task) in tasks.iter().enumerate() {
+        mermaid.push_str(&format!(
+            "  t{}[\"{}\"]:::{}\n",
+            index,
+            escape(&label(task)),
+            task.status.as_str()
+        ));
+    }
+    for (index, id) in missing.iter().enumerate() {
+        mermaid.push_str(&format!("  m{}[\"{}\"]:::missing\n", index, escape(id)));
+    }
+    for (from, to) in &edges {
+        mermaid.push_str(&format!("  {} --> {}\n", node_id(from), node_id(to)));
+    }
+    for status in [
+        TaskStatus::Pending,
+        TaskStatus::InProgress,
+        TaskStatus::Blocked,
+        TaskStatus::Scaffolded,
+        TaskStatus::Complete,
+    ] {
+        mermaid.push_str(&format!(
+            "  classDef {} fill:{},stroke:#6b7280\n",
+            status.as_str(),
+            status_color(&status)
+        ));
+    }
+    mermaid.push_str("  classDef missing fill:#ffffff,stroke:#6b7280,stroke-dasharray:4\n");
+    mermaid
+}
+
+/// Task as listed in critical path and ready set output
+#[derive(Debug, Serialize)]
+pub struct TaskRef {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+}
+
+impl From<&TaskEntry> for TaskRef {
+    fn from(task: &TaskEntry) -> Self {
+        Self {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            status: task.status.as_str().to_string(),
+            priority: task.priority.as_ref().map(|p| p.as_str().to_string()),
+        }
+    }
+}
+
+/// What stands between the project and done: the longest chain of
+/// incomplete tasks, and the tasks that can be started now
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    /// In the order the work has to happen
+    pub critical_path: Vec<TaskRef>,
+    /// Pending or scaffolded tasks whose dependencies are all complete
+    pub ready: Vec<TaskRef>,
+}
+
+/// Longest chain of incomplete tasks linked by `depends_on`, first task to
+/// do first. Ties go to the chain ending earliest in the task list;
+/// dependency cycles are cut where they close.
+pub fn critical_path(tasks: &[TaskEntry]) -> Vec<&TaskEntry> {
+    let incomplete: HashMap<&str, &TaskEntry> = tasks
+        .iter()
+        .filter(|t| t.status != TaskStatus::Complete)
+        .map(|t| (t.id.as_str(), t))
+        .collect();
+
+    // Longest chain ending at each task, built from its dependencies' chains
+    fn chain<'a>(
+        task: &'a TaskEntry,
+        incomplete: &HashMap<&str, &'a TaskEntry>,
+        memo: &mut HashMap<&'a str, Vec<&'a TaskEntry>>,
+        visiting: &mut Vec<&'a str>,
+    ) -> Vec<&'a TaskEntry> {
+        if let Some(found) = memo.get(task.id.as_str()) {
+            return found.clone();
+        }
+        visiting.push(&task.id);
+        let mut longest: Vec<&TaskEntry> = Vec::new();
+        for dep in task.depends_on.iter().flatten() {
+            let Some(dep_task) = incomplete.get(dep.as_str()) else {
+                continue;
+            };
+            if visiting.contains(&dep.as_str()) {
+                continue;
+            }
+            let candidate = chain(dep_task, incomplete, memo, visiting);
+            if candidate.len() > longest.len() {
+                longest = candidate;
+            }
+        }
+        visiting.pop();
+        longest.push(task);
+        memo.insert(&task.id, longest.clone());
+        longest
+    }
+
+    let mut memo = HashMap::new();
+    let mut longest: Vec<&TaskEntry> = Vec::new();
+    for task in tasks.iter().filter(|t| incomplete.contains_key(t.id.as_str())) {
+        let candidate = chain(task, &incomplete, &mut memo, &mut Vec::new());
+        if candidate.len() > longest.len() {
+            longest = candidate;
+        }
+    }
+    longest
+}
+
+/// Pending or scaffolded tasks whose dependencies are all complete, in task
+/// order. Dependencies missing from the task list count as incomplete.
+pub fn ready_tasks(tasks: &[TaskEntry]) -> Vec<&TaskEntry> {
+    let complete = |id: &String| {
+        tasks
+            .iter()
+            .any(|t| &t.id == id && t.status == TaskStatus::Complete)
+    };
+    tasks
+        .iter()
+        .filter(|t| matches!(t.status, TaskStatus::Pending | TaskStatus::Scaffolded))
+        .filter(|t| t.depends_on.iter().flatten().all(complete))
+        .collect()
+}
+
+pub fn readiness(tasks: &[TaskEntry]) -> Readiness {
+    Readiness {
+        critical_path: critical_path(tasks).into_iter().map(TaskRef::from).collect(),
+        ready: ready_tasks(tasks).into_iter().map(TaskRef::from).collect(),
+    }
+}
+
+fn print_readiness(readiness: &Readiness) {
+    println!(
+        "{} ({} task(s))",
+        "Critical Path".cyan().bold(),
+        readiness.critical_path.len()
+    );
+    if readiness.critical_path.is_empty() {
+        println!("  All tasks complete");
+    }
+    for (step, task) in readiness.critical_path.iter().enumerate() {
+        println!(
+            "  {:>2}. {:<12} {:<12} {}",
+            step + 1,
+            task.id,
+            task.status,
+            task.title
+        );
+    }
+
+    println!(
+        "\n{} ({} task(s))",
+        "Ready to Start".cyan().bold(),
+        readiness.ready.len()
+    );
+    if readiness.ready.is_empty() {
+        println!("  No pending tasks with all dependencies complete");
+    }
+    for task in &readiness.ready {
+        println!(
+            "  {:<12} {:<10} {}",
+            task.id,
+            task.priority.as_deref().unwrap_or("-"),
+            task.title
+        );
+    }
+}
+
+/// `rotd graph --critical-path`: a table, or JSON in agent mode and when
+/// written to a file
+fn run_readiness(out: Option<&Path>, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    let tasks = latest_tasks()?;
+    let readiness = readiness(&tasks);
+
+    let Some(out) = out else {
+        if is_agent_mode {
+            let mut result = serde_json::to_value(&readiness)?;
+            result["status"] = "success".into();
+            result["action"] = "critical_path".into();
+            println!("{}", result);
+        } else {
+            print_readiness(&readiness);
+        }
+        return Ok(());
+    };
+
+    if !dry_run {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out, serde_json::to_string_pretty(&readiness)? + "\n")
+            .with_context(|| format!("Failed to write {}", out.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "critical_path",
+                "critical_path": readiness.critical_path.len(),
+                "ready": readiness.ready.len(),
+                "out": out.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} critical path of {} task(s) and {} ready task(s) to {}",
+            if dry_run { "Would write" } else { "✓ Wrote" },
+            readiness.critical_path.len(),
+            readiness.ready.len(),
+            out.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// `rotd graph`
+pub fn run(
+    format: &str,
+    critical_path: bool,
+    out: Option<&Path>,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    if critical_path {
+        return run_readiness(out, dry_run, is_agent_mode);
+    }
+
+    let tasks = latest_tasks()?;
+    let content = match format {
+        "mermaid" => to_mermaid(&tasks),
+        _ => to_dot(&tasks),
+    };
+
+    let Some(out) = out else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    if !dry_run {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out, &content)
+            .with_context(|| format!("Failed to write {}", out.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "graph",
+                "format": format,
+                "tasks": tasks.len(),
+                "out": out.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} {} graph of {} task(s) to {}",
+            if dry_run { "Would write" } else { "✓ Wrote" },
+            format,
+            tasks.len(),
+            out.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, title: &str, status: &str, deps: &[&str]) -> TaskEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": title,
+            "status": status,
+            "depends_on": deps,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_graph() {
+        let tasks = vec![
+            task("1.1", "Schema", "complete", &[]),
+            task("1.2", "Say \"hi\"", "in_progress", &["1.1", "0.9"]),
+        ];
+
+        let dot = to_dot(&tasks);
+        assert!(dot.contains(
+            "  \"1.2\" [label=\"1.2\\nSay \\\"hi\\\"\", fillcolor=\"#bfdbfe\", tooltip=\"in_progress\"];\n"
+        ));
+        assert!(dot.contains("  \"0.9\" [style=dashed"));
+        assert!(dot.contains("  \"1.1\" -> \"1.2\";\n  \"0.9\" -> \"1.2\";\n"));
+
+        let mermaid = to_mermaid(&tasks);
+        assert!(mermaid.starts_with("flowchart LR\n  t0[\"1.1<br/>Schema\"]:::complete\n"));
+        assert!(mermaid.contains("  t1[\"1.2<br/>Say #quot;hi#quot;\"]:::in_progress\n"));
+        assert!(mermaid.contains("  m0[\"0.9\"]:::missing\n"));
+        assert!(mermaid.contains("  t0 --> t1\n  m0 --> t1\n"));
+        assert!(mermaid.contains("  classDef complete fill:#bbf7d0"));
+    }
+
+    #[test]
+    fn test_readiness() {
+        let tasks = vec![
+            task("1", "Schema", "complete", &[]),
+            task("2", "Parser", "in_progress", &["1"]),
+            task("3", "Docs", "pending", &["1"]),
+            task("4", "Validator", "pending", &["2"]),
+            task("5", "Release", "pending", &["4", "3"]),
+            task("6", "Cycle", "blocked", &["7"]),
+            task("7", "Cycle", "pending", &["6"]),
+            task("8", "Orphan", "pending", &["0"]),
+        ];
+        let ids = |tasks: Vec<&TaskEntry>| -> Vec<String> {
+            tasks.iter().map(|t| t.id.clone()).collect()
+        };
+
+        assert_eq!(ids(critical_path(&tasks)), vec!["2", "4", "5"]);
+        // Completed dependencies and missing ones are treated differently
+        assert_eq!(ids(ready_tasks(&tasks)), vec!["3"]);
+        assert!(critical_path(&tasks[..1]).is_empty());
+    }
+}
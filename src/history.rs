@@ -1,11 +1,38 @@
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
+use std::path::Path;
 
+use crate::HistoryCommands;
 use crate::common;
 use crate::fs_ops::{append_jsonl, read_jsonl};
-use crate::schema::{TaskEntry, TaskHistoryEvent, RotdConfig};
+use crate::output::escape_csv;
+use crate::schema::{LocalConfig, TaskEntry, TaskHistoryEvent, RotdConfig};
+
+/// Formats accepted by `history export`
+pub const EXPORT_FORMATS: [&str; 2] = ["json", "csv"];
+
+pub fn handle_command(cmd: HistoryCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        HistoryCommands::Export {
+            task_id,
+            all: _,
+            format,
+            report,
+            out,
+        } => cmd_export(
+            task_id.as_deref(),
+            &format,
+            report,
+            out.as_deref(),
+            dry_run,
+            is_agent_mode,
+        ),
+    }
+}
 
 pub fn get_agent_id() -> String {
     env::var("ROTD_AGENT_ID").unwrap_or_else(|_| "human".to_string())
@@ -22,12 +49,12 @@ pub fn append_task_history(
     let mut event = TaskHistoryEvent::new(
         task.id.clone(),
         agent_id,
-        format!("{:?}", task.status).to_lowercase(),
+        task.status.as_str().to_string(),
     );
     
     // Set previous values if we have them
     if let Some(prev) = prev_task {
-        event.prev_status = Some(format!("{:?}", prev.status).to_lowercase());
+        event.prev_status = Some(prev.status.as_str().to_string());
         
         if prev.priority != task.priority {
             event.prev_priority = prev.priority.as_ref().map(|p| p.as_str().to_string());
@@ -36,8 +63,8 @@ pub fn append_task_history(
     }
     
     event.comment = comment.map(|c| {
-        if c.len() > 280 {
-            format!("{}...", &c[..277])
+        if c.chars().count() > 280 {
+            format!("{}...", c.chars().take(277).collect::<String>())
         } else {
             c
         }
@@ -51,6 +78,20 @@ pub fn append_task_history(
     append_jsonl(&history_file, &event)
 }
 
+/// Append a history event unless `history_enabled` is off in the config.
+/// An unreadable config keeps history on.
+pub fn record_task_history(
+    task: &TaskEntry,
+    prev_task: Option<&TaskEntry>,
+    comment: Option<String>,
+    pss_delta: Option<f64>,
+) -> Result<()> {
+    if load_config().map_or(true, |config| config.history_enabled) {
+        append_task_history(task, prev_task, comment, pss_delta)?;
+    }
+    Ok(())
+}
+
 pub fn read_task_history(task_id: &str) -> Result<Vec<TaskHistoryEvent>> {
     let history_file = common::task_history_file(task_id);
     read_jsonl(&history_file)
@@ -77,8 +118,6 @@ pub fn get_task_history_stats(task_id: &str) -> Result<TaskHistoryStats> {
         status_counts,
         agent_contributions,
         total_pss_delta,
-        first_event: events.first().cloned(),
-        last_event: events.last().cloned(),
     })
 }
 
@@ -101,13 +140,20 @@ pub fn load_config() -> Result<RotdConfig> {
     let content = fs::read_to_string(&config_path)
         .context("Failed to read config file")?;
     
-    // Remove comments for JSON5/JSONC compatibility
-    let json_content = remove_jsonc_comments(&content);
-    
-    serde_json::from_str(&json_content)
+    parse_jsonc(&content)
         .context("Failed to parse config file")
 }
 
+pub fn load_local_config() -> Result<LocalConfig> {
+    let path = common::local_config_path();
+    if !path.exists() {
+        return Ok(LocalConfig::default());
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_jsonc(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
 pub fn save_config(config: &RotdConfig) -> Result<()> {
     let config_path = common::config_path();
     
@@ -119,18 +165,59 @@ pub fn save_config(config: &RotdConfig) -> Result<()> {
   // Compress closed tasks? ("closed" means status == "complete")
   "history_compress_closed": {},
   // Hard cap on total history directory size (MiB)
-  "history_total_cap_mib": {}
+  "history_total_cap_mib": {},
+  // Record a task history event on every task update?
+  "history_enabled": {},
+  // Stamp updated_at on task writes and set/clear completed with the status?
+  "manage_timestamps": {}{}
 }}"#,
         config.history_max_size_mib,
         config.history_compress_closed,
-        config.history_total_cap_mib
+        config.history_total_cap_mib,
+        config.history_enabled,
+        config.manage_timestamps,
+        profile_settings(config)?
     );
     
     fs::write(&config_path, jsonc_content)
         .context("Failed to write config file")
 }
 
-fn remove_jsonc_comments(content: &str) -> String {
+/// Config lines written for `rotd init --profile`, or nothing without one
+fn profile_settings(config: &RotdConfig) -> Result<String> {
+    let Some(profile) = &config.profile else {
+        return Ok(String::new());
+    };
+
+    Ok(format!(
+        r#",
+  // Language profile from `rotd init --profile`
+  "profile": {},
+  // Command PSS runs to check that the project compiles; for several, use
+  // "compile_checks": [{{ "command": "...", "dir": "...", "timeout_secs": 600 }}]
+  "compile_command": {},
+  // Stub markers, and the file extensions and directories scanned for them
+  "stub_patterns": {},
+  "stub_extensions": {},
+  "stub_dirs": {},
+  // Paths skipped by stub detection, gitignore-style, on top of .gitignore
+  "stub_ignore": {},
+  // Test directories recorded in new primers
+  "test_dirs": {},
+  // Command that produces coverage for the ratchet
+  "coverage_command": {}"#,
+        serde_json::to_string(profile)?,
+        serde_json::to_string(&config.compile_command)?,
+        serde_json::to_string(&config.stub_patterns)?,
+        serde_json::to_string(&config.stub_extensions)?,
+        serde_json::to_string(&config.stub_dirs)?,
+        serde_json::to_string(&config.stub_ignore)?,
+        serde_json::to_string(&config.test_dirs)?,
+        serde_json::to_string(&config.coverage_command)?
+    ))
+}
+
+pub fn remove_jsonc_comments(content: &str) -> String {
     let mut result = String::new();
     let mut in_string = false;
     let mut escape_next = false;
@@ -156,7 +243,7 @@ fn remove_jsonc_comments(content: &str) -> String {
                 if let Some(&'/') = chars.peek() {
                     // Single-line comment - skip to end of line
                     chars.next(); // consume second '/'
-                    while let Some(ch) = chars.next() {
+                    for ch in chars.by_ref() {
                         if ch == '\n' {
                             result.push('\n');
                             break;
@@ -166,7 +253,7 @@ fn remove_jsonc_comments(content: &str) -> String {
                     // Multi-line comment - skip to */
                     chars.next(); // consume '*'
                     let mut prev = ' ';
-                    while let Some(ch) = chars.next() {
+                    for ch in chars.by_ref() {
                         if prev == '*' && ch == '/' {
                             break;
                         }
@@ -183,12 +270,343 @@ fn remove_jsonc_comments(content: &str) -> String {
     result
 }
 
-pub fn ensure_history_dir() -> Result<()> {
-    let history_path = common::task_history_path();
-    if !history_path.exists() {
-        fs::create_dir_all(&history_path)
-            .context("Failed to create task_history directory")?;
+/// Drop commas directly before a closing `}` or `]`, which JSONC allows.
+/// Expects comments to be removed already; newlines are kept so parse
+/// errors still point at the right line.
+pub fn remove_trailing_commas(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut result = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut escape_next = false;
+    
+    for (i, &ch) in chars.iter().enumerate() {
+        if escape_next {
+            escape_next = false;
+        } else if in_string {
+            match ch {
+                '\\' => escape_next = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if ch == '"' {
+            in_string = true;
+        } else if ch == ',' {
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some('}') | Some(']')) {
+                continue;
+            }
+        }
+        result.push(ch);
+    }
+    
+    result
+}
+
+/// Parse a JSONC document: JSON with comments and trailing commas
+pub fn parse_jsonc<T: serde::de::DeserializeOwned>(content: &str) -> serde_json::Result<T> {
+    serde_json::from_str(&remove_trailing_commas(&remove_jsonc_comments(content)))
+}
+
+/// Carry the full-line comments of a JSONC document over to its rewritten
+/// form: comments before the opening brace, before a top-level field, and
+/// after the closing brace. `updated` must be pretty-printed JSON, with
+/// top-level fields indented by two spaces.
+pub fn preserve_jsonc_comments(original: &str, updated: &str) -> String {
+    let mut header = Vec::new();
+    let mut by_field: HashMap<String, Vec<String>> = HashMap::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    let mut in_block = false;
+    let mut seen_open = false;
+    
+    for line in original.lines() {
+        let trimmed = line.trim();
+        if in_block {
+            pending.push(trimmed.to_string());
+            in_block = !trimmed.contains("*/");
+            continue;
+        }
+        if trimmed.starts_with("//") || trimmed.starts_with("/*") {
+            in_block = trimmed.starts_with("/*") && !trimmed.contains("*/");
+            pending.push(trimmed.to_string());
+            continue;
+        }
+        
+        let code = remove_jsonc_comments(line);
+        let code = code.trim();
+        if !seen_open && code.starts_with('{') {
+            header.append(&mut pending);
+            seen_open = true;
+        }
+        if depth == 1 && !pending.is_empty() {
+            if let Some(field) = code.strip_prefix('"').and_then(|rest| rest.split('"').next()) {
+                by_field
+                    .entry(field.to_string())
+                    .or_default()
+                    .append(&mut pending);
+            }
+        }
+        // Comments inside nested values have no stable anchor; drop them
+        if !code.is_empty() {
+            pending.clear();
+        }
+
+        let mut in_string = false;
+        let mut escape_next = false;
+        for ch in code.chars() {
+            match ch {
+                _ if escape_next => escape_next = false,
+                '\\' if in_string => escape_next = true,
+                '"' => in_string = !in_string,
+                '{' | '[' if !in_string => depth += 1,
+                '}' | ']' if !in_string => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+    // Whatever is left trails the document
+    let trailing = pending;
+    
+    let mut result = String::new();
+    for comment in &header {
+        result.push_str(comment);
+        result.push('\n');
+    }
+    for line in updated.lines() {
+        let field = line
+            .strip_prefix("  \"")
+            .and_then(|rest| rest.split('"').next());
+        if let Some(comments) = field.and_then(|f| by_field.get(f)) {
+            for comment in comments {
+                result.push_str("  ");
+                result.push_str(comment);
+                result.push('\n');
+            }
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    for comment in &trailing {
+        result.push_str(comment);
+        result.push('\n');
+    }
+    
+    result
+}
+
+/// What an event changed, e.g. `pending → in_progress, priority low → high, PSS +1.5`
+pub fn describe_event(event: &TaskHistoryEvent) -> String {
+    let mut changes = vec![match &event.prev_status {
+        Some(prev) if *prev != event.status => format!("{} → {}", prev, event.status),
+        _ => event.status.clone(),
+    }];
+    if event.prev_priority.is_some() || event.priority.is_some() {
+        changes.push(format!(
+            "priority {} → {}",
+            event.prev_priority.as_deref().unwrap_or("none"),
+            event.priority.as_deref().unwrap_or("none")
+        ));
+    }
+    if event.prev_capability.is_some() || event.capability.is_some() {
+        changes.push(format!(
+            "capability {} → {}",
+            event.prev_capability.as_deref().unwrap_or("none"),
+            event.capability.as_deref().unwrap_or("none")
+        ));
     }
+    if let Some(delta) = event.pss_delta {
+        changes.push(format!("PSS {:+.1}", delta));
+    }
+    changes.join(", ")
+}
+
+/// Every task's history events, oldest first
+pub fn read_all_history() -> Result<Vec<TaskHistoryEvent>> {
+    let history_dir = common::task_history_path();
+    if !history_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut events = Vec::new();
+    for entry in fs::read_dir(&history_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("jsonl") {
+            events.extend(read_jsonl::<TaskHistoryEvent>(&path)?);
+        }
+    }
+    events.sort_by(|a, b| (a.timestamp, &a.task_id).cmp(&(b.timestamp, &b.task_id)));
+    Ok(events)
+}
+
+/// History events as CSV, one row per event
+pub fn events_to_csv(events: &[TaskHistoryEvent]) -> String {
+    let mut csv = String::from(
+        "timestamp,task_id,agent_id,prev_status,status,prev_priority,priority,prev_capability,capability,pss_delta,comment\n",
+    );
+    for event in events {
+        let timestamp = event.timestamp.to_rfc3339();
+        let pss_delta = event.pss_delta.map(|d| d.to_string()).unwrap_or_default();
+        let row = [
+            timestamp.as_str(),
+            &event.task_id,
+            &event.agent_id,
+            event.prev_status.as_deref().unwrap_or_default(),
+            &event.status,
+            event.prev_priority.as_deref().unwrap_or_default(),
+            event.priority.as_deref().unwrap_or_default(),
+            event.prev_capability.as_deref().unwrap_or_default(),
+            event.capability.as_deref().unwrap_or_default(),
+            &pss_delta,
+            event.comment.as_deref().unwrap_or_default(),
+        ];
+        let row: Vec<String> = row.iter().map(|field| escape_csv(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Time tasks spent in one status
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct StatusTime {
+    /// Stretches of time a task spent in the status
+    pub visits: usize,
+    pub total_hours: f64,
+    pub avg_hours: f64,
+}
+
+/// Summary of task history for `history export --report`
+#[derive(Debug, Serialize)]
+pub struct HistoryReport {
+    pub tasks: usize,
+    pub events: usize,
+    /// Keyed by status
+    pub status_time: BTreeMap<String, StatusTime>,
+    /// Consecutive events on a task by different agents, keyed `from -> to`
+    pub handoffs: BTreeMap<String, usize>,
+}
+
+/// Summarize events: each event's status lasts until the task's next event,
+/// or until `now` for a task not yet complete
+pub fn history_report(events: &[TaskHistoryEvent], now: DateTime<Utc>) -> HistoryReport {
+    let mut by_task: BTreeMap<&str, Vec<&TaskHistoryEvent>> = BTreeMap::new();
+    for event in events {
+        by_task.entry(&event.task_id).or_default().push(event);
+    }
+
+    let mut status_time: BTreeMap<String, StatusTime> = BTreeMap::new();
+    let mut handoffs: BTreeMap<String, usize> = BTreeMap::new();
+    for task_events in by_task.values_mut() {
+        task_events.sort_by_key(|e| e.timestamp);
+        for (i, event) in task_events.iter().enumerate() {
+            let end = match task_events.get(i + 1) {
+                Some(next) => {
+                    if next.agent_id != event.agent_id {
+                        *handoffs
+                            .entry(format!("{} -> {}", event.agent_id, next.agent_id))
+                            .or_insert(0) += 1;
+                    }
+                    next.timestamp
+                }
+                None if event.status != "complete" => now,
+                None => continue,
+            };
+            // A repeated status continues the same stretch
+            let continues = i > 0 && task_events[i - 1].status == event.status;
+            let time = status_time.entry(event.status.clone()).or_default();
+            if !continues {
+                time.visits += 1;
+            }
+            time.total_hours += (end - event.timestamp).num_seconds().max(0) as f64 / 3600.0;
+        }
+    }
+    for time in status_time.values_mut() {
+        time.total_hours = (time.total_hours * 100.0).round() / 100.0;
+        time.avg_hours = (time.total_hours / time.visits.max(1) as f64 * 100.0).round() / 100.0;
+    }
+
+    HistoryReport {
+        tasks: by_task.len(),
+        events: events.len(),
+        status_time,
+        handoffs,
+    }
+}
+
+fn report_to_csv(report: &HistoryReport) -> String {
+    let mut csv = String::from("section,name,count,total_hours,avg_hours\n");
+    for (status, time) in &report.status_time {
+        csv.push_str(&format!(
+            "status,{},{},{},{}\n",
+            escape_csv(status),
+            time.visits,
+            time.total_hours,
+            time.avg_hours
+        ));
+    }
+    for (handoff, count) in &report.handoffs {
+        csv.push_str(&format!("handoff,{},{},,\n", escape_csv(handoff), count));
+    }
+    csv
+}
+
+fn cmd_export(
+    task_id: Option<&str>,
+    format: &str,
+    report: bool,
+    out: Option<&Path>,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    common::check_rotd_initialized()?;
+
+    let events = match task_id {
+        Some(task_id) => read_task_history(task_id)?,
+        None => read_all_history()?,
+    };
+    let content = match (report, format) {
+        (true, "csv") => report_to_csv(&history_report(&events, Utc::now())),
+        (true, _) => serde_json::to_string_pretty(&history_report(&events, Utc::now()))? + "\n",
+        (false, "csv") => events_to_csv(&events),
+        (false, _) => serde_json::to_string_pretty(&events)? + "\n",
+    };
+
+    let Some(out) = out else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    if !dry_run {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(out, &content).with_context(|| format!("Failed to write {}", out.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "history_export",
+                "format": format,
+                "report": report,
+                "events": events.len(),
+                "out": out.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        use colored::Colorize;
+        println!(
+            "{} {}{} history event(s) to {}",
+            if dry_run { "Would export" } else { "✓ Exported" },
+            if report { "a report of " } else { "" },
+            events.len(),
+            out.display().to_string().cyan()
+        );
+    }
+
     Ok(())
 }
 
@@ -198,8 +616,6 @@ pub struct TaskHistoryStats {
     pub status_counts: HashMap<String, u32>,
     pub agent_contributions: HashMap<String, u32>,
     pub total_pss_delta: f64,
-    pub first_event: Option<TaskHistoryEvent>,
-    pub last_event: Option<TaskHistoryEvent>,
 }
 
 #[cfg(test)]
@@ -222,4 +638,77 @@ mod tests {
         assert_eq!(parsed["key"], "value");
         assert_eq!(parsed["number"], 42);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_jsonc_trailing_commas() {
+        let jsonc = "{\n  \"list\": [1, 2,], // done\n  \"text\": \"a,]\",\n}";
+        let parsed: serde_json::Value = parse_jsonc(jsonc).unwrap();
+        
+        assert_eq!(parsed["list"], serde_json::json!([1, 2]));
+        assert_eq!(parsed["text"], "a,]");
+    }
+    
+    #[test]
+    fn test_preserve_jsonc_comments() {
+        let original = "// Header\n{\n  // Who owns this\n  \"name\": \"old\",\n  \"nested\": {\n    // not top-level\n    \"a\": 1\n  },\n  /* Block\n     comment */\n  \"list\": []\n}\n// Footer\n";
+        let updated = "{\n  \"name\": \"new\",\n  \"nested\": {\n    \"a\": 2\n  },\n  \"list\": [\n    1\n  ]\n}";
+        
+        assert_eq!(
+            preserve_jsonc_comments(original, updated),
+            "// Header\n{\n  // Who owns this\n  \"name\": \"new\",\n  \"nested\": {\n    \"a\": 2\n  },\n  /* Block\n  comment */\n  \"list\": [\n    1\n  ]\n}\n// Footer\n"
+        );
+    }
+    
+    #[test]
+    fn test_describe_event() {
+        let mut event = TaskHistoryEvent::new("6.1".to_string(), "agent-a".to_string(), "complete".to_string());
+        assert_eq!(describe_event(&event), "complete");
+        
+        event.prev_status = Some("in_progress".to_string());
+        event.prev_priority = Some("low".to_string());
+        event.priority = Some("high".to_string());
+        event.pss_delta = Some(1.5);
+        assert_eq!(
+            describe_event(&event),
+            "in_progress → complete, priority low → high, PSS +1.5"
+        );
+    }
+    
+    #[test]
+    fn test_history_report() {
+        let at = |hour: u32| -> DateTime<Utc> {
+            format!("2026-01-31T{:02}:00:00Z", hour).parse().unwrap()
+        };
+        let event = |task: &str, agent: &str, status: &str, hour: u32| {
+            let mut event = TaskHistoryEvent::new(task.to_string(), agent.to_string(), status.to_string());
+            event.timestamp = at(hour);
+            event
+        };
+        let events = vec![
+            event("6.1", "agent-a", "pending", 0),
+            event("6.1", "agent-a", "in_progress", 2),
+            event("6.1", "agent-b", "in_progress", 3),
+            event("6.1", "agent-b", "complete", 6),
+            event("6.2", "agent-a", "pending", 4),
+        ];
+        
+        let report = history_report(&events, at(10));
+        assert_eq!((report.tasks, report.events), (2, 5));
+        assert_eq!(
+            report.status_time["pending"],
+            StatusTime { visits: 2, total_hours: 8.0, avg_hours: 4.0 }
+        );
+        assert_eq!(
+            report.status_time["in_progress"],
+            StatusTime { visits: 1, total_hours: 4.0, avg_hours: 4.0 }
+        );
+        assert!(!report.status_time.contains_key("complete"));
+        assert_eq!(report.handoffs.get("agent-a -> agent-b"), Some(&1));
+        
+        let csv = events_to_csv(&events[..1]);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("2026-01-31T00:00:00+00:00,6.1,agent-a,,pending,,,,,,")
+        );
+    }
+}
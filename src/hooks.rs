@@ -0,0 +1,299 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git::run_git;
+use crate::HooksCommands;
+
+/// Marker line identifying hook scripts written by rotd
+const HOOK_MARKER: &str = "# rotd-managed-hook";
+/// Suffix given to pre-existing hooks so the rotd hook can chain to them
+const CHAINED_SUFFIX: &str = ".rotd-chained";
+const MANAGED_HOOKS: [&str; 2] = ["pre-commit", "pre-push"];
+
+pub fn handle_command(cmd: HooksCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        HooksCommands::Install { force, hook } => cmd_install(&hook, force, dry_run, is_agent_mode),
+        HooksCommands::Uninstall { hook } => cmd_uninstall(&hook, dry_run, is_agent_mode),
+        HooksCommands::Status => cmd_status(is_agent_mode),
+    }
+}
+
+/// Resolve the hooks directory, honoring `core.hooksPath` and worktrees
+pub fn git_hooks_dir() -> Result<PathBuf> {
+    let dir = run_git(&["rev-parse", "--git-path", "hooks"])
+        .context("Not a git repository (or git is not installed)")?;
+    Ok(PathBuf::from(dir))
+}
+
+fn selected_hooks(hook: &[String]) -> Result<Vec<&'static str>> {
+    if hook.is_empty() {
+        return Ok(MANAGED_HOOKS.to_vec());
+    }
+    hook.iter()
+        .map(|h| {
+            MANAGED_HOOKS
+                .iter()
+                .find(|m| **m == h.as_str())
+                .copied()
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unsupported hook '{}'. Supported: {}",
+                        h,
+                        MANAGED_HOOKS.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+fn hook_script(hook: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+{marker} ({hook})
+# Installed by `rotd hooks install`; remove with `rotd hooks uninstall`.
+# Set ROTD_BIN to use a specific rotd binary, or ROTD_SKIP_HOOKS=1 to bypass.
+
+HOOK_DIR="$(dirname "$0")"
+if [ -x "$HOOK_DIR/{hook}{chained}" ]; then
+    "$HOOK_DIR/{hook}{chained}" "$@" || exit $?
+fi
+
+[ "$ROTD_SKIP_HOOKS" = "1" ] && exit 0
+[ -d .rotd ] || exit 0
+
+ROTD="${{ROTD_BIN:-rotd}}"
+"$ROTD" check --ci || exit $?
+"$ROTD" validate --all || exit $?
+"#,
+        marker = HOOK_MARKER,
+        hook = hook,
+        chained = CHAINED_SUFFIX
+    )
+}
+
+pub fn is_managed_hook(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|c| c.contains(HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+fn chained_path(hooks_dir: &Path, hook: &str) -> PathBuf {
+    hooks_dir.join(format!("{}{}", hook, CHAINED_SUFFIX))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn cmd_install(hook: &[String], force: bool, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    let hooks_dir = git_hooks_dir()?;
+    let hooks = selected_hooks(hook)?;
+    let mut results = Vec::new();
+
+    for name in hooks {
+        let path = hooks_dir.join(name);
+        let chained = chained_path(&hooks_dir, name);
+
+        let action = if !path.exists() {
+            "installed"
+        } else if is_managed_hook(&path) {
+            if !force {
+                results.push(serde_json::json!({"hook": name, "action": "unchanged"}));
+                continue;
+            }
+            "reinstalled"
+        } else if chained.exists() && !force {
+            return Err(anyhow::anyhow!(
+                "{} already chains to {}. Use --force to overwrite the chained hook.",
+                path.display(),
+                chained.display()
+            ));
+        } else {
+            "chained"
+        };
+
+        if !dry_run {
+            fs::create_dir_all(&hooks_dir)?;
+            if action == "chained" {
+                // Preserve the existing hook and run it before the rotd checks
+                fs::rename(&path, &chained)
+                    .with_context(|| format!("Failed to move existing hook {}", path.display()))?;
+            }
+            fs::write(&path, hook_script(name))?;
+            make_executable(&path)?;
+        }
+
+        results.push(serde_json::json!({
+            "hook": name,
+            "action": action,
+            "path": path.display().to_string(),
+        }));
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "hooks_install",
+                "dry_run": dry_run,
+                "hooks": results,
+            })
+        );
+    } else {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+        }
+        for r in &results {
+            let hook = r["hook"].as_str().unwrap_or_default();
+            match r["action"].as_str().unwrap_or_default() {
+                "unchanged" => println!(
+                    "  {} {} already installed (use --force to reinstall)",
+                    "•".yellow(),
+                    hook
+                ),
+                "chained" => println!(
+                    "  {} {} installed (existing hook kept as {}{})",
+                    "✓".green(),
+                    hook,
+                    hook,
+                    CHAINED_SUFFIX
+                ),
+                action => println!("  {} {} {}", "✓".green(), hook, action),
+            }
+        }
+        println!(
+            "Hooks run {} and {} before commits and pushes.",
+            "rotd check --ci".cyan(),
+            "rotd validate --all".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_uninstall(hook: &[String], dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    let hooks_dir = git_hooks_dir()?;
+    let hooks = selected_hooks(hook)?;
+    let mut results = Vec::new();
+
+    for name in hooks {
+        let path = hooks_dir.join(name);
+        let chained = chained_path(&hooks_dir, name);
+
+        if !path.exists() || !is_managed_hook(&path) {
+            results.push(serde_json::json!({"hook": name, "action": "not_installed"}));
+            continue;
+        }
+
+        let restored = chained.exists();
+        if !dry_run {
+            fs::remove_file(&path)?;
+            if restored {
+                fs::rename(&chained, &path)?;
+            }
+        }
+
+        results.push(serde_json::json!({
+            "hook": name,
+            "action": if restored { "restored" } else { "removed" },
+        }));
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "hooks_uninstall",
+                "dry_run": dry_run,
+                "hooks": results,
+            })
+        );
+    } else {
+        for r in &results {
+            let hook = r["hook"].as_str().unwrap_or_default();
+            match r["action"].as_str().unwrap_or_default() {
+                "not_installed" => println!("  {} {} not managed by rotd", "•".yellow(), hook),
+                "restored" => println!(
+                    "  {} {} removed (previous hook restored)",
+                    "✓".green(),
+                    hook
+                ),
+                _ => println!("  {} {} removed", "✓".green(), hook),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_status(is_agent_mode: bool) -> Result<()> {
+    let hooks_dir = git_hooks_dir()?;
+
+    let statuses: Vec<_> = MANAGED_HOOKS
+        .iter()
+        .map(|name| {
+            let path = hooks_dir.join(name);
+            let state = if !path.exists() {
+                "absent"
+            } else if is_managed_hook(&path) {
+                "installed"
+            } else {
+                "foreign"
+            };
+            serde_json::json!({
+                "hook": name,
+                "state": state,
+                "chained": chained_path(&hooks_dir, name).exists(),
+            })
+        })
+        .collect();
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "hooks_dir": hooks_dir.display().to_string(),
+                "hooks": statuses,
+            })
+        );
+    } else {
+        println!("Git hooks ({}):", hooks_dir.display());
+        for s in &statuses {
+            let state = match s["state"].as_str().unwrap_or_default() {
+                "installed" => "installed".green(),
+                "foreign" => "not managed by rotd".yellow(),
+                _ => "absent".normal(),
+            };
+            let chained = if s["chained"].as_bool().unwrap_or(false) {
+                " (chains to previous hook)"
+            } else {
+                ""
+            };
+            println!(
+                "  {:<11} {}{}",
+                s["hook"].as_str().unwrap_or_default(),
+                state,
+                chained
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -6,10 +6,29 @@ use crate::cli::commands::buckle_mode::BuckleModeState;
 use crate::common::check_rotd_initialized;
 use crate::fs_ops::*;
 use crate::github;
+use crate::profile::Profile;
 use crate::pss;
 use crate::schema::*;
 
-pub fn init(force: bool, dry_run: bool, verbose: bool) -> Result<()> {
+pub fn init(
+    force: bool,
+    git: bool,
+    profile: Option<&Profile>,
+    non_interactive: bool,
+    dry_run: bool,
+    verbose: bool,
+) -> Result<()> {
+    let layout = if git { "split" } else { "flat" };
+    let summary = |status: &str| {
+        format!(
+            "rotd-init status={} layout={} profile={} path={}",
+            status,
+            layout,
+            profile.map(|p| p.name).unwrap_or("-"),
+            crate::common::rotd_path().display()
+        )
+    };
+
     if dry_run {
         println!(
             "{}",
@@ -21,6 +40,11 @@ pub fn init(force: bool, dry_run: bool, verbose: bool) -> Result<()> {
     let rotd_dir = crate::common::rotd_path();
 
     if rotd_dir.exists() && !force {
+        if non_interactive {
+            return Err(anyhow::anyhow!(
+                ".rotd already exists. Use --force to overwrite."
+            ));
+        }
         if !dialoguer::Confirm::new()
             .with_prompt(format!("{} already exists. Overwrite?", ".rotd".yellow()))
             .default(false)
@@ -34,10 +58,26 @@ pub fn init(force: bool, dry_run: bool, verbose: bool) -> Result<()> {
     if dry_run {
         println!("Would create ROTD directory structure:");
         println!("  {}", ".rotd/".cyan());
-        println!("  ├── {}", "tasks.jsonl".white());
-        println!("  ├── {}", "session_state.json".white());
-        println!("  ├── {}", "coverage_history.json".white());
-        println!("  └── {}", "test_summaries/".cyan());
+        if git {
+            println!("  ├── {}", ".gitignore".white());
+            println!("  ├── {}", "shared/".cyan());
+            println!("  │   ├── {}", "tasks.jsonl".white());
+            println!("  │   ├── {}", "coverage_history.json".white());
+            println!("  │   └── {}", "test_summaries/".cyan());
+            println!("  └── {}", "local/".cyan());
+            println!("      └── {}", "session_state.json".white());
+        } else {
+            println!("  ├── {}", "tasks.jsonl".white());
+            println!("  ├── {}", "session_state.json".white());
+            println!("  ├── {}", "coverage_history.json".white());
+            println!("  └── {}", "test_summaries/".cyan());
+        }
+        if let Some(profile) = profile {
+            println!("Would apply the {} profile", profile.name.cyan());
+        }
+        if non_interactive {
+            println!("{}", summary("dry_run"));
+        }
         return Ok(());
     }
 
@@ -47,75 +87,88 @@ pub fn init(force: bool, dry_run: bool, verbose: bool) -> Result<()> {
 
     // Create directory structure
     std::fs::create_dir_all(&rotd_dir)?;
+    if git {
+        // Path helpers switch to the split layout once shared/ exists
+        std::fs::create_dir_all(rotd_dir.join(crate::common::SHARED_DIR))?;
+        std::fs::create_dir_all(rotd_dir.join(crate::common::LOCAL_DIR))?;
+        crate::git::write_rotd_gitignore()?;
+    }
     std::fs::create_dir_all(crate::common::test_summaries_path())?;
     std::fs::create_dir_all(crate::common::task_history_path())?;
 
     // Create initial files with templates
-    create_initial_files(verbose)?;
+    create_initial_files(profile, verbose)?;
 
     println!(
         "{}",
         "✓ ROTD project initialized successfully!".green().bold()
     );
+    if let Some(profile) = profile {
+        println!(
+            "  Profile {}: compile check {}, coverage {}",
+            profile.name.cyan(),
+            profile.compile_command.cyan(),
+            profile.coverage_command.cyan()
+        );
+    }
+    if git {
+        println!(
+            "  Commit {} and {}; {} is ignored.",
+            ".rotd/shared/".cyan(),
+            ".rotd/.gitignore".cyan(),
+            ".rotd/local/".cyan()
+        );
+    }
+    if non_interactive {
+        println!("{}", summary("success"));
+    }
 
     Ok(())
 }
 
-// Updates ROTD project version if available
-pub fn update(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
+// Updates ROTD project methodology from the latest release bundle
+pub fn update(
+    check_only: bool,
+    yes: bool,
+    force: bool,
+    skip_verify: bool,
+    channel: github::ReleaseChannel,
+    dry_run: bool,
+    verbose: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
-    // Get current project version
-    let version_path = crate::common::rotd_path().join("version.json");
-    let current_version = if version_path.exists() {
-        let v: ProjectVersion = read_json(&version_path)?;
-        v.version
-    } else {
-        "1.3.5".to_string()
-    };
-
-    // The latest methodology version available
-    let latest_methodology_version = "1.3.4";
-
     // Check for methodology updates
     println!("{}", "Checking for ROTD methodology updates...".cyan());
-    
-    // Compare semantic versions
-    let needs_update = match (current_version.as_str(), latest_methodology_version) {
-        (current, latest) if current == latest => false,
-        (current, latest) => {
-            // Simple version comparison - can be enhanced with semver crate if needed
-            let current_parts: Vec<u32> = current.trim_start_matches('v')
-                .split('.')
-                .filter_map(|s| s.parse().ok())
-                .collect();
-            let latest_parts: Vec<u32> = latest.trim_start_matches('v')
-                .split('.')
-                .filter_map(|s| s.parse().ok())
-                .collect();
-            
-            if current_parts.len() != 3 || latest_parts.len() != 3 {
-                true // Assume update needed if version format is unexpected
-            } else {
-                current_parts < latest_parts
-            }
+
+    let check = match crate::methodology::check_for_update(channel) {
+        Ok(Some(check)) => check,
+        Ok(None) => {
+            println!("   {} No releases found on GitHub.", "!".yellow());
+            return Ok(());
+        }
+        Err(e) => {
+            println!("   {} Could not fetch latest version.", "!".yellow());
+            println!("   Reason: {}", e);
+            return Ok(());
         }
     };
+    let current_version = check
+        .current_version
+        .clone()
+        .unwrap_or_else(|| "none".to_string());
 
     if check_only {
         // Display current and latest versions
         println!("   Current version: {}", current_version.green());
-        println!("   Latest version: {}", latest_methodology_version.green());
+        println!("   Latest version: {}", check.latest_version.green());
 
-        if needs_update {
+        if check.update_available {
             println!("   {} Update available!", "✓".green());
-            
+
+            print_release_changes(&check);
             if verbose {
-                println!("\nThis will update:");
-                println!("   • Project ROTD methodology to v{}", latest_methodology_version);
-                println!("   • Documentation templates and examples");
-                println!("   • Schema definitions and validation rules");
-                println!("   • Primer strategy templates");
+                println!("\nSee more: {}", check.release.html_url.cyan().underline());
             }
         } else {
             println!("   {} You have the latest version.", "✓".green());
@@ -125,25 +178,26 @@ pub fn update(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
     }
 
     // Check if update is available
-    if !needs_update {
+    if !check.update_available && !force {
         println!("{}", "✓ You're already using the latest version!".green());
         return Ok(());
     }
 
     println!("{}", "✓ Update available!".green().bold());
     println!("   Current version: {}", current_version);
-    println!("   Latest version: {}", latest_methodology_version);
+    println!("   Latest version: {}", check.latest_version);
 
     // Show what will be updated
     println!("\nThis update will:");
-    println!("   • Update project ROTD methodology to v{}", latest_methodology_version);
-    println!("   • Refresh documentation and templates");
-    println!("   • Update schema definitions");
+    println!("   • Install methodology templates and schemas from v{}", check.latest_version);
+    println!("   • Keep files you edited locally, writing the new copy as <file>{}", crate::methodology::CONFLICT_SUFFIX);
     println!("   • Add primer strategy support if missing");
+    print_release_changes(&check);
 
     // Confirm update
-    if !yes {
-        if !dialoguer::Confirm::new()
+    if !yes
+        && !dry_run
+        && !dialoguer::Confirm::new()
             .with_prompt("Do you want to update now?")
             .default(true)
             .interact()?
@@ -152,113 +206,125 @@ pub fn update(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
             println!("You can update later with {}", "rotd update".cyan());
             return Ok(());
         }
-    }
 
     // Perform the update
     println!("\n{}", "Updating project ROTD methodology...".cyan());
-    
-    let rotd_dir = crate::common::rotd_path();
-    
-    // Update version.json
-    let new_version = ProjectVersion {
-        version: latest_methodology_version.to_string(),
-        updated_at: Some(chrono::Utc::now()),
-        manifest_hash: None,
-    };
-    write_json(&version_path, &new_version)?;
-    println!("   ✓ Updated version.json to v{}", latest_methodology_version);
-    
-    // Add primer strategy if missing
-    let primer_path = rotd_dir.join("primer.jsonc");
-    if !primer_path.exists() {
-        println!("   ✓ Adding primer strategy support...");
-        
-        // Get project name from current directory
-        let current_dir = std::env::current_dir()?;
-        let project_name = current_dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Project")
-            .to_string();
-        
-        // Create basic primer structure
-        let primer = ProjectPrimer {
-            name: project_name.clone(),
-            scope: "root".to_string(),
-            description: "TODO: Add project description".to_string(),
-            status: "active".to_string(),
-            language: "TODO: Specify primary language".to_string(),
-            entry_points: vec!["TODO: Add entry points".to_string()],
-            test_dirs: vec!["tests/".to_string(), "test/".to_string()],
-            dependencies: vec!["TODO: List key dependencies".to_string()],
-            known_issues: vec!["TODO: Document any known issues".to_string()],
-            key_concepts: vec!["TODO: Add key concepts".to_string()],
-            preferred_agents: Some(vec!["Claude Sonnet".to_string(), "Claude Opus".to_string()]),
-            suggested_starting_points: vec![
-                "TODO: Add suggested starting points for new developers or agents".to_string()
-            ],
-            major_components: None,
-            update_triggers: Some(vec![
-                "Major architectural changes".to_string(),
-                "New features or significant functionality changes".to_string(),
-                "Documentation updates".to_string()
-            ]),
-        };
-        
-        // Write primer file with nice formatting
-        let primer_json = serde_json::to_string_pretty(&primer)?;
-        std::fs::write(&primer_path, primer_json)?;
+
+    let outcome = crate::methodology::install(&check, force, skip_verify, dry_run)?;
+    if skip_verify {
+        println!(
+            "   {} Skipping checksum and signature verification (--skip-verify)",
+            "!".yellow()
+        );
+    } else {
+        println!("   {} Checksum verified", "✓".green());
+        if outcome.signature_verified {
+            println!("   {} Signature verified", "✓".green());
+        }
+    }
+
+    for file in &outcome.files {
+        match file.action.as_str() {
+            "added" => println!("   {} Added {}", "+".green(), file.path),
+            "updated" => println!("   {} Updated {}", "✓".green(), file.path),
+            "conflict" => println!(
+                "   {} Kept local {} (new version in {}{})",
+                "!".yellow(),
+                file.path,
+                file.path,
+                crate::methodology::CONFLICT_SUFFIX
+            ),
+            _ if verbose => println!("   • Unchanged {}", file.path),
+            _ => {}
+        }
+    }
+    if outcome.primer_created {
         println!("   ✓ Created primer.jsonc template");
     }
-    
-    // Generate update manifest for tracking
-    let manifest = UpdateManifest {
-        version: latest_methodology_version.to_string(),
-        date: chrono::Utc::now().to_rfc3339(),
-        previous_version: current_version.clone(),
-        changes: vec![ChangeEntry {
-            change_type: "methodology_update".to_string(),
-            component: "rotd_project".to_string(),
-            description: format!("Updated ROTD methodology from {} to {}", current_version, latest_methodology_version),
-            breaking: false,
-            migration_required: false,
-        }],
-    };
-    
-    let manifest_path = rotd_dir.join("update_manifest.json");
-    write_json(&manifest_path, &manifest)?;
-    
+
+    if dry_run {
+        println!("\n{}", "Dry run: no files were written.".yellow());
+        return Ok(());
+    }
+
     println!("\n{}", "✓ Project methodology updated successfully!".green().bold());
-    println!("   Updated from: {}", current_version.yellow());
-    println!("   Updated to: {}", latest_methodology_version.green());
-    
-    if primer_path.exists() {
+    println!("   Updated from: {}", outcome.previous_version.yellow());
+    println!("   Updated to: {}", outcome.new_version.green());
+
+    let conflicts = outcome.conflicts();
+    if !conflicts.is_empty() {
+        println!(
+            "\n{} {} locally edited file(s) were not overwritten.",
+            "!".yellow(),
+            conflicts.len()
+        );
+        println!(
+            "   Merge the {} copies by hand, or rerun with {} to overwrite.",
+            crate::methodology::CONFLICT_SUFFIX,
+            "rotd update --force".cyan()
+        );
+    }
+
+    if outcome.primer_created {
         println!("\n{}", "📋 Primer Strategy Available".cyan());
         println!("   Use {} to customize your project primer", "rotd primer show".yellow());
         println!("   Use {} to validate primer accuracy", "rotd primer check".yellow());
     }
-    
+
     if verbose {
         println!("\n{}", "Files updated:".cyan());
-        println!("   • {}", version_path.display());
-        if primer_path.exists() {
-            println!("   • {}", primer_path.display());
-        }
-        println!("   • {}", manifest_path.display());
+        println!("   • {}", crate::common::version_path().display());
+        println!("   • {}", crate::common::update_manifest_path().display());
+        println!("   Bundle SHA-256: {}", outcome.bundle_sha256);
     }
 
     Ok(())
 }
 
+/// Print the changes between the project's version and the target release
+fn print_release_changes(check: &crate::methodology::UpdateCheck) {
+    let (breaking, other) = check.grouped_changes();
+    if breaking.is_empty() && other.is_empty() {
+        return;
+    }
+
+    if !breaking.is_empty() {
+        println!("\n{}", "Breaking changes:".red().bold());
+        for change in breaking {
+            let migration = if change.entry.migration_required {
+                " (migration required)".yellow().to_string()
+            } else {
+                String::new()
+            };
+            println!(
+                "   • [v{}] {}{}",
+                change.version, change.entry.description, migration
+            );
+        }
+    }
+    if !other.is_empty() {
+        println!("\n{}", "Other changes:".cyan());
+        for change in other {
+            println!("   • [v{}] {}", change.version, change.entry.description);
+        }
+    }
+}
+
 // Upgrades ROTD CLI binary to latest version
-pub fn upgrade(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
+pub fn upgrade(
+    check_only: bool,
+    yes: bool,
+    skip_verify: bool,
+    channel: github::ReleaseChannel,
+    verbose: bool,
+) -> Result<()> {
     // Get current binary version
     let current_version = env!("CARGO_PKG_VERSION");
 
     // Check for binary upgrades
     println!("{}", "Checking for ROTD CLI upgrades...".cyan());
 
-    let (upgrade_available, latest_release) = match github::check_update() {
+    let (upgrade_available, latest_release) = match github::check_update(channel) {
         Ok((available, release)) => (available, release),
         Err(e) => {
             println!("   {} Could not fetch latest version.", "!".yellow());
@@ -331,8 +397,8 @@ pub fn upgrade(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
     }
 
     // Confirm upgrade
-    if !yes {
-        if !dialoguer::Confirm::new()
+    if !yes
+        && !dialoguer::Confirm::new()
             .with_prompt("Do you want to upgrade now?")
             .default(true)
             .interact()?
@@ -341,7 +407,6 @@ pub fn upgrade(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
             println!("You can upgrade later with {}", "rotd upgrade".cyan());
             return Ok(());
         }
-    }
 
     // Download and install the new binary
     println!("\n{}", "Downloading and installing upgrade...".cyan());
@@ -354,24 +419,25 @@ pub fn upgrade(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
 
     // Download the binary
     println!("   Downloading from: {}", asset.browser_download_url);
-    let binary_data = github::download_binary(&asset.browser_download_url)?;
-
-    // Create temporary file for new binary
-    let temp_path = current_exe.with_extension("new");
-    std::fs::write(&temp_path, binary_data)?;
-
-    // Make it executable (Unix-like systems)
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(&temp_path)?.permissions();
-        perms.set_mode(0o755);
-        std::fs::set_permissions(&temp_path, perms)?;
+    let download = github::download_binary(&latest, &asset, skip_verify)?;
+    if skip_verify {
+        println!(
+            "   {} Skipping checksum and signature verification (--skip-verify)",
+            "!".yellow()
+        );
+    } else {
+        println!("   {} Checksum verified", "✓".green());
+        if download.signature_verified {
+            println!("   {} Signature verified", "✓".green());
+        }
+    }
+    if verbose {
+        println!("   SHA-256: {}", download.sha256);
     }
 
     // Replace the current binary
     println!("   Installing new binary...");
-    std::fs::rename(&temp_path, &current_exe)?;
+    crate::self_replace::replace_executable(&current_exe, &download.binary)?;
 
     println!(
         "\n{}",
@@ -384,9 +450,14 @@ pub fn upgrade(check_only: bool, yes: bool, verbose: bool) -> Result<()> {
 }
 
 // Displays version information in human-readable format
-pub fn version(project: bool, latest: bool, verbose: bool) -> Result<()> {
+pub fn version(
+    project: bool,
+    latest: bool,
+    channel: github::ReleaseChannel,
+    verbose: bool,
+) -> Result<()> {
     if project {
-        let version_path = crate::common::rotd_path().join("version.json");
+        let version_path = crate::common::version_path();
         let version = if version_path.exists() {
             let v: ProjectVersion = read_json(&version_path)?;
             v.version
@@ -407,7 +478,7 @@ pub fn version(project: bool, latest: bool, verbose: bool) -> Result<()> {
         }
     } else if latest {
         println!("Checking for latest version...");
-        match github::fetch_latest_release()? {
+        match github::fetch_latest_release(channel)? {
             Some(latest) => {
                 println!("Latest available version: {}", latest.version.green());
                 if verbose {
@@ -424,10 +495,10 @@ pub fn version(project: bool, latest: bool, verbose: bool) -> Result<()> {
         println!("ROTD CLI version: {}", cli_version.green());
 
         // Check project version if available
-        if let Ok(_) = crate::common::check_rotd_initialized() {
+        if crate::common::check_rotd_initialized().is_ok() {
             let initialized = true;
             if initialized {
-                let version_path = crate::common::rotd_path().join("version.json");
+                let version_path = crate::common::version_path();
                 let project_version = if version_path.exists() {
                     let v: ProjectVersion = read_json(&version_path)?;
                     v.version
@@ -441,7 +512,7 @@ pub fn version(project: bool, latest: bool, verbose: bool) -> Result<()> {
                 if verbose {
                     println!("\nChecking for updates...");
 
-                    match github::check_update() {
+                    match github::check_update(channel) {
                         Ok((update_available, latest_release)) => {
                             if let Some(latest) = latest_release {
                                 println!("Latest available version: {}", latest.version.green());
@@ -469,8 +540,20 @@ pub fn version(project: bool, latest: bool, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+// Displays the build metadata embedded in this binary
+pub fn build_info() -> Result<()> {
+    let info = &crate::build_info::BUILD_INFO;
+    println!("ROTD CLI version: {}", info.version.green());
+    println!("   Commit: {}", info.git_sha);
+    println!("   Built: {}", info.build_date);
+    println!("   Target: {}", info.target);
+    println!("   Compiler: {}", info.rustc_version);
+    println!("   Profile: {}", info.profile);
+    Ok(())
+}
+
 // Function to create initial files
-fn create_initial_files(verbose: bool) -> Result<()> {
+fn create_initial_files(profile: Option<&Profile>, verbose: bool) -> Result<()> {
     // Create basic task entry
     let initial_task = TaskEntry {
         id: "init".to_string(),
@@ -487,6 +570,10 @@ fn create_initial_files(verbose: bool) -> Result<()> {
         created: Some(chrono::Utc::now()),
         updated_at: Some(chrono::Utc::now()),
         completed: Some(chrono::Utc::now()),
+        pr_url: None,
+        assignee: None,
+        tags: Vec::new(),
+        schema: TASK_SCHEMA.to_string(),
     };
 
     if verbose {
@@ -502,6 +589,7 @@ fn create_initial_files(verbose: bool) -> Result<()> {
         current_task: Some("init".to_string()),
         status: "initialized".to_string(),
         deltas: None,
+        schema: SESSION_STATE_SCHEMA.to_string(),
     };
 
     if verbose {
@@ -534,10 +622,13 @@ fn create_initial_files(verbose: bool) -> Result<()> {
         println!("Creating version tracking...");
     }
 
-    write_json(&crate::common::rotd_path().join("version.json"), &version)?;
+    write_json(&crate::common::version_path(), &version)?;
 
     // Create default config
-    let config = crate::schema::RotdConfig::default();
+    let mut config = crate::schema::RotdConfig::default();
+    if let Some(profile) = profile {
+        profile.apply(&mut config);
+    }
     crate::history::save_config(&config)?;
 
     if verbose {
@@ -548,111 +639,67 @@ fn create_initial_files(verbose: bool) -> Result<()> {
 }
 
 // Human-friendly implementation of check with auto-fix functionality
-pub fn check(fix: bool, verbose: bool) -> Result<()> {
+pub fn check(fix: bool, ci: Option<f64>, dry_run: bool, verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     println!("{}", "ROTD Compliance Check".cyan().bold());
     println!();
 
-    let mut issues = Vec::new();
-    let mut score = 0;
-    let total_checks = 5;
-    let _fixed: Vec<String> = Vec::new();
-
-    // Check 1: Required files exist
-    let required_files = [
-        crate::common::tasks_path(),
-        crate::common::session_state_path(),
-        crate::common::coverage_history_path(),
-    ];
-
-    let files_exist = required_files.iter().all(|f| f.exists());
-    if files_exist {
-        println!("  {}", "✓ tasks.jsonl".green());
-        println!("  {}", "✓ session_state.json".green());
-        println!("  {}", "✓ coverage_history.json".green());
-        score += 1;
-    } else {
-        for file_path in &required_files {
-            if file_path.exists() {
-                println!(
-                    "  {}",
-                    format!("✓ {}", file_path.file_name().unwrap().to_string_lossy()).green()
-                );
-            } else {
-                println!(
-                    "  {}",
-                    format!("✗ {}", file_path.file_name().unwrap().to_string_lossy()).red()
-                );
+    let run = crate::checks::run()?;
+    run.log_waivers()?;
+
+    for result in &run.results {
+        let id = result.outcome.id;
+        if !result.outcome.passed() {
+            println!("  {}", format!("✗ {}", id).red());
+        } else if result.outcome.warnings().next().is_some() {
+            println!("  {}", format!("⚠ {} (warning)", id).yellow());
+        } else {
+            println!("  {}", format!("✓ {}", id).green());
+        }
+        if verbose {
+            for finding in &result.outcome.findings {
+                println!("    - {}", finding.message);
             }
         }
-        issues.push("Missing required files");
+        for waived in &result.waived {
+            println!(
+                "    {}",
+                format!(
+                    "~ {} (waived until {}: {})",
+                    waived.describe(),
+                    waived.expires,
+                    waived.reason
+                )
+                .dimmed()
+            );
+        }
     }
-
-    // Check 2: JSONL files are valid
-    let jsonl_valid = read_jsonl::<TaskEntry>(&crate::common::tasks_path()).is_ok();
-    if jsonl_valid {
-        score += 1;
-    } else {
-        println!("  {}", "✗ tasks.jsonl format".red());
-        issues.push("Invalid tasks.jsonl: Invalid JSON on line 16 in .rotd/tasks.jsonl");
+    for id in &run.disabled {
+        println!("  {}", format!("- {} (disabled)", id).dimmed());
     }
-
-    // Check 3: Test summaries exist for completed tasks
-    let tasks: Vec<TaskEntry> = read_jsonl(&crate::common::tasks_path()).unwrap_or_default();
-    let completed_tasks: Vec<_> = tasks
-        .iter()
-        .filter(|t| matches!(t.status, TaskStatus::Complete))
-        .collect();
-
-    let summaries_complete = completed_tasks
-        .iter()
-        .all(|t| crate::common::test_summary_file(&t.id).exists());
-
-    if summaries_complete {
-        score += 1;
-    } else {
-        let missing = completed_tasks
-            .iter()
-            .filter(|t| !crate::common::test_summary_file(&t.id).exists())
-            .collect::<Vec<_>>();
-
-        if !missing.is_empty() && verbose {
-            println!("  {}", "✗ Missing test summaries".red());
-            let _missing_count = missing.len();
-            for task in &missing {
-                println!(
-                    "    - Task {} is marked complete but has no test summary",
-                    task.id
-                );
-            }
-            issues.push("Missing test summaries for completed tasks");
-        }
+    for waiver in &run.expired_waivers {
+        println!(
+            "  {}",
+            format!(
+                "! Waiver for {} expired on {}: {}",
+                waiver.check, waiver.expires, waiver.reason
+            )
+            .yellow()
+        );
     }
-
-    // Check 4: No stubs remaining
-    let no_stubs = !pss::check_stubs_remaining();
-    if no_stubs {
-        score += 1;
-    } else {
-        if verbose {
-            println!("  {}", "✗ Stub code remaining".red());
-        }
-        issues.push("Stub code annotations remaining in project");
+    for id in &run.unknown_checks {
+        println!(
+            "  {}",
+            format!("! Unknown check '{}' in config", id).yellow()
+        );
     }
 
-    // Check 5: Session state is valid JSON
-    let session_valid = read_json::<SessionState>(&crate::common::session_state_path()).is_ok();
-    if session_valid {
-        score += 1;
-    } else {
-        if verbose {
-            println!("  {}", "✗ Invalid session_state.json".red());
-        }
-        issues.push("Invalid session state format");
-    }
+    let score = run.passed();
+    let total_checks = run.results.len();
+    let issues: Vec<&str> = run.failed().map(|r| r.outcome.id).collect();
 
-    let health_percentage = (score as f64 / total_checks as f64) * 100.0;
+    let health_percentage = run.health_percentage();
 
     println!();
     println!(
@@ -664,150 +711,67 @@ pub fn check(fix: bool, verbose: bool) -> Result<()> {
         println!();
         println!("Issues Found:");
         for (i, issue) in issues.iter().enumerate() {
-            println!("  {}. {}", i + 1, issue);
+            println!("  {}. {}", i + 1, crate::checks::description(issue));
+        }
+    }
+
+    let workload = crate::task::workload(&crate::checks::readable_tasks());
+    if !workload.is_empty() {
+        println!();
+        println!("Workload:");
+        for load in &workload {
+            let blocked = format!("{} blocked", load.blocked);
+            println!(
+                "  {:<16} {} open, {} in progress, {}",
+                load.assignee,
+                load.open,
+                load.in_progress,
+                if load.blocked > 0 { blocked.red() } else { blocked.normal() }
+            );
         }
     }
 
     // Apply fixes if requested
     if fix && !issues.is_empty() {
         println!();
-        println!("{}", "Auto-fixing issues...".cyan());
-
-        let mut fixed_any = false;
-
-        for issue in &issues {
-            if issue.contains("Missing required files") {
-                // Create missing files
-                for file_path in &required_files {
-                    if !file_path.exists() {
-                        match file_path.file_name().and_then(|f| f.to_str()) {
-                            Some("session_state.json") => {
-                                let session_state = SessionState {
-                                    session_id: "fix".to_string(),
-                                    timestamp: chrono::Utc::now(),
-                                    current_task: None,
-                                    status: "initialized".to_string(),
-                                    deltas: None,
-                                };
-                                if write_json(file_path, &session_state).is_ok() {
-                                    println!(
-                                        "  {}",
-                                        format!(
-                                            "✓ Created {}",
-                                            file_path.file_name().unwrap().to_string_lossy()
-                                        )
-                                        .green()
-                                    );
-                                    fixed_any = true;
-                                }
-                            }
-                            Some("coverage_history.json") => {
-                                let coverage_history = CoverageHistory {
-                                    floor: 70.0,
-                                    ratchet_threshold: 3.0,
-                                    history: Vec::new(),
-                                };
-                                if write_json(file_path, &coverage_history).is_ok() {
-                                    println!(
-                                        "  {}",
-                                        format!(
-                                            "✓ Created {}",
-                                            file_path.file_name().unwrap().to_string_lossy()
-                                        )
-                                        .green()
-                                    );
-                                    fixed_any = true;
-                                }
-                            }
-                            Some("tasks.jsonl") => {
-                                // Create empty file
-                                if std::fs::File::create(file_path).is_ok() {
-                                    println!(
-                                        "  {}",
-                                        format!(
-                                            "✓ Created {}",
-                                            file_path.file_name().unwrap().to_string_lossy()
-                                        )
-                                        .green()
-                                    );
-                                    fixed_any = true;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            } else if issue.contains("Invalid tasks.jsonl") {
-                // Attempt to fix invalid JSON in tasks.jsonl
-                if let Ok(content) = std::fs::read_to_string(&crate::common::tasks_path()) {
-                    let mut fixed_lines = Vec::new();
-                    let mut has_errors = false;
-                    let mut fixed_count = 0;
-
-                    for (_line_num, line) in content.lines().enumerate() {
-                        if line.trim().is_empty() {
-                            continue;
-                        }
-
-                        // Try to parse and re-serialize to fix formatting issues
-                        match serde_json::from_str::<serde_json::Value>(line) {
-                            Ok(value) => {
-                                if let Ok(fixed_line) = serde_json::to_string(&value) {
-                                    fixed_lines.push(fixed_line);
-                                } else {
-                                    has_errors = true;
-                                    fixed_lines.push(line.to_string());
-                                }
-                            }
-                            Err(_) => {
-                                // Try some basic fixes for common JSON errors
-                                let fixed = crate::agent::fix_common_json_errors(line);
-                                match serde_json::from_str::<serde_json::Value>(&fixed) {
-                                    Ok(value) => {
-                                        if let Ok(fixed_line) = serde_json::to_string(&value) {
-                                            fixed_lines.push(fixed_line);
-                                            fixed_count += 1;
-                                        } else {
-                                            has_errors = true;
-                                            fixed_lines.push(line.to_string());
-                                        }
-                                    }
-                                    Err(_) => {
-                                        has_errors = true;
-                                        fixed_lines.push(line.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
+        if dry_run {
+            println!("{}", "Auto-fix preview (dry run, nothing written):".cyan());
+        } else {
+            println!("{}", "Auto-fixing issues...".cyan());
+        }
 
-                    if !has_errors || fixed_count > 0 {
-                        // Create a backup first
-                        let backup_path = crate::common::rotd_path().join("tasks.jsonl.bak");
-                        if std::fs::copy(&crate::common::tasks_path(), &backup_path).is_ok() {
-                            // Write fixed content
-                            if std::fs::write(&crate::common::tasks_path(), fixed_lines.join("\n"))
-                                .is_ok()
-                            {
-                                println!(
-                                    "  {}",
-                                    format!(
-                                        "✓ Fixed JSON format in tasks.jsonl (fixed {} lines)",
-                                        fixed_count
-                                    )
-                                    .green()
-                                );
-                                fixed_any = true;
-                            }
-                        }
-                    }
+        let report = crate::checks::fix(&issues, dry_run)?;
+        for fixed in &report.fixed {
+            let mark = if dry_run { "~" } else { "✓" };
+            println!("  {}", format!("{} {}", mark, fixed.message).green());
+        }
+        for unfixed in &report.unfixed {
+            println!(
+                "  {}",
+                format!("! Not fixed: {}: {}", unfixed.issue, unfixed.reason).yellow()
+            );
+        }
+        if let Some(backup_dir) = &report.backup_dir {
+            println!("  Originals saved to {}", backup_dir.display().to_string().cyan());
+        }
+        for preview in &report.previews {
+            println!();
+            for line in preview.diff.lines() {
+                match line.chars().next() {
+                    Some('+') if !line.starts_with("+++") => println!("{}", line.green()),
+                    Some('-') if !line.starts_with("---") => println!("{}", line.red()),
+                    Some('@') => println!("{}", line.cyan()),
+                    _ => println!("{}", line),
                 }
             }
         }
+    }
 
-        if !fixed_any {
-            println!("  {}", "! Auto-fix not yet implemented".yellow());
-        }
+    if let Some(threshold) = ci {
+        let verdict = run.ci_verdict(threshold);
+        println!();
+        println!("{}", verdict.summary_line());
+        return verdict.into_result();
     }
 
     Ok(())
@@ -822,12 +786,17 @@ pub fn check_buckle_trigger(_verbose: bool) -> Result<()> {
         "Checking Buckle Mode trigger conditions...".cyan().bold()
     );
 
-    let triggered = false;
-    let reasons: Vec<String> = Vec::new();
+    let mut reasons: Vec<String> = Vec::new();
 
     // Check for compilation errors
     println!("Checking for compilation errors...");
-    // Implementation would check cargo/npm output for error count
+    let config = crate::history::load_config().unwrap_or_default();
+    reasons.extend(
+        crate::compile::run(&config)
+            .iter()
+            .filter(|o| !o.success)
+            .map(|o| format!("Compilation failed: {}", o.describe())),
+    );
 
     // Check task.jsonl integrity
     println!("Checking task tracking integrity...");
@@ -839,7 +808,12 @@ pub fn check_buckle_trigger(_verbose: bool) -> Result<()> {
 
     // Check session state
     println!("Checking session state currency...");
-    // Implementation would verify session_state.json is up to date
+    reasons.extend(
+        crate::output::stale_session_check()
+            .into_iter()
+            .map(|f| f.message),
+    );
+    let triggered = !reasons.is_empty();
 
     // Report findings
     if triggered {
@@ -871,7 +845,7 @@ pub fn enter_buckle_mode(task_id: &str, verbose: bool) -> Result<()> {
     );
 
     // Check if already in Buckle Mode
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if buckle_state_path.exists() {
         let state: BuckleModeState =
             serde_json::from_str(&std::fs::read_to_string(&buckle_state_path)?)?;
@@ -928,7 +902,7 @@ pub fn diagnose_buckle_mode(_verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         println!(
             "{}",
@@ -957,7 +931,18 @@ pub fn diagnose_buckle_mode(_verbose: bool) -> Result<()> {
 
     // Compilation status
     println!("\n{}", "Compilation Status:".cyan());
-    // Implementation would check cargo/npm build output
+    let config = crate::history::load_config().unwrap_or_default();
+    let outcomes = crate::compile::run(&config);
+    if outcomes.is_empty() {
+        println!("  No compile checks configured");
+    }
+    for outcome in &outcomes {
+        let mark = if outcome.success { "✓".green() } else { "✗".red() };
+        println!("  [{}] {}", mark, outcome.describe());
+        for error in outcome.errors.iter().take(5) {
+            println!("      {}", error.red());
+        }
+    }
 
     // Test status
     println!("\n{}", "Test Status:".cyan());
@@ -1001,7 +986,7 @@ pub fn fix_compilation(_verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         println!(
             "{}",
@@ -1046,7 +1031,7 @@ pub fn fix_artifacts(_verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         println!(
             "{}",
@@ -1091,7 +1076,7 @@ pub fn check_exit_criteria(_verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         println!(
             "{}",
@@ -1136,7 +1121,7 @@ pub fn exit_buckle_mode(_verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
 
     // Check Buckle Mode state
-    let buckle_state_path = crate::common::rotd_path().join("buckle_state.json");
+    let buckle_state_path = crate::common::buckle_state_path();
     if !buckle_state_path.exists() {
         println!("{}", "Not in Buckle Mode.".yellow());
         return Ok(());
@@ -1186,97 +1171,253 @@ pub fn exit_buckle_mode(_verbose: bool) -> Result<()> {
 }
 
 // Function to show task details
-pub fn show_task(task_id: &str, verbose: bool) -> Result<()> {
+pub fn show_task(
+    patterns: &[String],
+    assignee: Option<&str>,
+    tags: &[String],
+    history: bool,
+    verbose: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
-    let tasks = read_jsonl::<TaskEntry>(&crate::common::tasks_path())?;
+    let tasks = latest_tasks()?;
+    let all = ["*".to_string()];
+    let patterns = if patterns.is_empty() { &all[..] } else { patterns };
+    let (mut found, unmatched) = crate::task::resolve(patterns, &tasks);
+    if let Some(assignee) = assignee {
+        found.retain(|t| t.assignee.as_deref() == Some(assignee));
+        if found.is_empty() {
+            println!("No matching tasks assigned to {}", assignee);
+        }
+    }
+    if !tags.is_empty() && !found.is_empty() {
+        found.retain(|t| crate::task::has_tags(t, tags));
+        if found.is_empty() {
+            println!("No matching tasks tagged {}", tags.join(", "));
+        }
+    }
 
-    let task = tasks.iter().find(|t| t.id == task_id);
+    for pattern in &unmatched {
+        let what = if pattern.contains(['*', '?']) {
+            format!("No tasks match {}", pattern)
+        } else {
+            format!("Task {} not found", pattern)
+        };
+        println!("{}", what.red());
+    }
 
-    match task {
-        Some(task) => {
-            println!("{}", format!("Task {}", task_id).cyan().bold());
-            println!("  Title:       {}", task.title);
-            println!(
-                "  Status:      {}",
-                match task.status {
-                    TaskStatus::Pending => "Pending".yellow(),
-                    TaskStatus::InProgress => "In Progress".blue(),
-                    TaskStatus::Blocked => "Blocked".red(),
-                    TaskStatus::Complete => "Complete".green(),
-                    TaskStatus::Scaffolded => "Scaffolded".cyan(),
-                }
-            );
+    match found.as_slice() {
+        [] => {}
+        // A single task asked for by ID gets the full view
+        [task] if patterns.len() == 1 && !patterns[0].contains(['*', '?']) => {
+            show_task_details(task, verbose)?
+        }
+        _ => show_task_table(&found),
+    }
 
-            if let Some(priority) = &task.priority {
-                println!(
-                    "  Priority:    {}",
-                    match priority.as_str() {
-                        "urgent" => "Urgent".red().bold(),
-                        "high" => "High".red(),
-                        "medium" => "Medium".yellow(),
-                        "low" => "Low".green(),
-                        "deferred" => "Deferred".blue(),
-                        _ => priority.normal(),
-                    }
-                );
-            }
+    if history {
+        for task in &found {
+            show_task_history(&task.id)?;
+        }
+    }
 
-            if let Some(tests) = &task.tests {
-                println!("\nTests:");
-                for test in tests {
-                    println!("  - {}", test);
-                }
+    Ok(())
+}
+
+/// Chronological timeline of a task's history events
+fn show_task_history(task_id: &str) -> Result<()> {
+    let mut events = crate::history::read_task_history(task_id)?;
+    events.sort_by_key(|e| e.timestamp);
+
+    println!("\n{}", format!("History {}", task_id).cyan().bold());
+    if events.is_empty() {
+        println!("  No history recorded");
+        return Ok(());
+    }
+    let agent_width = events.iter().map(|e| e.agent_id.len()).max().unwrap_or(0);
+    for event in &events {
+        println!(
+            "  {}  {:<agent_width$}  {}",
+            event.timestamp.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+            event.agent_id,
+            crate::history::describe_event(event),
+            agent_width = agent_width
+        );
+        if let Some(comment) = &event.comment {
+            println!("  {:>16}  {}", "", comment.italic());
+        }
+    }
+    Ok(())
+}
+
+fn show_task_details(task: &TaskEntry, verbose: bool) -> Result<()> {
+    println!("{}", format!("Task {}", task.id).cyan().bold());
+    println!("  Title:       {}", task.title);
+    println!(
+        "  Status:      {}",
+        match task.status {
+            TaskStatus::Pending => "Pending".yellow(),
+            TaskStatus::InProgress => "In Progress".blue(),
+            TaskStatus::Blocked => "Blocked".red(),
+            TaskStatus::Complete => "Complete".green(),
+            TaskStatus::Scaffolded => "Scaffolded".cyan(),
+        }
+    );
+
+    if let Some(priority) = &task.priority {
+        println!(
+            "  Priority:    {}",
+            match priority.as_str() {
+                "urgent" => "Urgent".red().bold(),
+                "high" => "High".red(),
+                "medium" => "Medium".yellow(),
+                "low" => "Low".green(),
+                "deferred" => "Deferred".blue(),
+                _ => priority.normal(),
             }
+        );
+    }
 
-            if let Some(description) = &task.description {
-                println!("\nDescription:");
-                println!("{}", description);
+    if let Some(assignee) = &task.assignee {
+        println!("  Assignee:    {}", assignee);
+    }
+    if !task.tags.is_empty() {
+        println!("  Tags:        {}", task.tags.join(", "));
+    }
+
+    if let Some(tests) = &task.tests {
+        println!("\nTests:");
+        for test in tests {
+            println!("  - {}", test);
+        }
+    }
+
+    if let Some(description) = &task.description {
+        println!("\nDescription:");
+        println!("{}", description);
+    }
+
+    if verbose {
+        println!("\nTimestamps:");
+        if let Some(created) = &task.created {
+            println!("  Created:    {}", created);
+        }
+        if let Some(updated) = &task.updated_at {
+            println!("  Updated:    {}", updated);
+        }
+        if let Some(completed) = &task.completed {
+            println!("  Completed:  {}", completed);
+        }
+
+        let commits = crate::git::commits_for_task(&task.id);
+        if !commits.is_empty() {
+            println!("\nCommits:");
+            for commit in &commits {
+                println!(
+                    "  {} {} ({}, {})",
+                    &commit.sha[..commit.sha.len().min(8)],
+                    commit.subject,
+                    commit.author,
+                    commit.date.format("%Y-%m-%d")
+                );
             }
+        }
 
-            if verbose {
-                println!("\nTimestamps:");
-                if let Some(created) = &task.created {
-                    println!("  Created:    {}", created);
-                }
-                if let Some(updated) = &task.updated_at {
-                    println!("  Updated:    {}", updated);
-                }
-                if let Some(completed) = &task.completed {
-                    println!("  Completed:  {}", completed);
+        // Show test summary if available
+        let summary_path = crate::common::test_summary_file(&task.id);
+        if summary_path.exists() {
+            match read_json::<TestSummary>(&summary_path) {
+                Ok(summary) => {
+                    println!("\nTest Summary:");
+                    println!("  Total Tests: {}", summary.total_tests);
+                    println!("  Passed:      {}", summary.passed);
+                    println!("  Failed:      {}", summary.failed);
+                    println!(
+                        "  Pass Rate:   {:.1}%",
+                        (summary.passed as f64 / summary.total_tests as f64) * 100.0
+                    );
                 }
-
-                // Show test summary if available
-                let summary_path = crate::common::test_summary_file(&task.id);
-                if summary_path.exists() {
-                    match read_json::<TestSummary>(&summary_path) {
-                        Ok(summary) => {
-                            println!("\nTest Summary:");
-                            println!("  Total Tests: {}", summary.total_tests);
-                            println!("  Passed:      {}", summary.passed);
-                            println!("  Failed:      {}", summary.failed);
-                            println!(
-                                "  Pass Rate:   {:.1}%",
-                                (summary.passed as f64 / summary.total_tests as f64) * 100.0
-                            );
-                        }
-                        Err(_) => {
-                            println!("\nTest Summary: [Invalid format]");
-                        }
-                    }
+                Err(_) => {
+                    println!("\nTest Summary: [Invalid format]");
                 }
             }
         }
-        None => {
-            println!("{}", format!("Task {} not found", task_id).red());
-        }
     }
 
     Ok(())
 }
 
+/// One row per task for comparing several at once
+fn show_task_table(tasks: &[&TaskEntry]) {
+    let id_width = tasks.iter().map(|t| t.id.len()).max().unwrap_or(0).max(2);
+    let assignee_width = tasks
+        .iter()
+        .filter_map(|t| t.assignee.as_ref().map(|a| a.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max("Assignee".len());
+    println!(
+        "{}",
+        format!(
+            "{:<id_width$}  {:<11}  {:<8}  {:<assignee_width$}  {:>5}  {:>5}  {:<10}  Title",
+            "ID",
+            "Status",
+            "Priority",
+            "Assignee",
+            "Tests",
+            "PSS",
+            "Updated",
+            id_width = id_width,
+            assignee_width = assignee_width
+        )
+        .bold()
+    );
+
+    for task in tasks {
+        let label = |name: &str| format!("{:<11}", name);
+        let status = match task.status {
+            TaskStatus::Pending => label("Pending").yellow(),
+            TaskStatus::InProgress => label("In Progress").blue(),
+            TaskStatus::Blocked => label("Blocked").red(),
+            TaskStatus::Complete => label("Complete").green(),
+            TaskStatus::Scaffolded => label("Scaffolded").cyan(),
+        };
+        let pss = crate::pss::latest_score(&task.id)
+            .ok()
+            .flatten()
+            .map_or("-".to_string(), |s| s.score.to_string());
+        let mut title = task.title.clone();
+        if title.chars().count() > 50 {
+            title = title.chars().take(49).collect::<String>() + "…";
+        }
+
+        println!(
+            "{:<id_width$}  {}  {:<8}  {:<assignee_width$}  {:>5}  {:>5}  {:<10}  {}",
+            task.id,
+            status,
+            task.priority.as_ref().map_or("-", |p| p.as_str()),
+            task.assignee.as_deref().unwrap_or("-"),
+            task.tests.as_ref().map_or(0, |t| t.len()),
+            pss,
+            task.updated_at
+                .map_or("-".to_string(), |t| t.format("%Y-%m-%d").to_string()),
+            title,
+            id_width = id_width,
+            assignee_width = assignee_width
+        );
+    }
+    println!("\n{} task(s)", tasks.len());
+}
+
 // Function to list lessons learned
-pub fn show_lessons(tag: Option<&str>, verbose: bool) -> Result<()> {
+pub fn show_lessons(
+    tag: Option<&str>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    until: Option<chrono::DateTime<chrono::Utc>>,
+    limit: Option<usize>,
+    offset: usize,
+    verbose: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
     let lessons_path = crate::common::lessons_path();
@@ -1287,14 +1428,7 @@ pub fn show_lessons(tag: Option<&str>, verbose: bool) -> Result<()> {
     }
 
     let all_lessons = read_jsonl::<LessonLearned>(&lessons_path)?;
-
-    let filtered: Vec<_> = match tag {
-        Some(tag) => all_lessons
-            .into_iter()
-            .filter(|l| l.tags.iter().any(|t| t == tag))
-            .collect(),
-        None => all_lessons,
-    };
+    let filtered = crate::lessons::newest_first(all_lessons, tag, since, until);
 
     if filtered.is_empty() {
         println!(
@@ -1304,11 +1438,22 @@ pub fn show_lessons(tag: Option<&str>, verbose: bool) -> Result<()> {
         return Ok(());
     }
 
+    let total = filtered.len();
+    let page: Vec<_> = filtered
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    if page.is_empty() {
+        println!("No lessons past offset {} ({} found)", offset, total);
+        return Ok(());
+    }
+
     println!("{}", "Lessons Learned".cyan().bold());
     println!();
 
-    for (i, lesson) in filtered.iter().enumerate() {
-        println!("{}. {} ({})", i + 1, lesson.id.bold(), lesson.id);
+    for (i, lesson) in page.iter().enumerate() {
+        println!("{}. {} ({})", offset + i + 1, lesson.id.bold(), lesson.id);
 
         println!("   Problem: {}", lesson.diagnosis);
 
@@ -1327,44 +1472,70 @@ pub fn show_lessons(tag: Option<&str>, verbose: bool) -> Result<()> {
         println!();
     }
 
+    if page.len() < total {
+        println!(
+            "{}",
+            format!(
+                "Showing {}-{} of {} (use --offset {} for more)",
+                offset + 1,
+                offset + page.len(),
+                total,
+                offset + page.len()
+            )
+            .dimmed()
+        );
+    }
+
     Ok(())
 }
 
 // Function to show audit log
-pub fn show_audit(limit: usize, verbose: bool) -> Result<()> {
+pub fn show_audit(
+    limit: usize,
+    filter: &audit::AuditFilter,
+    format: &str,
+    verbose: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
-    let audit_path = crate::common::rotd_path().join("audit.log");
+    let entries = audit::read_entries()?;
+    let total = entries.len();
+    let matching: Vec<AuditEntry> = entries
+        .into_iter()
+        .filter(|entry| filter.matches(entry))
+        .collect();
+    let limited = &matching[..matching.len().min(limit)];
+
+    if format == "json" {
+        println!(
+            "{}",
+            serde_json::json!({
+                "total": total,
+                "matching": matching.len(),
+                "entries": limited,
+            })
+        );
+        return Ok(());
+    }
 
-    if !audit_path.exists() {
+    if total == 0 {
         println!("No audit entries yet.");
         return Ok(());
     }
-
-    let content = std::fs::read_to_string(&audit_path)?;
-    let mut entries = Vec::new();
-
-    for line in content.lines() {
-        if let Ok(entry) = serde_json::from_str::<AuditEntry>(line) {
-            entries.push(entry);
-        }
+    if limited.is_empty() {
+        println!("No audit entries match the filters ({} in the log).", total);
+        return Ok(());
     }
 
-    // Sort by timestamp, newest first
-    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-
-    // Take only the requested number of entries
-    let limited = if entries.len() > limit {
-        &entries[0..limit]
-    } else {
-        &entries
-    };
-
     println!(
         "{}",
-        format!("Audit Log (Last {} Entries)", limited.len())
-            .cyan()
-            .bold()
+        format!(
+            "Audit Log (Last {} of {} Entries)",
+            limited.len(),
+            matching.len()
+        )
+        .cyan()
+        .bold()
     );
     println!();
 
@@ -1396,51 +1567,48 @@ pub fn show_audit(limit: usize, verbose: bool) -> Result<()> {
 
 // Function for shell completions
 pub fn completions(shell: &str) -> Result<()> {
-    println!("Generating completions for {} shell...", shell);
-
-    // Implementation would generate shell completions
-
-    println!("{}", "✓ Completions generated.".green());
-
+    // Printed bare so the output can be eval'd or saved as-is
+    print!("{}", crate::completion::script(shell)?);
     Ok(())
 }
 
 // Function for validating schemas
-pub fn validate(all: bool, schema_type: Option<&str>, strict: bool, _verbose: bool) -> Result<()> {
+pub fn validate(
+    all: bool,
+    schema_type: Option<&str>,
+    strict: bool,
+    strict_parse: bool,
+    _verbose: bool,
+) -> Result<()> {
     check_rotd_initialized()?;
 
     println!("{}", "ROTD Schema Validation".cyan().bold());
 
     let mut passed = true;
 
-    if all || schema_type.is_none() || schema_type == Some("tasks") {
-        println!("\n{}", "Validating tasks.jsonl...".cyan());
-        match crate::agent::validate_tasks_jsonl(strict) {
-            Ok(result) => {
-                if result.status == "passed" {
-                    println!("  {}", "✓ tasks.jsonl validation passed".green());
-                    println!("    {} items checked", result.items_checked);
-                } else {
-                    passed = false;
-                    println!("  {}", "✗ tasks.jsonl validation failed".red());
-                    for error in &result.errors {
-                        println!("    - {}", error.red());
-                    }
-                    for warning in &result.warnings {
-                        println!("    - {}", warning.yellow());
-                    }
-                }
+    for report in crate::validate::run(all, schema_type, strict, strict_parse)? {
+        let name = report.outcome.id;
+        let result = report.to_result();
+        println!("\n{}", format!("Validating {}...", name).cyan());
+        if result.status == "passed" {
+            if report.items_checked == 0 {
+                println!("  {}", format!("- {}: nothing to check", name).dimmed());
+            } else {
+                println!("  {}", format!("✓ {} validation passed", name).green());
+                println!("    {} items checked", report.items_checked);
             }
-            Err(e) => {
-                passed = false;
-                println!("  {}", "✗ tasks.jsonl validation error".red());
-                println!("    {}", e);
+        } else {
+            passed = false;
+            println!("  {}", format!("✗ {} validation failed", name).red());
+            for error in &result.errors {
+                println!("    - {}", error.red());
             }
         }
+        for warning in &result.warnings {
+            println!("    - {}", warning.yellow());
+        }
     }
 
-    // Add validation for other schemas here
-
     if passed {
         println!("\n{}", "✓ All validations passed!".green().bold());
     } else {
@@ -1448,6 +1616,7 @@ pub fn validate(all: bool, schema_type: Option<&str>, strict: bool, _verbose: bo
         if strict {
             println!("  Run without --strict for more lenient validation");
         }
+        return Err(anyhow::anyhow!("Validation failed"));
     }
 
     Ok(())
@@ -1535,14 +1704,15 @@ pub fn score(task_id: &str, format: &str, verbose: bool) -> Result<()> {
                 println!("\nDetails:");
                 for (i, (key, criterion)) in score_result.criteria.iter().enumerate() {
                     println!(
-                        "{:2}. {} {}",
+                        "{:2}. {} {}: {}",
                         i + 1,
                         if criterion.score > 0 {
                             "✓".green()
                         } else {
                             "✗".red()
                         },
-                        format!("{}: {}", key, criterion.rationale)
+                        key,
+                        criterion.rationale
                     );
                 }
             }
@@ -1595,10 +1765,10 @@ pub fn show_help(verbose: bool) -> Result<()> {
 pub fn primer_init(force: bool, verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = crate::common::rotd_path().join("primer.jsonc");
+    let primer_path = crate::common::primer_path();
     
-    if primer_path.exists() && !force {
-        if !dialoguer::Confirm::new()
+    if primer_path.exists() && !force
+        && !dialoguer::Confirm::new()
             .with_prompt("Primer already exists. Overwrite?")
             .default(false)
             .interact()?
@@ -1606,7 +1776,6 @@ pub fn primer_init(force: bool, verbose: bool) -> Result<()> {
             println!("{}", "Primer initialization cancelled.".yellow());
             return Ok(());
         }
-    }
     
     println!("{}", "Initializing project primer...".cyan());
     
@@ -1617,46 +1786,27 @@ pub fn primer_init(force: bool, verbose: bool) -> Result<()> {
         .unwrap_or("unknown")
         .to_string();
     
-    // Detect language based on files present
-    let language = if std::path::Path::new("Cargo.toml").exists() {
-        "Rust"
-    } else if std::path::Path::new("package.json").exists() {
-        "JavaScript/TypeScript"
-    } else if std::path::Path::new("requirements.txt").exists() || std::path::Path::new("setup.py").exists() {
-        "Python"
-    } else {
-        "Unknown"
-    };
-    
-    // Find entry points
-    let entry_points = match language {
-        "Rust" => vec!["src/main.rs", "src/lib.rs"],
-        "JavaScript/TypeScript" => vec!["index.js", "src/index.js", "src/main.ts"],
-        "Python" => vec!["main.py", "app.py", "__main__.py"],
-        _ => vec!["main"],
-    }.into_iter().filter(|&path| std::path::Path::new(path).exists()).map(|s| s.to_string()).collect();
-    
-    // Find test directories
-    let test_dirs = vec!["tests/", "test/", "spec/", "src/"]
-        .into_iter()
-        .filter(|&path| std::path::Path::new(path).exists())
-        .map(|s| s.to_string())
-        .collect();
+    // Language, entry points, and test directories from the init profile,
+    // or detected from the files present
+    let config = crate::history::load_config()?;
+    let (language, entry_points, test_dirs) = crate::profile::primer_defaults(&config);
+    // Components discovered from the source tree, for the developer to refine
+    let components = crate::primer::discover_components(&config, &entry_points);
     
     let primer = ProjectPrimer {
         name: project_name,
         scope: "root".to_string(),
         description: "TODO: Add project description".to_string(),
         status: "active".to_string(),
-        language: language.to_string(),
+        language,
         entry_points,
         test_dirs,
-        dependencies: vec![], // TODO: Could parse from Cargo.toml, package.json, etc.
+        dependencies: crate::primer::project_dependencies().unwrap_or_default(),
         known_issues: vec![],
         key_concepts: vec![],
         preferred_agents: Some(vec!["Claude Sonnet".to_string()]),
         suggested_starting_points: vec!["TODO: Add suggested starting points".to_string()],
-        major_components: None,
+        major_components: Some(components.clone()).filter(|c| !c.is_empty()),
         update_triggers: Some(vec![
             "Major architectural changes".to_string(),
             "New dependencies added".to_string(),
@@ -1664,30 +1814,37 @@ pub fn primer_init(force: bool, verbose: bool) -> Result<()> {
         ]),
     };
     
-    // Convert to pretty JSON
-    let json_content = serde_json::to_string_pretty(&primer)?;
-    std::fs::write(&primer_path, json_content)?;
+    // Comments from a primer replaced with --force are kept
+    crate::primer::save(&primer_path, &primer)?;
     
     println!("{}", "✓ Primer initialized successfully!".green());
     println!("   Location: {}", primer_path.display().to_string().cyan());
+    if !components.is_empty() {
+        println!(
+            "   Components: {} discovered ({})",
+            components.len(),
+            components.keys().cloned().collect::<Vec<_>>().join(", ")
+        );
+    }
     
     if verbose {
         println!("\nNext steps:");
         println!("  1. Edit {} to add project description", primer_path.display());
         println!("  2. Add key concepts and dependencies");
-        println!("  3. Update suggested starting points");
+        println!("  3. Update suggested starting points and refine the discovered components");
         println!("  4. Run {} to validate", "rotd primer check".cyan());
     }
     
     Ok(())
 }
 
-pub fn primer_show(file: Option<&str>, verbose: bool) -> Result<()> {
+pub fn primer_show(file: Option<&str>, scope: Option<&str>, format: &str, verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = match file {
-        Some(f) => crate::common::rotd_path().join(f),
-        None => crate::common::rotd_path().join("primer.jsonc"),
+    let primer_path = match (file, scope) {
+        (Some(f), _) => crate::common::shared_path().join(f),
+        (None, Some(scope)) => crate::primer::find_scope(scope)?,
+        (None, None) => crate::common::primer_path(),
     };
     
     if !primer_path.exists() {
@@ -1696,7 +1853,11 @@ pub fn primer_show(file: Option<&str>, verbose: bool) -> Result<()> {
         return Ok(());
     }
     
-    let content = std::fs::read_to_string(&primer_path)?;
+    let content = if format == "md" {
+        crate::primer::to_markdown(&crate::primer::load(&primer_path)?)
+    } else {
+        std::fs::read_to_string(&primer_path)?
+    };
     
     if verbose {
         println!("{}", format!("Primer: {}", primer_path.display()).cyan().bold());
@@ -1711,7 +1872,7 @@ pub fn primer_show(file: Option<&str>, verbose: bool) -> Result<()> {
 pub fn primer_check(verbose: bool) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = crate::common::rotd_path().join("primer.jsonc");
+    let primer_path = crate::common::primer_path();
     
     if !primer_path.exists() {
         println!("{}", "✗ No primer.jsonc found".red());
@@ -1723,7 +1884,7 @@ pub fn primer_check(verbose: bool) -> Result<()> {
     
     // Try to parse the primer
     let content = std::fs::read_to_string(&primer_path)?;
-    let primer: ProjectPrimer = serde_json::from_str(&content)
+    let primer: ProjectPrimer = crate::history::parse_jsonc(&content)
         .map_err(|e| anyhow::anyhow!("Failed to parse primer.jsonc: {}", e))?;
     
     let mut issues = Vec::new();
@@ -1776,6 +1937,15 @@ pub fn primer_check(verbose: bool) -> Result<()> {
         }
     }
     
+    let (_, drift) = crate::primer::project_drift(&primer, &[])?;
+    if !drift.is_empty() {
+        println!("{}", "Drift from the current project:".yellow());
+        for change in &drift {
+            println!("  {}", change);
+        }
+        println!("Run {} to apply.", "rotd primer update".cyan());
+    }
+    
     if verbose {
         println!("\nPrimer summary:");
         println!("  Name: {}", primer.name);
@@ -1788,50 +1958,124 @@ pub fn primer_check(verbose: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn primer_parse(format: &str, verbose: bool) -> Result<()> {
+pub fn primer_update(fields: &[String], yes: bool, dry_run: bool) -> Result<()> {
     check_rotd_initialized()?;
     
-    let primer_path = crate::common::rotd_path().join("primer.jsonc");
-    
+    let primer_path = crate::common::primer_path();
     if !primer_path.exists() {
+        println!("{}", "✗ No primer.jsonc found".red());
+        println!("Run {} to create one.", "rotd primer init".cyan());
+        return Ok(());
+    }
+    
+    let mut primer = crate::primer::load(&primer_path)?;
+    let (state, changes) = crate::primer::project_drift(&primer, fields)?;
+    
+    if changes.is_empty() {
+        println!("{}", "✓ Primer is up to date with the project".green());
+        return Ok(());
+    }
+    
+    println!("{}", "Primer changes:".cyan());
+    for change in &changes {
+        let line = change.to_string();
+        match change.action {
+            crate::primer::ChangeAction::Add => println!("  {}", line.green()),
+            crate::primer::ChangeAction::Remove => println!("  {}", line.red()),
+        }
+    }
+    
+    if dry_run {
+        println!("{}", "DRY RUN MODE - No changes will be made".yellow().bold());
+        return Ok(());
+    }
+    
+    if !yes
+        && !dialoguer::Confirm::new()
+            .with_prompt(format!("Apply {} change(s) to the primer?", changes.len()))
+            .default(false)
+            .interact()?
+    {
+        println!("{}", "Primer update cancelled.".yellow());
+        return Ok(());
+    }
+    
+    crate::primer::apply_changes(&mut primer, &state, &changes);
+    crate::primer::save(&primer_path, &primer)?;
+    audit::log_info(
+        None,
+        "PRIMER_UPDATE",
+        &format!("Applied {} primer change(s)", changes.len()),
+    )?;
+    
+    println!("{}", format!("✓ Applied {} change(s) to the primer", changes.len()).green());
+    Ok(())
+}
+
+pub fn primer_parse(format: &str, task_id: Option<&str>, verbose: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    
+    let mut primers: Vec<ProjectPrimer> =
+        crate::primer::load_all()?.into_iter().map(|(_, p)| p).collect();
+    
+    if primers.is_empty() {
         println!("{}", "No primer.jsonc found".red());
         return Ok(());
     }
     
-    let content = std::fs::read_to_string(&primer_path)?;
-    let primer: ProjectPrimer = serde_json::from_str(&content)?;
+    if let Some(task_id) = task_id {
+        let task = latest_tasks()?
+            .into_iter()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+        primers.retain(|p| crate::primer::is_relevant(p, &task));
+    }
     
     match format {
         "json" => {
-            println!("{}", serde_json::to_string_pretty(&primer)?);
+            // Several primers print as an array
+            if let [primer] = primers.as_slice() {
+                println!("{}", serde_json::to_string_pretty(primer)?);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&primers)?);
+            }
         }
         "summary" => {
-            println!("{}", format!("Project: {}", primer.name).cyan().bold());
-            println!("Description: {}", primer.description);
-            println!("Language: {}", primer.language);
-            
-            if !primer.key_concepts.is_empty() {
-                println!("\nKey Concepts:");
-                for concept in &primer.key_concepts {
-                    println!("  - {}", concept);
+            for (i, primer) in primers.iter().enumerate() {
+                if i > 0 {
+                    println!();
                 }
-            }
-            
-            if !primer.suggested_starting_points.is_empty() {
-                println!("\nSuggested Starting Points:");
-                for point in &primer.suggested_starting_points {
-                    println!("  - {}", point);
+                if primer.scope == crate::primer::ROOT_SCOPE {
+                    println!("{}", format!("Project: {}", primer.name).cyan().bold());
+                } else {
+                    println!("{}", format!("Scope: {} ({})", primer.scope, primer.name).cyan().bold());
+                }
+                println!("Description: {}", primer.description);
+                println!("Language: {}", primer.language);
+                
+                if !primer.key_concepts.is_empty() {
+                    println!("\nKey Concepts:");
+                    for concept in &primer.key_concepts {
+                        println!("  - {}", concept);
+                    }
                 }
-            }
-            
-            if verbose {
-                println!("\nEntry Points: {}", primer.entry_points.join(", "));
-                println!("Test Directories: {}", primer.test_dirs.join(", "));
                 
-                if !primer.known_issues.is_empty() {
-                    println!("\nKnown Issues:");
-                    for issue in &primer.known_issues {
-                        println!("  - {}", issue);
+                if !primer.suggested_starting_points.is_empty() {
+                    println!("\nSuggested Starting Points:");
+                    for point in &primer.suggested_starting_points {
+                        println!("  - {}", point);
+                    }
+                }
+                
+                if verbose {
+                    println!("\nEntry Points: {}", primer.entry_points.join(", "));
+                    println!("Test Directories: {}", primer.test_dirs.join(", "));
+                    
+                    if !primer.known_issues.is_empty() {
+                        println!("\nKnown Issues:");
+                        for issue in &primer.known_issues {
+                            println!("  - {}", issue);
+                        }
                     }
                 }
             }
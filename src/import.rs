@@ -0,0 +1,530 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use std::path::Path;
+
+use crate::ImportCommands;
+use crate::audit;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{append_tasks, latest_tasks, safe_update_task};
+use crate::schema::{TASK_SCHEMA, TaskEntry, TaskStatus};
+
+/// Formats accepted by `rotd import tasks`
+pub const TASK_FORMATS: &[&str] = &["json", "csv", "yaml"];
+
+/// CSV columns holding lists, written as `;`-separated values
+const CSV_LIST_COLUMNS: &[&str] = &["tests", "depends_on", "tags"];
+
+pub fn handle_command(cmd: ImportCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        ImportCommands::Markdown { file } => cmd_markdown(&file, dry_run, is_agent_mode),
+        ImportCommands::Tasks { file, format } => {
+            cmd_tasks(&file, format.as_deref(), dry_run, is_agent_mode)
+        }
+        ImportCommands::Bundle { file, force } => {
+            crate::bundle::cmd_import(&file, force, dry_run, is_agent_mode)
+        }
+    }
+}
+
+/// A checkbox item from a markdown plan, at its nesting depth
+#[derive(Debug, Clone)]
+pub struct PlanItem {
+    pub depth: usize,
+    pub task: TaskEntry,
+}
+
+/// `- [ ] text` / `* [x] text` / `1. [ ] text`, as (indent, checked, text)
+fn checkbox(line: &str) -> Option<(usize, bool, &str)> {
+    let indent: usize = line
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+    let rest = line.trim_start();
+    let rest = match rest.strip_prefix(['-', '*', '+']) {
+        Some(rest) => rest,
+        None => {
+            let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+            rest[digits..]
+                .strip_prefix(['.', ')'])
+                .filter(|_| digits > 0)?
+        }
+    };
+    let rest = rest.strip_prefix(' ')?.trim_start();
+    let (checked, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (false, text)
+    } else if let Some(text) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, text)
+    } else {
+        return None;
+    };
+    Some((indent, checked, text.trim()))
+}
+
+/// Parse a markdown plan into tasks. Each heading that has checkbox items
+/// becomes a phase numbered from 1, its top-level items `<phase>.<n>`, and
+/// nested items `<parent>.<n>`. A parent depends on its nested items.
+pub fn parse_plan(source: &str, content: &str) -> Vec<PlanItem> {
+    let now = Utc::now();
+    let mut items: Vec<PlanItem> = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut phase = 0;
+    let mut phase_started = false;
+    let mut top_level = 0;
+    let mut in_fence = false;
+    // (indent, index into items, children so far) for the open parents
+    let mut stack: Vec<(usize, usize, usize)> = Vec::new();
+
+    for (line_num, line) in content.lines().enumerate() {
+        // Nothing inside fenced code blocks is part of the plan
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let hashes = line.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && line[hashes..].starts_with(' ') {
+            let title = line[hashes..].trim().trim_end_matches('#').trim_end();
+            if !title.is_empty() {
+                heading = Some(title.to_string());
+                phase_started = false;
+                stack.clear();
+            }
+            continue;
+        }
+        let Some((indent, checked, text)) = checkbox(line) else {
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+        if !phase_started {
+            phase += 1;
+            phase_started = true;
+            top_level = 0;
+        }
+
+        while stack.last().is_some_and(|(open, _, _)| *open >= indent) {
+            stack.pop();
+        }
+        let id = match stack.last_mut() {
+            Some((_, parent, children)) => {
+                *children += 1;
+                format!("{}.{}", items[*parent].task.id, children)
+            }
+            None => {
+                top_level += 1;
+                format!("{}.{}", phase, top_level)
+            }
+        };
+        if let Some((_, parent, _)) = stack.last() {
+            items[*parent]
+                .task
+                .depends_on
+                .get_or_insert_with(Vec::new)
+                .push(id.clone());
+        }
+
+        items.push(PlanItem {
+            depth: stack.len(),
+            task: TaskEntry {
+                id,
+                title: text.to_string(),
+                status: if checked {
+                    TaskStatus::Complete
+                } else {
+                    TaskStatus::Pending
+                },
+                tests: None,
+                description: None,
+                summary_file: None,
+                origin: Some(format!("{}:{}", source, line_num + 1)),
+                phase: heading.clone(),
+                depends_on: None,
+                priority: None,
+                priority_score: None,
+                created: Some(now),
+                updated_at: Some(now),
+                completed: checked.then_some(now),
+                pr_url: None,
+                assignee: None,
+                tags: Vec::new(),
+                schema: TASK_SCHEMA.to_string(),
+            },
+        });
+        stack.push((indent, items.len() - 1, 0));
+    }
+
+    items
+}
+
+fn cmd_markdown(file: &Path, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let items = parse_plan(&file.display().to_string(), &content);
+
+    // Re-importing the same plan yields the same IDs; those are skipped
+    let existing: Vec<String> = latest_tasks()?.into_iter().map(|t| t.id).collect();
+    let (skipped, new): (Vec<&PlanItem>, Vec<&PlanItem>) = items
+        .iter()
+        .partition(|item| existing.contains(&item.task.id));
+
+    if !dry_run {
+        for item in &new {
+            safe_update_task(&item.task, false)?;
+        }
+        if !new.is_empty() {
+            audit::log_info(
+                None,
+                "IMPORT_MARKDOWN",
+                &format!("Imported {} task(s) from {}", new.len(), file.display()),
+            )?;
+        }
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "import_markdown",
+                "file": file.display().to_string(),
+                "dry_run": dry_run,
+                "imported": new.iter().map(|i| &i.task).collect::<Vec<_>>(),
+                "skipped": skipped.iter().map(|i| &i.task.id).collect::<Vec<_>>(),
+            })
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "{}",
+            "DRY RUN MODE - No changes will be made".yellow().bold()
+        );
+    }
+    println!(
+        "Found {} checklist item(s) in {}",
+        items.len(),
+        file.display()
+    );
+    let mut phase = None;
+    for item in &new {
+        if item.task.phase.is_some() && item.task.phase != phase {
+            phase = item.task.phase.clone();
+            println!("  {}", phase.as_deref().unwrap_or_default().cyan().bold());
+        }
+        let mark = match item.task.status {
+            TaskStatus::Complete => "[x]",
+            _ => "[ ]",
+        };
+        let deps = match &item.task.depends_on {
+            Some(deps) => format!(" (depends on {})", deps.join(", ")),
+            None => String::new(),
+        };
+        println!(
+            "  {}{} {} {} {}{}",
+            "  ".repeat(item.depth),
+            "+".green(),
+            mark,
+            item.task.id.bold(),
+            item.task.title,
+            deps.dimmed()
+        );
+    }
+    if !skipped.is_empty() {
+        println!(
+            "  {} {} already imported: {}",
+            "•".yellow(),
+            skipped.len(),
+            skipped
+                .iter()
+                .map(|i| i.task.id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// A rejected row from a bulk import; `row` is the CSV line number, or the
+/// 1-based position in a JSON or YAML array
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RowError {
+    pub row: usize,
+    pub id: Option<String>,
+    pub error: String,
+}
+
+/// Format named by the file extension
+fn detect_format(file: &Path) -> Option<&'static str> {
+    match file.extension()?.to_str()?.to_lowercase().as_str() {
+        "json" => Some("json"),
+        "csv" => Some("csv"),
+        "yaml" | "yml" => Some("yaml"),
+        _ => None,
+    }
+}
+
+/// Split a file into raw task records, each with its row number
+pub fn parse_rows(content: &str, format: &str) -> Result<Vec<(usize, serde_json::Value)>> {
+    let records: Vec<serde_json::Value> = match format {
+        "json" => serde_json::from_str(content).context("Expected a JSON array of tasks")?,
+        "yaml" => serde_yaml::from_str(content).context("Expected a YAML sequence of tasks")?,
+        "csv" => return csv_rows(content),
+        other => anyhow::bail!("Unsupported import format: {}", other),
+    };
+    Ok(records
+        .into_iter()
+        .enumerate()
+        .map(|(i, record)| (i + 1, record))
+        .collect())
+}
+
+/// CSV rows as task records keyed by the header row. Empty cells are left
+/// out, list columns split on `;`, and `priority_score` read as a number.
+fn csv_rows(content: &str) -> Result<Vec<(usize, serde_json::Value)>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().context("Failed to read CSV header")?.clone();
+
+    let mut rows = Vec::new();
+    for result in reader.records() {
+        let record = result.context("Malformed CSV")?;
+        let line = record.position().map_or(0, |p| p.line() as usize);
+        let mut fields = serde_json::Map::new();
+        for (column, value) in headers.iter().zip(record.iter()) {
+            if value.is_empty() {
+                continue;
+            }
+            let value = if CSV_LIST_COLUMNS.contains(&column) {
+                value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .collect::<Vec<_>>()
+                    .into()
+            } else if column == "priority_score" {
+                value
+                    .parse::<f64>()
+                    .map_or_else(|_| value.into(), serde_json::Value::from)
+            } else {
+                value.into()
+            };
+            fields.insert(column.to_string(), value);
+        }
+        rows.push((line, serde_json::Value::Object(fields)));
+    }
+    Ok(rows)
+}
+
+/// Turn one record into a task, defaulting status to pending and stamping
+/// created/updated times that are missing
+fn parse_task(mut record: serde_json::Value, now: chrono::DateTime<Utc>) -> Result<TaskEntry> {
+    let fields = record
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("Expected an object"))?;
+    fields.entry("status").or_insert_with(|| "pending".into());
+
+    let unknown = crate::json_schema::unknown_fields("tasks", &record);
+    if !unknown.is_empty() {
+        anyhow::bail!("Unknown field(s): {}", unknown.join(", "));
+    }
+    let mut task: TaskEntry = serde_json::from_value(record)?;
+    task.validate()?;
+    task.created.get_or_insert(now);
+    task.updated_at.get_or_insert(now);
+    if task.status == TaskStatus::Complete {
+        task.completed.get_or_insert(now);
+    }
+    Ok(task)
+}
+
+/// Validate every record, accepting new tasks and rejecting bad rows, IDs
+/// already in tasks.jsonl, and IDs repeated within the file
+pub fn validate_rows(
+    rows: Vec<(usize, serde_json::Value)>,
+    existing: &[String],
+) -> (Vec<TaskEntry>, Vec<RowError>) {
+    let now = Utc::now();
+    let mut accepted: Vec<TaskEntry> = Vec::new();
+    let mut errors = Vec::new();
+    for (row, record) in rows {
+        let id = record.get("id").and_then(|v| v.as_str()).map(str::to_string);
+        let result = parse_task(record, now).and_then(|task| {
+            if existing.contains(&task.id) {
+                anyhow::bail!("Task {} already exists", task.id);
+            }
+            if accepted.iter().any(|t| t.id == task.id) {
+                anyhow::bail!("Task {} appears more than once", task.id);
+            }
+            Ok(task)
+        });
+        match result {
+            Ok(task) => accepted.push(task),
+            Err(e) => errors.push(RowError {
+                row,
+                id,
+                error: e.to_string(),
+            }),
+        }
+    }
+    (accepted, errors)
+}
+
+fn cmd_tasks(file: &Path, format: Option<&str>, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let format = format.or_else(|| detect_format(file)).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Cannot tell the format of {}; pass --format ({})",
+            file.display(),
+            TASK_FORMATS.join(", ")
+        )
+    })?;
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let rows = parse_rows(&content, format)?;
+    let total = rows.len();
+
+    let existing: Vec<String> = latest_tasks()?.into_iter().map(|t| t.id).collect();
+    let (accepted, errors) = validate_rows(rows, &existing);
+
+    if !dry_run && !accepted.is_empty() {
+        append_tasks(&accepted, None)?;
+        audit::log_info(
+            None,
+            "IMPORT_TASKS",
+            &format!(
+                "Imported {} of {} task(s) from {}",
+                accepted.len(),
+                total,
+                file.display()
+            ),
+        )?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "import_tasks",
+                "file": file.display().to_string(),
+                "format": format,
+                "dry_run": dry_run,
+                "total": total,
+                "imported": accepted.iter().map(|t| &t.id).collect::<Vec<_>>(),
+                "errors": errors,
+            })
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "{}",
+            "DRY RUN MODE - No changes will be made".yellow().bold()
+        );
+    }
+    for task in &accepted {
+        println!("  {} {} {}", "+".green(), task.id.bold(), task.title);
+    }
+    for error in &errors {
+        println!(
+            "  {} row {}{}: {}",
+            "✗".red(),
+            error.row,
+            error.id.as_deref().map_or(String::new(), |id| format!(" ({})", id)),
+            error.error
+        );
+    }
+    println!(
+        "{} {} of {} task(s) from {}{}",
+        if dry_run { "Would import" } else { "✓ Imported" },
+        accepted.len(),
+        total,
+        file.display(),
+        if errors.is_empty() {
+            String::new()
+        } else {
+            format!("; {} row(s) rejected", errors.len())
+        }
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plan() {
+        let plan = "# Plan\n\nIntro text.\n\n## Parser\n- [x] Tokenizer\n- [ ] Grammar\n  - [ ] Expressions\n  - [x] Statements\n    * [ ] Loops\n- plain bullet\n\n```\n- [ ] not a task\n# not a heading\n```\n## Release\n1. [ ] Changelog\n";
+        let items = parse_plan("PLAN.md", plan);
+
+        let summary: Vec<(&str, &str, usize)> = items
+            .iter()
+            .map(|i| (i.task.id.as_str(), i.task.title.as_str(), i.depth))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("1.1", "Tokenizer", 0),
+                ("1.2", "Grammar", 0),
+                ("1.2.1", "Expressions", 1),
+                ("1.2.2", "Statements", 1),
+                ("1.2.2.1", "Loops", 2),
+                ("2.1", "Changelog", 0),
+            ]
+        );
+        assert!(matches!(items[0].task.status, TaskStatus::Complete));
+        assert_eq!(items[1].task.phase.as_deref(), Some("Parser"));
+        assert_eq!(
+            items[1].task.depends_on,
+            Some(vec!["1.2.1".to_string(), "1.2.2".to_string()])
+        );
+        assert_eq!(items[3].task.depends_on, Some(vec!["1.2.2.1".to_string()]));
+        assert_eq!(items[5].task.phase.as_deref(), Some("Release"));
+        assert_eq!(items[5].task.origin.as_deref(), Some("PLAN.md:18"));
+    }
+
+    #[test]
+    fn test_bulk_import_rows() {
+        let csv = "id,title,status,priority,tags,depends_on\n7.1,Parser,,high,api;core,\n7.2,Lexer,done,,,7.1\n7.3,,pending,,,\n6.1,Existing,,,,\n7.1,Again,,,,\n";
+        let (accepted, errors) = validate_rows(parse_rows(csv, "csv").unwrap(), &["6.1".to_string()]);
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].status, TaskStatus::Pending);
+        assert_eq!(accepted[0].tags, vec!["api", "core"]);
+        assert!(accepted[0].created.is_some());
+        // CSV rows are numbered by line, counting the header
+        let rejected: Vec<usize> = errors.iter().map(|e| e.row).collect();
+        assert_eq!(rejected, vec![3, 4, 5, 6]);
+        assert!(errors[0].error.contains("unknown variant `done`"));
+        assert!(errors[1].error.contains("missing field `title`"));
+        assert!(errors[2].error.contains("already exists"));
+        assert!(errors[3].error.contains("more than once"));
+
+        let yaml = "- id: \"8.1\"\n  title: Docs\n  depends_on: [\"7.1\"]\n- id: \"8.2\"\n  title: Typo\n  stauts: pending\n";
+        let (accepted, errors) = validate_rows(parse_rows(yaml, "yaml").unwrap(), &[]);
+        assert_eq!(accepted[0].depends_on, Some(vec!["7.1".to_string()]));
+        assert_eq!(errors[0].row, 2);
+        assert!(errors[0].error.contains("stauts"));
+
+        let json = r#"[{"id": "9.1", "title": "Release", "status": "complete"}]"#;
+        let (accepted, _) = validate_rows(parse_rows(json, "json").unwrap(), &[]);
+        assert!(accepted[0].completed.is_some());
+        assert!(parse_rows("{}", "json").is_err());
+    }
+}
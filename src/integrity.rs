@@ -0,0 +1,508 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::common::check_rotd_initialized;
+use crate::github::sha256_hex;
+
+/// Fields a chained tasks.jsonl line carries: the hash of the line before
+/// it and the agent that appended it
+pub const PREV_FIELD: &str = "_prev";
+pub const AGENT_FIELD: &str = "_agent";
+
+/// Marker before the link on a chained audit log line
+const TEXT_MARKER: &str = " #chain prev=";
+
+/// What the first line of a file links to
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// How a file's lines carry their link
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineFormat {
+    /// `_prev` and `_agent` fields on each JSON object
+    Json,
+    /// A trailing ` #chain prev=<hash> agent=<id>`
+    Text,
+}
+
+/// Files covered by the hash chain
+fn chained_files() -> [(PathBuf, LineFormat); 2] {
+    [
+        (crate::common::tasks_path(), LineFormat::Json),
+        (crate::common::audit_log_path(), LineFormat::Text),
+    ]
+}
+
+/// Whether `hash_chain` is on in config.jsonc
+pub fn enabled() -> bool {
+    crate::history::load_config().is_ok_and(|config| config.hash_chain)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Link {
+    prev: String,
+    agent: String,
+}
+
+fn read_link(line: &str, format: LineFormat) -> Option<Link> {
+    match format {
+        LineFormat::Json => {
+            let value: serde_json::Value = serde_json::from_str(line).ok()?;
+            Some(Link {
+                prev: value.get(PREV_FIELD)?.as_str()?.to_string(),
+                agent: value.get(AGENT_FIELD)?.as_str()?.to_string(),
+            })
+        }
+        LineFormat::Text => {
+            let (_, link) = line.rsplit_once(TEXT_MARKER)?;
+            let (prev, agent) = link.split_once(" agent=")?;
+            Some(Link {
+                prev: prev.to_string(),
+                agent: agent.to_string(),
+            })
+        }
+    }
+}
+
+/// An audit log line without its link
+pub fn strip_text_link(line: &str) -> &str {
+    line.rsplit_once(TEXT_MARKER).map_or(line, |(entry, _)| entry)
+}
+
+/// Add fields, given as encoded JSON values, to the end of a JSON object
+/// line. Spliced in rather than re-serialized, so existing fields keep their
+/// order.
+pub fn append_fields(line: &str, fields: &[(&str, String)]) -> Result<String> {
+    let body = line
+        .trim_end()
+        .strip_suffix('}')
+        .ok_or_else(|| anyhow::anyhow!("Not a JSON object: {}", line))?;
+    let mut spliced = body.to_string();
+    for (name, value) in fields {
+        if !spliced.trim_end().ends_with('{') {
+            spliced.push(',');
+        }
+        spliced.push_str(&format!("{}:{}", serde_json::to_string(name)?, value));
+    }
+    spliced.push('}');
+    Ok(spliced)
+}
+
+fn attach(line: &str, link: &Link, format: LineFormat) -> Result<String> {
+    match format {
+        LineFormat::Json => append_fields(
+            line,
+            &[
+                (PREV_FIELD, serde_json::to_string(&link.prev)?),
+                (AGENT_FIELD, serde_json::to_string(&link.agent)?),
+            ],
+        ),
+        LineFormat::Text => Ok(format!("{}{}{} agent={}", line, TEXT_MARKER, link.prev, link.agent)),
+    }
+}
+
+/// A linked line without its link, and the link. `None` when the line has
+/// no link or one that isn't the trailing link `attach` writes.
+fn detach(line: &str, format: LineFormat) -> Option<(String, Link)> {
+    let link = read_link(line, format)?;
+    let entry = match format {
+        LineFormat::Json => {
+            let marker = format!(",{}:", serde_json::to_string(PREV_FIELD).ok()?);
+            let (body, _) = line.trim_end().rsplit_once(&marker)?;
+            format!("{}}}", body)
+        }
+        LineFormat::Text => strip_text_link(line).to_string(),
+    };
+    (attach(&entry, &link, format).ok()? == line.trim_end()).then_some((entry, link))
+}
+
+/// A JSONL line's content without its chain link, for comparing entries
+/// whose links differ
+pub fn unlinked(line: &str) -> String {
+    match detach(line, LineFormat::Json) {
+        Some((entry, _)) => entry,
+        None => strip_text_link(line.trim()).to_string(),
+    }
+}
+
+/// Re-link JSONL lines after a merge (the git merge driver or `rotd
+/// sync`), which interleaves two chains. From the first linked line on,
+/// every line is linked to the line now before it; each keeps the agent
+/// it was written by, and unlinked lines get their signer or else the
+/// merging agent. Files
+/// without links are returned unchanged, and an intact chain is left as is.
+pub fn relink(lines: Vec<String>) -> Vec<String> {
+    let Some(start) = lines
+        .iter()
+        .position(|line| read_link(line, LineFormat::Json).is_some())
+    else {
+        return lines;
+    };
+    let mut prev = match start {
+        0 => GENESIS.to_string(),
+        _ => sha256_hex(lines[start - 1].as_bytes()),
+    };
+    let merging_agent = crate::history::get_agent_id();
+
+    let mut relinked = lines;
+    for line in &mut relinked[start..] {
+        let (entry, agent) = match detach(line, LineFormat::Json) {
+            Some((entry, link)) => (entry, link.agent),
+            None => {
+                let signer = serde_json::from_str::<serde_json::Value>(line)
+                    .ok()
+                    .and_then(|value| {
+                        let sig = value.get(crate::signing::SIGNATURE_FIELD)?;
+                        Some(sig.get("agent")?.as_str()?.to_string())
+                    });
+                (line.clone(), signer.unwrap_or_else(|| merging_agent.clone()))
+            }
+        };
+        let link = Link {
+            prev: prev.clone(),
+            agent,
+        };
+        if let Ok(linked) = attach(&entry, &link, LineFormat::Json) {
+            *line = linked;
+        }
+        prev = sha256_hex(line.as_bytes());
+    }
+    relinked
+}
+
+/// Chain `lines`, about to be appended to `path`, onto the lines already
+/// there. Returned unchanged unless `hash_chain` is on and `path` is
+/// tasks.jsonl or the audit log. Call while holding the file's lock.
+pub fn link(path: &Path, lines: Vec<String>) -> Result<Vec<String>> {
+    let Some(format) = chained_files()
+        .into_iter()
+        .find(|(chained, _)| chained == path)
+        .map(|(_, format)| format)
+    else {
+        return Ok(lines);
+    };
+    if !enabled() {
+        return Ok(lines);
+    }
+
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    let mut prev = content
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map_or_else(|| GENESIS.to_string(), |line| sha256_hex(line.as_bytes()));
+    let agent = crate::history::get_agent_id();
+
+    lines
+        .into_iter()
+        .map(|line| {
+            let link = Link {
+                prev: prev.clone(),
+                agent: agent.clone(),
+            };
+            let linked = attach(&line, &link, format)?;
+            prev = sha256_hex(linked.as_bytes());
+            Ok(linked)
+        })
+        .collect()
+}
+
+/// Where a chain stops matching the file
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Divergence {
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Result of walking one file's chain
+#[derive(Debug, Serialize)]
+pub struct ChainReport {
+    pub file: String,
+    pub lines: usize,
+    /// First line carrying a link; lines before it predate the chain and
+    /// are not covered
+    pub chained_from: Option<usize>,
+    /// Hash of the last line. Edits to the last line only show up against
+    /// a head recorded earlier, e.g. in a commit message or CI log.
+    pub head: Option<String>,
+    pub divergence: Option<Divergence>,
+}
+
+/// Walk the lines of `content`, checking each link against the line
+/// before it, and stop at the first that doesn't match
+fn verify_content(file: &str, content: &str, format: LineFormat) -> ChainReport {
+    let mut report = ChainReport {
+        file: file.to_string(),
+        lines: 0,
+        chained_from: None,
+        head: None,
+        divergence: None,
+    };
+    // Line number and hash of the previous non-blank line
+    let mut prev: Option<(usize, String)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_num = index + 1;
+        report.lines += 1;
+
+        let reason = match (read_link(line, format), report.chained_from) {
+            (None, None) => None,
+            (None, Some(_)) => Some(format!(
+                "line {} has no link: it was added by hand, or written with hash_chain off",
+                line_num
+            )),
+            (Some(link), _) => {
+                report.chained_from.get_or_insert(line_num);
+                let expected = prev.as_ref().map_or(GENESIS, |(_, hash)| hash.as_str());
+                (link.prev != expected).then(|| match &prev {
+                    Some((prev_num, _)) => format!(
+                        "line {} does not link to line {}: line {} was edited, or lines were inserted or removed before line {}",
+                        line_num, prev_num, prev_num, line_num
+                    ),
+                    None => format!(
+                        "line {} links to a line that is missing: lines were removed before it",
+                        line_num
+                    ),
+                })
+            }
+        };
+        if let Some(reason) = reason {
+            report.divergence = Some(Divergence {
+                line: line_num,
+                reason,
+            });
+            return report;
+        }
+        prev = Some((line_num, sha256_hex(line.as_bytes())));
+    }
+
+    if report.chained_from.is_some() {
+        report.head = prev.map(|(_, hash)| hash);
+    }
+    report
+}
+
+/// Check the chain of every covered file
+pub fn verify() -> Result<Vec<ChainReport>> {
+    chained_files()
+        .into_iter()
+        .filter(|(path, _)| path.exists())
+        .map(|(path, format)| {
+            let content = std::fs::read_to_string(&path)?;
+            Ok(verify_content(&path.display().to_string(), &content, format))
+        })
+        .collect()
+}
+
+/// `rotd verify-integrity`: fails when any chain is broken
+pub fn cmd_verify(is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    let reports = verify()?;
+    let broken: Vec<&ChainReport> = reports.iter().filter(|r| r.divergence.is_some()).collect();
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if broken.is_empty() { "success" } else { "tampered" },
+                "action": "verify_integrity",
+                "hash_chain": enabled(),
+                "files": reports,
+            })
+        );
+    } else {
+        for report in &reports {
+            match (&report.divergence, report.chained_from) {
+                (Some(divergence), _) => println!(
+                    "{} {}: chain broken at line {}\n  {}",
+                    "✗".red(),
+                    report.file,
+                    divergence.line,
+                    divergence.reason
+                ),
+                (None, Some(from)) => {
+                    println!(
+                        "{} {}: chain intact from line {} ({} lines)",
+                        "✓".green(),
+                        report.file,
+                        from,
+                        report.lines
+                    );
+                    if let Some(head) = &report.head {
+                        println!("  head: {}", head);
+                    }
+                }
+                (None, None) => println!("- {}: no hash chain", report.file),
+            }
+        }
+        if !enabled() {
+            println!("Set \"hash_chain\": true in config.jsonc to chain new entries");
+        }
+    }
+
+    match broken.first() {
+        Some(report) => Err(anyhow::anyhow!(
+            "Integrity check failed: {} diverges at line {}",
+            report.file,
+            report.divergence.as_ref().map_or(0, |d| d.line)
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(lines: &[&str], format: LineFormat, agent: &str) -> Vec<String> {
+        chain_from(GENESIS, lines, format, agent)
+    }
+
+    fn chain_from(prev: &str, lines: &[&str], format: LineFormat, agent: &str) -> Vec<String> {
+        let mut prev = prev.to_string();
+        lines
+            .iter()
+            .map(|line| {
+                let link = Link {
+                    prev: prev.clone(),
+                    agent: agent.to_string(),
+                };
+                let linked = attach(line, &link, format).unwrap();
+                prev = sha256_hex(linked.as_bytes());
+                linked
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let lines = chain(
+            &[
+                r#"{"id":"1.1","title":"A","status":"pending"}"#,
+                r#"{"id":"1.1","title":"A","status":"complete"}"#,
+                r#"{"id":"1.2","title":"B","status":"pending"}"#,
+            ],
+            LineFormat::Json,
+            "agent-7",
+        );
+        assert!(lines[0].starts_with(r#"{"id":"1.1","title":"A","status":"pending","_prev":"000"#));
+        assert_eq!(read_link(&lines[1], LineFormat::Json).unwrap().agent, "agent-7");
+
+        // Lines from before the chain began are not covered, but the first
+        // chained line still links to the one before it
+        let content = format!("{{\"id\":\"0.1\"}}\n\n{}\n", lines.join("\n"));
+        let report = verify_content("tasks.jsonl", &content, LineFormat::Json);
+        assert_eq!(report.chained_from, Some(3));
+        assert!(report.divergence.unwrap().reason.contains("does not link to line 1"));
+        let late = chain_from(
+            &sha256_hex(br#"{"id":"0.1"}"#),
+            &[r#"{"id":"1.1","title":"A","status":"pending"}"#],
+            LineFormat::Json,
+            "agent-7",
+        );
+        let content = format!("{{\"id\":\"0.1\"}}\n\n{}\n", late[0]);
+        let report = verify_content("tasks.jsonl", &content, LineFormat::Json);
+        assert_eq!((report.chained_from, report.divergence), (Some(3), None));
+
+        let content = format!("{}\n", lines.join("\n"));
+        let report = verify_content("tasks.jsonl", &content, LineFormat::Json);
+        assert_eq!((report.lines, report.chained_from), (3, Some(1)));
+        assert_eq!(report.divergence, None);
+        assert_eq!(report.head, Some(sha256_hex(lines[2].as_bytes())));
+
+        // Editing a line breaks the link from the next one
+        let edited = content.replacen(r#""status":"complete""#, r#""status":"pending""#, 1);
+        let divergence = verify_content("tasks.jsonl", &edited, LineFormat::Json)
+            .divergence
+            .unwrap();
+        assert_eq!(divergence.line, 3);
+        assert!(divergence.reason.contains("line 2 was edited"));
+
+        // So does removing the first line
+        let removed = lines[1..].join("\n");
+        let divergence = verify_content("tasks.jsonl", &removed, LineFormat::Json)
+            .divergence
+            .unwrap();
+        assert_eq!(divergence.line, 1);
+
+        // And a line added by hand
+        let added = format!("{}\n{{\"id\":\"9.9\"}}\n", lines.join("\n"));
+        let divergence = verify_content("tasks.jsonl", &added, LineFormat::Json)
+            .divergence
+            .unwrap();
+        assert_eq!(divergence.line, 4);
+        assert!(divergence.reason.contains("no link"));
+    }
+
+    #[test]
+    fn test_text_links() {
+        let lines = chain(
+            &[
+                "[2026-01-31 09:15:00 UTC] [WARNING] pss_low 6.1 - Score below threshold",
+                "[2026-01-31 09:20:00 UTC] [INFO] LESSON_LOGGED GLOBAL - Logged L-1",
+            ],
+            LineFormat::Text,
+            "human",
+        );
+        assert_eq!(
+            strip_text_link(&lines[1]),
+            "[2026-01-31 09:20:00 UTC] [INFO] LESSON_LOGGED GLOBAL - Logged L-1"
+        );
+        assert_eq!(read_link(&lines[0], LineFormat::Text).unwrap().prev, GENESIS);
+
+        let content = lines.join("\n");
+        assert!(verify_content("audit.log", &content, LineFormat::Text).divergence.is_none());
+        let edited = content.replacen("WARNING", "INFO", 1);
+        assert_eq!(
+            verify_content("audit.log", &edited, LineFormat::Text).divergence.unwrap().line,
+            2
+        );
+    }
+
+    #[test]
+    fn test_merges_relink_the_chain() {
+        let base = chain(
+            &[
+                r#"{"id":"1.1","title":"A","status":"pending"}"#,
+                r#"{"id":"1.2","title":"B","status":"pending"}"#,
+            ],
+            LineFormat::Json,
+            "agent-1",
+        );
+        let head = sha256_hex(base[1].as_bytes());
+        let ours_new = chain_from(
+            &head,
+            &[r#"{"id":"1.1","title":"A","status":"in_progress"}"#],
+            LineFormat::Json,
+            "agent-1",
+        );
+        let theirs_new = chain_from(
+            &head,
+            &[r#"{"id":"1.2","title":"B","status":"in_progress","_sig":{"agent":"agent-2"}}"#],
+            LineFormat::Json,
+            "agent-2",
+        );
+        let ours = [base.clone(), ours_new].concat().join("\n");
+        let theirs = [base.clone(), theirs_new].concat().join("\n");
+
+        // Both sides link to the same line, so a plain union would break
+        let merged = crate::merge::merge_jsonl_lines(&base.join("\n"), &ours, &theirs);
+        assert_eq!(merged.len(), 4);
+        let report = verify_content("tasks.jsonl", &merged.join("\n"), LineFormat::Json);
+        assert_eq!(report.divergence, None);
+        assert_eq!(report.chained_from, Some(1));
+        assert_eq!(read_link(&merged[3], LineFormat::Json).unwrap().agent, "agent-2");
+        assert!(merged[3].contains(r#""_sig":{"agent":"agent-2"},"_prev":"#));
+
+        // Re-linked lines still match their originals on the next merge
+        let again = crate::merge::merge_jsonl_lines(&base.join("\n"), &merged.join("\n"), &theirs);
+        assert_eq!(again, merged);
+        // An intact chain is left as is
+        assert_eq!(relink(merged.clone()), merged);
+        assert_eq!(unlinked(&merged[0]), r#"{"id":"1.1","title":"A","status":"pending"}"#);
+    }
+}
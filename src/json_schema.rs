@@ -0,0 +1,435 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use schemars::{JsonSchema, schema_for};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::SchemaCommands;
+use crate::coord::WorkRegistry;
+use crate::output::{Finding, parse_jsonl_lines};
+use crate::schema::{
+    AuditEntry, CoverageHistory, LessonLearned, PSSScore, ProjectPrimer, SessionLogEntry,
+    SessionState, TaskEntry, TaskHistoryEvent, TestSummary,
+};
+
+/// Artifacts with an exportable JSON Schema. JSONL artifacts are described
+/// one line (record) at a time.
+pub const ARTIFACT_SCHEMAS: [&str; 11] = [
+    "tasks",
+    "lessons",
+    "pss_scores",
+    "test_summaries",
+    "session_state",
+    "coverage_history",
+    "primer",
+    "task_history",
+    "sessions",
+    "audit",
+    "active_work_registry",
+];
+
+pub fn handle_command(cmd: SchemaCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        SchemaCommands::Export { schema_type, dir } => export(
+            schema_type.as_deref(),
+            dir.as_deref(),
+            is_agent_mode,
+            dry_run,
+        ),
+    }
+}
+
+fn document<T: JsonSchema>(name: &str) -> serde_json::Value {
+    let mut value = serde_json::to_value(schema_for!(T)).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.insert("title".to_string(), serde_json::json!(name));
+        object.insert(
+            "description".to_string(),
+            serde_json::json!(format!(
+                "ROTD {} artifact (rotd {})",
+                name,
+                env!("CARGO_PKG_VERSION")
+            )),
+        );
+    }
+    value
+}
+
+/// Draft 2020-12 JSON Schema for one artifact, generated from its Rust type
+pub fn artifact_schema(name: &str) -> Result<serde_json::Value> {
+    let schema = match name {
+        "tasks" => document::<TaskEntry>(name),
+        "lessons" => document::<LessonLearned>(name),
+        "pss_scores" => document::<PSSScore>(name),
+        "test_summaries" => document::<TestSummary>(name),
+        "session_state" => document::<SessionState>(name),
+        "coverage_history" => document::<CoverageHistory>(name),
+        "primer" => document::<ProjectPrimer>(name),
+        "task_history" => document::<TaskHistoryEvent>(name),
+        "sessions" => document::<SessionLogEntry>(name),
+        "audit" => document::<AuditEntry>(name),
+        "active_work_registry" => document::<WorkRegistry>(name),
+        other => {
+            return Err(anyhow::anyhow!(
+                "Unknown artifact type '{}'. Expected one of: {}",
+                other,
+                ARTIFACT_SCHEMAS.join(", ")
+            ));
+        }
+    };
+    Ok(schema)
+}
+
+fn export(
+    schema_type: Option<&str>,
+    dir: Option<&Path>,
+    is_agent_mode: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let names: Vec<&str> = match schema_type {
+        Some(name) => vec![name],
+        None => ARTIFACT_SCHEMAS.to_vec(),
+    };
+
+    let Some(dir) = dir else {
+        // Print to stdout: the document itself for one type, keyed by type otherwise
+        let output = match schema_type {
+            Some(name) => artifact_schema(name)?,
+            None => {
+                let mut all = serde_json::Map::new();
+                for name in &names {
+                    all.insert(name.to_string(), artifact_schema(name)?);
+                }
+                serde_json::Value::Object(all)
+            }
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    };
+
+    if !dry_run {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let mut written = Vec::new();
+    for name in &names {
+        let path = dir.join(format!("{}.schema.json", name));
+        let schema = artifact_schema(name)?;
+        if !dry_run {
+            fs::write(&path, serde_json::to_string_pretty(&schema)? + "\n")
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        written.push(path.display().to_string());
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "dry_run": dry_run,
+                "files": written,
+            })
+        );
+    } else {
+        let verb = if dry_run { "Would write" } else { "Wrote" };
+        for path in &written {
+            println!("{} {} {}", "✓".green(), verb, path);
+        }
+    }
+    Ok(())
+}
+
+/// Exported artifact name for a `validate --schema` name
+fn artifact_name(schema_name: &str) -> &str {
+    match schema_name {
+        "coordination" => "active_work_registry",
+        other => other,
+    }
+}
+
+/// Records of an artifact as JSON values, with the file (and line, for
+/// JSONL) each came from. Unparseable records are skipped; the built-in
+/// validation already reports them.
+pub fn artifact_records(schema_name: &str) -> Vec<(PathBuf, Option<usize>, serde_json::Value)> {
+    let jsonl = |path: PathBuf| {
+        parse_jsonl_lines::<serde_json::Value>(&path)
+            .0
+            .into_iter()
+            .map(|(line, value)| (path.clone(), Some(line), value))
+            .collect()
+    };
+    let document = |path: PathBuf, jsonc: bool| {
+        let content = std::fs::read_to_string(&path).ok();
+        let content = match content {
+            Some(content) if jsonc => crate::history::remove_trailing_commas(
+                &crate::history::remove_jsonc_comments(&content),
+            ),
+            Some(content) => content,
+            None => return Vec::new(),
+        };
+        serde_json::from_str(&content)
+            .ok()
+            .map(|value| vec![(path, None, value)])
+            .unwrap_or_default()
+    };
+
+    match schema_name {
+        "tasks" => jsonl(crate::common::tasks_path()),
+        "lessons" => jsonl(crate::common::lessons_path()),
+        "pss_scores" => jsonl(crate::common::pss_scores_path()),
+        "session_state" => document(crate::common::session_state_path(), false),
+        "coverage_history" => document(crate::common::coverage_history_path(), false),
+        "primer" => document(crate::common::primer_path(), true),
+        "coordination" => document(crate::common::active_work_registry_path(), false),
+        "test_summaries" => {
+            let mut paths: Vec<PathBuf> = fs::read_dir(crate::common::test_summaries_path())
+                .map(|entries| {
+                    entries
+                        .filter_map(|e| e.ok().map(|e| e.path()))
+                        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                        .collect()
+                })
+                .unwrap_or_default();
+            paths.sort();
+            paths
+                .into_iter()
+                .flat_map(|path| document(path, false))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Check an artifact against the team's `<artifact>.schema.json` overlay in
+/// the schemas directory, if there is one
+pub fn overlay_findings(schema_name: &str) -> Vec<Finding> {
+    let file_name = format!("{}.schema.json", artifact_name(schema_name));
+    let overlay_path = crate::common::schemas_path().join(&file_name);
+    let content = match fs::read_to_string(&overlay_path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    let overlay: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(overlay) => overlay,
+        Err(e) => {
+            return vec![
+                Finding::new(
+                    "invalid_overlay",
+                    format!("Invalid JSON in {}: {}", file_name, e),
+                )
+                .at(&overlay_path, Some(e.line())),
+            ];
+        }
+    };
+    let compiled = match jsonschema::JSONSchema::options()
+        .with_draft(jsonschema::Draft::Draft202012)
+        .compile(&overlay)
+    {
+        Ok(compiled) => compiled,
+        Err(e) => {
+            let message = format!("Invalid JSON Schema in {}: {}", file_name, e);
+            return vec![Finding::new("invalid_overlay", message).at(&overlay_path, None)];
+        }
+    };
+
+    let mut findings = Vec::new();
+    for (path, line, record) in artifact_records(schema_name) {
+        if let Err(errors) = compiled.validate(&record) {
+            for error in errors {
+                let location = match error.instance_path.to_string() {
+                    pointer if pointer.is_empty() => "(root)".to_string(),
+                    pointer => pointer,
+                };
+                findings.push(
+                    Finding::new(
+                        "schema_overlay",
+                        format!("{} {}: {}", file_name, location, error),
+                    )
+                    .at(&path, line),
+                );
+            }
+        }
+    }
+    findings
+}
+
+/// Whether unknown fields are rejected: `--strict-parse` or `strict_parse`
+/// in the config
+pub fn strict_parse_enabled(flag: bool) -> bool {
+    flag || crate::history::load_config()
+        .map(|config| config.strict_parse)
+        .unwrap_or(false)
+}
+
+fn collect_unknown(
+    node: &serde_json::Value,
+    root: &serde_json::Value,
+    value: &serde_json::Value,
+    path: &str,
+    found: &mut Vec<String>,
+) {
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    if let Some(reference) = node.get("$ref").and_then(|r| r.as_str()) {
+        if let Some(target) = reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+            collect_unknown(target, root, value, path, found);
+        }
+        return;
+    }
+    // `Option<Struct>` is an `anyOf` of the struct and null
+    if let Some(variants) = node.get("anyOf").and_then(|v| v.as_array()) {
+        for variant in variants.iter().filter(|v| v["type"] != "null") {
+            collect_unknown(variant, root, value, path, found);
+        }
+        return;
+    }
+
+    match value {
+        serde_json::Value::Object(map) => {
+            let additional = node.get("additionalProperties");
+            if let Some(properties) = node.get("properties").and_then(|p| p.as_object()) {
+                for (key, child) in map {
+                    match properties.get(key) {
+                        Some(schema) => {
+                            collect_unknown(schema, root, child, &child_path(key), found)
+                        }
+                        // Structs: serde would silently drop the field
+                        None if additional.is_none_or(|a| a == false) => {
+                            found.push(child_path(key))
+                        }
+                        None => {}
+                    }
+                }
+            } else if let Some(schema) = additional.filter(|a| a.is_object()) {
+                // Maps keyed by name, such as PSS criteria
+                for (key, child) in map {
+                    collect_unknown(schema, root, child, &child_path(key), found);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            if let Some(schema) = node.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    collect_unknown(schema, root, item, &format!("{}[{}]", path, i), found);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fields of a record that its artifact type does not define, as paths like
+/// `criteria.tests.rationle`. Serde drops these silently when parsing.
+pub fn unknown_fields(schema_name: &str, value: &serde_json::Value) -> Vec<String> {
+    let Ok(schema) = artifact_schema(artifact_name(schema_name)) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    collect_unknown(&schema, &schema, value, "", &mut found);
+    // Hash chain links and signatures are not part of the record
+    found.retain(|field| {
+        ![
+            crate::integrity::PREV_FIELD,
+            crate::integrity::AGENT_FIELD,
+            crate::signing::SIGNATURE_FIELD,
+        ]
+        .contains(&field.as_str())
+    });
+    found
+}
+
+/// Reject agent input with fields its artifact type does not define
+pub fn deny_unknown_fields(schema_name: &str, value: &serde_json::Value) -> Result<()> {
+    let fields = unknown_fields(schema_name, value);
+    if fields.is_empty() {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "{}",
+        serde_json::json!({
+            "error": "unknown_fields",
+            "message": format!("Unknown field(s): {}", fields.join(", ")),
+            "fields": fields,
+        })
+    ))
+}
+
+/// Unknown fields in every record of an artifact
+pub fn unknown_field_findings(schema_name: &str) -> Vec<Finding> {
+    artifact_records(schema_name)
+        .into_iter()
+        .filter_map(|(path, line, record)| {
+            let fields = unknown_fields(schema_name, &record);
+            (!fields.is_empty()).then(|| {
+                Finding::new(
+                    "unknown_fields",
+                    format!("Unknown field(s): {}", fields.join(", ")),
+                )
+                .at(&path, line)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_schema() {
+        for name in ARTIFACT_SCHEMAS {
+            let schema = artifact_schema(name).unwrap();
+            assert_eq!(
+                schema["$schema"],
+                "https://json-schema.org/draft/2020-12/schema"
+            );
+            assert_eq!(schema["title"], name);
+        }
+
+        let tasks = artifact_schema("tasks").unwrap();
+        let required = tasks["required"].as_array().unwrap();
+        assert!(required.contains(&serde_json::json!("id")));
+        assert!(required.contains(&serde_json::json!("status")));
+        assert!(!required.contains(&serde_json::json!("pr_url")));
+
+        assert!(artifact_schema("nope").is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let score = serde_json::json!({
+            "task_id": "1.1",
+            "score": 1,
+            "timestamp": "2025-01-01T00:00:00Z",
+            "scroe": 2,
+            "criteria": {"tests": {"score": 1, "rationle": "typo"}},
+        });
+        assert_eq!(
+            unknown_fields("pss_scores", &score),
+            vec!["criteria.tests.rationle", "scroe"]
+        );
+
+        // Free-form maps accept anything
+        let lesson = serde_json::json!({
+            "id": "l1",
+            "trigger": [],
+            "context": {"anything": {"goes": true}},
+            "diagnosis": "d",
+            "remediation": "r",
+            "tags": [],
+        });
+        assert!(unknown_fields("lessons", &lesson).is_empty());
+
+        let registry = serde_json::json!({"tasks": [{"id": "1", "blocked_reasn": "x"}]});
+        assert_eq!(
+            unknown_fields("coordination", &registry),
+            vec!["tasks[0].blocked_reasn"]
+        );
+    }
+}
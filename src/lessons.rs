@@ -0,0 +1,567 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::LessonsCommands;
+use crate::audit;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{read_jsonl, with_lock_result};
+use crate::output::escape_csv;
+use crate::schema::LessonLearned;
+
+/// Formats accepted by `lessons export`
+pub const EXPORT_FORMATS: [&str; 2] = ["md", "csv"];
+
+/// Section for lessons without tags in exports
+const UNTAGGED: &str = "untagged";
+
+pub fn handle_command(cmd: LessonsCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        LessonsCommands::Tags => cmd_tags(is_agent_mode),
+        LessonsCommands::Retag { from, to } => cmd_retag(&from, &to, dry_run, is_agent_mode),
+        LessonsCommands::Export { format, out } => {
+            cmd_export(&format, out.as_deref(), dry_run, is_agent_mode)
+        }
+    }
+}
+
+/// How many lessons carry each tag, most used first (ties by name)
+pub fn tag_counts(lessons: &[serde_json::Value]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for lesson in lessons {
+        let Some(tags) = lesson.get("tags").and_then(|t| t.as_array()) else {
+            continue;
+        };
+        // A tag repeated within one lesson still counts that lesson once
+        let mut seen: Vec<&str> = tags.iter().filter_map(|t| t.as_str()).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        for tag in seen {
+            *counts.entry(tag.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+/// Replace every tag in `from` with `to` on one lesson, keeping the tag
+/// order and dropping duplicates the rename creates. Returns whether the
+/// lesson changed.
+pub fn retag(lesson: &mut serde_json::Value, from: &[String], to: &str) -> bool {
+    let Some(tags) = lesson.get_mut("tags").and_then(|t| t.as_array_mut()) else {
+        return false;
+    };
+    if !tags
+        .iter()
+        .any(|t| t.as_str().is_some_and(|t| from.iter().any(|f| f == t)))
+    {
+        return false;
+    }
+
+    let mut renamed: Vec<serde_json::Value> = Vec::with_capacity(tags.len());
+    for tag in tags.drain(..) {
+        let tag = match tag.as_str() {
+            Some(t) if from.iter().any(|f| f == t) => serde_json::Value::from(to),
+            _ => tag,
+        };
+        if !renamed.contains(&tag) {
+            renamed.push(tag);
+        }
+    }
+    *tags = renamed;
+    true
+}
+
+/// Lessons for `show-lessons`: those with `tag` recorded within
+/// `since`..=`until`, newest first. Lessons without a timestamp are only
+/// kept when no time bound is given, and then come last.
+pub fn newest_first(
+    lessons: Vec<LessonLearned>,
+    tag: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Vec<LessonLearned> {
+    let bounded = since.is_some() || until.is_some();
+    let mut selected: Vec<LessonLearned> = lessons
+        .into_iter()
+        .filter(|l| tag.is_none_or(|tag| l.tags.iter().any(|t| t == tag)))
+        .filter(|l| match l.timestamp {
+            Some(time) => since.is_none_or(|s| time >= s) && until.is_none_or(|u| time <= u),
+            None => !bounded,
+        })
+        .collect();
+    // Later log entries first among equal (or missing) timestamps
+    selected.reverse();
+    selected.sort_by_key(|l| std::cmp::Reverse(l.timestamp));
+    selected
+}
+
+/// Lessons under each of their tags, tags in name order with untagged
+/// lessons last, and each group oldest first
+pub fn group_by_tag(lessons: &[LessonLearned]) -> Vec<(&str, Vec<&LessonLearned>)> {
+    let mut groups: BTreeMap<&str, Vec<&LessonLearned>> = BTreeMap::new();
+    let mut untagged = Vec::new();
+    for lesson in lessons {
+        if lesson.tags.is_empty() {
+            untagged.push(lesson);
+        }
+        let mut tags: Vec<&str> = lesson.tags.iter().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags.dedup();
+        for tag in tags {
+            groups.entry(tag).or_default().push(lesson);
+        }
+    }
+
+    let mut groups: Vec<(&str, Vec<&LessonLearned>)> = groups.into_iter().collect();
+    if !untagged.is_empty() {
+        groups.push((UNTAGGED, untagged));
+    }
+    // Stable, so lessons without a timestamp keep their log order up front
+    for (_, group) in &mut groups {
+        group.sort_by_key(|lesson| lesson.timestamp);
+    }
+    groups
+}
+
+/// Render lessons as a Markdown knowledge base with a section per tag
+pub fn to_markdown(lessons: &[LessonLearned]) -> String {
+    use std::fmt::Write;
+
+    let mut md = String::from("# Lessons Learned\n");
+    for (tag, group) in group_by_tag(lessons) {
+        let _ = writeln!(md, "\n## {}\n", tag);
+        for lesson in group {
+            let _ = writeln!(md, "### {}\n", lesson.id);
+            if let Some(timestamp) = lesson.timestamp {
+                let _ = writeln!(md, "_Recorded {}_\n", timestamp.format("%Y-%m-%d %H:%M UTC"));
+            }
+            let _ = writeln!(md, "- **Problem:** {}", lesson.diagnosis);
+            let _ = writeln!(md, "- **Solution:** {}", lesson.remediation);
+            if !lesson.trigger.is_empty() {
+                let _ = writeln!(md, "- **Triggers:** {}", lesson.trigger.join(", "));
+            }
+            let other: Vec<&str> = lesson
+                .tags
+                .iter()
+                .map(String::as_str)
+                .filter(|t| *t != tag)
+                .collect();
+            if !other.is_empty() {
+                let _ = writeln!(md, "- **Also tagged:** {}", other.join(", "));
+            }
+            md.push('\n');
+        }
+        md.pop();
+    }
+    md
+}
+
+/// Render lessons as CSV, one row per tag a lesson carries
+pub fn to_csv(lessons: &[LessonLearned]) -> String {
+    let mut csv = String::from("tag,id,timestamp,diagnosis,remediation,trigger,tags\n");
+    for (tag, group) in group_by_tag(lessons) {
+        for lesson in group {
+            let timestamp = lesson
+                .timestamp
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default();
+            let row = [
+                tag,
+                &lesson.id,
+                &timestamp,
+                &lesson.diagnosis,
+                &lesson.remediation,
+                &lesson.trigger.join("; "),
+                &lesson.tags.join("; "),
+            ];
+            let row: Vec<String> = row.iter().map(|field| escape_csv(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+/// Lowercase words of three or more characters from a lesson's diagnosis,
+/// remediation, and triggers
+fn tokens(lesson: &LessonLearned) -> BTreeSet<String> {
+    std::iter::once(&lesson.diagnosis)
+        .chain(std::iter::once(&lesson.remediation))
+        .chain(&lesson.trigger)
+        .flat_map(|text| text.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn jaccard<T: Ord>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Existing lessons most like `lesson`, best first, scored 0-1 from word
+/// overlap (weighted 0.6) and shared tags (0.4). Lessons scoring below
+/// `min_score` and earlier entries with the same ID are left out.
+pub fn similar<'a>(
+    lesson: &LessonLearned,
+    existing: &'a [LessonLearned],
+    limit: usize,
+    min_score: f64,
+) -> Vec<(&'a LessonLearned, f64)> {
+    let words = tokens(lesson);
+    let tags: BTreeSet<&str> = lesson.tags.iter().map(String::as_str).collect();
+
+    let mut scored: Vec<(&LessonLearned, f64)> = existing
+        .iter()
+        .filter(|other| other.id != lesson.id)
+        .map(|other| {
+            let other_tags: BTreeSet<&str> = other.tags.iter().map(String::as_str).collect();
+            let score = 0.6 * jaccard(&words, &tokens(other)) + 0.4 * jaccard(&tags, &other_tags);
+            (other, score)
+        })
+        .filter(|(_, score)| *score >= min_score)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+    scored
+}
+
+fn cmd_tags(is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let lessons = read_jsonl::<serde_json::Value>(&crate::common::lessons_path())?;
+    let counts = tag_counts(&lessons);
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "lessons_tags",
+                "lessons": lessons.len(),
+                "tags": counts
+                    .iter()
+                    .map(|(tag, count)| serde_json::json!({ "tag": tag, "count": count }))
+                    .collect::<Vec<_>>(),
+            })
+        );
+        return Ok(());
+    }
+
+    if counts.is_empty() {
+        println!("No tagged lessons yet.");
+        return Ok(());
+    }
+
+    println!("{}", "Lesson Tags".cyan().bold());
+    println!();
+    let width = counts.iter().map(|(tag, _)| tag.len()).max().unwrap_or(0);
+    for (tag, count) in &counts {
+        println!(
+            "  {}  {} lesson{}",
+            format!("{:<width$}", tag, width = width).blue(),
+            count,
+            if *count == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_retag(from: &[String], to: &str, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let to = to.trim();
+    if to.is_empty() {
+        return Err(anyhow::anyhow!("--to must name a tag"));
+    }
+
+    let path = crate::common::lessons_path();
+    let changed = if path.exists() {
+        with_lock_result(&path, || {
+            let content = std::fs::read_to_string(&path)?;
+            let mut changed = Vec::new();
+            // Lines that don't parse are kept untouched
+            let lines: Vec<String> = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    let Ok(mut lesson) = serde_json::from_str::<serde_json::Value>(line) else {
+                        return Ok(line.to_string());
+                    };
+                    if !retag(&mut lesson, from, to) {
+                        return Ok(line.to_string());
+                    }
+                    changed.push(
+                        lesson
+                            .get("id")
+                            .and_then(|id| id.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                    );
+                    serde_json::to_string(&lesson)
+                })
+                .collect::<serde_json::Result<_>>()?;
+
+            if !dry_run && !changed.is_empty() {
+                let mut rewritten = lines.join("\n");
+                rewritten.push('\n');
+                std::fs::write(&path, rewritten)?;
+            }
+            Ok(changed)
+        })?
+    } else {
+        Vec::new()
+    };
+
+    if !dry_run && !changed.is_empty() {
+        audit::log_info(
+            None,
+            "LESSONS_RETAG",
+            &format!(
+                "Retagged {} lesson(s): {} -> {}",
+                changed.len(),
+                from.join(", "),
+                to
+            ),
+        )?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "lessons_retag",
+                "from": from,
+                "to": to,
+                "dry_run": dry_run,
+                "retagged": changed,
+            })
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "{}",
+            "DRY RUN MODE - No changes will be made".yellow().bold()
+        );
+    }
+    if changed.is_empty() {
+        println!("No lessons tagged {}", from.join(", "));
+        return Ok(());
+    }
+    println!(
+        "{} {} lesson(s) from {} to {}",
+        if dry_run { "Would retag" } else { "✓ Retagged" },
+        changed.len(),
+        from.join(", ").yellow(),
+        to.blue()
+    );
+    for id in &changed {
+        println!("  • {}", id);
+    }
+
+    Ok(())
+}
+
+fn cmd_export(format: &str, out: Option<&Path>, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let lessons = read_jsonl::<LessonLearned>(&crate::common::lessons_path())?;
+    let content = match format {
+        "csv" => to_csv(&lessons),
+        _ => to_markdown(&lessons),
+    };
+
+    let Some(out) = out else {
+        if is_agent_mode {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "success",
+                    "action": "lessons_export",
+                    "format": format,
+                    "lessons": lessons.len(),
+                    "content": content,
+                })
+            );
+        } else {
+            print!("{}", content);
+        }
+        return Ok(());
+    };
+
+    if !dry_run {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out, &content)
+            .with_context(|| format!("Failed to write {}", out.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "lessons_export",
+                "format": format,
+                "lessons": lessons.len(),
+                "out": out.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} {} lesson(s) to {}",
+            if dry_run { "Would export" } else { "✓ Exported" },
+            lessons.len(),
+            out.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tag_counts() {
+        let lessons = vec![
+            json!({"id": "a", "tags": ["locking", "io"]}),
+            json!({"id": "b", "tags": ["locks", "locking", "locking"]}),
+            json!({"id": "c", "tags": ["io"]}),
+            json!({"id": "d"}),
+        ];
+
+        assert_eq!(
+            tag_counts(&lessons),
+            vec![
+                ("io".to_string(), 2),
+                ("locking".to_string(), 2),
+                ("locks".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retag() {
+        let from = vec!["locks".to_string(), "file-lock".to_string()];
+        let mut lesson = json!({"id": "a", "tags": ["file-lock", "io", "locking", "locks"]});
+
+        assert!(retag(&mut lesson, &from, "locking"));
+        assert_eq!(lesson["tags"], json!(["locking", "io"]));
+        // Nothing left to rename
+        assert!(!retag(&mut lesson, &from, "locking"));
+        assert!(!retag(&mut json!({"id": "b"}), &from, "locking"));
+    }
+
+    fn lesson(id: &str, tags: &[&str], day: Option<u32>) -> LessonLearned {
+        serde_json::from_value(json!({
+            "id": id,
+            "trigger": ["cargo test"],
+            "context": {},
+            "diagnosis": format!("{} broke, badly", id),
+            "remediation": "Use \"retry\"",
+            "tags": tags,
+            "timestamp": day.map(|d| format!("2026-01-{:02}T10:00:00Z", d)),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_export() {
+        let lessons = vec![
+            lesson("late", &["locking"], Some(9)),
+            lesson("early", &["locking", "io"], Some(2)),
+            lesson("loose", &[], None),
+        ];
+
+        let groups: Vec<(&str, Vec<&str>)> = group_by_tag(&lessons)
+            .into_iter()
+            .map(|(tag, group)| (tag, group.iter().map(|l| l.id.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("io", vec!["early"]),
+                ("locking", vec!["early", "late"]),
+                ("untagged", vec!["loose"]),
+            ]
+        );
+
+        let md = to_markdown(&lessons);
+        assert!(md.starts_with("# Lessons Learned\n\n## io\n\n### early\n\n_Recorded 2026-01-02 10:00 UTC_\n"));
+        assert!(md.contains("- **Also tagged:** io\n"));
+        assert!(md.ends_with("### loose\n\n- **Problem:** loose broke, badly\n- **Solution:** Use \"retry\"\n- **Triggers:** cargo test\n"));
+
+        let csv = to_csv(&lessons);
+        assert_eq!(csv.lines().count(), 5);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("io,early,2026-01-02T10:00:00+00:00,\"early broke, badly\",\"Use \"\"retry\"\"\",cargo test,locking; io")
+        );
+    }
+
+    #[test]
+    fn test_similar() {
+        let mut existing = vec![
+            lesson("flaky-lock", &["locking"], Some(1)),
+            lesson("parser", &["parsing"], Some(2)),
+            lesson("self", &["locking"], Some(3)),
+        ];
+        existing[0].diagnosis = "Lock file left behind after crash".to_string();
+        existing[1].diagnosis = "Tokenizer drops unicode escapes".to_string();
+        let mut new = lesson("self", &["locking", "io"], None);
+        new.diagnosis = "Stale lock file after a crash blocks writes".to_string();
+
+        let found: Vec<&str> = similar(&new, &existing, 3, 0.2)
+            .iter()
+            .map(|(l, _)| l.id.as_str())
+            .collect();
+        assert_eq!(found, vec!["flaky-lock"]);
+        assert!(similar(&new, &existing, 0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_newest_first() {
+        let lessons = || {
+            vec![
+                lesson("old", &["io"], Some(1)),
+                lesson("undated", &["io"], None),
+                lesson("new", &["io"], Some(20)),
+                lesson("mid", &["locking"], Some(10)),
+            ]
+        };
+        let ids = |lessons: Vec<LessonLearned>| -> Vec<String> {
+            lessons.into_iter().map(|l| l.id).collect()
+        };
+        let day = |d: u32| Some(crate::common::parse_since(&format!("2026-01-{:02}", d)).unwrap());
+
+        assert_eq!(
+            ids(newest_first(lessons(), None, None, None)),
+            vec!["new", "mid", "old", "undated"]
+        );
+        assert_eq!(
+            ids(newest_first(lessons(), Some("io"), None, None)),
+            vec!["new", "old", "undated"]
+        );
+        assert_eq!(
+            ids(newest_first(lessons(), None, day(5), day(15))),
+            vec!["mid"]
+        );
+    }
+}
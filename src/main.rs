@@ -1,17 +1,54 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
 
 mod agent;
 mod audit;
+mod build_info;
+mod bundle;
+mod checks;
 mod cli;
 mod common;
+mod compile;
+mod completion;
+mod coverage;
 mod coord;
+mod coord_server;
 mod fs_ops;
+mod git;
 mod github;
+mod graph;
 mod history;
+mod hooks;
 mod human;
+mod import;
+mod integrity;
+mod json_schema;
+mod lessons;
+mod merge;
+mod methodology;
+mod metrics;
+mod milestone;
+mod output;
+mod phase;
+mod profile;
+mod primer;
+mod prioritize;
 mod pss;
+mod report;
 mod schema;
+mod secrets;
+mod self_replace;
+mod session;
+mod signing;
+mod stubs;
+mod sync;
+mod task;
+mod template;
+mod todos;
+mod tui;
+mod validate;
+mod watch;
 
 use cli::commands::buckle_mode::{BuckleModeArgs, handle_buckle_mode};
 
@@ -34,6 +71,10 @@ pub struct Cli {
     /// Show what would be done without making changes
     #[arg(long, global = true)]
     dry_run: bool,
+
+    /// Reject unknown fields in agent input and report them in validate
+    #[arg(long, global = true)]
+    strict_parse: bool,
 }
 
 #[derive(Subcommand)]
@@ -43,6 +84,32 @@ enum Commands {
         /// Force initialization even if .rotd directory exists
         #[arg(short, long)]
         force: bool,
+        /// Split .rotd into shared/ (committed) and local/ (ignored) and write .rotd/.gitignore
+        #[arg(long)]
+        git: bool,
+        /// Preconfigure compile checks, stub detection, tests, and coverage for a language
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(profile::PROFILE_NAMES))]
+        profile: Option<String>,
+        /// Never prompt: fail if .rotd exists (unless --force) and print a summary
+        /// line. Implied when stdin is not a terminal.
+        #[arg(short = 'y', long, visible_alias = "yes")]
+        non_interactive: bool,
+        /// Create pending tasks from TODO/FIXME comments after initializing
+        #[arg(long)]
+        scan_todos: bool,
+    },
+
+    /// Create pending tasks from TODO/FIXME comments in the source tree
+    ScanTodos,
+
+    /// Report tokens, keys, and passwords already written to .rotd/
+    ScanSecrets,
+
+    /// List stub markers that fail the PSS stub check, with surrounding lines
+    Stubs {
+        /// Lines of source to show before and after each stub
+        #[arg(short = 'C', long, default_value = "2")]
+        context: usize,
     },
 
     /// Buckle Mode recovery operations
@@ -51,16 +118,36 @@ enum Commands {
     /// Generate PSS score for a task
     Score {
         /// Task ID to score
-        task_id: String,
-        /// Output format: table, json, or summary
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        task_id: Option<String>,
+        /// Score every task as a tasks x criteria matrix, without recording the scores
+        #[arg(long)]
+        all: bool,
+        /// Output format: table, json, or summary; table, json, or csv with --all
         #[arg(short, long, default_value = "table")]
         format: String,
     },
 
-    /// Display task details
+    /// Query recorded PSS scores
+    Pss {
+        #[command(subcommand)]
+        subcommand: PssCommands,
+    },
+
+    /// Display task details, or a table when several tasks match
     ShowTask {
-        /// Task ID to display
-        task_id: String,
+        /// Task IDs or glob patterns to display, e.g. 6.1 6.2 or "6.*"
+        #[arg(required_unless_present_any = ["assignee", "task_tag"])]
+        task_id: Vec<String>,
+        /// Only tasks assigned to this person or agent (all tasks when no IDs are given)
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Only tasks carrying this tag; repeat to require several
+        #[arg(long = "tag", id = "task_tag")]
+        tags: Vec<String>,
+        /// Print each task's history of status, priority, and PSS changes
+        #[arg(long)]
+        history: bool,
     },
 
     /// List logged lessons in readable format
@@ -68,6 +155,24 @@ enum Commands {
         /// Filter by tag
         #[arg(short, long)]
         tag: Option<String>,
+        /// Only lessons recorded at or after this time (YYYY-MM-DD, RFC 3339, or an age like 7d)
+        #[arg(long, value_parser = common::parse_since)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        /// Only lessons recorded at or before this time (a date includes the whole day)
+        #[arg(long, value_parser = common::parse_until)]
+        until: Option<chrono::DateTime<chrono::Utc>>,
+        /// Show at most this many lessons
+        #[arg(short, long)]
+        limit: Option<usize>,
+        /// Skip this many of the newest matching lessons
+        #[arg(long, default_value = "0")]
+        offset: usize,
+    },
+
+    /// Manage and export lessons learned
+    Lessons {
+        #[command(subcommand)]
+        subcommand: LessonsCommands,
     },
 
     /// Show audit violations
@@ -75,6 +180,21 @@ enum Commands {
         /// Number of recent entries to show
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// Only entries with this severity (repeat for several)
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(audit::SEVERITIES))]
+        severity: Vec<String>,
+        /// Only entries for this task
+        #[arg(long = "task")]
+        task_id: Option<String>,
+        /// Only entries for this rule, e.g. LESSON_LOGGED (case-insensitive)
+        #[arg(long)]
+        rule: Option<String>,
+        /// Only entries logged at or after this time (YYYY-MM-DD, RFC 3339, or an age like 7d)
+        #[arg(long, value_parser = common::parse_since)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        /// Output format: table or json
+        #[arg(short, long, default_value = "table", value_parser = clap::builder::PossibleValuesParser::new(["table", "json"]))]
+        format: String,
     },
 
     /// Agent-oriented commands
@@ -92,6 +212,20 @@ enum Commands {
         /// Check if Buckle Mode trigger conditions are met
         #[arg(long)]
         buckle_trigger: bool,
+
+        /// Exit non-zero when health is below the threshold or a critical
+        /// check fails (for CI and git hooks)
+        #[arg(long)]
+        ci: bool,
+
+        /// Minimum health percentage for --ci (default: ci_threshold in the
+        /// config, else 100)
+        #[arg(long, requires = "ci")]
+        threshold: Option<f64>,
+
+        /// Report format: text, github (workflow annotations), sarif, or junit
+        #[arg(long, default_value = "text", conflicts_with_all = ["fix", "buckle_trigger"])]
+        output: String,
     },
 
     /// Generate shell completions
@@ -100,6 +234,14 @@ enum Commands {
         shell: String,
     },
 
+    /// Print completion candidates for the shell scripts from `completions`
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Command line after `rotd`, ending with the word being completed
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+
     /// Update ROTD methodology and templates
     Update {
         /// Check for updates without applying
@@ -108,6 +250,15 @@ enum Commands {
         /// Skip confirmation prompts
         #[arg(short, long)]
         yes: bool,
+        /// Overwrite locally edited methodology files and reapply an up-to-date version
+        #[arg(long)]
+        force: bool,
+        /// Apply without checking the bundle checksum and signature
+        #[arg(long)]
+        skip_verify: bool,
+        /// Release channel: stable or beta (defaults to release_channel in config)
+        #[arg(long)]
+        channel: Option<String>,
     },
 
     /// Upgrade ROTD CLI binary to latest version
@@ -118,6 +269,12 @@ enum Commands {
         /// Skip confirmation prompts
         #[arg(short, long)]
         yes: bool,
+        /// Install without checking the release checksum and signature
+        #[arg(long)]
+        skip_verify: bool,
+        /// Release channel: stable or beta (defaults to release_channel in config)
+        #[arg(long)]
+        channel: Option<String>,
     },
 
     /// Show version information
@@ -128,6 +285,12 @@ enum Commands {
         /// Show latest available version
         #[arg(long)]
         latest: bool,
+        /// Show the commit, build date, target, and compiler of this binary
+        #[arg(long, conflicts_with_all = ["project", "latest"])]
+        build_info: bool,
+        /// Release channel: stable or beta (defaults to release_channel in config)
+        #[arg(long)]
+        channel: Option<String>,
     },
 
     /// Validate ROTD artifacts
@@ -141,6 +304,69 @@ enum Commands {
         /// Strict validation mode
         #[arg(long)]
         strict: bool,
+        /// Report format: text, github (workflow annotations), sarif, or junit
+        #[arg(long, default_value = "text")]
+        output: String,
+    },
+
+    /// Manage the keys agents sign their task updates and test summaries with
+    Keys {
+        #[command(subcommand)]
+        subcommand: KeysCommands,
+    },
+
+    /// Walk the hash chain of tasks.jsonl and the audit log and report the
+    /// first line that doesn't match
+    VerifyIntegrity,
+
+    /// Task workflow commands
+    Task {
+        #[command(subcommand)]
+        subcommand: TaskCommands,
+    },
+
+    /// Stream change events for .rotd artifacts, or re-run check as they change
+    Watch {
+        /// Output format: json or text
+        #[arg(short, long, default_value = "json")]
+        format: String,
+        /// Quiet period in milliseconds before a changed file is reported
+        #[arg(long, default_value = "200")]
+        debounce_ms: u64,
+        /// Re-run check when source files or artifacts change, printing new and resolved findings
+        #[arg(long)]
+        check: bool,
+        /// With --check, also report stub markers added or removed
+        #[arg(long, requires = "check")]
+        stubs: bool,
+    },
+
+    /// Git merge driver for JSONL logs: rotd merge-jsonl %O %A %B
+    MergeJsonl {
+        /// Common ancestor version (%O)
+        base: std::path::PathBuf,
+        /// Current branch version (%A); receives the merged result
+        ours: std::path::PathBuf,
+        /// Other branch version (%B)
+        theirs: std::path::PathBuf,
+    },
+
+    /// GitHub issue integration
+    Github {
+        #[command(subcommand)]
+        subcommand: GithubCommands,
+    },
+
+    /// Link git commits to ROTD tasks
+    Git {
+        #[command(subcommand)]
+        subcommand: GitCommands,
+    },
+
+    /// Manage git hooks that run ROTD checks
+    Hooks {
+        #[command(subcommand)]
+        subcommand: HooksCommands,
     },
 
     /// Multi-agent coordination commands
@@ -154,6 +380,122 @@ enum Commands {
         #[command(subcommand)]
         subcommand: PrimerCommands,
     },
+
+    /// JSON Schema documents for ROTD artifacts
+    Schema {
+        #[command(subcommand)]
+        subcommand: SchemaCommands,
+    },
+
+    /// Pack .rotd/ into a portable tar.gz bundle with a manifest of
+    /// checksums and schema versions; locks, heartbeats, keys, and backups
+    /// are left out
+    Export {
+        /// Bundle to write
+        #[arg(default_value = "rotd-bundle.tar.gz")]
+        out: std::path::PathBuf,
+    },
+
+    /// Serve this project's coordination state over HTTP so agents on other
+    /// machines can claim, release, beat, and track quota against it
+    Serve {
+        /// Serve coord claim/release/beat/quota (clients set ROTD_COORD_SERVER
+        /// or coord_server in config.local.jsonc)
+        #[arg(long, required = true)]
+        coord: bool,
+        /// Address to listen on
+        #[arg(long, default_value = coord_server::DEFAULT_BIND)]
+        bind: String,
+    },
+
+    /// Push .rotd/ to, or pull it from, the S3 bucket or git remote set as
+    /// `sync` in config; logs changed on both sides are union-merged
+    Sync {
+        #[command(subcommand)]
+        subcommand: SyncCommands,
+    },
+
+    /// Import tasks from other formats, or a bundle written by rotd export
+    Import {
+        #[command(subcommand)]
+        subcommand: ImportCommands,
+    },
+
+    /// Task history export and reporting
+    History {
+        #[command(subcommand)]
+        subcommand: HistoryCommands,
+    },
+
+    /// Milestones with target dates, tracked in milestones.json
+    Milestone {
+        #[command(subcommand)]
+        subcommand: MilestoneCommands,
+    },
+
+    /// Progress by task phase
+    Phase {
+        #[command(subcommand)]
+        subcommand: PhaseCommands,
+    },
+
+    /// Agent session lifecycle, logged to sessions.jsonl
+    Session {
+        #[command(subcommand)]
+        subcommand: SessionCommands,
+    },
+
+    /// Delivery metrics and monitoring export
+    Metrics {
+        #[command(subcommand)]
+        subcommand: MetricsCommands,
+    },
+
+    /// Chart coverage history against the floor, or prune old entries
+    Coverage {
+        #[command(subcommand)]
+        subcommand: CoverageCommands,
+    },
+
+    /// Live terminal dashboard of tasks, coordination, audit, and coverage
+    Tui {
+        /// Seconds between refreshes
+        #[arg(long, default_value = "2")]
+        interval: u64,
+    },
+
+    /// Project status report in Markdown, or an HTML dashboard with --html
+    Report {
+        /// File to write, e.g. docs/status.md (defaults to stdout)
+        #[arg(short, long)]
+        out: Option<std::path::PathBuf>,
+        /// Write a self-contained HTML dashboard to index.html in this directory instead
+        #[arg(long, value_name = "DIR", conflicts_with = "out")]
+        html: Option<std::path::PathBuf>,
+        /// Start of the period for coverage, lessons, and audit: a date, RFC 3339 time, or age like 7d
+        #[arg(long, value_parser = common::parse_since, default_value = "7d")]
+        since: chrono::DateTime<chrono::Utc>,
+    },
+
+    /// Task dependency graph as Graphviz DOT or a Mermaid flowchart
+    Graph {
+        /// Output format: dot or mermaid
+        #[arg(long, default_value = "dot", value_parser = clap::builder::PossibleValuesParser::new(graph::GRAPH_FORMATS))]
+        format: String,
+        /// Instead of the graph, list the longest chain of incomplete tasks and the tasks ready to start
+        #[arg(long, conflicts_with = "format")]
+        critical_path: bool,
+        /// File to write, e.g. docs/tasks.dot (defaults to stdout)
+        #[arg(short, long)]
+        out: Option<std::path::PathBuf>,
+    },
+
+    /// Compute priority_score for incomplete tasks from priority, age, dependents, and phase
+    Prioritize {
+        /// Show each task's factor values and weights
+        #[arg(long)]
+        explain: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -172,13 +514,23 @@ enum AgentCommands {
         /// Auto-populate updated_at timestamp
         #[arg(long)]
         timestamp: bool,
+        /// Note recorded with the task history event (up to 280 characters)
+        #[arg(long)]
+        comment: Option<String>,
+        /// Apply a status change the transition rules reject, logging an audit warning
+        #[arg(long)]
+        allow_transition: bool,
+        /// Read the input as a patch for this task: a JSON object is an RFC 7386
+        /// merge patch, an array an RFC 6902 JSON Patch
+        #[arg(long, value_name = "TASK_ID")]
+        patch: Option<String>,
     },
 
-    /// Append test summary
+    /// Append test summary from JSON input
     AppendSummary {
-        /// Test summary file path
+        /// Read from file instead of stdin
         #[arg(short, long)]
-        file: String,
+        file: Option<String>,
     },
 
     /// Log lesson learned from JSON input
@@ -191,7 +543,15 @@ enum AgentCommands {
     /// Update coverage ratchet
     RatchetCoverage {
         /// New coverage percentage
-        coverage: f64,
+        #[arg(required_unless_present_any = ["from_file", "run"])]
+        coverage: Option<f64>,
+        /// Read the percentage from a coverage report (tarpaulin, istanbul/c8,
+        /// coverage.py JSON, Go cover profile, lcov, or Cobertura XML)
+        #[arg(long, value_name = "REPORT", conflicts_with_all = ["coverage", "run"])]
+        from_file: Option<std::path::PathBuf>,
+        /// Run coverage_command from config.jsonc and read its report
+        #[arg(long, conflicts_with = "coverage")]
+        run: bool,
         /// Task ID associated with coverage update
         #[arg(short, long)]
         task_id: Option<String>,
@@ -199,6 +559,39 @@ enum AgentCommands {
 
     /// Show minified command info for LLM agents
     Info,
+
+    /// Emit everything a new session needs to pick up work as one JSON bundle
+    Resume {
+        /// Number of recent history events to include
+        #[arg(long, default_value = "20")]
+        history: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum LessonsCommands {
+    /// List lesson tags with how many lessons use each
+    Tags,
+
+    /// Rename tags across lessons_learned.jsonl, merging them into one
+    Retag {
+        /// Tag to replace (repeat to merge several)
+        #[arg(long, required = true)]
+        from: Vec<String>,
+        /// Tag to use instead
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Write lessons grouped by tag as a Markdown or CSV knowledge base
+    Export {
+        /// Output format: md or csv
+        #[arg(long, default_value = "md", value_parser = clap::builder::PossibleValuesParser::new(lessons::EXPORT_FORMATS))]
+        format: String,
+        /// File to write, e.g. docs/lessons.md (defaults to stdout)
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -214,6 +607,12 @@ enum CoordCommands {
         /// Claim any task regardless of priority
         #[arg(long)]
         any: bool,
+        /// Claim order: fifo (oldest first), priority (then score, then age), or score (then priority, then age)
+        #[arg(long, default_value = "priority", conflicts_with = "any", value_parser = clap::builder::PossibleValuesParser::new(coord::CLAIM_STRATEGIES))]
+        strategy: String,
+        /// Only claim tasks carrying this tag; repeat to require several
+        #[arg(long = "tag", id = "task_tag")]
+        tags: Vec<String>,
     },
 
     /// Release a claimed task
@@ -254,6 +653,16 @@ enum CoordCommands {
     /// List current work registry
     Ls,
 
+    /// Show the work registry as a board with a column per status
+    Board {
+        /// Redraw on an interval until interrupted
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between redraws in watch mode
+        #[arg(long, default_value = "2", requires = "watch")]
+        interval: u64,
+    },
+
     /// View task history
     History {
         /// Task ID to view history for
@@ -271,6 +680,172 @@ enum CoordCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum TaskCommands {
+    /// Start work on a task: mark it in_progress, claim it, and make it the session's current task
+    Start {
+        /// Task ID to start
+        task_id: String,
+        /// Create (or switch to) a git branch named after the task
+        #[arg(short, long)]
+        branch: bool,
+        /// Start even if the task is complete or has unmet dependencies
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Set who is responsible for a task (separate from coordination claims)
+    Assign {
+        /// Task ID to assign
+        task_id: String,
+        /// Person or agent to assign (defaults to ROTD_AGENT_ID, else "human")
+        #[arg(conflicts_with = "clear")]
+        assignee: Option<String>,
+        /// Remove the task's assignee
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// Add tags to a task, or remove them with --remove
+    Tag {
+        /// Task ID to tag
+        task_id: String,
+        /// Tags to add or remove
+        #[arg(required = true, id = "task_tag")]
+        tags: Vec<String>,
+        /// Remove the tags instead of adding them
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Create a task, optionally from a template in .rotd/templates/
+    New {
+        /// ID for the new task
+        task_id: String,
+        /// Task title
+        title: String,
+        /// Template name, e.g. bugfix for .rotd/templates/bugfix.json
+        #[arg(long)]
+        template: Option<String>,
+        /// Template variable as NAME=VALUE; repeatable
+        #[arg(long = "var")]
+        vars: Vec<String>,
+        /// Priority, overriding the template's
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(schema::PRIORITIES))]
+        priority: Option<String>,
+        /// Phase, overriding the template's
+        #[arg(long)]
+        phase: Option<String>,
+    },
+
+    /// Set the status of several tasks at once
+    SetStatus {
+        /// Comma-separated task IDs or glob patterns, e.g. 6.1,6.2 or "6.*"
+        #[arg(long, value_delimiter = ',', required_unless_present = "filters")]
+        ids: Vec<String>,
+        /// Only tasks matching key=value (status, phase, priority, assignee, tag); repeatable
+        #[arg(long = "filter")]
+        filters: Vec<String>,
+        /// New status
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(schema::TASK_STATUSES))]
+        status: String,
+        /// Why the status changed, recorded in task history and the audit log
+        #[arg(long)]
+        reason: Option<String>,
+        /// Apply status changes the transition rules reject, logging an audit warning
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum GithubCommands {
+    /// Create tasks from GitHub issues (set GITHUB_TOKEN for private repos)
+    Import {
+        /// Repository as owner/name
+        #[arg(long)]
+        repo: String,
+        /// Only import issues with this label
+        #[arg(long, default_value = "rotd")]
+        label: String,
+        /// Issue state to import: open, closed, or all
+        #[arg(long, default_value = "open")]
+        state: String,
+    },
+
+    /// Push task status changes to linked issues and pull newly labeled issues
+    Sync {
+        /// Repository as owner/name (defaults to the one used for import)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Only push local changes; skip fetching issues
+        #[arg(long)]
+        no_pull: bool,
+    },
+
+    /// Open a pull request for a task from the current branch
+    Pr {
+        /// Task ID the pull request implements
+        task_id: String,
+        /// Repository as owner/name (defaults to the import repo, then the origin remote)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Base branch (defaults to the repository's default branch)
+        #[arg(long)]
+        base: Option<String>,
+        /// Open as a draft pull request
+        #[arg(long)]
+        draft: bool,
+        /// Push the current branch to origin first
+        #[arg(long)]
+        push: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum GitCommands {
+    /// Add a ROTD-Task trailer for the task to the HEAD commit (amends HEAD)
+    Attach {
+        /// Task ID to link
+        task_id: String,
+    },
+
+    /// Print a commit message skeleton for a task
+    Message {
+        /// Task ID to describe
+        task_id: String,
+        /// Install the message as git's commit.template
+        #[arg(long)]
+        install: bool,
+    },
+
+    /// Register the JSONL merge driver in git config and .gitattributes
+    ConfigureMerge,
+}
+
+#[derive(Subcommand)]
+enum HooksCommands {
+    /// Install pre-commit and pre-push hooks (existing hooks are chained)
+    Install {
+        /// Reinstall even if rotd hooks are already present
+        #[arg(short, long)]
+        force: bool,
+        /// Only install the given hook(s): pre-commit, pre-push
+        #[arg(long)]
+        hook: Vec<String>,
+    },
+
+    /// Remove rotd hooks and restore any chained hooks
+    Uninstall {
+        /// Only remove the given hook(s): pre-commit, pre-push
+        #[arg(long)]
+        hook: Vec<String>,
+    },
+
+    /// Show which hooks are installed
+    Status,
+}
+
 #[derive(Subcommand)]
 enum PrimerCommands {
     /// Initialize primer for current project
@@ -285,35 +860,328 @@ enum PrimerCommands {
         /// Show specific primer file
         #[arg(short, long)]
         file: Option<String>,
+
+        /// Show the primer for a scope (`.rotd/primers/<scope>.jsonc`)
+        #[arg(long, conflicts_with = "file")]
+        scope: Option<String>,
+
+        /// Output format: raw (the file as stored) or md (rendered Markdown)
+        #[arg(long, default_value = "raw", value_parser = clap::builder::PossibleValuesParser::new(["raw", "md"]))]
+        format: String,
     },
 
     /// Validate primer against current project state
     Check,
 
+    /// Apply drift between the primer and the project (new entry points,
+    /// removed test dirs, changed dependencies, new components)
+    Update {
+        /// Only update these fields
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(primer::DRIFT_FIELDS))]
+        field: Vec<String>,
+
+        /// Apply without confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Parse primer and output structured information for agents
     Parse {
         /// Output format: json or summary
         #[arg(short, long, default_value = "json")]
         format: String,
+
+        /// Only include the root primer and the scopes relevant to this task
+        #[arg(long)]
+        task: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchemaCommands {
+    /// Print (or write) draft 2020-12 JSON Schemas generated from the ROTD types
+    Export {
+        /// Only export one artifact type
+        #[arg(long = "type", value_parser = clap::builder::PossibleValuesParser::new(json_schema::ARTIFACT_SCHEMAS))]
+        schema_type: Option<String>,
+        /// Write <type>.schema.json files to this directory instead of stdout
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImportCommands {
+    /// Create tasks from a markdown checklist: headings become phases and
+    /// nested items become dependencies of their parent
+    Markdown {
+        /// Markdown file, e.g. PLAN.md
+        file: std::path::PathBuf,
+    },
+
+    /// Create tasks from a JSON array, a CSV file with a header row, or a
+    /// YAML list; invalid rows are reported and the rest imported together
+    Tasks {
+        /// File to import, e.g. tasks.csv
+        file: std::path::PathBuf,
+        /// File format (detected from the extension when omitted)
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(import::TASK_FORMATS))]
+        format: Option<String>,
+    },
+
+    /// Unpack a bundle written by rotd export into .rotd/, checking its
+    /// checksums and schema versions first
+    Bundle {
+        /// Bundle to import, e.g. rotd-bundle.tar.gz
+        file: std::path::PathBuf,
+        /// Replace existing files (backed up first) and accept newer schemas
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SyncCommands {
+    /// Upload local changes; stops if a non-log file also changed remotely
+    Push {
+        /// Overwrite the remote copies instead of merging or stopping
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Download remote changes; stops if a non-log file also changed locally
+    Pull {
+        /// Replace the local copies instead of merging or stopping
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommands {
+    /// Flatten task history events to JSON or CSV, or summarize them with --report
+    Export {
+        /// Task whose history to export
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        task_id: Option<String>,
+        /// Export the history of every task
+        #[arg(long)]
+        all: bool,
+        /// Output format: json or csv
+        #[arg(long, default_value = "json", value_parser = clap::builder::PossibleValuesParser::new(history::EXPORT_FORMATS))]
+        format: String,
+        /// Report average time in each status and hand-offs between agents instead
+        #[arg(long)]
+        report: bool,
+        /// File to write (defaults to stdout)
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MetricsCommands {
+    /// Completed tasks per week, cycle time, and remaining work by phase
+    Velocity {
+        /// Number of weeks to cover, ending with the current one
+        #[arg(long, default_value = "8")]
+        weeks: usize,
+        /// Output format: table, json, or csv
+        #[arg(long, default_value = "table", value_parser = clap::builder::PossibleValuesParser::new(metrics::VELOCITY_FORMATS))]
+        format: String,
+        /// File to write (defaults to stdout); a table is written as JSON
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
+
+    /// Write task counts, health, coverage, quota, and stale locks for monitoring
+    Export {
+        /// Prometheus textfile collector file to write, e.g. /var/lib/node_exporter/rotd.prom
+        #[arg(long, value_name = "PATH")]
+        prometheus: std::path::PathBuf,
+        /// Seconds without a heartbeat before a lock counts as stale
+        #[arg(long, default_value = "900")]
+        stale_timeout: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum PssCommands {
+    /// Most recently recorded score for a task
+    Latest {
+        /// Task ID
+        task_id: String,
+    },
+
+    /// Score a task and list next steps for each criterion it misses
+    Explain {
+        /// Task ID
+        task_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CoverageCommands {
+    /// Coverage and floor over time as an ASCII chart, or a JSON series
+    Chart {
+        /// Number of most recent entries to show
+        #[arg(long, default_value = "60")]
+        last: usize,
+        /// Output format: ascii or json
+        #[arg(long, default_value = "ascii", value_parser = clap::builder::PossibleValuesParser::new(coverage::CHART_FORMATS))]
+        format: String,
+    },
+
+    /// Drop old coverage history entries; the newest entry is always kept
+    Prune {
+        /// Keep at most this many entries (defaults to coverage_keep_entries)
+        #[arg(long)]
+        keep: Option<usize>,
+        /// Drop entries older than this many days (defaults to coverage_keep_days)
+        #[arg(long)]
+        days: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeysCommands {
+    /// Generate an Ed25519 signing key for an agent and register its public key
+    Generate {
+        /// Agent ID (defaults to ROTD_AGENT_ID, else "human")
+        agent_id: Option<String>,
+        /// Replace the agent's existing key
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// List agents with registered signing keys
+    List,
+}
+
+#[derive(Subcommand)]
+enum MilestoneCommands {
+    /// Create a milestone with a target date
+    Create {
+        /// Milestone ID, e.g. beta
+        id: String,
+        /// Display title (defaults to the ID)
+        #[arg(long)]
+        title: Option<String>,
+        /// Target date, YYYY-MM-DD
+        #[arg(long)]
+        target: chrono::NaiveDate,
+    },
+
+    /// Add tasks to a milestone, or remove them with --remove
+    Assign {
+        /// Milestone ID
+        id: String,
+        /// Task IDs or glob patterns, e.g. 6.1 "7.*"
+        #[arg(required = true)]
+        task_id: Vec<String>,
+        /// Remove the tasks from the milestone instead
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Completion, projected finish, and risk for each milestone
+    Status {
+        /// Only this milestone
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PhaseCommands {
+    /// Per phase: task counts by status, completion, blocked tasks, and average PSS score
+    Status,
+}
+
+#[derive(Subcommand)]
+enum SessionCommands {
+    /// Start a new session with a fresh session ID
+    Start {
+        /// Task the session works on (defaults to the previous session's)
+        #[arg(long)]
+        task: Option<String>,
+        /// End a still-active session as abandoned instead of failing
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// End the active session
+    End {
+        /// How the session ended
+        #[arg(long, default_value = "complete", value_parser = clap::builder::PossibleValuesParser::new(session::END_STATUSES))]
+        status: String,
+        /// Note for whoever picks up next
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// Show the current session
+    Show {
+        /// Also list recent entries from sessions.jsonl, newest first
+        #[arg(long)]
+        log: bool,
+        /// Number of log entries to list
+        #[arg(short, long, default_value = "10", requires = "log")]
+        limit: usize,
+        /// Also list what changed during the session (so far, while active)
+        #[arg(long)]
+        deltas: bool,
     },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // A Windows upgrade leaves the previous binary parked next to this one
+    self_replace::cleanup_parked();
+
     // Agent mode automatically sets minimal output
     let is_agent_mode = cli.agent || matches!(cli.command, Commands::Agent { .. });
 
     match cli.command {
-        Commands::Init { force } => {
+        Commands::Init {
+            force,
+            git,
+            profile,
+            non_interactive,
+            scan_todos,
+        } => {
+            let profile = profile.as_deref().map(profile::get).transpose()?;
             if is_agent_mode {
-                agent::init(force, cli.dry_run)
+                agent::init(force, git, profile, cli.dry_run)?;
             } else {
-                human::init(force, cli.dry_run, cli.verbose)
+                let non_interactive = non_interactive || !std::io::stdin().is_terminal();
+                human::init(
+                    force,
+                    git,
+                    profile,
+                    non_interactive,
+                    cli.dry_run,
+                    cli.verbose,
+                )?;
             }
+            // A dry run created nothing to add tasks to
+            if scan_todos && !cli.dry_run {
+                todos::cmd_scan(is_agent_mode, false)?;
+            }
+            Ok(())
         }
 
-        Commands::Score { task_id, format } => {
+        Commands::ScanTodos => todos::cmd_scan(is_agent_mode, cli.dry_run),
+
+        Commands::ScanSecrets => secrets::cmd_scan(is_agent_mode),
+
+        Commands::Stubs { context } => stubs::cmd_stubs(context, is_agent_mode),
+
+        Commands::Score {
+            task_id: Some(task_id),
+            format,
+            ..
+        } => {
             if is_agent_mode {
                 agent::score(&task_id, &format)
             } else {
@@ -321,11 +1189,48 @@ fn main() -> Result<()> {
             }
         }
 
-        Commands::ShowTask { task_id } => human::show_task(&task_id, cli.verbose),
+        // --all, which clap requires when there is no task ID
+        Commands::Score { format, .. } => pss::cmd_score_all(&format, is_agent_mode),
 
-        Commands::ShowLessons { tag } => human::show_lessons(tag.as_deref(), cli.verbose),
+        Commands::Pss { subcommand } => pss::handle_command(subcommand, is_agent_mode),
 
-        Commands::ShowAudit { limit } => human::show_audit(limit, cli.verbose),
+        Commands::ShowTask {
+            task_id,
+            history,
+            assignee,
+            tags,
+        } => human::show_task(&task_id, assignee.as_deref(), &tags, history, cli.verbose),
+
+        Commands::ShowLessons {
+            tag,
+            since,
+            until,
+            limit,
+            offset,
+        } => human::show_lessons(tag.as_deref(), since, until, limit, offset, cli.verbose),
+
+        Commands::Lessons { subcommand } => {
+            lessons::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::ShowAudit {
+            limit,
+            severity,
+            task_id,
+            rule,
+            since,
+            format,
+        } => {
+            let filter = audit::AuditFilter {
+                severities: severity,
+                task_id,
+                rule,
+                since,
+            };
+            // Agent mode always gets JSON
+            let format = if is_agent_mode { "json" } else { format.as_str() };
+            human::show_audit(limit, &filter, format, cli.verbose)
+        }
 
         Commands::Agent { subcommand } => match subcommand {
             AgentCommands::UpdateTask {
@@ -333,55 +1238,127 @@ fn main() -> Result<()> {
                 strict,
                 pss,
                 timestamp,
-            } => agent::update_task(file.as_deref(), strict, pss, timestamp, cli.dry_run),
-            AgentCommands::AppendSummary { file } => agent::append_summary(&file, cli.dry_run),
-            AgentCommands::LogLesson { file } => agent::log_lesson(file.as_deref(), cli.dry_run),
-            AgentCommands::RatchetCoverage { coverage, task_id } => {
-                agent::ratchet_coverage(coverage, task_id.as_deref(), cli.dry_run)
-            }
+                comment,
+                allow_transition,
+                patch,
+            } => agent::update_task(
+                file.as_deref(),
+                agent::UpdateTaskOptions {
+                    strict,
+                    strict_parse: json_schema::strict_parse_enabled(cli.strict_parse),
+                    pss,
+                    timestamp,
+                    comment,
+                    allow_transition,
+                    patch,
+                },
+                cli.dry_run,
+            ),
+            AgentCommands::AppendSummary { file } => agent::append_summary(
+                file.as_deref(),
+                json_schema::strict_parse_enabled(cli.strict_parse),
+                cli.dry_run,
+            ),
+            AgentCommands::LogLesson { file } => agent::log_lesson(
+                file.as_deref(),
+                json_schema::strict_parse_enabled(cli.strict_parse),
+                cli.dry_run,
+            ),
+            AgentCommands::RatchetCoverage {
+                coverage,
+                from_file,
+                run,
+                task_id,
+            } => agent::ratchet_coverage(
+                coverage,
+                from_file.as_deref(),
+                run,
+                task_id.as_deref(),
+                cli.dry_run,
+            ),
             AgentCommands::Info => agent::info(),
+            AgentCommands::Resume { history } => agent::resume(history),
         },
 
         Commands::Check {
             fix,
             buckle_trigger,
+            ci,
+            threshold,
+            output: format,
         } => {
-            if buckle_trigger {
+            let format = output::OutputFormat::parse(&format)?;
+            // The CI threshold, when gating
+            let ci = ci.then(|| checks::ci_threshold(threshold)).transpose()?;
+            if format != output::OutputFormat::Text {
+                output::check(format, ci)
+            } else if buckle_trigger {
                 if is_agent_mode {
                     agent::check_buckle_trigger()
                 } else {
                     human::check_buckle_trigger(cli.verbose)
                 }
             } else if is_agent_mode {
-                agent::check(fix)
+                agent::check(fix, ci, cli.dry_run)
             } else {
-                human::check(fix, cli.verbose)
+                human::check(fix, ci, cli.dry_run, cli.verbose)
             }
         }
 
         Commands::Completions { shell } => human::completions(&shell),
 
-        Commands::Update { check, yes } => {
+        Commands::Complete { words } => {
+            use clap::CommandFactory;
+            completion::complete(Cli::command(), &words, is_agent_mode)
+        }
+
+        Commands::Update {
+            check,
+            yes,
+            force,
+            skip_verify,
+            channel,
+        } => {
+            let channel = github::ReleaseChannel::resolve(channel.as_deref())?;
             if is_agent_mode {
-                agent::update(check, yes)
+                agent::update(check, yes, force, skip_verify, channel, cli.dry_run)
             } else {
-                human::update(check, yes, cli.verbose)
+                human::update(check, yes, force, skip_verify, channel, cli.dry_run, cli.verbose)
             }
         }
 
-        Commands::Upgrade { check, yes } => {
+        Commands::Upgrade {
+            check,
+            yes,
+            skip_verify,
+            channel,
+        } => {
+            let channel = github::ReleaseChannel::resolve(channel.as_deref())?;
             if is_agent_mode {
-                agent::upgrade(check, yes)
+                agent::upgrade(check, yes, skip_verify, channel)
             } else {
-                human::upgrade(check, yes, cli.verbose)
+                human::upgrade(check, yes, skip_verify, channel, cli.verbose)
             }
         }
 
-        Commands::Version { project, latest } => {
+        Commands::Version {
+            project,
+            latest,
+            build_info,
+            channel,
+        } => {
+            if build_info {
+                return if is_agent_mode {
+                    agent::build_info()
+                } else {
+                    human::build_info()
+                };
+            }
+            let channel = github::ReleaseChannel::resolve(channel.as_deref())?;
             if is_agent_mode {
-                agent::version(project, latest)
+                agent::version(project, latest, channel)
             } else {
-                human::version(project, latest, cli.verbose)
+                human::version(project, latest, channel, cli.verbose)
             }
         }
 
@@ -391,14 +1368,46 @@ fn main() -> Result<()> {
             all,
             schema,
             strict,
+            output: format,
         } => {
-            if is_agent_mode {
-                agent::validate(all, schema.as_deref(), strict)
+            let format = output::OutputFormat::parse(&format)?;
+            let strict_parse = json_schema::strict_parse_enabled(cli.strict_parse);
+            if format != output::OutputFormat::Text {
+                output::validate(format, all, schema.as_deref(), strict, strict_parse)
+            } else if is_agent_mode {
+                agent::validate(all, schema.as_deref(), strict, strict_parse)
             } else {
-                human::validate(all, schema.as_deref(), strict, cli.verbose)
+                human::validate(all, schema.as_deref(), strict, strict_parse, cli.verbose)
             }
         }
 
+        Commands::Keys { subcommand } => {
+            signing::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::VerifyIntegrity => integrity::cmd_verify(is_agent_mode),
+
+        Commands::Task { subcommand } => task::handle_command(subcommand, is_agent_mode, cli.dry_run),
+
+        Commands::Watch {
+            format,
+            debounce_ms,
+            check,
+            stubs,
+        } => watch::watch(&format, debounce_ms, check, stubs, is_agent_mode),
+
+        Commands::MergeJsonl { base, ours, theirs } => merge::merge_jsonl(&base, &ours, &theirs),
+
+        Commands::Github { subcommand } => {
+            github::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Git { subcommand } => git::handle_command(subcommand, is_agent_mode, cli.dry_run),
+
+        Commands::Hooks { subcommand } => {
+            hooks::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
         Commands::Coord { subcommand } => {
             coord::handle_command(subcommand, is_agent_mode, cli.verbose)
         }
@@ -411,11 +1420,11 @@ fn main() -> Result<()> {
                     human::primer_init(force, cli.verbose)
                 }
             }
-            PrimerCommands::Show { file } => {
+            PrimerCommands::Show { file, scope, format } => {
                 if is_agent_mode {
-                    agent::primer_show(file.as_deref())
+                    agent::primer_show(file.as_deref(), scope.as_deref(), &format)
                 } else {
-                    human::primer_show(file.as_deref(), cli.verbose)
+                    human::primer_show(file.as_deref(), scope.as_deref(), &format, cli.verbose)
                 }
             }
             PrimerCommands::Check => {
@@ -425,13 +1434,84 @@ fn main() -> Result<()> {
                     human::primer_check(cli.verbose)
                 }
             }
-            PrimerCommands::Parse { format } => {
+            PrimerCommands::Update { field, yes } => {
                 if is_agent_mode {
-                    agent::primer_parse(&format)
+                    agent::primer_update(&field, yes, cli.dry_run)
                 } else {
-                    human::primer_parse(&format, cli.verbose)
+                    human::primer_update(&field, yes, cli.dry_run)
                 }
             }
+            PrimerCommands::Parse { format, task } => {
+                if is_agent_mode {
+                    agent::primer_parse(&format, task.as_deref())
+                } else {
+                    human::primer_parse(&format, task.as_deref(), cli.verbose)
+                }
+            }
+        },
+
+        Commands::Schema { subcommand } => {
+            json_schema::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Serve { coord: _, bind } => coord_server::cmd_serve(&bind, is_agent_mode),
+
+        Commands::Sync { subcommand } => sync::handle_command(subcommand, is_agent_mode, cli.dry_run),
+
+        Commands::Export { out } => bundle::cmd_export(&out, cli.dry_run, is_agent_mode),
+
+        Commands::Import { subcommand } => {
+            import::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Metrics { subcommand } => {
+            metrics::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Coverage { subcommand } => {
+            coverage::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Tui { interval } => tui::run(interval, is_agent_mode),
+
+        Commands::Report { out, html, since } => report::run(
+            out.as_deref(),
+            html.as_deref(),
+            since,
+            cli.dry_run,
+            is_agent_mode,
+        ),
+
+        Commands::Graph {
+            format,
+            critical_path,
+            out,
+        } => graph::run(
+            &format,
+            critical_path,
+            out.as_deref(),
+            cli.dry_run,
+            is_agent_mode,
+        ),
+
+        Commands::Prioritize { explain } => {
+            prioritize::run(explain, cli.dry_run, is_agent_mode)
+        }
+
+        Commands::History { subcommand } => {
+            history::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Milestone { subcommand } => {
+            milestone::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Phase { subcommand } => {
+            phase::handle_command(subcommand, is_agent_mode, cli.dry_run)
+        }
+
+        Commands::Session { subcommand } => {
+            session::handle_command(subcommand, is_agent_mode, cli.dry_run)
         }
     }
 }
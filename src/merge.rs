@@ -0,0 +1,220 @@
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Name of the merge driver registered in git config
+pub const MERGE_DRIVER: &str = "rotd-jsonl";
+
+/// Identity of a JSONL line for deduplication: its `id` and `updated_at`
+/// when it has both, so one write of a task that both sides carry collapses
+/// even if its other fields were serialized differently. Lines without them
+/// fall back to their content with keys in a fixed order, so successive
+/// lines for the same task (pending, then complete) stay distinct. Hash chain
+/// links are left out, since merging re-links lines.
+fn line_key(line: &str) -> String {
+    let entry = crate::integrity::unlinked(line);
+    match serde_json::from_str::<serde_json::Value>(&entry) {
+        Ok(value) => match (id_of(&value), updated_at_of(&value)) {
+            (Some(id), Some(updated_at)) => {
+                serde_json::json!({ "id": id, "updated_at": updated_at }).to_string()
+            }
+            _ => value.to_string(),
+        },
+        Err(_) => entry.trim().to_string(),
+    }
+}
+
+fn id_of(value: &serde_json::Value) -> Option<String> {
+    match value.get("id")? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn updated_at_of(value: &serde_json::Value) -> Option<&str> {
+    value.get("updated_at")?.as_str()
+}
+
+/// Order of two `updated_at` values: as instants when both parse, otherwise
+/// as text
+fn compare_updated_at(a: &str, b: &str) -> std::cmp::Ordering {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Put each task's entries in `updated_at` order within the slots they
+/// already occupy, so the last line for a task is its newest write however
+/// the two sides interleaved. Tasks with any untimestamped entry keep their
+/// order.
+fn order_by_updated_at(lines: Vec<String>) -> Vec<String> {
+    let values: Vec<Option<serde_json::Value>> = lines
+        .iter()
+        .map(|l| serde_json::from_str(&crate::integrity::unlinked(l)).ok())
+        .collect();
+    let mut slots: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, value) in values.iter().enumerate() {
+        if let Some(id) = value.as_ref().and_then(id_of) {
+            slots.entry(id).or_default().push(i);
+        }
+    }
+
+    let mut ordered = lines.clone();
+    for positions in slots.values().filter(|p| p.len() > 1) {
+        let stamps: Option<Vec<&str>> = positions
+            .iter()
+            .map(|&i| values[i].as_ref().and_then(updated_at_of))
+            .collect();
+        let Some(stamps) = stamps else { continue };
+        let mut by_time: Vec<usize> = (0..positions.len()).collect();
+        by_time.sort_by(|&a, &b| compare_updated_at(stamps[a], stamps[b]));
+        for (&slot, &from) in positions.iter().zip(&by_time) {
+            ordered[slot] = lines[positions[from]].clone();
+        }
+    }
+    ordered
+}
+
+fn non_empty_lines(content: &str) -> Vec<&str> {
+    content.lines().filter(|l| !l.trim().is_empty()).collect()
+}
+
+/// Three-way union of JSONL logs.
+/// Keeps our lines in order, then appends their new lines in order. Lines
+/// present in the base that either side removed stay removed, and entries
+/// with the same `id` and `updated_at` are written once. When both sides
+/// changed a task, its entries are then ordered by `updated_at`, so the
+/// newest write is the one readers see. A hash chain is re-linked over the result (see
+/// `integrity::relink`), so `verify-integrity` still passes after a merge.
+pub fn merge_jsonl_lines(base: &str, ours: &str, theirs: &str) -> Vec<String> {
+    let base_keys: HashSet<String> = non_empty_lines(base).into_iter().map(line_key).collect();
+    let ours_lines = non_empty_lines(ours);
+    let theirs_lines = non_empty_lines(theirs);
+    let ours_keys: HashSet<String> = ours_lines.iter().map(|l| line_key(l)).collect();
+    let theirs_keys: HashSet<String> = theirs_lines.iter().map(|l| line_key(l)).collect();
+
+    let mut seen = HashSet::new();
+    let mut merged = Vec::new();
+
+    for line in ours_lines.iter().chain(theirs_lines.iter()) {
+        let key = line_key(line);
+        let removed_by_one_side =
+            base_keys.contains(&key) && !(ours_keys.contains(&key) && theirs_keys.contains(&key));
+        if removed_by_one_side || !seen.insert(key) {
+            continue;
+        }
+        merged.push(line.to_string());
+    }
+
+    crate::integrity::relink(order_by_updated_at(merged))
+}
+
+/// Entry point for `rotd merge-jsonl %O %A %B`; writes the result to `ours`
+/// as git expects from a merge driver
+pub fn merge_jsonl(base: &Path, ours: &Path, theirs: &Path) -> Result<()> {
+    let read = |p: &Path| -> Result<String> {
+        if p.exists() {
+            fs::read_to_string(p).with_context(|| format!("Failed to read {}", p.display()))
+        } else {
+            Ok(String::new())
+        }
+    };
+
+    let merged = merge_jsonl_lines(&read(base)?, &read(ours)?, &read(theirs)?);
+
+    let mut content = merged.join("\n");
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    fs::write(ours, content).with_context(|| format!("Failed to write {}", ours.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_jsonl_union() {
+        let base = r#"{"id":"1","updated_at":"t1"}
+{"id":"2","updated_at":"t1"}
+"#;
+        let ours = r#"{"id":"1","updated_at":"t1"}
+{"id":"2","updated_at":"t1"}
+{"id":"3","updated_at":"t2"}
+"#;
+        // Theirs pruned task 2, re-logged task 1, and added task 4
+        let theirs = r#"{"id":"1","updated_at":"t1"}
+{"id":"1","updated_at":"t3"}
+{"id":"4","updated_at":"t2"}
+"#;
+        assert_eq!(
+            merge_jsonl_lines(base, ours, theirs),
+            vec![
+                r#"{"id":"1","updated_at":"t1"}"#,
+                r#"{"id":"3","updated_at":"t2"}"#,
+                r#"{"id":"1","updated_at":"t3"}"#,
+                r#"{"id":"4","updated_at":"t2"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_jsonl_keeps_status_history_without_timestamps() {
+        let base = r#"{"id":"1","status":"pending"}
+"#;
+        let ours = r#"{"id":"1","status":"pending"}
+{"id":"1","status":"complete"}
+"#;
+        let theirs = r#"{"id":"1","status":"pending"}
+{"status":"pending","id":"2"}
+"#;
+        assert_eq!(
+            merge_jsonl_lines(base, ours, theirs),
+            vec![
+                r#"{"id":"1","status":"pending"}"#,
+                r#"{"id":"1","status":"complete"}"#,
+                r#"{"status":"pending","id":"2"}"#,
+            ]
+        );
+        // The same entry with its keys in another order is still one entry
+        assert_eq!(
+            merge_jsonl_lines("", r#"{"id":"2","status":"pending"}"#, theirs).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_merge_jsonl_both_sides_modify_same_task() {
+        let base = r#"{"id":"1","status":"pending","updated_at":"2026-01-01T00:00:00Z"}
+"#;
+        // Ours completed the task last; theirs started it in between
+        let ours = r#"{"id":"1","status":"pending","updated_at":"2026-01-01T00:00:00Z"}
+{"id":"1","status":"complete","updated_at":"2026-01-03T00:00:00Z"}
+"#;
+        let theirs = r#"{"id":"1","status":"pending","updated_at":"2026-01-01T00:00:00Z"}
+{"id":"1","status":"in_progress","updated_at":"2026-01-02T00:00:00+00:00"}
+{"updated_at":"2026-01-03T00:00:00Z","id":"1","status":"complete","notes":"done"}
+"#;
+        assert_eq!(
+            merge_jsonl_lines(base, ours, theirs),
+            vec![
+                r#"{"id":"1","status":"pending","updated_at":"2026-01-01T00:00:00Z"}"#,
+                r#"{"id":"1","status":"in_progress","updated_at":"2026-01-02T00:00:00+00:00"}"#,
+                r#"{"id":"1","status":"complete","updated_at":"2026-01-03T00:00:00Z"}"#,
+            ]
+        );
+
+        // Whichever side is "ours", the newest write ends up last
+        let swapped = merge_jsonl_lines(base, theirs, ours);
+        assert_eq!(swapped.len(), 3);
+        assert!(swapped[2].contains("2026-01-03"));
+        assert!(swapped[1].contains("in_progress"));
+    }
+}
@@ -0,0 +1,620 @@
+use anyhow::{Context, Result};
+use semver::Version;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path};
+
+use crate::fs_ops::{read_json, write_json};
+use crate::github::{self, GitHubAsset, GitHubRelease, ReleaseChannel, ReleaseInfo};
+use crate::schema::{AppliedFile, ChangeEntry, ProjectPrimer, ProjectVersion, UpdateManifest};
+
+/// Release assets named `rotd-methodology-<version>.tar.gz` carry the
+/// templates and schemas that `rotd update` installs
+pub const BUNDLE_PREFIX: &str = "rotd-methodology";
+
+/// Optional list of `ChangeEntry` records at the root of a bundle
+pub const BUNDLE_CHANGES_FILE: &str = "CHANGES.json";
+
+/// Top-level directories a bundle may carry; everything else in the project
+/// directory (tasks, lessons, scores) is never touched by an update
+pub const BUNDLE_ROOTS: [&str; 2] = ["templates", "schema"];
+
+/// Where a bundle's `templates/` is installed under `.rotd/`, kept apart from
+/// the task templates `rotd task new --template` reads from `.rotd/templates/`
+pub const TEMPLATES_INSTALL_DIR: &str = "methodology/templates";
+
+/// Suffix for the incoming copy of a file that was edited locally
+pub const CONFLICT_SUFFIX: &str = ".new";
+
+/// Contents of a methodology bundle
+#[derive(Debug, Default)]
+pub struct Bundle {
+    /// Files keyed by path relative to `.rotd/`
+    pub files: Vec<(String, Vec<u8>)>,
+    pub changes: Vec<ChangeEntry>,
+}
+
+/// Result of comparing the project against the latest release
+pub struct UpdateCheck {
+    /// Recorded in `version.json`; `None` when the project has never been updated
+    pub current_version: Option<String>,
+    pub latest_version: String,
+    pub release: ReleaseInfo,
+    pub update_available: bool,
+    /// Changes from every release after the current version, oldest first
+    pub changes: Vec<ReleaseChange>,
+}
+
+/// A change announced in a release's notes
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseChange {
+    pub version: String,
+    #[serde(flatten)]
+    pub entry: ChangeEntry,
+}
+
+impl UpdateCheck {
+    /// Changes split into breaking and non-breaking
+    pub fn grouped_changes(&self) -> (Vec<&ReleaseChange>, Vec<&ReleaseChange>) {
+        self.changes.iter().partition(|c| c.entry.breaking)
+    }
+}
+
+/// What an applied update changed
+#[derive(Debug, Serialize)]
+pub struct UpdateOutcome {
+    pub previous_version: String,
+    pub new_version: String,
+    pub bundle_sha256: String,
+    pub checksum_verified: bool,
+    pub signature_verified: bool,
+    pub files: Vec<AppliedFile>,
+    pub primer_created: bool,
+}
+
+impl UpdateOutcome {
+    pub fn conflicts(&self) -> Vec<&AppliedFile> {
+        self.files
+            .iter()
+            .filter(|f| f.action == "conflict")
+            .collect()
+    }
+}
+
+fn current_version() -> Result<Option<String>> {
+    let path = crate::common::version_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let version: ProjectVersion = read_json(&path)?;
+    Ok(Some(version.version))
+}
+
+fn parse_version(value: &str) -> Option<Version> {
+    Version::parse(value.trim_start_matches('v')).ok()
+}
+
+/// Turn a release-notes bullet into a change entry.
+/// Conventional prefixes (`feat(schema)!: ...`) set the type and component;
+/// `!` or the word "breaking" marks it breaking, and "migration" marks it as
+/// needing one.
+pub fn parse_change_line(line: &str) -> ChangeEntry {
+    let text = line.trim().trim_start_matches(['-', '*', '+']).trim();
+
+    let mut change_type = "change".to_string();
+    let mut component = "methodology".to_string();
+    let mut breaking = false;
+    let mut description = text.to_string();
+
+    if let Some((prefix, rest)) = text.split_once(':') {
+        let conventional = !prefix.is_empty()
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "()!-_".contains(c));
+        if conventional {
+            let prefix = match prefix.strip_suffix('!') {
+                Some(p) => {
+                    breaking = true;
+                    p
+                }
+                None => prefix,
+            };
+            match prefix.split_once('(') {
+                Some((kind, scope)) => {
+                    change_type = kind.to_string();
+                    component = scope.trim_end_matches(')').to_string();
+                }
+                // `BREAKING: ...` is a marker, not a change type
+                None if prefix.eq_ignore_ascii_case("breaking") => breaking = true,
+                None => change_type = prefix.to_string(),
+            }
+            description = rest.trim().to_string();
+        }
+    }
+
+    let lower = text.to_lowercase();
+    ChangeEntry {
+        change_type,
+        component,
+        breaking: breaking || lower.contains("breaking"),
+        migration_required: lower.contains("migration"),
+        description,
+    }
+}
+
+/// Changes from releases on `channel` newer than `current` and no newer than
+/// `latest`, oldest release first
+pub fn changes_between(
+    releases: &[GitHubRelease],
+    channel: ReleaseChannel,
+    current: Option<&Version>,
+    latest: &Version,
+) -> Vec<ReleaseChange> {
+    let mut in_range: Vec<(Version, &GitHubRelease)> = releases
+        .iter()
+        .filter_map(|r| {
+            let version = parse_version(&r.tag_name)?;
+            let newer = current.is_none_or(|c| version > *c);
+            (newer && version <= *latest && channel.accepts(r, &version)).then_some((version, r))
+        })
+        .collect();
+    in_range.sort_by(|a, b| a.0.cmp(&b.0));
+
+    in_range
+        .into_iter()
+        .flat_map(|(version, release)| {
+            github::extract_changes(release.body.as_deref().unwrap_or_default())
+                .into_iter()
+                .map(move |line| ReleaseChange {
+                    version: version.to_string(),
+                    entry: parse_change_line(&line),
+                })
+        })
+        .collect()
+}
+
+/// Look up the newest methodology release on a channel
+pub fn check_for_update(channel: ReleaseChannel) -> Result<Option<UpdateCheck>> {
+    let current_version = current_version()?;
+    let releases = github::fetch_releases()?;
+    let (latest, semver) = match github::select_release(&releases, channel) {
+        Some(selected) => selected,
+        None => return Ok(None),
+    };
+
+    let current = current_version.as_deref().and_then(parse_version);
+    let update_available = current.as_ref().is_none_or(|c| semver > *c);
+    let changes = changes_between(&releases, channel, current.as_ref(), &semver);
+
+    Ok(Some(UpdateCheck {
+        current_version,
+        latest_version: semver.to_string(),
+        release: github::release_info(latest, semver),
+        update_available,
+        changes,
+    }))
+}
+
+/// The bundle asset attached to a release
+pub fn find_bundle_asset(release: &ReleaseInfo) -> Result<GitHubAsset> {
+    release
+        .assets
+        .iter()
+        .find(|a| a.name.starts_with(BUNDLE_PREFIX) && a.name.ends_with(".tar.gz"))
+        .cloned()
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Release {} has no methodology bundle ({}-<version>.tar.gz)",
+                release.version,
+                BUNDLE_PREFIX
+            )
+        })
+}
+
+/// Validate a bundle entry path, dropping a leading `rotd-methodology-*/`
+/// directory if the archive was packed with one, and return where it is
+/// installed under `.rotd/`
+fn bundle_entry_path(path: &Path) -> Result<Option<String>> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Bundle entry {} escapes the .rotd directory",
+                    path.display()
+                ));
+            }
+        }
+    }
+    if parts.len() > 1 && parts[0].starts_with(BUNDLE_PREFIX) {
+        parts.remove(0);
+    }
+
+    let relative = parts.join("/");
+    if relative == BUNDLE_CHANGES_FILE {
+        return Ok(Some(relative));
+    }
+    match parts.first() {
+        Some(root) if parts.len() > 1 && root == "templates" => Ok(Some(format!(
+            "{}/{}",
+            TEMPLATES_INSTALL_DIR,
+            parts[1..].join("/")
+        ))),
+        Some(root) if parts.len() > 1 && BUNDLE_ROOTS.contains(&root.as_str()) => {
+            Ok(Some(relative))
+        }
+        // READMEs and licenses at the top level are not installed
+        _ if parts.len() <= 1 => Ok(None),
+        _ => Err(anyhow::anyhow!(
+            "Bundle entry {} is outside {}/",
+            relative,
+            BUNDLE_ROOTS.join("/ and ")
+        )),
+    }
+}
+
+/// Unpack a `.tar.gz` methodology bundle in memory
+pub fn read_bundle(data: &[u8]) -> Result<Bundle> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(data));
+    let mut bundle = Bundle::default();
+
+    for entry in archive
+        .entries()
+        .context("Methodology bundle is not a valid tar.gz archive")?
+    {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = match bundle_entry_path(&entry.path()?)? {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+
+        if path == BUNDLE_CHANGES_FILE {
+            bundle.changes = serde_json::from_slice(&content).with_context(|| {
+                format!("Invalid {} in methodology bundle", BUNDLE_CHANGES_FILE)
+            })?;
+        } else {
+            bundle.files.push((path, content));
+        }
+    }
+
+    if bundle.files.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Methodology bundle contains no files under {}/",
+            BUNDLE_ROOTS.join("/ or ")
+        ));
+    }
+    bundle.files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(bundle)
+}
+
+/// Write bundle files under `root`.
+/// A file is overwritten only if it is missing or still matches what the
+/// previous update installed; a locally edited file is kept and the incoming
+/// copy is written next to it as `<file>.new`, unless `force` is set.
+pub fn apply_bundle(
+    root: &Path,
+    bundle: &Bundle,
+    previous: &[AppliedFile],
+    force: bool,
+    dry_run: bool,
+) -> Result<Vec<AppliedFile>> {
+    let installed: HashMap<&str, &str> = previous
+        .iter()
+        .map(|f| (f.path.as_str(), f.sha256.as_str()))
+        .collect();
+    let mut applied = Vec::new();
+
+    for (path, content) in &bundle.files {
+        let target = root.join(path);
+        let incoming = github::sha256_hex(content);
+
+        let action = if !target.exists() {
+            "added"
+        } else {
+            let local = github::sha256_hex(
+                &fs::read(&target)
+                    .with_context(|| format!("Failed to read {}", target.display()))?,
+            );
+            if local == incoming {
+                "unchanged"
+            } else if force || installed.get(path.as_str()) == Some(&local.as_str()) {
+                "updated"
+            } else {
+                "conflict"
+            }
+        };
+
+        if !dry_run && action != "unchanged" {
+            let destination = if action == "conflict" {
+                root.join(format!("{}{}", path, CONFLICT_SUFFIX))
+            } else {
+                target
+            };
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&destination, content)
+                .with_context(|| format!("Failed to write {}", destination.display()))?;
+        }
+
+        applied.push(AppliedFile {
+            path: path.clone(),
+            sha256: incoming,
+            action: action.to_string(),
+        });
+    }
+
+    Ok(applied)
+}
+
+/// Create a starter `primer.jsonc` if the project has none
+fn create_primer_if_missing() -> Result<bool> {
+    let primer_path = crate::common::primer_path();
+    if primer_path.exists() {
+        return Ok(false);
+    }
+
+    // Get project name from current directory
+    let current_dir = std::env::current_dir()?;
+    let project_name = current_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Project")
+        .to_string();
+
+    let primer = ProjectPrimer {
+        name: project_name,
+        scope: "root".to_string(),
+        description: "TODO: Add project description".to_string(),
+        status: "active".to_string(),
+        language: "TODO: Specify primary language".to_string(),
+        entry_points: vec!["TODO: Add entry points".to_string()],
+        test_dirs: vec!["tests/".to_string(), "test/".to_string()],
+        dependencies: vec!["TODO: List key dependencies".to_string()],
+        known_issues: vec!["TODO: Document any known issues".to_string()],
+        key_concepts: vec!["TODO: Add key concepts".to_string()],
+        preferred_agents: Some(vec!["Claude Sonnet".to_string(), "Claude Opus".to_string()]),
+        suggested_starting_points: vec![
+            "TODO: Add suggested starting points for new developers or agents".to_string(),
+        ],
+        major_components: None,
+        update_triggers: Some(vec![
+            "Major architectural changes".to_string(),
+            "New features or significant functionality changes".to_string(),
+            "Documentation updates".to_string(),
+        ]),
+    };
+
+    crate::primer::save(&primer_path, &primer)?;
+    Ok(true)
+}
+
+/// Download the release's bundle, apply it to `.rotd/`, and record the
+/// result in `version.json` and `update_manifest.json`.
+/// With `dry_run` the bundle is downloaded and compared but nothing is written.
+pub fn install(
+    check: &UpdateCheck,
+    force: bool,
+    skip_verify: bool,
+    dry_run: bool,
+) -> Result<UpdateOutcome> {
+    let asset = find_bundle_asset(&check.release)?;
+    let download = github::download_verified(&check.release, &asset, skip_verify)?;
+    let bundle = read_bundle(&download.data)?;
+
+    let manifest_path = crate::common::update_manifest_path();
+    let previous_files = if manifest_path.exists() {
+        read_json::<UpdateManifest>(&manifest_path)
+            .map(|m| m.files)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let files = apply_bundle(
+        &crate::common::shared_path(),
+        &bundle,
+        &previous_files,
+        force,
+        dry_run,
+    )?;
+    let primer_created = if dry_run {
+        !crate::common::primer_path().exists()
+    } else {
+        create_primer_if_missing()?
+    };
+
+    let previous_version = check
+        .current_version
+        .clone()
+        .unwrap_or_else(|| "none".to_string());
+
+    let mut changes = vec![ChangeEntry {
+        change_type: "methodology_update".to_string(),
+        component: "rotd_project".to_string(),
+        description: format!(
+            "Updated ROTD methodology from {} to {}",
+            previous_version, check.latest_version
+        ),
+        breaking: false,
+        migration_required: false,
+    }];
+    changes.extend(bundle.changes.iter().cloned());
+
+    let outcome = UpdateOutcome {
+        previous_version: previous_version.clone(),
+        new_version: check.latest_version.clone(),
+        bundle_sha256: download.sha256.clone(),
+        checksum_verified: download.checksum_verified,
+        signature_verified: download.signature_verified,
+        files: files.clone(),
+        primer_created,
+    };
+    if dry_run {
+        return Ok(outcome);
+    }
+
+    write_json(
+        &manifest_path,
+        &UpdateManifest {
+            version: check.latest_version.clone(),
+            date: chrono::Utc::now().to_rfc3339(),
+            changes,
+            previous_version,
+            files,
+        },
+    )?;
+    write_json(
+        &crate::common::version_path(),
+        &ProjectVersion {
+            version: check.latest_version.clone(),
+            updated_at: Some(chrono::Utc::now()),
+            manifest_hash: Some(download.sha256),
+        },
+    )?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn test_read_bundle() {
+        let data = bundle_archive(&[
+            ("rotd-methodology-1.4.0/README.md", b"readme"),
+            ("rotd-methodology-1.4.0/schema/task.schema.json", b"{}"),
+            ("rotd-methodology-1.4.0/templates/primer.jsonc", b"{}"),
+            (
+                "rotd-methodology-1.4.0/CHANGES.json",
+                br#"[{"change_type":"schema","component":"task","description":"Add tags","breaking":true,"migration_required":false}]"#,
+            ),
+        ]);
+        let bundle = read_bundle(&data).unwrap();
+        let paths: Vec<&str> = bundle.files.iter().map(|(p, _)| p.as_str()).collect();
+        // Bundle templates don't land among the task templates in .rotd/templates/
+        assert_eq!(
+            paths,
+            vec!["methodology/templates/primer.jsonc", "schema/task.schema.json"]
+        );
+        assert_eq!(bundle.changes.len(), 1);
+        assert!(bundle.changes[0].breaking);
+
+        // Anything outside templates/ and schema/ is rejected
+        let data = bundle_archive(&[
+            ("templates/task.jsonc", b"{}"),
+            ("local/tasks.jsonl", b"{}"),
+        ]);
+        assert!(read_bundle(&data).is_err());
+    }
+
+    #[test]
+    fn test_changes_between() {
+        let release = |tag: &str, body: &str, prerelease: bool| GitHubRelease {
+            tag_name: tag.to_string(),
+            name: None,
+            published_at: None,
+            body: Some(body.to_string()),
+            html_url: String::new(),
+            assets: Vec::new(),
+            draft: false,
+            prerelease,
+        };
+        let releases = vec![
+            release(
+                "v1.5.0",
+                "- feat(schema)!: Rename phase to milestone",
+                false,
+            ),
+            release("v1.3.0", "- Old change", false),
+            release(
+                "v1.4.0",
+                "Notes\n- fix: Tighten PSS rubric\n- BREAKING: drop v1 tasks (migration required)",
+                false,
+            ),
+            release("v1.6.0-beta.1", "- Beta only", true),
+        ];
+        let current = Version::parse("1.3.0").unwrap();
+        let latest = Version::parse("1.5.0").unwrap();
+
+        let changes = changes_between(&releases, ReleaseChannel::Stable, Some(&current), &latest);
+        let summary: Vec<(&str, &str, bool)> = changes
+            .iter()
+            .map(|c| {
+                (
+                    c.version.as_str(),
+                    c.entry.description.as_str(),
+                    c.entry.breaking,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("1.4.0", "Tighten PSS rubric", false),
+                ("1.4.0", "drop v1 tasks (migration required)", true),
+                ("1.5.0", "Rename phase to milestone", true),
+            ]
+        );
+        assert_eq!(changes[0].entry.change_type, "fix");
+        assert_eq!(changes[1].entry.change_type, "change");
+        assert!(changes[1].entry.migration_required);
+        assert_eq!(changes[2].entry.component, "schema");
+    }
+
+    #[test]
+    fn test_apply_bundle_detects_local_edits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let bundle = |content: &[u8]| Bundle {
+            files: vec![
+                ("schema/a.json".to_string(), content.to_vec()),
+                ("schema/b.json".to_string(), content.to_vec()),
+            ],
+            changes: Vec::new(),
+        };
+
+        let first = apply_bundle(root, &bundle(b"v1"), &[], false, false).unwrap();
+        assert!(first.iter().all(|f| f.action == "added"));
+
+        // a.json is edited locally; b.json still matches what was installed
+        fs::write(root.join("schema/a.json"), b"mine").unwrap();
+        let second = apply_bundle(root, &bundle(b"v2"), &first, false, false).unwrap();
+        let actions: Vec<&str> = second.iter().map(|f| f.action.as_str()).collect();
+        assert_eq!(actions, vec!["conflict", "updated"]);
+        assert_eq!(fs::read(root.join("schema/a.json")).unwrap(), b"mine");
+        assert_eq!(fs::read(root.join("schema/a.json.new")).unwrap(), b"v2");
+        assert_eq!(fs::read(root.join("schema/b.json")).unwrap(), b"v2");
+
+        let forced = apply_bundle(root, &bundle(b"v2"), &second, true, false).unwrap();
+        assert_eq!(forced[0].action, "updated");
+        assert_eq!(forced[1].action, "unchanged");
+        assert_eq!(fs::read(root.join("schema/a.json")).unwrap(), b"v2");
+    }
+}
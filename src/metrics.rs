@@ -0,0 +1,564 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::MetricsCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::latest_tasks;
+use crate::output::escape_csv;
+use crate::schema::{TaskEntry, TaskHistoryEvent, TaskStatus};
+
+/// Output formats for `metrics velocity`
+pub const VELOCITY_FORMATS: [&str; 3] = ["table", "json", "csv"];
+
+/// Phase name for tasks without one
+const NO_PHASE: &str = "none";
+
+pub fn handle_command(cmd: MetricsCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        MetricsCommands::Velocity { weeks, format, out } => {
+            cmd_velocity(weeks, &format, out.as_deref(), dry_run, is_agent_mode)
+        }
+        MetricsCommands::Export {
+            prometheus,
+            stale_timeout,
+        } => cmd_export(&prometheus, stale_timeout, dry_run, is_agent_mode),
+    }
+}
+
+/// Completions and remaining work for one week
+#[derive(Debug, Serialize, PartialEq)]
+pub struct WeekVelocity {
+    /// Monday the week starts on
+    pub week_start: NaiveDate,
+    pub completed: usize,
+    /// Tasks created and not yet complete at the end of the week
+    pub remaining: usize,
+    /// `remaining`, split by phase
+    pub remaining_by_phase: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct CycleTime {
+    /// Completed tasks with a known start
+    pub tasks: usize,
+    pub average_hours: f64,
+    pub median_hours: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Velocity {
+    pub weeks: Vec<WeekVelocity>,
+    /// Average completions per week over `weeks`
+    pub average_per_week: f64,
+    pub cycle_time: CycleTime,
+    /// Tasks not yet complete, by phase
+    pub remaining_by_phase: BTreeMap<String, usize>,
+}
+
+/// When work on a task started and finished, where known
+struct TaskSpan<'a> {
+    phase: &'a str,
+    created: Option<DateTime<Utc>>,
+    started: Option<DateTime<Utc>>,
+    completed: Option<DateTime<Utc>>,
+}
+
+/// Spans from the latest task entries. Completion comes from `completed`,
+/// then the first history event marking the task complete, then
+/// `updated_at`; the start is the first move to in_progress, else `created`.
+fn task_spans<'a>(tasks: &'a [TaskEntry], history: &[TaskHistoryEvent]) -> Vec<TaskSpan<'a>> {
+    tasks
+        .iter()
+        .map(|task| {
+            let mut events: Vec<&TaskHistoryEvent> =
+                history.iter().filter(|e| e.task_id == task.id).collect();
+            events.sort_by_key(|e| e.timestamp);
+            let first = |status: &str| events.iter().find(|e| e.status == status).map(|e| e.timestamp);
+
+            let completed = (task.status == TaskStatus::Complete).then(|| {
+                task.completed
+                    .or_else(|| first(TaskStatus::Complete.as_str()))
+                    .or(task.updated_at)
+            });
+            TaskSpan {
+                phase: task.phase.as_deref().unwrap_or(NO_PHASE),
+                created: task.created,
+                started: first(TaskStatus::InProgress.as_str()).or(task.created),
+                completed: completed.flatten(),
+            }
+        })
+        .collect()
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Velocity over the `weeks` weeks up to and including the one holding `now`
+pub fn velocity(
+    tasks: &[TaskEntry],
+    history: &[TaskHistoryEvent],
+    weeks: usize,
+    now: DateTime<Utc>,
+) -> Velocity {
+    let spans = task_spans(tasks, history);
+    let current = week_start(now.date_naive());
+
+    let weeks: Vec<WeekVelocity> = (0..weeks)
+        .rev()
+        .map(|ago| {
+            let start = current - Duration::weeks(ago as i64);
+            let end = start + Duration::weeks(1);
+            let end_time = end.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+
+            let completed = spans
+                .iter()
+                .filter_map(|s| s.completed)
+                .filter(|c| week_start(c.date_naive()) == start)
+                .count();
+            let mut remaining_by_phase: BTreeMap<String, usize> = BTreeMap::new();
+            // Tasks without a creation time count as existing all along
+            for span in spans.iter().filter(|s| {
+                s.created.is_none_or(|c| c < end_time) && s.completed.is_none_or(|c| c >= end_time)
+            }) {
+                *remaining_by_phase.entry(span.phase.to_string()).or_default() += 1;
+            }
+            WeekVelocity {
+                week_start: start,
+                completed,
+                remaining: remaining_by_phase.values().sum(),
+                remaining_by_phase,
+            }
+        })
+        .collect();
+
+    let mut hours: Vec<f64> = spans
+        .iter()
+        .filter_map(|s| Some((s.completed? - s.started?).num_minutes() as f64 / 60.0))
+        .filter(|h| *h >= 0.0)
+        .collect();
+    hours.sort_by(f64::total_cmp);
+    let cycle_time = match hours.len() {
+        0 => CycleTime::default(),
+        n => CycleTime {
+            tasks: n,
+            average_hours: round(hours.iter().sum::<f64>() / n as f64),
+            median_hours: round(match n % 2 {
+                0 => (hours[n / 2 - 1] + hours[n / 2]) / 2.0,
+                _ => hours[n / 2],
+            }),
+        },
+    };
+
+    let mut remaining_by_phase: BTreeMap<String, usize> = BTreeMap::new();
+    for span in spans.iter().filter(|s| s.completed.is_none()) {
+        *remaining_by_phase.entry(span.phase.to_string()).or_default() += 1;
+    }
+
+    let average_per_week = match weeks.len() {
+        0 => 0.0,
+        n => round(weeks.iter().map(|w| w.completed).sum::<usize>() as f64 / n as f64),
+    };
+    Velocity {
+        weeks,
+        average_per_week,
+        cycle_time,
+        remaining_by_phase,
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// One row per week, with a remaining column per phase
+pub fn velocity_to_csv(velocity: &Velocity) -> String {
+    let phases: std::collections::BTreeSet<&String> = velocity
+        .weeks
+        .iter()
+        .flat_map(|w| w.remaining_by_phase.keys())
+        .chain(velocity.remaining_by_phase.keys())
+        .collect();
+    let mut csv = String::from("week_start,completed,remaining");
+    for phase in &phases {
+        csv.push_str(&format!(",{}", escape_csv(&format!("remaining_{}", phase))));
+    }
+    csv.push('\n');
+    for week in &velocity.weeks {
+        csv.push_str(&format!("{},{},{}", week.week_start, week.completed, week.remaining));
+        for phase in &phases {
+            let count = week.remaining_by_phase.get(phase.as_str()).copied().unwrap_or(0);
+            csv.push_str(&format!(",{}", count));
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+fn print_table(velocity: &Velocity) {
+    println!("{}", "Velocity".cyan().bold());
+    println!("{:<12} {:>9} {:>9}", "Week of", "Completed", "Remaining");
+    for week in &velocity.weeks {
+        println!(
+            "{:<12} {:>9} {:>9}",
+            week.week_start.to_string(),
+            week.completed,
+            week.remaining
+        );
+    }
+    println!("\nAverage: {:.1} task(s) per week", velocity.average_per_week);
+
+    let cycle = &velocity.cycle_time;
+    if cycle.tasks > 0 {
+        println!(
+            "Cycle time: {:.1}h average, {:.1}h median over {} task(s)",
+            cycle.average_hours, cycle.median_hours, cycle.tasks
+        );
+    } else {
+        println!("Cycle time: no completed tasks with a known start");
+    }
+
+    if !velocity.remaining_by_phase.is_empty() {
+        println!("\n{}", "Remaining by Phase".cyan().bold());
+        for (phase, count) in &velocity.remaining_by_phase {
+            println!("  {:<20} {}", phase, count);
+        }
+    }
+}
+
+fn cmd_velocity(
+    weeks: usize,
+    format: &str,
+    out: Option<&Path>,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks = latest_tasks()?;
+    let history = crate::history::read_all_history()?;
+    let velocity = velocity(&tasks, &history, weeks, Utc::now());
+
+    // Agent mode gets JSON unless CSV was asked for
+    let format = match format {
+        "table" if is_agent_mode || out.is_some() => "json",
+        format => format,
+    };
+    let content = match format {
+        "csv" => velocity_to_csv(&velocity),
+        "json" => serde_json::to_string_pretty(&velocity)? + "\n",
+        _ => {
+            print_table(&velocity);
+            return Ok(());
+        }
+    };
+
+    let Some(out) = out else {
+        print!("{}", content);
+        return Ok(());
+    };
+
+    if !dry_run {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out, &content)
+            .with_context(|| format!("Failed to write {}", out.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "metrics_velocity",
+                "format": format,
+                "weeks": velocity.weeks.len(),
+                "out": out.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} {} week(s) of velocity to {}",
+            if dry_run { "Would export" } else { "✓ Exported" },
+            velocity.weeks.len(),
+            out.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Point-in-time project health, as exported to Prometheus
+#[derive(Debug, Default, Serialize)]
+pub struct HealthMetrics {
+    /// Latest entry per task, by status
+    pub tasks: BTreeMap<&'static str, usize>,
+    pub checks_passed: usize,
+    pub checks_total: usize,
+    pub health_percent: f64,
+    pub coverage_floor: Option<f64>,
+    pub coverage_latest: Option<f64>,
+    pub quota_tokens_used: Option<u64>,
+    pub quota_requests: Option<u64>,
+    pub stale_locks: usize,
+}
+
+impl HealthMetrics {
+    pub fn collect(stale_timeout: u64) -> Result<Self> {
+        let mut tasks: BTreeMap<&'static str, usize> = [
+            TaskStatus::Pending,
+            TaskStatus::InProgress,
+            TaskStatus::Blocked,
+            TaskStatus::Scaffolded,
+            TaskStatus::Complete,
+        ]
+        .iter()
+        .map(|status| (status_name(status), 0))
+        .collect();
+        for task in latest_tasks()? {
+            *tasks.entry(status_name(&task.status)).or_default() += 1;
+        }
+
+        let checks = crate::checks::run()?;
+        let coverage: Option<crate::schema::CoverageHistory> =
+            crate::fs_ops::read_json(&crate::common::coverage_history_path()).ok();
+        let quota = crate::coord::read_quota();
+
+        Ok(Self {
+            tasks,
+            checks_passed: checks.passed(),
+            checks_total: checks.results.len(),
+            health_percent: round(checks.health_percentage()),
+            coverage_floor: coverage.as_ref().map(|c| c.floor),
+            coverage_latest: coverage
+                .as_ref()
+                .and_then(|c| c.history.iter().max_by_key(|e| e.timestamp))
+                .map(|e| e.coverage),
+            quota_tokens_used: quota.as_ref().map(|q| q.tokens_used),
+            quota_requests: quota.as_ref().map(|q| q.requests),
+            stale_locks: crate::coord::stale_locks(stale_timeout)?.len(),
+        })
+    }
+}
+
+fn status_name(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "pending",
+        TaskStatus::InProgress => "in_progress",
+        TaskStatus::Complete => "complete",
+        TaskStatus::Blocked => "blocked",
+        TaskStatus::Scaffolded => "scaffolded",
+    }
+}
+
+/// Metrics in the Prometheus text exposition format, as read by the node
+/// exporter's textfile collector. Metrics with no source data are left out.
+pub fn prometheus_text(metrics: &HealthMetrics) -> String {
+    let mut text = String::new();
+    let mut gauge = |name: &str, help: &str, samples: &[(String, String)]| {
+        text.push_str(&format!("# HELP rotd_{} {}\n# TYPE rotd_{} gauge\n", name, help, name));
+        for (labels, value) in samples {
+            text.push_str(&format!("rotd_{}{} {}\n", name, labels, value));
+        }
+    };
+    let single = |value: String| vec![(String::new(), value)];
+
+    let tasks: Vec<(String, String)> = metrics
+        .tasks
+        .iter()
+        .map(|(status, count)| (format!("{{status=\"{}\"}}", status), count.to_string()))
+        .collect();
+    gauge("tasks", "Tasks by status (latest entry per task).", &tasks);
+    gauge(
+        "health_percent",
+        "Percentage of enabled checks passing.",
+        &single(metrics.health_percent.to_string()),
+    );
+    gauge(
+        "checks_passed",
+        "Enabled checks passing.",
+        &single(metrics.checks_passed.to_string()),
+    );
+    gauge(
+        "checks_total",
+        "Enabled checks.",
+        &single(metrics.checks_total.to_string()),
+    );
+    if let Some(floor) = metrics.coverage_floor {
+        gauge(
+            "coverage_floor_percent",
+            "Coverage ratchet floor.",
+            &single(floor.to_string()),
+        );
+    }
+    if let Some(latest) = metrics.coverage_latest {
+        gauge(
+            "coverage_percent",
+            "Most recently recorded coverage.",
+            &single(latest.to_string()),
+        );
+    }
+    if let Some(tokens) = metrics.quota_tokens_used {
+        gauge(
+            "quota_tokens_used",
+            "Tokens used since the quota was last reset.",
+            &single(tokens.to_string()),
+        );
+    }
+    if let Some(requests) = metrics.quota_requests {
+        gauge(
+            "quota_requests",
+            "Requests since the quota was last reset.",
+            &single(requests.to_string()),
+        );
+    }
+    gauge(
+        "stale_locks",
+        "Agent task locks whose holder's heartbeat has gone stale.",
+        &single(metrics.stale_locks.to_string()),
+    );
+    text
+}
+
+fn cmd_export(path: &Path, stale_timeout: u64, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let metrics = HealthMetrics::collect(stale_timeout)?;
+    if !dry_run {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        // The collector may read at any moment, so swap the file in whole
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, prometheus_text(&metrics))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "metrics_export",
+                "format": "prometheus",
+                "out": path.display().to_string(),
+                "metrics": metrics,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} Prometheus metrics to {}",
+            if dry_run { "Would write" } else { "✓ Wrote" },
+            path.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_velocity() {
+        let task = |value: serde_json::Value| -> TaskEntry { serde_json::from_value(value).unwrap() };
+        let tasks = vec![
+            // Completed in the week of Jan 19, started from history
+            task(json!({"id": "1.1", "title": "A", "status": "complete", "phase": "1",
+                        "created": "2026-01-12T09:00:00Z", "completed": "2026-01-20T09:00:00Z"})),
+            // Completion only known from history
+            task(json!({"id": "1.2", "title": "B", "status": "complete", "phase": "1",
+                        "created": "2026-01-19T09:00:00Z"})),
+            task(json!({"id": "2.1", "title": "C", "status": "in_progress", "phase": "2",
+                        "created": "2026-01-21T09:00:00Z"})),
+            task(json!({"id": "2.2", "title": "D", "status": "pending"})),
+        ];
+        let event = |task_id: &str, status: &str, time: &str| {
+            let mut event = TaskHistoryEvent::new(
+                task_id.to_string(),
+                "agent-a".to_string(),
+                status.to_string(),
+            );
+            event.timestamp = time.parse().unwrap();
+            event
+        };
+        let history = vec![
+            event("1.1", "in_progress", "2026-01-19T09:00:00Z"),
+            event("1.2", "complete", "2026-01-27T21:00:00Z"),
+        ];
+
+        let now: DateTime<Utc> = "2026-01-29T12:00:00Z".parse().unwrap();
+        let velocity = velocity(&tasks, &history, 3, now);
+
+        let weeks: Vec<(String, usize, usize)> = velocity
+            .weeks
+            .iter()
+            .map(|w| (w.week_start.to_string(), w.completed, w.remaining))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("2026-01-12".to_string(), 0, 2),
+                ("2026-01-19".to_string(), 1, 3),
+                ("2026-01-26".to_string(), 1, 2),
+            ]
+        );
+        assert_eq!(velocity.average_per_week, 0.7);
+        // 1.1: 24h from its first in_progress; 1.2: 204h from creation
+        assert_eq!(
+            velocity.cycle_time,
+            CycleTime {
+                tasks: 2,
+                average_hours: 114.0,
+                median_hours: 114.0
+            }
+        );
+        assert_eq!(
+            velocity.remaining_by_phase,
+            BTreeMap::from([("2".to_string(), 1), (NO_PHASE.to_string(), 1)])
+        );
+
+        let csv = velocity_to_csv(&velocity);
+        assert_eq!(
+            csv.lines().next(),
+            Some("week_start,completed,remaining,remaining_1,remaining_2,remaining_none")
+        );
+        assert_eq!(csv.lines().nth(2), Some("2026-01-19,1,3,1,1,1"));
+    }
+
+    #[test]
+    fn test_prometheus_text() {
+        let metrics = HealthMetrics {
+            tasks: BTreeMap::from([("complete", 3), ("pending", 1)]),
+            checks_passed: 8,
+            checks_total: 9,
+            health_percent: 88.9,
+            coverage_floor: Some(70.0),
+            stale_locks: 1,
+            ..Default::default()
+        };
+
+        let text = prometheus_text(&metrics);
+        assert!(text.starts_with(
+            "# HELP rotd_tasks Tasks by status (latest entry per task).\n# TYPE rotd_tasks gauge\n\
+             rotd_tasks{status=\"complete\"} 3\nrotd_tasks{status=\"pending\"} 1\n"
+        ));
+        assert!(text.contains("\nrotd_health_percent 88.9\n"));
+        assert!(text.contains("\nrotd_coverage_floor_percent 70\n"));
+        assert!(text.ends_with("\nrotd_stale_locks 1\n"));
+        // No coverage history or quota file, so no samples for them
+        assert!(!text.contains("rotd_coverage_percent") && !text.contains("rotd_quota"));
+    }
+}
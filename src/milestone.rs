@@ -0,0 +1,414 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::MilestoneCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, read_json, write_json};
+use crate::output::Finding;
+use crate::schema::{TaskEntry, TaskStatus};
+
+/// Days of completed tasks used to estimate the pace of work
+const PACE_WINDOW_DAYS: i64 = 28;
+
+/// A target date for a set of tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub id: String,
+    pub title: String,
+    pub target_date: NaiveDate,
+    #[serde(default)]
+    pub tasks: Vec<String>,
+    pub created: DateTime<Utc>,
+}
+
+/// Contents of milestones.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Milestones {
+    pub milestones: Vec<Milestone>,
+}
+
+pub fn handle_command(cmd: MilestoneCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        MilestoneCommands::Create {
+            id,
+            title,
+            target,
+        } => cmd_create(&id, title, target, dry_run, is_agent_mode),
+        MilestoneCommands::Assign {
+            id,
+            task_id,
+            remove,
+        } => cmd_assign(&id, &task_id, remove, dry_run, is_agent_mode),
+        MilestoneCommands::Status { id } => cmd_status(id.as_deref(), is_agent_mode),
+    }
+}
+
+/// Milestones from milestones.json; none when the file doesn't exist
+pub fn load() -> Result<Milestones> {
+    let path = crate::common::milestones_path();
+    if !path.exists() {
+        return Ok(Milestones::default());
+    }
+    read_json(&path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Progress toward a milestone and whether its target date is in danger
+#[derive(Debug, Serialize)]
+pub struct MilestoneStatus {
+    pub id: String,
+    pub title: String,
+    pub target_date: NaiveDate,
+    pub days_left: i64,
+    pub total: usize,
+    pub complete: usize,
+    pub completion_percent: f64,
+    /// Assigned tasks not yet complete, including ones missing from tasks.jsonl
+    pub remaining: Vec<String>,
+    /// Estimated finish at the recent pace, when there is one
+    pub projected_date: Option<NaiveDate>,
+    pub at_risk: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk: Option<String>,
+}
+
+/// Tasks completed per day over the last `PACE_WINDOW_DAYS`
+pub fn recent_pace(tasks: &[TaskEntry], now: DateTime<Utc>) -> f64 {
+    let since = now - Duration::days(PACE_WINDOW_DAYS);
+    let completed = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Complete)
+        .filter(|t| t.completed.is_some_and(|c| c >= since))
+        .count();
+    completed as f64 / PACE_WINDOW_DAYS as f64
+}
+
+/// A milestone is at risk when it has incomplete tasks and its target date
+/// has passed, nothing has been completed recently, or the recent pace
+/// projects a finish after the target date
+pub fn assess(
+    milestone: &Milestone,
+    tasks: &[TaskEntry],
+    pace_per_day: f64,
+    today: NaiveDate,
+) -> MilestoneStatus {
+    let remaining: Vec<String> = milestone
+        .tasks
+        .iter()
+        .filter(|id| {
+            !tasks
+                .iter()
+                .any(|t| &&t.id == id && t.status == TaskStatus::Complete)
+        })
+        .cloned()
+        .collect();
+    let total = milestone.tasks.len();
+    let complete = total - remaining.len();
+    let days_left = (milestone.target_date - today).num_days();
+
+    let projected_date = (!remaining.is_empty() && pace_per_day > 0.0).then(|| {
+        let days = (remaining.len() as f64 / pace_per_day).ceil() as i64;
+        today + Duration::days(days)
+    });
+    let risk = if remaining.is_empty() {
+        None
+    } else if days_left < 0 {
+        Some(format!(
+            "{} task(s) incomplete {} day(s) after the target date",
+            remaining.len(),
+            -days_left
+        ))
+    } else {
+        match projected_date {
+            None => Some(format!(
+                "{} task(s) remaining and none completed in the last {} days",
+                remaining.len(),
+                PACE_WINDOW_DAYS
+            )),
+            Some(projected) if projected > milestone.target_date => Some(format!(
+                "{} task(s) remaining; at {:.1} task(s)/week the work finishes around {}",
+                remaining.len(),
+                pace_per_day * 7.0,
+                projected
+            )),
+            Some(_) => None,
+        }
+    };
+
+    MilestoneStatus {
+        id: milestone.id.clone(),
+        title: milestone.title.clone(),
+        target_date: milestone.target_date,
+        days_left,
+        total,
+        complete,
+        completion_percent: if total == 0 {
+            0.0
+        } else {
+            (complete as f64 / total as f64 * 1000.0).round() / 10.0
+        },
+        remaining,
+        projected_date,
+        at_risk: risk.is_some(),
+        risk,
+    }
+}
+
+fn assess_all(milestones: &Milestones, tasks: &[TaskEntry]) -> Vec<MilestoneStatus> {
+    let now = Utc::now();
+    let pace = recent_pace(tasks, now);
+    milestones
+        .milestones
+        .iter()
+        .map(|m| assess(m, tasks, pace, now.date_naive()))
+        .collect()
+}
+
+/// `rotd check` findings for milestones at risk
+pub fn risk_findings() -> Vec<Finding> {
+    let path = crate::common::milestones_path();
+    let (Ok(milestones), Ok(tasks)) = (load(), latest_tasks()) else {
+        return Vec::new();
+    };
+    assess_all(&milestones, &tasks)
+        .into_iter()
+        .filter_map(|status| {
+            let risk = status.risk?;
+            Some(
+                Finding::new(
+                    "milestone_at_risk",
+                    format!(
+                        "Milestone {} (due {}) is at risk: {}",
+                        status.id, status.target_date, risk
+                    ),
+                )
+                .at(&path, None),
+            )
+        })
+        .collect()
+}
+
+fn save(milestones: &Milestones, dry_run: bool) -> Result<()> {
+    if dry_run {
+        return Ok(());
+    }
+    write_json(&crate::common::milestones_path(), milestones)
+}
+
+fn cmd_create(
+    id: &str,
+    title: Option<String>,
+    target: NaiveDate,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let mut milestones = load()?;
+    if milestones.milestones.iter().any(|m| m.id == id) {
+        return Err(anyhow::anyhow!("Milestone {} already exists", id));
+    }
+    let milestone = Milestone {
+        id: id.to_string(),
+        title: title.unwrap_or_else(|| id.to_string()),
+        target_date: target,
+        tasks: Vec::new(),
+        created: Utc::now(),
+    };
+    milestones.milestones.push(milestone.clone());
+    save(&milestones, dry_run)?;
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "milestone_create",
+                "milestone": milestone,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} milestone {} due {}",
+            if dry_run { "Would create" } else { "✓ Created" },
+            id.bold(),
+            target
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_assign(
+    id: &str,
+    patterns: &[String],
+    remove: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let mut milestones = load()?;
+    let milestone = milestones
+        .milestones
+        .iter_mut()
+        .find(|m| m.id == id)
+        .ok_or_else(|| anyhow::anyhow!("Milestone {} not found", id))?;
+
+    let tasks = latest_tasks()?;
+    let (matched, unmatched) = crate::task::resolve(patterns, &tasks);
+    let mut changed = Vec::new();
+    if remove {
+        // Removal also accepts IDs of tasks that have since disappeared
+        for task_id in matched.iter().map(|t| &t.id).chain(&unmatched) {
+            if let Some(i) = milestone.tasks.iter().position(|t| t == task_id) {
+                milestone.tasks.remove(i);
+                changed.push(task_id.clone());
+            }
+        }
+    } else {
+        if !unmatched.is_empty() {
+            return Err(anyhow::anyhow!("No task matches {}", unmatched.join(", ")));
+        }
+        for task in matched {
+            if !milestone.tasks.contains(&task.id) {
+                milestone.tasks.push(task.id.clone());
+                changed.push(task.id.clone());
+            }
+        }
+    }
+    save(&milestones, dry_run)?;
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": if remove { "milestone_unassign" } else { "milestone_assign" },
+                "milestone": id,
+                "tasks": changed,
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        let verb = match (remove, dry_run) {
+            (false, false) => "✓ Assigned",
+            (false, true) => "Would assign",
+            (true, false) => "✓ Removed",
+            (true, true) => "Would remove",
+        };
+        println!(
+            "{} {} task(s) {} milestone {}",
+            verb,
+            changed.len(),
+            if remove { "from" } else { "to" },
+            id.bold()
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_status(id: Option<&str>, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let mut milestones = load()?;
+    if let Some(id) = id {
+        milestones.milestones.retain(|m| m.id == id);
+        if milestones.milestones.is_empty() {
+            return Err(anyhow::anyhow!("Milestone {} not found", id));
+        }
+    }
+    let tasks = latest_tasks()?;
+    let statuses = assess_all(&milestones, &tasks);
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "milestone_status",
+                "milestones": statuses,
+            })
+        );
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!("No milestones. Create one with `rotd milestone create`.");
+        return Ok(());
+    }
+    for status in &statuses {
+        let due = if status.days_left >= 0 {
+            format!("due {} ({} day(s) left)", status.target_date, status.days_left)
+        } else {
+            format!("due {} ({} day(s) overdue)", status.target_date, -status.days_left)
+        };
+        println!("{} {} — {}", status.id.bold(), status.title, due.dimmed());
+        println!(
+            "  {}/{} complete ({:.1}%)",
+            status.complete, status.total, status.completion_percent
+        );
+        if !status.remaining.is_empty() {
+            println!("  Remaining: {}", status.remaining.join(", "));
+        }
+        match &status.risk {
+            Some(risk) => println!("  {} {}", "⚠ At risk:".yellow().bold(), risk),
+            None if status.remaining.is_empty() => println!("  {}", "✓ Done".green()),
+            None => println!("  {}", "✓ On track".green()),
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assess() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        let task = |id: &str, status: &str| -> TaskEntry {
+            serde_json::from_value(serde_json::json!({"id": id, "title": id, "status": status}))
+                .unwrap()
+        };
+        let tasks = vec![
+            task("1.1", "complete"),
+            task("1.2", "pending"),
+            task("1.3", "in_progress"),
+        ];
+        let milestone = |target: NaiveDate, ids: &[&str]| Milestone {
+            id: "beta".to_string(),
+            title: "Beta".to_string(),
+            target_date: target,
+            tasks: ids.iter().map(|s| s.to_string()).collect(),
+            created: Utc::now(),
+        };
+        let in_days = |days| today + Duration::days(days);
+
+        // 2 remaining at 1 task every 2 days: done in 4 days
+        let on_track = assess(&milestone(in_days(5), &["1.1", "1.2", "1.3"]), &tasks, 0.5, today);
+        assert_eq!((on_track.complete, on_track.total), (1, 3));
+        assert_eq!(on_track.remaining, vec!["1.2", "1.3"]);
+        assert_eq!(on_track.projected_date, Some(in_days(4)));
+        assert!(!on_track.at_risk);
+
+        let behind = assess(&milestone(in_days(3), &["1.2", "1.3"]), &tasks, 0.5, today);
+        assert!(behind.at_risk);
+        assert!(behind.risk.unwrap().contains("finishes around 2025-07-05"));
+
+        let stalled = assess(&milestone(in_days(30), &["1.2"]), &tasks, 0.0, today);
+        assert!(stalled.at_risk && stalled.projected_date.is_none());
+
+        let overdue = assess(&milestone(in_days(-2), &["1.2", "9.9"]), &tasks, 1.0, today);
+        assert_eq!(overdue.risk.as_deref(), Some("2 task(s) incomplete 2 day(s) after the target date"));
+
+        let done = assess(&milestone(in_days(-2), &["1.1"]), &tasks, 0.0, today);
+        assert!(!done.at_risk);
+        assert_eq!(done.completion_percent, 100.0);
+    }
+}
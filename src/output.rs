@@ -0,0 +1,817 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+use crate::common::check_rotd_initialized;
+use crate::schema::{PSSScore, SessionState, TaskEntry, TaskStatus};
+
+/// Machine-readable report formats for `check` and `validate` (`--output`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Github,
+    Sarif,
+    Junit,
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "text" => Ok(Self::Text),
+            "github" => Ok(Self::Github),
+            "sarif" => Ok(Self::Sarif),
+            "junit" => Ok(Self::Junit),
+            other => Err(anyhow::anyhow!(
+                "Unknown output format '{}'. Supported: text, github, sarif, junit",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl Level {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        }
+    }
+}
+
+/// A single problem, optionally tied to a file location
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub rule: String,
+    pub level: Level,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+}
+
+impl Finding {
+    pub fn new(rule: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            level: Level::Error,
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
+
+    pub fn at(mut self, file: &Path, line: Option<usize>) -> Self {
+        self.file = Some(file.to_path_buf());
+        self.line = line;
+        self
+    }
+}
+
+/// One health check or schema validation; passes when it has no findings
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub id: &'static str,
+    pub findings: Vec<Finding>,
+}
+
+impl Outcome {
+    /// Warnings are reported but do not fail the outcome
+    pub fn passed(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(|f| f.level == Level::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(|f| f.level == Level::Warning)
+    }
+}
+
+/// Parsed JSONL entries with their 1-based line numbers, plus parse errors
+pub fn parse_jsonl_lines<T>(path: &Path) -> (Vec<(usize, T)>, Vec<Finding>)
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return (items, errors),
+    };
+
+    for (line_num, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<T>(line) {
+            Ok(item) => items.push((line_num + 1, item)),
+            Err(e) => {
+                // serde reports positions within the line; the line is ours
+                let message = e.to_string();
+                let suffix = format!(" at line {} column {}", e.line(), e.column());
+                let message = message.strip_suffix(&suffix).unwrap_or(&message);
+                errors.push(
+                    Finding::new(
+                        "invalid_json",
+                        format!("Invalid JSON at column {}: {}", e.column(), message),
+                    )
+                    .at(path, Some(line_num + 1)),
+                );
+            }
+        }
+    }
+
+    (items, errors)
+}
+
+/// The same checks as `rotd check`, with a finding per offending artifact
+pub fn check_outcomes() -> Vec<Outcome> {
+    let tasks_path = crate::common::tasks_path();
+    let session_path = crate::common::session_state_path();
+    let mut outcomes = Vec::new();
+
+    let required_files = [
+        tasks_path.clone(),
+        session_path.clone(),
+        crate::common::coverage_history_path(),
+    ];
+    outcomes.push(Outcome {
+        id: "missing_required_files",
+        findings: required_files
+            .iter()
+            .filter(|f| !f.exists())
+            .map(|f| {
+                Finding::new(
+                    "missing_required_files",
+                    format!("Missing required file {}", f.display()),
+                )
+                .at(f, None)
+            })
+            .collect(),
+    });
+
+    let (tasks, parse_errors) = parse_jsonl_lines::<TaskEntry>(&tasks_path);
+    outcomes.push(Outcome {
+        id: "invalid_jsonl",
+        findings: parse_errors
+            .into_iter()
+            .map(|f| Finding {
+                rule: "invalid_jsonl".to_string(),
+                ..f
+            })
+            .collect(),
+    });
+
+    let completed: Vec<&(usize, TaskEntry)> = tasks
+        .iter()
+        .filter(|(_, t)| matches!(t.status, TaskStatus::Complete))
+        .collect();
+    outcomes.push(Outcome {
+        id: "missing_test_summaries",
+        findings: completed
+            .iter()
+            .filter(|(_, t)| !crate::common::test_summary_file(&t.id).exists())
+            .map(|(line, t)| {
+                Finding::new(
+                    "missing_test_summaries",
+                    format!(
+                        "Task {} is complete but {} is missing",
+                        t.id,
+                        crate::common::test_summary_file(&t.id).display()
+                    ),
+                )
+                .at(&tasks_path, Some(*line))
+            })
+            .collect(),
+    });
+
+    outcomes.push(Outcome {
+        id: "stubs_remaining",
+        findings: crate::pss::find_stubs()
+            .into_iter()
+            .map(|(file, line, pattern)| {
+                Finding::new(
+                    "stubs_remaining",
+                    format!("Stub marker {} remains", pattern),
+                )
+                .at(&file, Some(line))
+            })
+            .collect(),
+    });
+
+    outcomes.push(Outcome {
+        id: "invalid_session_state",
+        findings: match crate::fs_ops::read_json::<SessionState>(&session_path) {
+            Ok(_) => Vec::new(),
+            Err(e) => vec![
+                Finding::new(
+                    "invalid_session_state",
+                    format!("Invalid session state: {}", e),
+                )
+                .at(&session_path, None),
+            ],
+        },
+    });
+
+    outcomes.push(Outcome {
+        id: "stale_session",
+        findings: stale_session_check(),
+    });
+
+    outcomes.push(Outcome {
+        id: "dependency_integrity",
+        findings: dependency_findings(&tasks, &tasks_path),
+    });
+
+    // Summaries and scores left behind when a task is renamed or dropped
+    let is_task = |id: &str| tasks.iter().any(|(_, t)| t.id == id);
+    outcomes.push(Outcome {
+        id: "orphaned_test_summaries",
+        findings: test_summary_files()
+            .into_iter()
+            .filter(|(_, task_id)| !is_task(task_id))
+            .map(|(path, task_id)| {
+                Finding::new(
+                    "orphaned_test_summaries",
+                    format!("Test summary for unknown task {}", task_id),
+                )
+                .at(&path, None)
+            })
+            .collect(),
+    });
+
+    let pss_path = crate::common::pss_scores_path();
+    let (scores, _) = parse_jsonl_lines::<PSSScore>(&pss_path);
+    outcomes.push(Outcome {
+        id: "orphaned_pss_scores",
+        findings: scores
+            .iter()
+            .filter(|(_, score)| !is_task(&score.task_id))
+            .map(|(line, score)| {
+                Finding::new(
+                    "orphaned_pss_scores",
+                    format!("PSS score for unknown task {}", score.task_id),
+                )
+                .at(&pss_path, Some(*line))
+            })
+            .collect(),
+    });
+
+    // Only once ROTD-Task trailers are in use, matching `rotd check`
+    let commit_index = crate::git::task_commit_index();
+    if !commit_index.is_empty() {
+        outcomes.push(Outcome {
+            id: "completed_tasks_without_commits",
+            findings: completed
+                .iter()
+                .filter(|(_, t)| !commit_index.contains_key(&t.id))
+                .map(|(line, t)| {
+                    Finding::new(
+                        "completed_tasks_without_commits",
+                        format!(
+                            "Task {} is complete but no commit carries its trailer",
+                            t.id
+                        ),
+                    )
+                    .at(&tasks_path, Some(*line))
+                })
+                .collect(),
+        });
+    }
+
+    // Only once milestones are in use
+    if crate::common::milestones_path().exists() {
+        outcomes.push(Outcome {
+            id: "milestone_at_risk",
+            findings: crate::milestone::risk_findings(),
+        });
+    }
+
+    outcomes
+}
+
+/// `<task_id>.json` files in test_summaries/, sorted by path
+pub fn test_summary_files() -> Vec<(PathBuf, String)> {
+    let mut files: Vec<(PathBuf, String)> = std::fs::read_dir(crate::common::test_summaries_path())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|path| {
+            let task_id = path.file_stem()?.to_str()?.to_string();
+            Some((path, task_id))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// The last line for each task ID, in order of first appearance
+fn latest_entries(tasks: &[(usize, TaskEntry)]) -> Vec<&(usize, TaskEntry)> {
+    let mut latest: Vec<&(usize, TaskEntry)> = Vec::new();
+    for entry in tasks {
+        match latest.iter_mut().find(|(_, t)| t.id == entry.1.id) {
+            Some(slot) => *slot = entry,
+            None => latest.push(entry),
+        }
+    }
+    latest
+}
+
+/// The `stale_session` check against the project's session state, using
+/// `stale_session_hours` from the config. An unreadable session is reported
+/// by `invalid_session_state` instead.
+pub fn stale_session_check() -> Vec<Finding> {
+    let session_path = crate::common::session_state_path();
+    let Ok(session) = crate::fs_ops::read_json::<SessionState>(&session_path) else {
+        return Vec::new();
+    };
+    let (tasks, _) = parse_jsonl_lines::<TaskEntry>(&crate::common::tasks_path());
+    let max_age_hours = crate::history::load_config()
+        .unwrap_or_default()
+        .stale_session_hours;
+    stale_session_findings(&session, &tasks, &session_path, max_age_hours, Utc::now())
+}
+
+/// A session that hasn't been touched for `max_age_hours` while tasks are
+/// still in progress, one of the Buckle Mode triggers
+fn stale_session_findings(
+    session: &SessionState,
+    tasks: &[(usize, TaskEntry)],
+    session_path: &Path,
+    max_age_hours: u64,
+    now: DateTime<Utc>,
+) -> Vec<Finding> {
+    let age = now.signed_duration_since(session.timestamp);
+    if age.num_hours() < max_age_hours as i64 {
+        return Vec::new();
+    }
+    let in_progress: Vec<&str> = latest_entries(tasks)
+        .into_iter()
+        .filter(|(_, t)| matches!(t.status, TaskStatus::InProgress))
+        .map(|(_, t)| t.id.as_str())
+        .collect();
+    if in_progress.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        Finding::new(
+            "stale_session",
+            format!(
+                "Session {} was last updated {}h ago while task(s) {} are in progress",
+                session.session_id,
+                age.num_hours(),
+                in_progress.join(", ")
+            ),
+        )
+        .at(session_path, None),
+    ]
+}
+
+/// Problems with `depends_on` in the latest entry for each task: references
+/// to unknown or archived tasks, and complete tasks waiting on incomplete ones
+fn dependency_findings(tasks: &[(usize, TaskEntry)], tasks_path: &Path) -> Vec<Finding> {
+    let latest = latest_entries(tasks);
+    let (archived, _) = parse_jsonl_lines::<TaskEntry>(&crate::common::archived_tasks_path());
+    let is_complete = |task: &TaskEntry| matches!(task.status, TaskStatus::Complete);
+
+    let mut findings = Vec::new();
+    for (line, task) in &latest {
+        for dep in task.depends_on.iter().flatten() {
+            let message = match latest.iter().find(|(_, t)| &t.id == dep) {
+                Some((_, dep_task)) if is_complete(task) && !is_complete(dep_task) => format!(
+                    "Task {} is complete but depends on incomplete task {}",
+                    task.id, dep
+                ),
+                Some(_) => continue,
+                None if archived.iter().any(|(_, t)| &t.id == dep) => {
+                    format!("Task {} depends on archived task {}", task.id, dep)
+                }
+                None => format!("Task {} depends on unknown task {}", task.id, dep),
+            };
+            findings
+                .push(Finding::new("dependency_integrity", message).at(tasks_path, Some(*line)));
+        }
+    }
+    findings
+}
+
+/// Warning and error entries from the audit log. These are reported
+/// alongside check results but do not count as failed checks.
+pub fn audit_findings() -> Vec<Finding> {
+    let path = crate::common::audit_log_path();
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line_num, line)| {
+            let entry = crate::audit::parse_log_line(line)?;
+            let level = match entry.severity.as_str() {
+                "error" => Level::Error,
+                "warning" => Level::Warning,
+                _ => return None,
+            };
+            let message = match &entry.task_id {
+                Some(task_id) => format!("Task {}: {}", task_id, entry.message),
+                None => entry.message.clone(),
+            };
+            Some(Finding {
+                level,
+                ..Finding::new(entry.rule, message).at(&path, Some(line_num + 1))
+            })
+        })
+        .collect()
+}
+
+/// Escape workflow command data (`%`, CR, LF) and, for properties, `:` and `,`
+fn escape_annotation(value: &str, property: bool) -> String {
+    let escaped = value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A");
+    if property {
+        escaped.replace(':', "%3A").replace(',', "%2C")
+    } else {
+        escaped
+    }
+}
+
+/// GitHub Actions `::error` / `::warning` workflow command for a finding
+pub fn github_annotation(finding: &Finding) -> String {
+    let mut props = Vec::new();
+    if let Some(file) = &finding.file {
+        props.push(format!(
+            "file={}",
+            escape_annotation(&file.display().to_string(), true)
+        ));
+        if let Some(line) = finding.line {
+            props.push(format!("line={}", line));
+        }
+    }
+    props.push(format!("title={}", escape_annotation(&finding.rule, true)));
+
+    format!(
+        "::{} {}::{}",
+        finding.level.as_str(),
+        props.join(","),
+        escape_annotation(&finding.message, false)
+    )
+}
+
+fn rule_description(rule: &str) -> String {
+    match rule {
+        "missing_required_files" => "Required ROTD files must exist".to_string(),
+        "invalid_jsonl" | "invalid_json" => {
+            "JSONL artifacts must contain one valid JSON object per line".to_string()
+        }
+        "missing_test_summaries" => "Completed tasks must have a test summary".to_string(),
+        "stubs_remaining" => "Source must not contain stub markers".to_string(),
+        "invalid_session_state" => "Session state must be valid JSON".to_string(),
+        "stale_session" => {
+            "Session state must be refreshed while tasks are in progress".to_string()
+        }
+        "dependency_integrity" => {
+            "Task dependencies must name existing tasks, and complete tasks must not depend on incomplete ones".to_string()
+        }
+        "orphaned_test_summaries" | "orphaned_pss_scores" => {
+            "Test summaries and PSS scores must belong to a task in tasks.jsonl".to_string()
+        }
+        "completed_tasks_without_commits" => {
+            "Completed tasks must be linked to a commit via a ROTD-Task trailer".to_string()
+        }
+        "milestone_at_risk" => {
+            "Milestones should be on track to finish their tasks by the target date".to_string()
+        }
+        "invalid_task" => "Task entries must match the task schema".to_string(),
+        "unknown_schema" => "Validated schema type must be known".to_string(),
+        other => format!("ROTD audit rule {}", other),
+    }
+}
+
+/// SARIF 2.1.0 log with one result per finding, suitable for code scanning upload
+pub fn sarif_report(findings: &[&Finding]) -> serde_json::Value {
+    let mut rules: Vec<&str> = Vec::new();
+    for finding in findings {
+        if !rules.contains(&finding.rule.as_str()) {
+            rules.push(&finding.rule);
+        }
+    }
+
+    let results: Vec<serde_json::Value> = findings
+        .iter()
+        .map(|finding| {
+            let mut result = serde_json::json!({
+                "ruleId": finding.rule,
+                "ruleIndex": rules.iter().position(|r| *r == finding.rule),
+                "level": finding.level.as_str(),
+                "message": { "text": finding.message },
+            });
+            if let Some(file) = &finding.file {
+                let mut location = serde_json::json!({
+                    "artifactLocation": {
+                        "uri": file.display().to_string().replace('\\', "/"),
+                        "uriBaseId": "%SRCROOT%",
+                    },
+                });
+                if let Some(line) = finding.line {
+                    location["region"] = serde_json::json!({ "startLine": line });
+                }
+                result["locations"] = serde_json::json!([{ "physicalLocation": location }]);
+            }
+            result
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "rotd",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/jmfigueroa/rotd",
+                    "rules": rules.iter().map(|rule| serde_json::json!({
+                        "id": rule,
+                        "shortDescription": { "text": rule_description(rule) },
+                    })).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Escape text for XML or HTML content and attribute values
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Quote a CSV field when it holds a comma, quote, or line break
+pub fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// JUnit XML report with one test case per check; findings become the failure body
+pub fn junit_report(command: &str, outcomes: &[Outcome]) -> String {
+    let suite = format!("rotd.{}", command);
+    let failures = outcomes.iter().filter(|o| !o.passed()).count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"rotd\" tests=\"{}\" failures=\"{}\">\n",
+        outcomes.len(),
+        failures
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+        suite,
+        outcomes.len(),
+        failures
+    ));
+
+    for outcome in outcomes {
+        if outcome.passed() {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\"/>\n",
+                escape_xml(outcome.id),
+                suite
+            ));
+            continue;
+        }
+
+        let details: Vec<String> = outcome
+            .findings
+            .iter()
+            .map(|f| match (&f.file, f.line) {
+                (Some(file), Some(line)) => format!("{}:{}: {}", file.display(), line, f.message),
+                (Some(file), None) => format!("{}: {}", file.display(), f.message),
+                _ => f.message.clone(),
+            })
+            .collect();
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\">\n",
+            escape_xml(outcome.id),
+            suite
+        ));
+        xml.push_str(&format!(
+            "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+            escape_xml(&outcome.findings[0].message),
+            escape_xml(&outcome.findings[0].rule),
+            escape_xml(&details.join("\n"))
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn render(format: OutputFormat, command: &str, outcomes: &[Outcome], advisories: &[Finding]) {
+    let passed = outcomes.iter().filter(|o| o.passed()).count();
+    let findings: Vec<&Finding> = outcomes
+        .iter()
+        .flat_map(|o| &o.findings)
+        .chain(advisories)
+        .collect();
+    match format {
+        // Audit advisories are not checks, so they have no test case
+        OutputFormat::Junit => print!("{}", junit_report(command, outcomes)),
+        OutputFormat::Sarif => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&sarif_report(&findings)).unwrap_or_default()
+            );
+        }
+        OutputFormat::Github => {
+            for finding in &findings {
+                println!("{}", github_annotation(finding));
+            }
+            let failed: Vec<&str> = outcomes
+                .iter()
+                .filter(|o| !o.passed())
+                .map(|o| o.id)
+                .collect();
+            if failed.is_empty() {
+                println!(
+                    "rotd {}: {}/{} checks passed",
+                    command,
+                    passed,
+                    outcomes.len()
+                );
+            } else {
+                println!(
+                    "rotd {}: {}/{} checks passed (failed: {})",
+                    command,
+                    passed,
+                    outcomes.len(),
+                    failed.join(", ")
+                );
+            }
+        }
+        // Text output is handled by the agent and human renderers
+        OutputFormat::Text => {}
+    }
+}
+
+/// `rotd check --output <format>`
+pub fn check(format: OutputFormat, ci: Option<f64>) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let run = crate::checks::run()?;
+    run.log_waivers()?;
+    let outcomes = run.outcomes();
+    render(format, "check", &outcomes, &audit_findings());
+
+    match ci {
+        Some(threshold) => run.ci_verdict(threshold).into_result(),
+        None => Ok(()),
+    }
+}
+
+/// `rotd validate --output <format>`
+pub fn validate(
+    format: OutputFormat,
+    all: bool,
+    schema_type: Option<&str>,
+    strict: bool,
+    strict_parse: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let outcomes = match crate::validate::run(all, schema_type, strict, strict_parse) {
+        Ok(reports) => reports.into_iter().map(|r| r.outcome).collect(),
+        Err(e) => vec![Outcome {
+            id: "schema",
+            findings: vec![Finding::new("unknown_schema", e.to_string())],
+        }],
+    };
+    render(format, "validate", &outcomes, &[]);
+
+    let errors: usize = outcomes.iter().map(|o| o.errors().count()).sum();
+    if errors > 0 {
+        return Err(anyhow::anyhow!(
+            "Validation failed with {} error(s)",
+            errors
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_annotation_escapes() {
+        let finding = Finding::new("invalid_json", "Invalid JSON: 50% bad\nline")
+            .at(Path::new(".rotd/tasks.jsonl"), Some(3));
+        assert_eq!(
+            github_annotation(&finding),
+            "::error file=.rotd/tasks.jsonl,line=3,title=invalid_json::Invalid JSON: 50%25 bad%0Aline"
+        );
+        assert_eq!(
+            github_annotation(&Finding::new("missing_required_files", "Missing a, b")),
+            "::error title=missing_required_files::Missing a, b"
+        );
+    }
+
+    #[test]
+    fn test_junit_report_marks_failures() {
+        let outcomes = vec![
+            Outcome {
+                id: "invalid_jsonl",
+                findings: Vec::new(),
+            },
+            Outcome {
+                id: "missing_test_summaries",
+                findings: vec![
+                    Finding::new("missing_test_summaries", "Task <a> & b")
+                        .at(Path::new(".rotd/tasks.jsonl"), Some(4)),
+                ],
+            },
+        ];
+        let xml = junit_report("check", &outcomes);
+
+        assert!(xml.contains(r#"<testsuite name="rotd.check" tests="2" failures="1""#));
+        assert!(xml.contains(r#"<testcase name="invalid_jsonl" classname="rotd.check"/>"#));
+        assert!(xml.contains(
+            r#"<failure message="Task &lt;a&gt; &amp; b" type="missing_test_summaries">.rotd/tasks.jsonl:4: Task &lt;a&gt; &amp; b</failure>"#
+        ));
+    }
+
+    #[test]
+    fn test_sarif_report_rules_and_locations() {
+        let parse = Finding::new("invalid_json", "bad").at(Path::new(".rotd/tasks.jsonl"), Some(2));
+        let audit = Finding {
+            level: Level::Warning,
+            ..Finding::new("coverage_drop", "dropped")
+        };
+        let report = sarif_report(&[&parse, &audit, &parse]);
+
+        let run = &report["runs"][0];
+        assert_eq!(report["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][1]["ruleIndex"], 1);
+        assert_eq!(run["results"][1]["level"], "warning");
+        assert!(run["results"][1].get("locations").is_none());
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            2
+        );
+    }
+
+    #[test]
+    fn test_stale_session_findings() {
+        let task = |id: &str, status: &str| -> TaskEntry {
+            serde_json::from_value(serde_json::json!({"id": id, "title": id, "status": status}))
+                .unwrap()
+        };
+        let tasks = vec![
+            (1, task("1.1", "in_progress")),
+            (2, task("1.2", "pending")),
+            (3, task("1.1", "complete")),
+            (4, task("1.3", "in_progress")),
+        ];
+        let now = Utc::now();
+        let session = SessionState {
+            session_id: "s1".to_string(),
+            timestamp: now - chrono::Duration::hours(30),
+            current_task: None,
+            status: "active".to_string(),
+            deltas: None,
+            schema: crate::schema::SESSION_STATE_SCHEMA.to_string(),
+        };
+        let path = Path::new(".rotd/session_state.json");
+
+        let findings = stale_session_findings(&session, &tasks, path, 24, now);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "Session s1 was last updated 30h ago while task(s) 1.3 are in progress"
+        );
+        assert!(stale_session_findings(&session, &tasks, path, 48, now).is_empty());
+        // Nothing in progress means an old session is just finished work
+        assert!(stale_session_findings(&session, &tasks[..3], path, 24, now).is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::PhaseCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, read_jsonl};
+use crate::schema::{PSSScore, TaskEntry, TaskStatus};
+
+pub fn handle_command(cmd: PhaseCommands, is_agent_mode: bool, _dry_run: bool) -> Result<()> {
+    match cmd {
+        PhaseCommands::Status => cmd_status(is_agent_mode),
+    }
+}
+
+/// Statuses in table column order
+const STATUSES: [TaskStatus; 5] = [
+    TaskStatus::Pending,
+    TaskStatus::InProgress,
+    TaskStatus::Blocked,
+    TaskStatus::Scaffolded,
+    TaskStatus::Complete,
+];
+
+/// Progress of one phase
+#[derive(Debug, Serialize)]
+pub struct PhaseSummary {
+    pub phase: String,
+    pub total: usize,
+    /// Task count per status, every status listed
+    pub counts: BTreeMap<String, usize>,
+    pub completion_percent: f64,
+    pub blocked: Vec<String>,
+    /// Mean of each task's latest PSS score, over the tasks scored so far
+    pub average_pss: Option<f64>,
+    pub scored: usize,
+}
+
+/// Per-phase summaries in phase order, with tasks that have no phase last
+pub fn summarize(tasks: &[TaskEntry], scores: &[PSSScore]) -> Vec<PhaseSummary> {
+    // Later entries supersede earlier ones
+    let latest_score: HashMap<&str, u32> = scores
+        .iter()
+        .map(|s| (s.task_id.as_str(), s.score))
+        .collect();
+
+    crate::report::tasks_by_phase(tasks)
+        .into_iter()
+        .map(|(phase, tasks)| {
+            let counts: BTreeMap<String, usize> = STATUSES
+                .iter()
+                .map(|status| {
+                    let count = tasks.iter().filter(|t| &t.status == status).count();
+                    (status.as_str().to_string(), count)
+                })
+                .collect();
+            let complete = counts[TaskStatus::Complete.as_str()];
+            let phase_scores: Vec<u32> = tasks
+                .iter()
+                .filter_map(|t| latest_score.get(t.id.as_str()).copied())
+                .collect();
+
+            PhaseSummary {
+                phase: phase.to_string(),
+                total: tasks.len(),
+                completion_percent: (complete as f64 / tasks.len() as f64 * 1000.0).round()
+                    / 10.0,
+                blocked: tasks
+                    .iter()
+                    .filter(|t| t.status == TaskStatus::Blocked)
+                    .map(|t| t.id.clone())
+                    .collect(),
+                average_pss: (!phase_scores.is_empty()).then(|| {
+                    let sum: u32 = phase_scores.iter().sum();
+                    (sum as f64 / phase_scores.len() as f64 * 10.0).round() / 10.0
+                }),
+                scored: phase_scores.len(),
+                counts,
+            }
+        })
+        .collect()
+}
+
+fn cmd_status(is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks = latest_tasks()?;
+    let scores = read_jsonl::<PSSScore>(&crate::common::pss_scores_path()).unwrap_or_default();
+    let phases = summarize(&tasks, &scores);
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "phase_status",
+                "phases": phases,
+            })
+        );
+        return Ok(());
+    }
+
+    if phases.is_empty() {
+        println!("No tasks");
+        return Ok(());
+    }
+
+    println!("{}", "Phase Status".cyan().bold());
+    println!(
+        "{:<20} {:>5} {:>7} {:>8} {:>8} {:>8} {:>8} {:>7} {:>8}",
+        "Phase", "Tasks", "Pending", "Progress", "Blocked", "Scaffold", "Complete", "Done", "Avg PSS"
+    );
+    for phase in &phases {
+        let count = |status: TaskStatus| phase.counts[status.as_str()];
+        let blocked = count(TaskStatus::Blocked).to_string();
+        println!(
+            "{:<20} {:>5} {:>7} {:>8} {:>8} {:>8} {:>8} {:>6.1}% {:>8}",
+            phase.phase,
+            phase.total,
+            count(TaskStatus::Pending),
+            count(TaskStatus::InProgress),
+            if phase.blocked.is_empty() {
+                blocked.normal()
+            } else {
+                blocked.red()
+            },
+            count(TaskStatus::Scaffolded),
+            count(TaskStatus::Complete),
+            phase.completion_percent,
+            phase
+                .average_pss
+                .map_or("-".to_string(), |avg| format!("{:.1}", avg))
+        );
+    }
+
+    let blocked: Vec<&PhaseSummary> = phases.iter().filter(|p| !p.blocked.is_empty()).collect();
+    if !blocked.is_empty() {
+        println!("\n{}", "Blocked".red().bold());
+        for phase in blocked {
+            println!("  {}: {}", phase.phase, phase.blocked.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        let task = |id: &str, status: &str, phase: Option<&str>| -> TaskEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "title": id, "status": status, "phase": phase,
+            }))
+            .unwrap()
+        };
+        let score = |task_id: &str, score: u32| -> PSSScore {
+            serde_json::from_value(serde_json::json!({
+                "task_id": task_id, "score": score,
+                "timestamp": "2025-07-01T00:00:00Z", "criteria": {},
+            }))
+            .unwrap()
+        };
+        let tasks = vec![
+            task("1.1", "complete", Some("mvp")),
+            task("1.2", "blocked", Some("mvp")),
+            task("1.3", "in_progress", Some("mvp")),
+            task("2.1", "pending", None),
+        ];
+        let scores = vec![score("1.1", 5), score("1.1", 9), score("1.3", 6)];
+
+        let phases = summarize(&tasks, &scores);
+        assert_eq!(phases.len(), 2);
+        let mvp = &phases[0];
+        assert_eq!((mvp.phase.as_str(), mvp.total), ("mvp", 3));
+        assert_eq!(mvp.counts["blocked"], 1);
+        assert_eq!(mvp.counts["scaffolded"], 0);
+        assert_eq!(mvp.completion_percent, 33.3);
+        assert_eq!(mvp.blocked, vec!["1.2"]);
+        // Latest scores only: (9 + 6) / 2
+        assert_eq!((mvp.average_pss, mvp.scored), (Some(7.5), 2));
+        assert_eq!(phases[1].average_pss, None);
+        assert_eq!(phases[1].completion_percent, 0.0);
+    }
+}
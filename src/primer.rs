@@ -0,0 +1,798 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::schema::{ComponentInfo, ProjectPrimer, RotdConfig, TaskEntry};
+
+/// Scope of the project-wide primer in `primer.jsonc`
+pub const ROOT_SCOPE: &str = "root";
+
+/// Primer fields that `rotd primer update` keeps in step with the project
+pub const DRIFT_FIELDS: [&str; 4] = [
+    "entry_points",
+    "test_dirs",
+    "dependencies",
+    "major_components",
+];
+
+/// Path as stored in the primer: `/`-separated, without a leading `./`
+fn primer_path_string(path: &Path) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Whether a file is, or lives under, one of the entry points
+fn is_entry_point(file: &str, entry_points: &[String]) -> bool {
+    entry_points.iter().any(|entry| {
+        let entry = entry.trim_start_matches("./");
+        match entry.strip_suffix('/') {
+            Some(dir) => file.starts_with(&format!("{}/", dir)),
+            None => file == entry,
+        }
+    })
+}
+
+/// Group source files into components: each top-level directory under a
+/// source root is one component, and each file directly in a root is a
+/// module of its own. A module and a directory of the same name (`checks.rs`
+/// and `checks/`) are merged. `files` pairs each path with its line count.
+pub fn components_from_files(
+    roots: &[String],
+    files: &[(String, usize)],
+    entry_points: &[String],
+) -> BTreeMap<String, ComponentInfo> {
+    // name -> (location, files, lines)
+    let mut groups: BTreeMap<String, (String, Vec<String>, usize)> = BTreeMap::new();
+
+    for (file, lines) in files {
+        let Some((root, relative)) = roots.iter().find_map(|root| {
+            let root = root.trim_start_matches("./").trim_end_matches('/');
+            if root.is_empty() || root == "." {
+                Some(("", file.as_str()))
+            } else {
+                file.strip_prefix(root)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .map(|rest| (root, rest))
+            }
+        }) else {
+            continue;
+        };
+        let prefix = if root.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", root)
+        };
+
+        let (name, location) = match relative.split_once('/') {
+            Some((dir, _)) => (dir.to_string(), format!("{}{}/", prefix, dir)),
+            None => {
+                let stem = Path::new(relative)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| relative.to_string());
+                (stem, file.clone())
+            }
+        };
+
+        let group = groups
+            .entry(name)
+            .or_insert_with(|| (location.clone(), Vec::new(), 0));
+        // Prefer the directory as the location when a module has one
+        if location.ends_with('/') {
+            group.0 = location;
+        }
+        group.1.push(file.clone());
+        group.2 += lines;
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, (location, mut files, lines))| {
+            files.sort();
+            let mut description = if files.len() == 1 {
+                let unit = if lines == 1 { "line" } else { "lines" };
+                format!("{} ({} {})", location, lines, unit)
+            } else {
+                format!("{} ({} files, {} lines)", location, files.len(), lines)
+            };
+            if files.iter().any(|f| is_entry_point(f, entry_points)) {
+                description.push_str("; contains an entry point");
+            }
+            (name, ComponentInfo { description, files })
+        })
+        .collect()
+}
+
+/// Components discovered from the source tree covered by stub detection,
+/// as a starting point for the primer's `major_components`
+pub fn discover_components(
+    config: &RotdConfig,
+    entry_points: &[String],
+) -> BTreeMap<String, ComponentInfo> {
+    let files: Vec<(String, usize)> = crate::pss::source_files(config)
+        .into_iter()
+        .map(|path| {
+            let lines = std::fs::read_to_string(&path)
+                .map(|content| content.lines().count())
+                .unwrap_or(0);
+            (primer_path_string(&path), lines)
+        })
+        .collect();
+
+    components_from_files(&crate::pss::source_dirs(config), &files, entry_points)
+}
+
+/// Dependency names declared in Cargo.toml
+fn cargo_dependencies(content: &str) -> Vec<String> {
+    let mut deps = Vec::new();
+    let mut in_deps = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(section) = line.strip_prefix('[') {
+            let section = section.trim_end_matches(']');
+            let table = section.rsplit('.').next().unwrap_or_default();
+            // `[dependencies.serde]` names the dependency in the header
+            match section.split_once('.') {
+                Some((kind, name)) if kind.ends_with("dependencies") && !name.contains('.') => {
+                    deps.push(name.trim_matches('"').to_string());
+                    in_deps = false;
+                }
+                _ => in_deps = table.ends_with("dependencies"),
+            }
+            continue;
+        }
+        if in_deps && !line.starts_with('#') {
+            if let Some((name, _)) = line.split_once('=') {
+                deps.push(name.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    deps
+}
+
+/// Dependency names in package.json, including dev and peer dependencies
+fn package_json_dependencies(content: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .filter_map(|key| value.get(key)?.as_object())
+        .flat_map(|deps| deps.keys().cloned())
+        .collect()
+}
+
+/// Package names in requirements.txt
+fn requirements_dependencies(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .filter_map(|line| {
+            let name = line
+                .split(|c: char| "=<>!~;[ ".contains(c))
+                .next()
+                .unwrap_or_default();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Module paths required in go.mod
+fn go_mod_dependencies(content: &str) -> Vec<String> {
+    let mut deps = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line == "require (" {
+            in_block = true;
+        } else if in_block && line == ")" {
+            in_block = false;
+        } else if let Some(module) = line
+            .strip_prefix("require ")
+            .or_else(|| in_block.then_some(line))
+        {
+            if let Some(name) = module.split_whitespace().next() {
+                if !name.starts_with("//") {
+                    deps.push(name.to_string());
+                }
+            }
+        }
+    }
+    deps
+}
+
+type ManifestParser = fn(&str) -> Vec<String>;
+
+/// Dependencies declared in the project's manifests, or `None` when there is
+/// no manifest rotd can read
+pub fn project_dependencies() -> Option<Vec<String>> {
+    let parsers: [(&str, ManifestParser); 4] = [
+        ("Cargo.toml", cargo_dependencies),
+        ("package.json", package_json_dependencies),
+        ("requirements.txt", requirements_dependencies),
+        ("go.mod", go_mod_dependencies),
+    ];
+    let mut found = false;
+    let mut deps = Vec::new();
+    for (manifest, parse) in parsers {
+        if let Ok(content) = std::fs::read_to_string(manifest) {
+            found = true;
+            deps.extend(parse(&content));
+        }
+    }
+    deps.sort();
+    deps.dedup();
+    found.then_some(deps)
+}
+
+/// The parts of the project a primer describes, as they are now
+#[derive(Debug, Clone, Default)]
+pub struct ProjectState {
+    pub entry_points: Vec<String>,
+    pub test_dirs: Vec<String>,
+    /// `None` when the project has no readable manifest
+    pub dependencies: Option<Vec<String>>,
+    pub components: BTreeMap<String, ComponentInfo>,
+}
+
+pub fn current_state(config: &RotdConfig) -> ProjectState {
+    let (_, entry_points, test_dirs) = crate::profile::primer_defaults(config);
+    let components = discover_components(config, &entry_points);
+    ProjectState {
+        entry_points,
+        test_dirs,
+        dependencies: project_dependencies(),
+        components,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Add,
+    Remove,
+}
+
+/// One difference between the primer and the project
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PrimerChange {
+    pub field: &'static str,
+    pub action: ChangeAction,
+    pub value: String,
+}
+
+impl std::fmt::Display for PrimerChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = match self.action {
+            ChangeAction::Add => '+',
+            ChangeAction::Remove => '-',
+        };
+        write!(f, "{} {}: {}", sign, self.field, self.value)
+    }
+}
+
+/// The dependency an entry names, so `serde (serialization)` still matches
+/// `serde` after a developer annotates it
+fn dependency_name(entry: &str) -> &str {
+    entry
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or(entry)
+}
+
+/// Differences between the primer and the project. Paths are only dropped
+/// once they no longer exist, and components once none of their files do,
+/// so hand-added entries survive an update.
+pub fn drift(
+    primer: &ProjectPrimer,
+    state: &ProjectState,
+    exists: impl Fn(&str) -> bool,
+) -> Vec<PrimerChange> {
+    let mut changes = Vec::new();
+    let mut change = |field, action, value: &str| {
+        changes.push(PrimerChange {
+            field,
+            action,
+            value: value.to_string(),
+        })
+    };
+
+    for (field, listed, detected) in [
+        ("entry_points", &primer.entry_points, &state.entry_points),
+        ("test_dirs", &primer.test_dirs, &state.test_dirs),
+    ] {
+        for path in detected.iter().filter(|p| !listed.contains(p)) {
+            change(field, ChangeAction::Add, path);
+        }
+        for path in listed.iter().filter(|p| !exists(p)) {
+            change(field, ChangeAction::Remove, path);
+        }
+    }
+
+    if let Some(deps) = &state.dependencies {
+        let listed: Vec<&str> = primer
+            .dependencies
+            .iter()
+            .map(|d| dependency_name(d))
+            .collect();
+        for dep in deps.iter().filter(|d| !listed.contains(&d.as_str())) {
+            change("dependencies", ChangeAction::Add, dep);
+        }
+        for entry in &primer.dependencies {
+            if !deps.iter().any(|d| d == dependency_name(entry)) {
+                change("dependencies", ChangeAction::Remove, entry);
+            }
+        }
+    }
+
+    let components = primer.major_components.clone().unwrap_or_default();
+    for name in state.components.keys() {
+        if !components.contains_key(name) {
+            change("major_components", ChangeAction::Add, name);
+        }
+    }
+    for (name, component) in &components {
+        if !component.files.is_empty() && !component.files.iter().any(|f| exists(f)) {
+            change("major_components", ChangeAction::Remove, name);
+        }
+    }
+
+    changes
+}
+
+/// Apply changes from [`drift`] to the primer
+pub fn apply_changes(primer: &mut ProjectPrimer, state: &ProjectState, changes: &[PrimerChange]) {
+    for change in changes {
+        let list = match change.field {
+            "entry_points" => &mut primer.entry_points,
+            "test_dirs" => &mut primer.test_dirs,
+            "dependencies" => &mut primer.dependencies,
+            _ => {
+                let components = primer.major_components.get_or_insert_with(BTreeMap::new);
+                match change.action {
+                    ChangeAction::Add => {
+                        if let Some(component) = state.components.get(&change.value) {
+                            components.insert(change.value.clone(), component.clone());
+                        }
+                    }
+                    ChangeAction::Remove => {
+                        components.remove(&change.value);
+                    }
+                }
+                continue;
+            }
+        };
+        match change.action {
+            ChangeAction::Add => list.push(change.value.clone()),
+            ChangeAction::Remove => list.retain(|v| *v != change.value),
+        }
+    }
+}
+
+/// The project's current state and its drift from the primer, limited to
+/// `fields` when any are given
+pub fn project_drift(
+    primer: &ProjectPrimer,
+    fields: &[String],
+) -> Result<(ProjectState, Vec<PrimerChange>)> {
+    let state = current_state(&crate::history::load_config()?);
+    let changes = drift(primer, &state, |path| Path::new(path).exists())
+        .into_iter()
+        .filter(|c| fields.is_empty() || fields.iter().any(|f| f == c.field))
+        .collect();
+    Ok((state, changes))
+}
+
+/// Primer file for a scope: `primer.jsonc` for the root scope, otherwise
+/// `primers/<scope>.jsonc`
+pub fn scope_path(scope: &str) -> Result<PathBuf> {
+    if scope == ROOT_SCOPE {
+        return Ok(crate::common::primer_path());
+    }
+    let valid = !scope.is_empty()
+        && !scope.starts_with('.')
+        && scope
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c));
+    if !valid {
+        return Err(anyhow::anyhow!(
+            "Invalid primer scope '{}': use letters, digits, '-', '_' and '.'",
+            scope
+        ));
+    }
+    Ok(crate::common::primers_path().join(format!("{}.jsonc", scope)))
+}
+
+/// The file holding a scope's primer, falling back to any scoped primer
+/// whose `scope` field matches when the file name doesn't
+pub fn find_scope(scope: &str) -> Result<PathBuf> {
+    let path = scope_path(scope)?;
+    if path.exists() {
+        return Ok(path);
+    }
+    Ok(load_all()?
+        .into_iter()
+        .find(|(_, primer)| primer.scope == scope)
+        .map(|(path, _)| path)
+        .unwrap_or(path))
+}
+
+/// The root primer (when present) followed by the scoped primers in scope
+/// order
+pub fn load_all() -> Result<Vec<(PathBuf, ProjectPrimer)>> {
+    let mut primers = Vec::new();
+    let root = crate::common::primer_path();
+    if root.exists() {
+        primers.push((root.clone(), load(&root)?));
+    }
+
+    let mut scoped = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(crate::common::primers_path()) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|ext| ext == "jsonc" || ext == "json")
+            {
+                scoped.push((path.clone(), load(&path)?));
+            }
+        }
+    }
+    scoped.sort_by(|a, b| a.1.scope.cmp(&b.1.scope));
+    primers.extend(scoped);
+    Ok(primers)
+}
+
+/// Whether a primer helps with a task. The root primer always does; a scoped
+/// primer does when the task names its scope, or its origin lies under one
+/// of the scope's entry points, test dirs, or component files.
+pub fn is_relevant(primer: &ProjectPrimer, task: &TaskEntry) -> bool {
+    if primer.scope == ROOT_SCOPE {
+        return true;
+    }
+
+    let scope = primer.scope.to_lowercase();
+    let names_scope = |text: &str| {
+        text.to_lowercase()
+            .split(|c: char| !(c.is_alphanumeric() || "-_.".contains(c)))
+            .any(|word| word == scope)
+    };
+    let mentioned = [
+        Some(task.id.as_str()),
+        Some(task.title.as_str()),
+        task.description.as_deref(),
+        task.phase.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(names_scope);
+    if mentioned {
+        return true;
+    }
+
+    let Some(origin) = task.origin.as_deref() else {
+        return false;
+    };
+    let origin = origin.trim_start_matches("./");
+    let components = primer.major_components.iter().flat_map(|c| c.values());
+    primer
+        .entry_points
+        .iter()
+        .chain(&primer.test_dirs)
+        .chain(components.flat_map(|c| &c.files))
+        .map(|path| path.trim_start_matches("./").trim_end_matches('/'))
+        .filter(|path| !path.is_empty() && *path != ".")
+        .any(|path| {
+            origin == path
+                || origin.starts_with(&format!("{}/", path))
+                || origin.starts_with(&format!("{}:", path))
+        })
+}
+
+/// Scopes of the primers relevant to a task
+pub fn relevant_scopes(task: &TaskEntry) -> Result<Vec<String>> {
+    Ok(load_all()?
+        .into_iter()
+        .filter(|(_, primer)| is_relevant(primer, task))
+        .map(|(_, primer)| primer.scope)
+        .collect())
+}
+
+/// Render a primer as a Markdown document, for a README or an agent prompt.
+/// Empty sections are left out.
+pub fn to_markdown(primer: &ProjectPrimer) -> String {
+    use std::fmt::Write;
+
+    let code = |items: &[String]| {
+        items
+            .iter()
+            .map(|i| format!("`{}`", i))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut md = String::new();
+
+    if primer.scope == ROOT_SCOPE {
+        let _ = writeln!(md, "# {}\n", primer.name);
+    } else {
+        let _ = writeln!(md, "# {} ({})\n", primer.name, primer.scope);
+    }
+    if !primer.description.is_empty() {
+        let _ = writeln!(md, "{}\n", primer.description);
+    }
+
+    let _ = writeln!(md, "- **Language:** {}", primer.language);
+    let _ = writeln!(md, "- **Status:** {}", primer.status);
+    if !primer.entry_points.is_empty() {
+        let _ = writeln!(md, "- **Entry points:** {}", code(&primer.entry_points));
+    }
+    if !primer.test_dirs.is_empty() {
+        let _ = writeln!(md, "- **Tests:** {}", code(&primer.test_dirs));
+    }
+    if !primer.dependencies.is_empty() {
+        let _ = writeln!(md, "- **Dependencies:** {}", primer.dependencies.join(", "));
+    }
+
+    let list = |md: &mut String, title: &str, items: &[String]| {
+        if !items.is_empty() {
+            let _ = writeln!(md, "\n## {}\n", title);
+            for item in items {
+                let _ = writeln!(md, "- {}", item);
+            }
+        }
+    };
+    list(
+        &mut md,
+        "Starting Points",
+        &primer.suggested_starting_points,
+    );
+    list(&mut md, "Key Concepts", &primer.key_concepts);
+
+    if let Some(components) = primer.major_components.as_ref().filter(|c| !c.is_empty()) {
+        let _ = writeln!(md, "\n## Components\n");
+        for (name, component) in components {
+            let _ = writeln!(md, "### {}\n", name);
+            if !component.description.is_empty() {
+                let _ = writeln!(md, "{}\n", component.description);
+            }
+            if !component.files.is_empty() {
+                let _ = writeln!(md, "Files: {}\n", code(&component.files));
+            }
+        }
+        md.pop();
+    }
+
+    list(&mut md, "Known Issues", &primer.known_issues);
+    if let Some(triggers) = &primer.update_triggers {
+        list(&mut md, "Update This Primer When", triggers);
+    }
+
+    md
+}
+
+/// Load a primer file
+pub fn load(path: &Path) -> Result<ProjectPrimer> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    crate::history::parse_jsonc(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Header for new primer files
+const PRIMER_HEADER: &str = "// ROTD project primer: orientation for anyone starting work here.\n\
+// Comments on their own line above a top-level field are kept when rotd\n\
+// rewrites this file.\n";
+
+/// Write a primer file, keeping the comments of the file it replaces
+pub fn save(path: &Path, primer: &ProjectPrimer) -> Result<()> {
+    let json = serde_json::to_string_pretty(primer)?;
+    let content = match std::fs::read_to_string(path) {
+        Ok(original) => crate::history::preserve_jsonc_comments(&original, &json),
+        Err(_) => format!("{}{}\n", PRIMER_HEADER, json),
+    };
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_components_from_files() {
+        let files: Vec<(String, usize)> = [
+            ("src/main.rs", 40),
+            ("src/checks.rs", 10),
+            ("src/checks/deps.rs", 20),
+            ("src/ui/mod.rs", 5),
+            ("src/ui/table.rs", 15),
+            ("examples/demo.rs", 3),
+        ]
+        .iter()
+        .map(|(f, n)| (f.to_string(), *n))
+        .collect();
+        let components =
+            components_from_files(&["src".to_string()], &files, &["src/main.rs".to_string()]);
+
+        assert_eq!(
+            components.keys().collect::<Vec<_>>(),
+            vec!["checks", "main", "ui"]
+        );
+        assert_eq!(
+            components["main"].description,
+            "src/main.rs (40 lines); contains an entry point"
+        );
+        assert_eq!(
+            components["checks"].description,
+            "src/checks/ (2 files, 30 lines)"
+        );
+        assert_eq!(
+            components["checks"].files,
+            vec!["src/checks.rs", "src/checks/deps.rs"]
+        );
+        assert_eq!(components["ui"].files.len(), 2);
+
+        // A `.` root groups by top-level directory; entry point dirs match
+        let go = components_from_files(
+            &[".".to_string()],
+            &[("cmd/rotd/main.go".to_string(), 12)],
+            &["cmd/".to_string()],
+        );
+        assert_eq!(
+            go["cmd"].description,
+            "cmd/ (12 lines); contains an entry point"
+        );
+    }
+
+    #[test]
+    fn test_cargo_dependencies() {
+        let manifest = "[package]\nname = \"demo\"\n\n[dependencies]\nserde = { version = \"1\" }\n# comment = 1\nanyhow = \"1\"\n\n[dependencies.clap]\nversion = \"4\"\n\n[target.'cfg(unix)'.dependencies]\nlibc = \"0.2\"\n\n[dev-dependencies]\ntempfile = \"3\"\n";
+        assert_eq!(
+            cargo_dependencies(manifest),
+            vec!["serde", "anyhow", "clap", "libc", "tempfile"]
+        );
+        assert_eq!(
+            requirements_dependencies("# pinned\nrequests>=2.0\n-r dev.txt\nflask[async]==3.0\n"),
+            vec!["requests", "flask"]
+        );
+    }
+
+    #[test]
+    fn test_drift_and_apply() {
+        let mut primer: ProjectPrimer = serde_json::from_value(serde_json::json!({
+            "name": "demo",
+            "scope": "root",
+            "description": "Demo",
+            "status": "active",
+            "language": "Rust",
+            "entry_points": ["src/main.rs", "src/custom.rs"],
+            "test_dirs": ["spec/"],
+            "dependencies": ["serde (serialization)", "left-pad"],
+            "known_issues": [],
+            "key_concepts": [],
+            "preferred_agents": null,
+            "suggested_starting_points": [],
+            "major_components": {
+                "old": {"description": "Gone", "files": ["src/old.rs"]},
+                "main": {"description": "Refined by hand", "files": ["src/main.rs"]}
+            },
+            "update_triggers": null
+        }))
+        .unwrap();
+        let state = ProjectState {
+            entry_points: vec!["src/main.rs".to_string(), "src/lib.rs".to_string()],
+            test_dirs: vec!["tests/".to_string()],
+            dependencies: Some(vec!["anyhow".to_string(), "serde".to_string()]),
+            components: components_from_files(
+                &["src".to_string()],
+                &[
+                    ("src/main.rs".to_string(), 10),
+                    ("src/lib.rs".to_string(), 5),
+                ],
+                &[],
+            ),
+        };
+        let existing = ["src/main.rs", "src/custom.rs", "src/lib.rs", "tests/"];
+
+        let changes = drift(&primer, &state, |p| existing.contains(&p));
+        let summary: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            summary,
+            vec![
+                "+ entry_points: src/lib.rs",
+                "+ test_dirs: tests/",
+                "- test_dirs: spec/",
+                "+ dependencies: anyhow",
+                "- dependencies: left-pad",
+                "+ major_components: lib",
+                "- major_components: old",
+            ]
+        );
+
+        apply_changes(&mut primer, &state, &changes);
+        assert_eq!(
+            primer.entry_points,
+            vec!["src/main.rs", "src/custom.rs", "src/lib.rs"]
+        );
+        assert_eq!(primer.test_dirs, vec!["tests/"]);
+        assert_eq!(primer.dependencies, vec!["serde (serialization)", "anyhow"]);
+        let components = primer.major_components.as_ref().unwrap();
+        assert_eq!(components.keys().collect::<Vec<_>>(), vec!["lib", "main"]);
+        assert_eq!(components["main"].description, "Refined by hand");
+        assert!(drift(&primer, &state, |p| existing.contains(&p)).is_empty());
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        let primer = |scope: &str| -> ProjectPrimer {
+            serde_json::from_value(serde_json::json!({
+                "name": "shop",
+                "scope": scope,
+                "description": "",
+                "status": "active",
+                "language": "TypeScript",
+                "entry_points": ["packages/api/src/index.ts"],
+                "test_dirs": ["packages/api/test/"],
+                "dependencies": [],
+                "known_issues": [],
+                "key_concepts": [],
+                "preferred_agents": null,
+                "suggested_starting_points": [],
+                "major_components": null,
+                "update_triggers": null
+            }))
+            .unwrap()
+        };
+        let task = |title: &str, origin: Option<&str>| -> TaskEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": "4.1",
+                "title": title,
+                "status": "pending",
+                "origin": origin,
+            }))
+            .unwrap()
+        };
+
+        let api = primer("api");
+        assert!(is_relevant(&primer(ROOT_SCOPE), &task("Anything", None)));
+        assert!(is_relevant(&api, &task("Add API rate limits", None)));
+        assert!(!is_relevant(&api, &task("Rapid checkout", None)));
+        assert!(is_relevant(
+            &api,
+            &task("Fix crash", Some("packages/api/test/cart.test.ts:40"))
+        ));
+        assert!(!is_relevant(
+            &api,
+            &task("Fix crash", Some("packages/web/src/index.ts:3"))
+        ));
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let primer: ProjectPrimer = serde_json::from_value(serde_json::json!({
+            "name": "shop",
+            "scope": "api",
+            "description": "Checkout API",
+            "status": "active",
+            "language": "TypeScript",
+            "entry_points": ["src/index.ts"],
+            "test_dirs": [],
+            "dependencies": [],
+            "known_issues": [],
+            "key_concepts": ["Carts expire after an hour"],
+            "preferred_agents": null,
+            "suggested_starting_points": [],
+            "major_components": {
+                "cart": {"description": "Cart state", "files": ["src/cart.ts"]}
+            },
+            "update_triggers": null
+        }))
+        .unwrap();
+
+        assert_eq!(
+            to_markdown(&primer),
+            "# shop (api)\n\nCheckout API\n\n- **Language:** TypeScript\n- **Status:** active\n- **Entry points:** `src/index.ts`\n\n## Key Concepts\n\n- Carts expire after an hour\n\n## Components\n\n### cart\n\nCart state\n\nFiles: `src/cart.ts`\n"
+        );
+    }
+}
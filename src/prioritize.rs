@@ -0,0 +1,278 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, safe_update_task};
+use crate::schema::{PrioritizeSettings, Priority, TaskEntry, TaskStatus};
+
+/// One input to a task's score, scaled to 0-1
+#[derive(Debug, Serialize)]
+pub struct Factor {
+    pub name: &'static str,
+    pub value: f64,
+    pub weight: f64,
+    /// The raw attribute the value came from, e.g. `12 days old`
+    pub detail: String,
+}
+
+/// A task's computed `priority_score` and how it was reached
+#[derive(Debug, Serialize)]
+pub struct Prioritized {
+    pub task_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<f64>,
+    pub score: f64,
+    pub factors: Vec<Factor>,
+}
+
+impl Prioritized {
+    pub fn changed(&self) -> bool {
+        self.previous != Some(self.score)
+    }
+}
+
+fn priority_value(priority: Option<&Priority>) -> f64 {
+    match priority {
+        Some(Priority::Urgent) => 1.0,
+        Some(Priority::High) => 0.75,
+        Some(Priority::Medium) | None => 0.5,
+        Some(Priority::Low) => 0.25,
+        Some(Priority::Deferred) => 0.0,
+    }
+}
+
+/// Scores for every incomplete task, highest first (ties keep task order)
+pub fn score_tasks(
+    tasks: &[TaskEntry],
+    settings: &PrioritizeSettings,
+    now: DateTime<Utc>,
+) -> Vec<Prioritized> {
+    let incomplete: Vec<&TaskEntry> = tasks
+        .iter()
+        .filter(|t| t.status != TaskStatus::Complete)
+        .collect();
+
+    let mut scored: Vec<Prioritized> = incomplete
+        .iter()
+        .map(|task| {
+            let age_days = task
+                .created
+                .map(|created| (now - created).num_hours().max(0) as f64 / 24.0)
+                .unwrap_or(0.0);
+            let dependents = incomplete
+                .iter()
+                .filter(|t| t.depends_on.iter().flatten().any(|d| d == &task.id))
+                .count();
+            let phase = task.phase.as_deref();
+            let phase_value = phase
+                .and_then(|p| settings.phase_weights.get(p))
+                .copied()
+                .unwrap_or(0.5);
+
+            let factors = vec![
+                Factor {
+                    name: "priority",
+                    value: priority_value(task.priority.as_ref()),
+                    weight: settings.priority_weight,
+                    detail: task
+                        .priority
+                        .as_ref()
+                        .map_or("none (as medium)", |p| p.as_str())
+                        .to_string(),
+                },
+                Factor {
+                    name: "age",
+                    value: ratio(age_days, settings.age_cap_days),
+                    weight: settings.age_weight,
+                    detail: format!("{:.0} days old", age_days),
+                },
+                Factor {
+                    name: "fan_out",
+                    value: ratio(dependents as f64, settings.fan_out_cap as f64),
+                    weight: settings.fan_out_weight,
+                    detail: format!("{} incomplete task(s) waiting", dependents),
+                },
+                Factor {
+                    name: "phase",
+                    value: phase_value.clamp(0.0, 1.0),
+                    weight: settings.phase_weight,
+                    detail: phase.unwrap_or("none").to_string(),
+                },
+            ];
+
+            let total_weight: f64 = factors.iter().map(|f| f.weight.max(0.0)).sum();
+            let score = if total_weight > 0.0 {
+                let weighted: f64 = factors.iter().map(|f| f.value * f.weight.max(0.0)).sum();
+                (weighted / total_weight * 1000.0).round() / 10.0
+            } else {
+                0.0
+            };
+
+            Prioritized {
+                task_id: task.id.clone(),
+                title: task.title.clone(),
+                previous: task.priority_score,
+                score,
+                factors,
+            }
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+    scored
+}
+
+/// `value / cap`, clamped to 0-1
+fn ratio(value: f64, cap: f64) -> f64 {
+    if cap <= 0.0 {
+        return 1.0;
+    }
+    (value / cap).clamp(0.0, 1.0)
+}
+
+/// `rotd prioritize`: score incomplete tasks and append the ones whose
+/// score changed
+pub fn run(explain: bool, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let config = crate::history::load_config()?;
+    let settings = config.prioritize.unwrap_or_default();
+    let tasks = latest_tasks()?;
+    let scored = score_tasks(&tasks, &settings, Utc::now());
+
+    let mut updated = Vec::new();
+    for result in scored.iter().filter(|r| r.changed()) {
+        if !dry_run {
+            if let Some(task) = tasks.iter().find(|t| t.id == result.task_id) {
+                let mut task = task.clone();
+                task.priority_score = Some(result.score);
+                task.update_timestamp();
+                safe_update_task(&task, false)?;
+            }
+        }
+        updated.push(result.task_id.clone());
+    }
+
+    // Keep claim ordering in the coordination registry in step
+    let registry_updated = if dry_run {
+        0
+    } else {
+        let scores = scored
+            .iter()
+            .map(|r| (r.task_id.clone(), r.score))
+            .collect();
+        crate::coord::set_priority_scores(&scores)?
+    };
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "prioritize",
+                "updated": updated,
+                "registry_updated": registry_updated,
+                "tasks": scored,
+                "dry_run": dry_run,
+            })
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "{}",
+            "DRY RUN MODE - No changes will be made".yellow().bold()
+        );
+    }
+    if scored.is_empty() {
+        println!("No incomplete tasks to prioritize");
+        return Ok(());
+    }
+    println!("{:>6}  {:<12} Title", "Score", "Task");
+    for result in &scored {
+        let change = match result.previous {
+            Some(previous) if result.changed() => format!(" (was {:.1})", previous),
+            None => " (new)".to_string(),
+            Some(_) => String::new(),
+        };
+        println!(
+            "{:>6.1}  {:<12} {}{}",
+            result.score,
+            result.task_id.bold(),
+            result.title,
+            change.dimmed()
+        );
+        if explain {
+            for factor in &result.factors {
+                println!(
+                    "          {:<9} {:.2} × {:.2}  {}",
+                    factor.name,
+                    factor.value,
+                    factor.weight,
+                    factor.detail.dimmed()
+                );
+            }
+        }
+    }
+    println!(
+        "\n{} priority_score on {} task(s)",
+        if dry_run { "Would update" } else { "✓ Updated" },
+        updated.len()
+    );
+    if registry_updated > 0 {
+        println!("  {} coordination registry task(s) updated", registry_updated);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, priority: &str, age_days: i64, phase: &str, deps: &[&str]) -> TaskEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": id,
+            "status": "pending",
+            "priority": priority,
+            "created": Utc::now() - chrono::Duration::days(age_days),
+            "phase": phase,
+            "depends_on": deps,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_score_tasks() {
+        let mut settings = PrioritizeSettings::default();
+        settings.phase_weights.insert("mvp".to_string(), 1.0);
+        let tasks = vec![
+            task("a", "low", 0, "later", &[]),
+            task("b", "urgent", 60, "mvp", &[]),
+            task("c", "medium", 15, "mvp", &["b"]),
+        ];
+
+        let scored = score_tasks(&tasks, &settings, Utc::now());
+        let order: Vec<&str> = scored.iter().map(|s| s.task_id.as_str()).collect();
+        assert_eq!(order, vec!["b", "c", "a"]);
+        // urgent 1.0×0.4 + age 1.0×0.2 + fan-out 0.2×0.3 + phase 1.0×0.1
+        assert_eq!(scored[0].score, 76.0);
+        // low 0.25×0.4 + age 0 + fan-out 0 + unlisted phase 0.5×0.1
+        assert_eq!(scored[2].score, 15.0);
+        assert!(scored.iter().all(|s| s.changed()));
+
+        let zero = PrioritizeSettings {
+            priority_weight: 0.0,
+            age_weight: 0.0,
+            fan_out_weight: 0.0,
+            phase_weight: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(score_tasks(&tasks, &zero, Utc::now())[0].score, 0.0);
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+
+use crate::schema::RotdConfig;
+
+/// Language presets for `rotd init --profile`
+pub struct Profile {
+    pub name: &'static str,
+    /// Language recorded in the primer
+    pub language: &'static str,
+    pub compile_command: &'static str,
+    pub stub_patterns: &'static [&'static str],
+    /// File extensions scanned for stubs
+    pub stub_extensions: &'static [&'static str],
+    /// Directories scanned for stubs
+    pub stub_dirs: &'static [&'static str],
+    /// Paths skipped by stub detection beyond the project's `.gitignore`
+    pub stub_ignore: &'static [&'static str],
+    pub test_dirs: &'static [&'static str],
+    pub coverage_command: &'static str,
+    /// Candidate entry points for the primer; only existing ones are kept
+    pub entry_points: &'static [&'static str],
+}
+
+pub const PROFILE_NAMES: [&str; 4] = ["rust", "node", "python", "go"];
+
+const PROFILES: [Profile; 4] = [
+    Profile {
+        name: "rust",
+        language: "Rust",
+        compile_command: "cargo check",
+        stub_patterns: &["#[rotd_stub]", "TODO(", "unimplemented!", "todo!"],
+        stub_extensions: &["rs"],
+        stub_dirs: &["src"],
+        stub_ignore: &[],
+        test_dirs: &["tests/"],
+        coverage_command: "cargo tarpaulin --out Json",
+        entry_points: &["src/main.rs", "src/lib.rs"],
+    },
+    Profile {
+        name: "node",
+        language: "JavaScript/TypeScript",
+        compile_command: "npm run typecheck",
+        stub_patterns: &[
+            "TODO(",
+            "throw new Error(\"TODO\")",
+            "throw new Error('TODO')",
+        ],
+        stub_extensions: &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+        stub_dirs: &["src"],
+        stub_ignore: &["node_modules/", "dist/"],
+        test_dirs: &["test/", "tests/", "__tests__/"],
+        coverage_command: "npx c8 --reporter=json-summary npm test",
+        entry_points: &["index.js", "src/index.js", "src/index.ts", "src/main.ts"],
+    },
+    Profile {
+        name: "python",
+        language: "Python",
+        compile_command: "python -m compileall -q .",
+        stub_patterns: &["TODO(", "raise NotImplementedError"],
+        stub_extensions: &["py"],
+        stub_dirs: &["."],
+        stub_ignore: &["venv/", "build/"],
+        test_dirs: &["tests/", "test/"],
+        coverage_command: "coverage run -m pytest",
+        entry_points: &["main.py", "app.py", "__main__.py"],
+    },
+    Profile {
+        name: "go",
+        language: "Go",
+        compile_command: "go build ./...",
+        stub_patterns: &["TODO(", "panic(\"TODO\")", "panic(\"not implemented\")"],
+        stub_extensions: &["go"],
+        stub_dirs: &["."],
+        stub_ignore: &["vendor/"],
+        test_dirs: &["."],
+        coverage_command: "go test -coverprofile=coverage.out ./...",
+        entry_points: &["main.go", "cmd/"],
+    },
+];
+
+pub fn get(name: &str) -> Result<&'static Profile> {
+    PROFILES.iter().find(|p| p.name == name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown init profile '{}'. Expected one of: {}",
+            name,
+            PROFILE_NAMES.join(", ")
+        )
+    })
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+impl Profile {
+    /// Fill the profile's settings into a config
+    pub fn apply(&self, config: &mut RotdConfig) {
+        config.profile = Some(self.name.to_string());
+        config.compile_command = Some(self.compile_command.to_string());
+        config.stub_patterns = strings(self.stub_patterns);
+        config.stub_extensions = strings(self.stub_extensions);
+        config.stub_dirs = strings(self.stub_dirs);
+        config.stub_ignore = strings(self.stub_ignore);
+        config.test_dirs = strings(self.test_dirs);
+        config.coverage_command = Some(self.coverage_command.to_string());
+    }
+}
+
+/// Language, entry points, and test directories for a new primer: from the
+/// configured profile when there is one, otherwise detected from the files
+/// present
+pub fn primer_defaults(config: &RotdConfig) -> (String, Vec<String>, Vec<String>) {
+    let exists = |path: &&str| std::path::Path::new(path).exists();
+
+    let profile = config.profile.as_deref().and_then(|name| get(name).ok());
+    let (language, candidates): (&str, &[&str]) = match profile {
+        Some(profile) => (profile.language, profile.entry_points),
+        None if exists(&"Cargo.toml") => ("Rust", &["src/main.rs", "src/lib.rs"]),
+        None if exists(&"package.json") => {
+            ("JavaScript/TypeScript", &["index.js", "src/index.js", "src/main.ts"])
+        }
+        None if exists(&"requirements.txt") || exists(&"setup.py") => {
+            ("Python", &["main.py", "app.py", "__main__.py"])
+        }
+        None => ("Unknown", &["main"]),
+    };
+    let entry_points = candidates
+        .iter()
+        .copied()
+        .filter(exists)
+        .map(|s| s.to_string())
+        .collect();
+
+    let test_dirs = if config.test_dirs.is_empty() {
+        ["tests/", "test/", "spec/", "src/"]
+            .into_iter()
+            .filter(exists)
+            .map(|s| s.to_string())
+            .collect()
+    } else {
+        config.test_dirs.clone()
+    };
+
+    (language.to_string(), entry_points, test_dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_apply() {
+        let mut config = RotdConfig::default();
+        get("python").unwrap().apply(&mut config);
+        assert_eq!(config.profile.as_deref(), Some("python"));
+        assert_eq!(config.stub_extensions, vec!["py"]);
+        assert!(config.stub_patterns.contains(&"raise NotImplementedError".to_string()));
+        assert_eq!(primer_defaults(&config).0, "Python");
+        assert!(get("cobol").is_err());
+    }
+}
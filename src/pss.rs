@@ -1,9 +1,109 @@
 use anyhow::Result;
 use chrono::Utc;
+use colored::Colorize;
+use serde::Serialize;
 use std::collections::HashMap;
 
-use crate::fs_ops::{append_jsonl, read_json, read_jsonl};
-use crate::schema::{CoverageHistory, CriterionScore, PSSScore, TaskEntry, TestSummary};
+use crate::PssCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{append_jsonl, latest_tasks, read_json, read_jsonl, with_lock};
+use crate::output::escape_csv;
+use crate::schema::{
+    CoverageHistory, CriterionScore, PSS_SCORE_SCHEMA, PSSScore, RotdConfig, TaskEntry,
+    TestSummary,
+};
+
+/// A PSS criterion and how to earn it
+pub struct Criterion {
+    pub name: &'static str,
+    /// What earns the point
+    pub checks: &'static str,
+    /// Next steps when the point is missing
+    pub remediation: &'static str,
+    /// Commands that help, with `{id}` for the task ID and `{summary}` for
+    /// its test summary file
+    pub commands: &'static [&'static str],
+}
+
+/// PSS criteria in scoring order
+pub const CRITERIA: [Criterion; 10] = [
+    Criterion {
+        name: "llm_engaged",
+        checks: "The task is in progress or complete",
+        remediation: "Start work on the task so it is marked in progress",
+        commands: &["rotd task start {id}"],
+    },
+    Criterion {
+        name: "compiles",
+        checks: "The configured compile checks pass",
+        remediation: "Fix the build errors named in the rationale, then score again",
+        commands: &["rotd score {id}"],
+    },
+    Criterion {
+        name: "core_impl",
+        checks: "The task is complete and, once commits carry ROTD-Task trailers, has a linked commit",
+        remediation: "Finish the implementation and mark the task complete; if commits are linked to tasks, link the implementing commit",
+        commands: &[
+            "rotd task set-status --ids {id} --status complete",
+            "rotd git attach {id}",
+        ],
+    },
+    Criterion {
+        name: "tests_written",
+        checks: "The task's test summary records at least one test",
+        remediation: "Write tests for the task and record their results in a test summary",
+        commands: &["rotd agent append-summary --file {summary}"],
+    },
+    Criterion {
+        name: "tests_pass",
+        checks: "At least 70% of the recorded tests pass",
+        remediation: "Fix the failing tests and record a new test summary",
+        commands: &["rotd agent append-summary --file {summary}"],
+    },
+    Criterion {
+        name: "doc_maintained",
+        checks: "Documentation is maintained (always awarded for now)",
+        remediation: "Nothing to do",
+        commands: &[],
+    },
+    Criterion {
+        name: "stub_free",
+        checks: "No stub markers remain in the source",
+        remediation: "Replace the remaining stubs with real implementations, or exclude generated code with stub_ignore in config.jsonc",
+        commands: &["rotd stubs"],
+    },
+    Criterion {
+        name: "history_maintained",
+        checks: "The task is in tasks.jsonl and has a test summary",
+        remediation: "Record the task and a test summary for it",
+        commands: &[
+            "rotd show-task {id}",
+            "rotd agent append-summary --file {summary}",
+        ],
+    },
+    Criterion {
+        name: "qts_floor",
+        checks: "Coverage in the test summary meets the coverage floor",
+        remediation: "Record coverage in the test summary, adding tests until it reaches the floor",
+        commands: &["rotd coverage chart", "rotd agent append-summary --file {summary}"],
+    },
+    Criterion {
+        name: "qts_ratchet",
+        checks: "Coverage in the test summary clears the floor by more than the ratchet threshold",
+        remediation: "Raise coverage past the floor plus the ratchet threshold, then ratchet the floor up",
+        commands: &["rotd agent ratchet-coverage --run --task-id {id}"],
+    },
+];
+
+/// Output formats for `score --all`
+pub const MATRIX_FORMATS: [&str; 3] = ["table", "json", "csv"];
+
+pub fn handle_command(cmd: PssCommands, is_agent_mode: bool) -> Result<()> {
+    match cmd {
+        PssCommands::Latest { task_id } => cmd_latest(&task_id, is_agent_mode),
+        PssCommands::Explain { task_id } => cmd_explain(&task_id, is_agent_mode),
+    }
+}
 
 pub fn score_task(task_id: &str) -> Result<PSSScore> {
     let mut criteria = HashMap::new();
@@ -12,12 +112,15 @@ pub fn score_task(task_id: &str) -> Result<PSSScore> {
     let tasks: Vec<TaskEntry> = read_jsonl(&crate::common::tasks_path())?;
     let task = tasks.iter().find(|t| t.id == task_id);
 
-    let test_summary = load_test_summary(task_id).ok();
+    // Placeholders from `check --fix` don't count as results
+    let test_summary = load_test_summary(task_id)
+        .ok()
+        .filter(|ts| ts.status != crate::checks::UNVERIFIED);
     let coverage_history =
         read_json::<CoverageHistory>(&crate::common::coverage_history_path()).ok();
 
     // 1. LLM Engagement
-    let engaged = task.map_or(false, |t| {
+    let engaged = task.is_some_and(|t| {
         matches!(
             t.status,
             crate::schema::TaskStatus::InProgress | crate::schema::TaskStatus::Complete
@@ -37,7 +140,8 @@ pub fn score_task(task_id: &str) -> Result<PSSScore> {
     );
 
     // 2. Compiles
-    let compiles = check_compiles();
+    let compile_outcomes = check_compiles();
+    let compiles = crate::compile::compiles(&compile_outcomes);
     criteria.insert(
         "compiles".to_string(),
         CriterionScore {
@@ -45,29 +149,43 @@ pub fn score_task(task_id: &str) -> Result<PSSScore> {
             rationale: if compiles {
                 "Project compiles cleanly".to_string()
             } else {
-                "Compilation errors detected".to_string()
+                let failed: Vec<String> = compile_outcomes
+                    .iter()
+                    .filter(|o| !o.success)
+                    .map(|o| o.describe())
+                    .collect();
+                format!("Compilation errors detected: {}", failed.join(", "))
             },
         },
     );
 
     // 3. Core Implementation
-    let implemented = task.map_or(false, |t| {
+    // Once the project links commits via ROTD-Task trailers, a completed task
+    // also needs at least one commit to count as implemented.
+    let commit_index = crate::git::task_commit_index();
+    let linked_commits = commit_index.get(task_id).map(|c| c.len()).unwrap_or(0);
+    let implemented = task.is_some_and(|t| {
         matches!(t.status, crate::schema::TaskStatus::Complete)
-    });
+    }) && (commit_index.is_empty() || linked_commits > 0);
+    let status_rationale = format!(
+        "Task status: {:?}",
+        task.map(|t| &t.status)
+            .unwrap_or(&crate::schema::TaskStatus::Pending)
+    );
     criteria.insert(
         "core_impl".to_string(),
         CriterionScore {
             score: if implemented { 1 } else { 0 },
-            rationale: format!(
-                "Task status: {:?}",
-                task.map(|t| &t.status)
-                    .unwrap_or(&crate::schema::TaskStatus::Pending)
-            ),
+            rationale: if commit_index.is_empty() {
+                status_rationale
+            } else {
+                format!("{}, {} linked commit(s)", status_rationale, linked_commits)
+            },
         },
     );
 
     // 4. Tests Written
-    let tests_written = test_summary.as_ref().map_or(false, |ts| ts.total_tests > 0);
+    let tests_written = test_summary.as_ref().is_some_and(|ts| ts.total_tests > 0);
     criteria.insert(
         "tests_written".to_string(),
         CriterionScore {
@@ -226,6 +344,7 @@ pub fn score_task(task_id: &str) -> Result<PSSScore> {
         score: total_score,
         timestamp: Utc::now(),
         criteria,
+        schema: PSS_SCORE_SCHEMA.to_string(),
     })
 }
 
@@ -238,80 +357,545 @@ pub fn save_score(score: &PSSScore, dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    append_jsonl(&crate::common::pss_scores_path(), score)
+    let config = crate::history::load_config().unwrap_or_default();
+    if config.pss_replace_scores {
+        replace_score(score)
+    } else {
+        append_jsonl(&crate::common::pss_scores_path(), score)
+    }
+}
+
+/// Write `score` in place of the task's earlier scores. Lines that don't
+/// parse are kept for `rotd check` to report.
+fn replace_score(score: &PSSScore) -> Result<()> {
+    let path = crate::common::pss_scores_path();
+    let line = serde_json::to_string(score)?;
+    with_lock(&path, || {
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        let mut rewritten: String = content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter(|l| {
+                serde_json::from_str::<serde_json::Value>(l)
+                    .ok()
+                    .and_then(|v| v.get("task_id")?.as_str().map(str::to_string))
+                    .is_none_or(|id| id != score.task_id)
+            })
+            .map(|l| format!("{}\n", l))
+            .collect();
+        rewritten.push_str(&line);
+        rewritten.push('\n');
+        std::fs::write(&path, rewritten)?;
+        Ok(())
+    })
+}
+
+/// Most recently recorded score for a task
+pub fn latest_score(task_id: &str) -> Result<Option<PSSScore>> {
+    let scores: Vec<PSSScore> = read_jsonl(&crate::common::pss_scores_path())?;
+    Ok(scores.into_iter().rev().find(|s| s.task_id == task_id))
 }
 
-fn load_test_summary(task_id: &str) -> Result<TestSummary> {
-    read_json(&crate::common::test_summary_file(task_id))
+/// One row per task with each criterion's score and the total
+pub fn scores_to_csv(scores: &[PSSScore]) -> String {
+    let names: Vec<&str> = CRITERIA.iter().map(|c| c.name).collect();
+    let mut csv = format!("task_id,{},total\n", names.join(","));
+    for score in scores {
+        csv.push_str(&escape_csv(&score.task_id));
+        for criterion in &CRITERIA {
+            match score.criteria.get(criterion.name) {
+                Some(criterion) => csv.push_str(&format!(",{}", criterion.score)),
+                None => csv.push(','),
+            }
+        }
+        csv.push_str(&format!(",{}\n", score.score));
+    }
+    csv
 }
 
-fn check_compiles() -> bool {
-    // Check for package.json (Node.js/TypeScript)
-    if std::path::Path::new("package.json").exists() {
-        return std::process::Command::new("npm")
-            .args(&["run", "typecheck"])
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false);
+/// Score every task without recording the scores, for `score --all`
+pub fn cmd_score_all(format: &str, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    if !MATRIX_FORMATS.contains(&format) {
+        anyhow::bail!(
+            "Unknown format '{}' for --all (expected {})",
+            format,
+            MATRIX_FORMATS.join(", ")
+        );
+    }
+
+    let scores = latest_tasks()?
+        .iter()
+        .map(|task| score_task(&task.id))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Agent mode gets JSON unless CSV was asked for
+    let format = match format {
+        "table" if is_agent_mode => "json",
+        format => format,
+    };
+    match format {
+        "csv" => print!("{}", scores_to_csv(&scores)),
+        "json" => println!("{}", serde_json::to_string_pretty(&scores)?),
+        _ => print_matrix(&scores),
+    }
+    Ok(())
+}
+
+/// Criteria as numbered columns, with a key underneath
+fn print_matrix(scores: &[PSSScore]) {
+    if scores.is_empty() {
+        println!("No tasks to score");
+        return;
+    }
+    let id_width = scores.iter().map(|s| s.task_id.len()).max().unwrap_or(0).max(4);
+    let columns: String = (1..=CRITERIA.len()).map(|n| format!("{:>3}", n)).collect();
+    println!("{:<id_width$} {}  Total", "Task".bold(), columns);
+    for score in scores {
+        let cells: String = CRITERIA
+            .iter()
+            .map(|criterion| match score.criteria.get(criterion.name) {
+                Some(c) if c.score > 0 => format!("{:>3}", c.score).green().to_string(),
+                Some(c) => format!("{:>3}", c.score).red().to_string(),
+                None => format!("{:>3}", "-"),
+            })
+            .collect();
+        println!(
+            "{:<id_width$} {}  {}/{}",
+            score.task_id,
+            cells,
+            score.score,
+            CRITERIA.len()
+        );
+    }
+    println!();
+    for (n, criterion) in CRITERIA.iter().enumerate() {
+        println!("  {:>2} {}", n + 1, criterion.name);
+    }
+}
+
+/// A criterion the task misses, with what to do about it
+#[derive(Debug, Serialize)]
+pub struct Remediation {
+    pub criterion: &'static str,
+    pub rationale: String,
+    pub checks: &'static str,
+    pub remediation: &'static str,
+    pub commands: Vec<String>,
+}
+
+/// Remediation for each criterion `score` misses, in scoring order
+pub fn explain(score: &PSSScore) -> Vec<Remediation> {
+    let summary = crate::common::test_summary_file(&score.task_id);
+    CRITERIA
+        .iter()
+        .filter_map(|criterion| {
+            let scored = score.criteria.get(criterion.name)?;
+            (scored.score == 0).then(|| Remediation {
+                criterion: criterion.name,
+                rationale: scored.rationale.clone(),
+                checks: criterion.checks,
+                remediation: criterion.remediation,
+                commands: criterion
+                    .commands
+                    .iter()
+                    .map(|c| {
+                        c.replace("{id}", &score.task_id)
+                            .replace("{summary}", &summary.display().to_string())
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Score a task without recording it and explain each missed criterion
+fn cmd_explain(task_id: &str, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let score = score_task(task_id)?;
+    let failing = explain(&score);
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "task_id": task_id,
+                "score": score.score,
+                "max_score": CRITERIA.len(),
+                "failing": failing,
+            })
+        );
+        return Ok(());
+    }
+
+    println!("Task {}: {}/{}", task_id.cyan(), score.score, CRITERIA.len());
+    if failing.is_empty() {
+        println!("{} Every criterion passes", "✓".green());
+        return Ok(());
+    }
+    for item in &failing {
+        println!("\n{} {}: {}", "✗".red(), item.criterion.bold(), item.rationale);
+        println!("  Needs: {}", item.checks);
+        println!("  Next: {}", item.remediation);
+        for command in &item.commands {
+            println!("    {}", command.cyan());
+        }
+    }
+    Ok(())
+}
+
+fn cmd_latest(task_id: &str, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let Some(score) = latest_score(task_id)? else {
+        if is_agent_mode {
+            return Err(anyhow::anyhow!(
+                "{}",
+                serde_json::json!({"error": "no_score", "message": format!("No PSS score recorded for task {}", task_id)})
+            ));
+        }
+        anyhow::bail!("No PSS score recorded for task {}; run `rotd score {}`", task_id, task_id);
+    };
+
+    if is_agent_mode {
+        println!("{}", serde_json::to_string(&score)?);
+        return Ok(());
     }
 
-    // Check for Cargo.toml (Rust)
-    if std::path::Path::new("Cargo.toml").exists() {
-        return std::process::Command::new("cargo")
-            .args(&["check"])
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false);
+    println!(
+        "Task {}: {}/{} (scored {})",
+        task_id.cyan(),
+        score.score,
+        score.criteria.len(),
+        score.timestamp.format("%Y-%m-%d %H:%M")
+    );
+    let mut criteria: Vec<_> = score.criteria.iter().collect();
+    criteria.sort_by_key(|(name, _)| name.as_str());
+    for (name, criterion) in criteria {
+        println!(
+            "  {} {}: {}",
+            if criterion.score > 0 { "✓".green() } else { "✗".red() },
+            name,
+            criterion.rationale
+        );
     }
+    Ok(())
+}
 
-    // Default assume compilation passes
-    true
+pub fn load_test_summary(task_id: &str) -> Result<TestSummary> {
+    read_json(&crate::common::test_summary_file(task_id))
+}
+
+fn check_compiles() -> Vec<crate::compile::CompileOutcome> {
+    let config = crate::history::load_config().unwrap_or_default();
+    crate::compile::run(&config)
 }
 
 pub fn check_stubs_remaining() -> bool {
-    use walkdir::WalkDir;
+    !find_stubs().is_empty()
+}
 
-    let stub_patterns = [
-        "#[rotd_stub]",
-        "TODO(",
-        "unimplemented!",
-        "todo!",
-        "throw new Error(\"TODO\")",
-    ];
-
-    for entry in WalkDir::new("src").into_iter().filter_map(|e| e.ok()) {
-        if entry.file_type().is_file() {
-            if let Some(ext) = entry.path().extension() {
-                if matches!(
-                    ext.to_str(),
-                    Some("rs") | Some("ts") | Some("tsx") | Some("js") | Some("jsx")
-                ) {
-                    if let Ok(content) = std::fs::read_to_string(entry.path()) {
-                        // Skip checking this file's pattern definition line
-                        if entry.path().ends_with("pss.rs") {
-                            // Check for stubs but exclude the pattern definition line
-                            for (_line_num, line) in content.lines().enumerate() {
-                                if line.contains("let stub_patterns") {
-                                    continue;
-                                }
-                                for pattern in &stub_patterns {
-                                    if line.contains(pattern) {
-                                        return true;
-                                    }
-                                }
-                            }
-                        } else {
-                            for pattern in &stub_patterns {
-                                if content.contains(pattern) {
-                                    return true;
-                                }
-                            }
-                        }
-                    }
+/// Stub markers, extensions, and directories used when the config has none
+const DEFAULT_STUB_PATTERNS: [&str; 5] = [
+    "#[rotd_stub]",
+    "TODO(",
+    "unimplemented!",
+    "todo!",
+    "throw new Error(\"TODO\")",
+];
+const DEFAULT_STUB_EXTENSIONS: [&str; 5] = ["rs", "ts", "tsx", "js", "jsx"];
+const DEFAULT_STUB_DIRS: [&str; 1] = ["src"];
+
+fn or_default(configured: &[String], default: &[&str]) -> Vec<String> {
+    if configured.is_empty() {
+        default.iter().map(|s| s.to_string()).collect()
+    } else {
+        configured.to_vec()
+    }
+}
+
+/// Directories scanned for stubs (by default, `src`)
+pub fn source_dirs(config: &RotdConfig) -> Vec<String> {
+    or_default(&config.stub_dirs, &DEFAULT_STUB_DIRS)
+}
+
+/// Gitignore-style rules for paths stub detection skips
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: String,
+    /// `!pattern` re-includes what an earlier rule ignored
+    negate: bool,
+    /// `pattern/` only matches directories
+    dir_only: bool,
+    /// Patterns containing a `/` match the whole path from the project
+    /// root; others match any file or directory name
+    anchored: bool,
+}
+
+impl IgnoreRules {
+    /// Rules from `.gitignore`-formatted lines; blank lines and `#`
+    /// comments are skipped
+    pub fn parse<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let rules = lines
+            .into_iter()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (negate, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let line = line.strip_prefix("**/").unwrap_or(line);
+                IgnoreRule {
+                    pattern: line.trim_start_matches('/').to_string(),
+                    negate,
+                    dir_only,
+                    anchored: line.contains('/'),
                 }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// The project's `.gitignore` followed by the config's `stub_ignore`
+    /// globs, so config entries take precedence
+    pub fn load(config: &RotdConfig) -> Self {
+        let gitignore = std::fs::read_to_string(crate::common::GITIGNORE_FILE).unwrap_or_default();
+        Self::parse(
+            gitignore
+                .lines()
+                .chain(config.stub_ignore.iter().map(String::as_str)),
+        )
+    }
+
+    /// Whether `path`, relative to the project root, is ignored; the last
+    /// matching rule wins
+    pub fn is_ignored(&self, path: &std::path::Path, is_dir: bool) -> bool {
+        let components: Vec<String> = path
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        let Some(name) = components.last() else {
+            return false;
+        };
+        let relative = components.join("/");
+
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let subject = if rule.anchored { &relative } else { name };
+            if crate::task::glob_match(&rule.pattern, subject) {
+                ignored = !rule.negate;
             }
         }
+        ignored
     }
+}
+
+/// Source files covered by stub detection: files with the configured
+/// extensions under the configured directories, minus ignored paths
+pub fn source_files(config: &RotdConfig) -> Vec<std::path::PathBuf> {
+    use walkdir::WalkDir;
 
-    false
+    let extensions = or_default(&config.stub_extensions, &DEFAULT_STUB_EXTENSIONS);
+    let dirs = source_dirs(config);
+    let ignore = IgnoreRules::load(config);
+
+    let mut files = Vec::new();
+    for dir in &dirs {
+        // Hidden directories (.git, .venv, .rotd) never hold project source
+        let entries = WalkDir::new(dir).into_iter().filter_entry(|e| {
+            e.depth() == 0
+                || !(e.file_name().to_string_lossy().starts_with('.')
+                    || ignore.is_ignored(e.path(), e.file_type().is_dir()))
+        });
+        for entry in entries.filter_map(|e| e.ok()) {
+            let has_extension = entry
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.iter().any(|e| e == ext));
+            if entry.file_type().is_file() && has_extension {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    files
+}
+
+/// Locations of stub markers as `(file, 1-based line, pattern)`, using the
+/// stub settings from the config (by default, Rust and TypeScript in `src/`)
+pub fn find_stubs() -> Vec<(std::path::PathBuf, usize, String)> {
+    let config = crate::history::load_config().unwrap_or_default();
+    let stub_patterns = or_default(&config.stub_patterns, &DEFAULT_STUB_PATTERNS);
+
+    let mut stubs = Vec::new();
+    for path in source_files(&config) {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for (line_num, line) in content.lines().enumerate() {
+            if let Some(pattern) = stub_patterns.iter().find(|p| line.contains(p.as_str())) {
+                stubs.push((path.clone(), line_num + 1, pattern.clone()));
+            }
+        }
+    }
+
+    stubs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_scores_to_csv_and_explain() {
+        let score = |task_id: &str, passed: &[&str]| PSSScore {
+            task_id: task_id.to_string(),
+            score: passed.len() as u32,
+            timestamp: Utc::now(),
+            criteria: passed
+                .iter()
+                .map(|name| {
+                    let criterion = CriterionScore {
+                        score: 1,
+                        rationale: String::new(),
+                    };
+                    (name.to_string(), criterion)
+                })
+                .collect(),
+            schema: PSS_SCORE_SCHEMA.to_string(),
+        };
+
+        let csv = scores_to_csv(&[
+            score("6.1", &["llm_engaged", "qts_ratchet"]),
+            score("phase,2", &["compiles"]),
+        ]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "task_id,llm_engaged,compiles,core_impl,tests_written,tests_pass,doc_maintained,stub_free,history_maintained,qts_floor,qts_ratchet,total"
+        );
+        // Criteria missing from a score are left blank
+        assert_eq!(lines[1], "6.1,1,,,,,,,,,1,2");
+        assert_eq!(lines[2], "\"phase,2\",,1,,,,,,,,,1");
+
+        // Every criterion scored, three of them passing
+        let mut scored = score("6.1", &["llm_engaged", "compiles", "qts_ratchet"]);
+        for criterion in &CRITERIA {
+            scored
+                .criteria
+                .entry(criterion.name.to_string())
+                .or_insert(CriterionScore {
+                    score: 0,
+                    rationale: String::new(),
+                });
+        }
+        let failing = explain(&scored);
+        let names: Vec<&str> = failing.iter().map(|r| r.criterion).collect();
+        assert_eq!(
+            names,
+            vec![
+                "core_impl",
+                "tests_written",
+                "tests_pass",
+                "doc_maintained",
+                "stub_free",
+                "history_maintained",
+                "qts_floor",
+            ]
+        );
+        assert_eq!(
+            failing[0].commands,
+            vec!["rotd task set-status --ids 6.1 --status complete", "rotd git attach 6.1"]
+        );
+        assert!(failing[1].commands[0].ends_with("6.1.json"));
+    }
+
+    #[test]
+    fn test_explain_maps_each_criterion_to_its_remediation() {
+        for failing in &CRITERIA {
+            let score = PSSScore {
+                task_id: "6.1".to_string(),
+                score: CRITERIA.len() as u32 - 1,
+                timestamp: Utc::now(),
+                criteria: CRITERIA
+                    .iter()
+                    .map(|c| {
+                        let missed = c.name == failing.name;
+                        let criterion = CriterionScore {
+                            score: u32::from(!missed),
+                            rationale: format!("rationale for {}", c.name),
+                        };
+                        (c.name.to_string(), criterion)
+                    })
+                    .collect(),
+                schema: PSS_SCORE_SCHEMA.to_string(),
+            };
+
+            let explained = explain(&score);
+            assert_eq!(explained.len(), 1, "{}", failing.name);
+            let item = &explained[0];
+            assert_eq!(item.criterion, failing.name);
+            assert_eq!(item.remediation, failing.remediation);
+            assert_eq!(item.checks, failing.checks);
+            assert_eq!(item.rationale, format!("rationale for {}", failing.name));
+            assert_eq!(item.commands.len(), failing.commands.len());
+            assert!(
+                item.commands.iter().all(|c| !c.contains('{')),
+                "{:?}",
+                item.commands
+            );
+        }
+
+        let remediation = |name: &str| {
+            CRITERIA
+                .iter()
+                .find(|c| c.name == name)
+                .map(|c| c.remediation)
+                .unwrap()
+        };
+        assert!(remediation("stub_free").contains("stub_ignore"));
+        assert!(remediation("tests_pass").starts_with("Fix the failing tests"));
+        assert!(remediation("qts_ratchet").contains("ratchet the floor up"));
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = IgnoreRules::parse([
+            "# vendored code",
+            "vendor/",
+            "*.generated.rs",
+            "/src/gen/*",
+            "!src/gen/keep.rs",
+            "",
+        ]);
+
+        assert!(rules.is_ignored(Path::new("vendor"), true));
+        assert!(rules.is_ignored(Path::new("./crates/vendor"), true));
+        // Directory-only rules leave files of the same name alone
+        assert!(!rules.is_ignored(Path::new("src/vendor"), false));
+        assert!(rules.is_ignored(Path::new("src/schema.generated.rs"), false));
+        assert!(rules.is_ignored(Path::new("src/gen/types.rs"), false));
+        assert!(!rules.is_ignored(Path::new("src/gen/keep.rs"), false));
+        // Anchored rules only match from the project root
+        assert!(!rules.is_ignored(Path::new("lib/src/gen/types.rs"), false));
+        assert!(!rules.is_ignored(Path::new("src/main.rs"), false));
+    }
 }
@@ -0,0 +1,899 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::audit;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, read_json, read_jsonl};
+use crate::output::escape_xml;
+use crate::schema::{
+    AuditEntry, CoverageHistory, LessonLearned, PSSScore, TaskEntry, TaskHistoryEvent, TaskStatus,
+};
+
+/// PSS score a task needs to count as passing
+const PSS_PASSING: u32 = 6;
+
+/// Task statuses in the order report columns list them
+const STATUS_COLUMNS: [TaskStatus; 5] = [
+    TaskStatus::Pending,
+    TaskStatus::InProgress,
+    TaskStatus::Blocked,
+    TaskStatus::Scaffolded,
+    TaskStatus::Complete,
+];
+
+/// Phase heading for tasks without one
+const NO_PHASE: &str = "(none)";
+
+/// Most audit entries listed under highlights
+const AUDIT_HIGHLIGHTS: usize = 10;
+
+/// Everything a status report draws on
+pub struct ReportData {
+    pub project: String,
+    pub generated_at: DateTime<Utc>,
+    /// Start of the window for coverage, lessons, and audit sections
+    pub since: DateTime<Utc>,
+    /// Latest entry per task
+    pub tasks: Vec<TaskEntry>,
+    /// Every score recorded, oldest first
+    pub scores: Vec<PSSScore>,
+    pub coverage: Option<CoverageHistory>,
+    pub lessons: Vec<LessonLearned>,
+    /// Audit entries since `since`, newest first
+    pub audit: Vec<AuditEntry>,
+    /// Task history events since `since`
+    pub history: Vec<TaskHistoryEvent>,
+}
+
+impl ReportData {
+    /// Read the project's artifacts, keeping activity from `since` on
+    pub fn load(since: DateTime<Utc>) -> Result<Self> {
+        let primer = crate::primer::load(&crate::common::primer_path()).ok();
+        let project = primer.map(|p| p.name).unwrap_or_else(|| {
+            std::env::current_dir()
+                .ok()
+                .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "project".to_string())
+        });
+
+        let mut scores = read_jsonl::<PSSScore>(&crate::common::pss_scores_path())?;
+        scores.sort_by_key(|s| s.timestamp);
+
+        let lessons = read_jsonl::<LessonLearned>(&crate::common::lessons_path())?
+            .into_iter()
+            .filter(|l| l.timestamp.is_some_and(|t| t >= since))
+            .collect();
+
+        let audit = audit::read_entries()?
+            .into_iter()
+            .filter(|e| e.timestamp >= since)
+            .collect();
+
+        let history = crate::history::read_all_history()?
+            .into_iter()
+            .filter(|e| e.timestamp >= since)
+            .collect();
+
+        Ok(Self {
+            project,
+            generated_at: Utc::now(),
+            since,
+            tasks: latest_tasks()?,
+            scores,
+            coverage: read_json(&crate::common::coverage_history_path()).ok(),
+            lessons,
+            audit,
+            history,
+        })
+    }
+
+    /// Latest score per task, by task ID
+    pub fn latest_scores(&self) -> Vec<&PSSScore> {
+        let mut latest: BTreeMap<&str, &PSSScore> = BTreeMap::new();
+        for score in &self.scores {
+            latest.insert(&score.task_id, score);
+        }
+        latest.into_values().collect()
+    }
+}
+
+fn status_label(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "Pending",
+        TaskStatus::InProgress => "In progress",
+        TaskStatus::Complete => "Complete",
+        TaskStatus::Blocked => "Blocked",
+        TaskStatus::Scaffolded => "Scaffolded",
+    }
+}
+
+/// Escape `|` so a value can sit in a Markdown table cell
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Count of tasks in each status, in `STATUS_COLUMNS` order
+pub fn status_counts<'a>(tasks: impl IntoIterator<Item = &'a TaskEntry>) -> [usize; 5] {
+    let mut counts = [0; 5];
+    for task in tasks {
+        if let Some(i) = STATUS_COLUMNS.iter().position(|s| s == &task.status) {
+            counts[i] += 1;
+        }
+    }
+    counts
+}
+
+/// Tasks grouped by phase, phases sorted with unphased tasks last
+pub fn tasks_by_phase(tasks: &[TaskEntry]) -> Vec<(&str, Vec<&TaskEntry>)> {
+    let mut phases: BTreeMap<Option<&str>, Vec<&TaskEntry>> = BTreeMap::new();
+    for task in tasks {
+        phases.entry(task.phase.as_deref()).or_default().push(task);
+    }
+    let mut grouped: Vec<(Option<&str>, Vec<&TaskEntry>)> = phases.into_iter().collect();
+    // None sorts first in a BTreeMap; list it last instead
+    grouped.sort_by_key(|(phase, _)| (phase.is_none(), *phase));
+    grouped
+        .into_iter()
+        .map(|(phase, tasks)| (phase.unwrap_or(NO_PHASE), tasks))
+        .collect()
+}
+
+/// Tasks grouped under each of their tags, sorted by tag; untagged tasks are left out
+pub fn tasks_by_tag(tasks: &[TaskEntry]) -> Vec<(&str, Vec<&TaskEntry>)> {
+    let mut tags: BTreeMap<&str, Vec<&TaskEntry>> = BTreeMap::new();
+    for task in tasks {
+        for tag in &task.tags {
+            tags.entry(tag.as_str()).or_default().push(task);
+        }
+    }
+    tags.into_iter().collect()
+}
+
+/// The report as a Markdown document
+pub fn to_markdown(data: &ReportData) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# {} Status Report\n", data.project);
+    let _ = writeln!(
+        md,
+        "_Generated {}, covering activity since {}._\n",
+        data.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        data.since.format("%Y-%m-%d")
+    );
+
+    write_tasks(&mut md, data);
+    write_scores(&mut md, data);
+    write_coverage(&mut md, data);
+    write_lessons(&mut md, data);
+    write_audit(&mut md, data);
+    md
+}
+
+fn write_tasks(md: &mut String, data: &ReportData) {
+    md.push_str("## Tasks\n\n");
+    if data.tasks.is_empty() {
+        md.push_str("No tasks recorded.\n\n");
+        return;
+    }
+
+    let totals = status_counts(&data.tasks);
+    let summary: Vec<String> = STATUS_COLUMNS
+        .iter()
+        .zip(totals)
+        .filter(|(_, n)| *n > 0)
+        .map(|(status, n)| format!("{} {}", n, status_label(status).to_lowercase()))
+        .collect();
+    let _ = writeln!(md, "{} tasks: {}.\n", data.tasks.len(), summary.join(", "));
+
+    let headings: Vec<&str> = STATUS_COLUMNS.iter().map(status_label).collect();
+    let _ = writeln!(md, "| Phase | {} | Total |", headings.join(" | "));
+    let _ = writeln!(md, "|---|{}---:|", "---:|".repeat(STATUS_COLUMNS.len()));
+    for (phase, tasks) in tasks_by_phase(&data.tasks) {
+        let counts: Vec<String> = status_counts(tasks.iter().copied())
+            .iter()
+            .map(usize::to_string)
+            .collect();
+        let _ = writeln!(
+            md,
+            "| {} | {} | {} |",
+            cell(phase),
+            counts.join(" | "),
+            tasks.len()
+        );
+    }
+    md.push('\n');
+
+    let tagged = tasks_by_tag(&data.tasks);
+    if !tagged.is_empty() {
+        let _ = writeln!(md, "| Tag | {} | Total |", headings.join(" | "));
+        let _ = writeln!(md, "|---|{}---:|", "---:|".repeat(STATUS_COLUMNS.len()));
+        for (tag, tasks) in tagged {
+            let counts: Vec<String> = status_counts(tasks.iter().copied())
+                .iter()
+                .map(usize::to_string)
+                .collect();
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} |",
+                cell(tag),
+                counts.join(" | "),
+                tasks.len()
+            );
+        }
+        md.push('\n');
+    }
+
+    for status in [TaskStatus::InProgress, TaskStatus::Blocked] {
+        let tasks: Vec<&TaskEntry> = data.tasks.iter().filter(|t| t.status == status).collect();
+        if tasks.is_empty() {
+            continue;
+        }
+        let _ = writeln!(md, "### {}\n", status_label(&status));
+        for task in tasks {
+            let _ = writeln!(md, "- **{}** {}", task.id, task.title);
+        }
+        md.push('\n');
+    }
+}
+
+fn write_scores(md: &mut String, data: &ReportData) {
+    md.push_str("## PSS Scores\n\n");
+    let latest = data.latest_scores();
+    if latest.is_empty() {
+        md.push_str("No tasks scored.\n\n");
+        return;
+    }
+
+    let scores: Vec<u32> = latest.iter().map(|s| s.score).collect();
+    let average = scores.iter().sum::<u32>() as f64 / scores.len() as f64;
+    let passing = scores.iter().filter(|s| **s >= PSS_PASSING).count();
+    let _ = writeln!(
+        md,
+        "{} tasks scored: average {:.1}/10, lowest {}, highest {}; {} of {} at or above {}.\n",
+        scores.len(),
+        average,
+        scores.iter().min().unwrap_or(&0),
+        scores.iter().max().unwrap_or(&0),
+        passing,
+        scores.len(),
+        PSS_PASSING
+    );
+
+    let mut below: Vec<&PSSScore> = latest.into_iter().filter(|s| s.score < PSS_PASSING).collect();
+    if !below.is_empty() {
+        below.sort_by_key(|s| s.score);
+        md.push_str("Below passing:\n\n");
+        for score in below {
+            let _ = writeln!(md, "- **{}** {}/10", score.task_id, score.score);
+        }
+        md.push('\n');
+    }
+}
+
+fn write_coverage(md: &mut String, data: &ReportData) {
+    md.push_str("## Coverage\n\n");
+    let Some(coverage) = &data.coverage else {
+        md.push_str("No coverage history recorded.\n\n");
+        return;
+    };
+
+    let recent: Vec<_> = coverage
+        .history
+        .iter()
+        .filter(|e| e.timestamp >= data.since)
+        .collect();
+    let _ = write!(md, "Floor {:.1}%", coverage.floor);
+    if let Some(latest) = coverage.history.iter().max_by_key(|e| e.timestamp) {
+        let _ = write!(md, ", latest {:.1}% ({})", latest.coverage, latest.task_id);
+    }
+    if let (Some(first), Some(last)) = (recent.first(), recent.last()) {
+        let _ = write!(md, ", {:+.1} points this period", last.coverage - first.coverage);
+    }
+    md.push_str(".\n\n");
+
+    if !recent.is_empty() {
+        md.push_str("| Date | Task | Coverage | Ratchet |\n|---|---|---:|---|\n");
+        for entry in recent {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {:.1}% | {} |",
+                entry.timestamp.format("%Y-%m-%d"),
+                cell(&entry.task_id),
+                entry.coverage,
+                if entry.triggered_ratchet { "yes" } else { "" }
+            );
+        }
+        md.push('\n');
+    }
+}
+
+fn write_lessons(md: &mut String, data: &ReportData) {
+    md.push_str("## Recent Lessons\n\n");
+    if data.lessons.is_empty() {
+        md.push_str("No lessons logged this period.\n\n");
+        return;
+    }
+
+    for lesson in &data.lessons {
+        let _ = write!(md, "- **{}**: {} → {}", lesson.id, lesson.diagnosis, lesson.remediation);
+        if !lesson.tags.is_empty() {
+            let _ = write!(md, " _({})_", lesson.tags.join(", "));
+        }
+        md.push('\n');
+    }
+    md.push('\n');
+}
+
+fn write_audit(md: &mut String, data: &ReportData) {
+    md.push_str("## Audit Highlights\n\n");
+    if data.audit.is_empty() {
+        md.push_str("No audit entries this period.\n");
+        return;
+    }
+
+    let counts: Vec<String> = audit::SEVERITIES
+        .iter()
+        .rev()
+        .filter_map(|severity| {
+            let n = data.audit.iter().filter(|e| e.severity == *severity).count();
+            (n > 0).then(|| format!("{} {}", n, severity))
+        })
+        .collect();
+    let _ = writeln!(md, "{} entries: {}.\n", data.audit.len(), counts.join(", "));
+
+    let serious: Vec<&AuditEntry> = data
+        .audit
+        .iter()
+        .filter(|e| e.severity == "error" || e.severity == "critical")
+        .take(AUDIT_HIGHLIGHTS)
+        .collect();
+    for entry in serious {
+        let _ = writeln!(
+            md,
+            "- {} **{}** {} ({}): {}",
+            entry.timestamp.format("%Y-%m-%d"),
+            entry.severity.to_uppercase(),
+            entry.rule,
+            entry.task_id.as_deref().unwrap_or("global"),
+            entry.message
+        );
+    }
+}
+
+/// File the HTML dashboard is written to inside its output directory
+pub const DASHBOARD_FILE: &str = "index.html";
+
+const DASHBOARD_STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:0 auto;max-width:1100px;padding:24px;color:#1f2328;background:#f6f8fa}
+h1{margin-bottom:4px}
+.meta{color:#656d76;margin-top:0}
+section{background:#fff;border:1px solid #d0d7de;border-radius:8px;padding:16px 20px;margin:16px 0}
+.board{display:grid;grid-template-columns:repeat(5,1fr);gap:12px}
+.column h3{font-size:14px;margin:0 0 8px}
+.card{border:1px solid #d0d7de;border-left:4px solid #8c959f;border-radius:6px;padding:6px 8px;margin-bottom:6px;font-size:13px;background:#fff}
+.card .id{font-weight:600}
+.card .phase{color:#656d76;font-size:12px}
+.in_progress{border-left-color:#0969da}.blocked{border-left-color:#cf222e}.complete{border-left-color:#1a7f37}.scaffolded{border-left-color:#9a6700}
+table{border-collapse:collapse;width:100%;font-size:14px}
+th,td{text-align:left;padding:4px 8px;border-bottom:1px solid #d0d7de}
+td.num,th.num{text-align:right}
+.low{color:#cf222e;font-weight:600}
+svg{width:100%;height:auto}
+svg .line{fill:none;stroke:#0969da;stroke-width:2}
+svg .point{fill:#0969da}
+svg .guide{stroke:#cf222e;stroke-dasharray:4 4}
+svg .axis{stroke:#8c959f}
+svg text{font-size:11px;fill:#656d76}
+.empty{color:#656d76}
+";
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 200.0;
+const CHART_MARGIN: f64 = 32.0;
+
+/// Inline SVG line chart of `points`, scaled from 0 to `y_max`, with an
+/// optional dashed `guide` line (a floor or threshold)
+pub fn line_chart(points: &[(DateTime<Utc>, f64)], y_max: f64, guide: Option<f64>, unit: &str) -> String {
+    if points.is_empty() {
+        return "<p class=\"empty\">No data recorded.</p>".to_string();
+    }
+
+    let plot_width = CHART_WIDTH - 2.0 * CHART_MARGIN;
+    let y = |value: f64| {
+        CHART_HEIGHT - CHART_MARGIN - value.clamp(0.0, y_max) / y_max * (CHART_HEIGHT - 2.0 * CHART_MARGIN)
+    };
+    let x = |i: usize| match points.len() {
+        1 => CHART_MARGIN + plot_width / 2.0,
+        n => CHART_MARGIN + i as f64 * plot_width / (n - 1) as f64,
+    };
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 {} {}\" role=\"img\">",
+        CHART_WIDTH, CHART_HEIGHT
+    );
+    let bottom = y(0.0);
+    let _ = write!(
+        svg,
+        "<line class=\"axis\" x1=\"{m}\" y1=\"{b:.1}\" x2=\"{r}\" y2=\"{b:.1}\"/>\
+         <text x=\"4\" y=\"{t:.1}\">{max}{unit}</text><text x=\"4\" y=\"{b:.1}\">0{unit}</text>",
+        m = CHART_MARGIN,
+        r = CHART_WIDTH - CHART_MARGIN,
+        b = bottom,
+        t = y(y_max) + 4.0,
+        max = y_max,
+        unit = unit
+    );
+    if let Some(guide) = guide {
+        let _ = write!(
+            svg,
+            "<line class=\"guide\" x1=\"{}\" y1=\"{g:.1}\" x2=\"{}\" y2=\"{g:.1}\"/>",
+            CHART_MARGIN,
+            CHART_WIDTH - CHART_MARGIN,
+            g = y(guide)
+        );
+    }
+
+    let path: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, value))| format!("{:.1},{:.1}", x(i), y(*value)))
+        .collect();
+    let _ = write!(svg, "<polyline class=\"line\" points=\"{}\"/>", path.join(" "));
+    for (i, (time, value)) in points.iter().enumerate() {
+        let _ = write!(
+            svg,
+            "<circle class=\"point\" cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\"><title>{} {:.1}{}</title></circle>",
+            x(i),
+            y(*value),
+            time.format("%Y-%m-%d"),
+            value,
+            unit
+        );
+    }
+
+    let label_y = CHART_HEIGHT - 8.0;
+    let (first, last) = (points[0].0, points[points.len() - 1].0);
+    let _ = write!(
+        svg,
+        "<text x=\"{}\" y=\"{}\">{}</text><text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text></svg>",
+        CHART_MARGIN,
+        label_y,
+        first.format("%Y-%m-%d"),
+        CHART_WIDTH - CHART_MARGIN,
+        label_y,
+        last.format("%Y-%m-%d")
+    );
+    svg
+}
+
+/// Average PSS score per day, oldest first
+pub fn daily_average_scores(scores: &[PSSScore]) -> Vec<(DateTime<Utc>, f64)> {
+    let mut days: BTreeMap<chrono::NaiveDate, (DateTime<Utc>, u32, u32)> = BTreeMap::new();
+    for score in scores {
+        let day = days
+            .entry(score.timestamp.date_naive())
+            .or_insert((score.timestamp, 0, 0));
+        day.1 += score.score;
+        day.2 += 1;
+    }
+    days.into_values()
+        .map(|(time, total, count)| (time, total as f64 / count as f64))
+        .collect()
+}
+
+/// Activity of one agent in the task history
+#[derive(Debug, PartialEq)]
+pub struct AgentActivity<'a> {
+    pub agent_id: &'a str,
+    pub events: usize,
+    pub tasks: usize,
+    pub completed: usize,
+    pub last_active: DateTime<Utc>,
+}
+
+/// Per-agent activity, most recently active first
+pub fn agent_activity(history: &[TaskHistoryEvent]) -> Vec<AgentActivity<'_>> {
+    let mut agents: BTreeMap<&str, (Vec<&TaskHistoryEvent>, DateTime<Utc>)> = BTreeMap::new();
+    for event in history {
+        let entry = agents
+            .entry(event.agent_id.as_str())
+            .or_insert((Vec::new(), event.timestamp));
+        entry.0.push(event);
+        entry.1 = entry.1.max(event.timestamp);
+    }
+
+    let mut activity: Vec<AgentActivity> = agents
+        .into_iter()
+        .map(|(agent_id, (events, last_active))| {
+            let tasks: std::collections::BTreeSet<&str> =
+                events.iter().map(|e| e.task_id.as_str()).collect();
+            AgentActivity {
+                agent_id,
+                events: events.len(),
+                tasks: tasks.len(),
+                completed: events.iter().filter(|e| e.status == "complete").count(),
+                last_active,
+            }
+        })
+        .collect();
+    activity.sort_by_key(|a| std::cmp::Reverse(a.last_active));
+    activity
+}
+
+/// The report as a self-contained HTML dashboard: a task board, coverage
+/// chart, PSS trends, and agent activity
+pub fn to_html(data: &ReportData) -> String {
+    let mut html = String::new();
+    let title = format!("{} Dashboard", escape_xml(&data.project));
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>\n{DASHBOARD_STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p class=\"meta\">Generated {}, covering activity since {}</p>\n",
+        data.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        data.since.format("%Y-%m-%d")
+    );
+
+    write_html_board(&mut html, data);
+    write_html_coverage(&mut html, data);
+    write_html_scores(&mut html, data);
+    write_html_activity(&mut html, data);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn write_html_board(html: &mut String, data: &ReportData) {
+    let counts = status_counts(&data.tasks);
+    let _ = write!(html, "<section>\n<h2>Tasks ({})</h2>\n<div class=\"board\">\n", data.tasks.len());
+    for (status, count) in STATUS_COLUMNS.iter().zip(counts) {
+        let _ = writeln!(
+            html,
+            "<div class=\"column\"><h3>{} ({})</h3>",
+            status_label(status),
+            count
+        );
+        for task in data.tasks.iter().filter(|t| &t.status == status) {
+            let _ = write!(
+                html,
+                "<div class=\"card {}\"><span class=\"id\">{}</span> {}",
+                status.as_str(),
+                escape_xml(&task.id),
+                escape_xml(&task.title)
+            );
+            if let Some(phase) = &task.phase {
+                let _ = write!(html, "<div class=\"phase\">Phase {}</div>", escape_xml(phase));
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</div>\n</section>\n");
+}
+
+fn write_html_coverage(html: &mut String, data: &ReportData) {
+    html.push_str("<section>\n<h2>Coverage</h2>\n");
+    match &data.coverage {
+        Some(coverage) => {
+            let mut points: Vec<(DateTime<Utc>, f64)> = coverage
+                .history
+                .iter()
+                .map(|e| (e.timestamp, e.coverage))
+                .collect();
+            points.sort_by_key(|(time, _)| *time);
+            let _ = writeln!(html, "<p>Floor {:.1}% (dashed)</p>", coverage.floor);
+            html.push_str(&line_chart(&points, 100.0, Some(coverage.floor), "%"));
+        }
+        None => html.push_str("<p class=\"empty\">No coverage history recorded.</p>"),
+    }
+    html.push_str("\n</section>\n");
+}
+
+fn write_html_scores(html: &mut String, data: &ReportData) {
+    let _ = write!(
+        html,
+        "<section>\n<h2>PSS Trends</h2>\n<p>Daily average score; passing is {} (dashed)</p>\n",
+        PSS_PASSING
+    );
+    html.push_str(&line_chart(
+        &daily_average_scores(&data.scores),
+        10.0,
+        Some(PSS_PASSING as f64),
+        "",
+    ));
+
+    let mut latest = data.latest_scores();
+    if !latest.is_empty() {
+        latest.sort_by_key(|s| s.score);
+        html.push_str(
+            "\n<table><tr><th>Task</th><th class=\"num\">Latest score</th><th>Scored</th></tr>\n",
+        );
+        for score in latest {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num{}\">{}/10</td><td>{}</td></tr>",
+                escape_xml(&score.task_id),
+                if score.score < PSS_PASSING { " low" } else { "" },
+                score.score,
+                score.timestamp.format("%Y-%m-%d")
+            );
+        }
+        html.push_str("</table>");
+    }
+    html.push_str("\n</section>\n");
+}
+
+fn write_html_activity(html: &mut String, data: &ReportData) {
+    html.push_str("<section>\n<h2>Agent Activity</h2>\n");
+    let activity = agent_activity(&data.history);
+    if activity.is_empty() {
+        html.push_str("<p class=\"empty\">No task history this period.</p>\n</section>\n");
+        return;
+    }
+
+    html.push_str(
+        "<table><tr><th>Agent</th><th class=\"num\">Events</th><th class=\"num\">Tasks</th>\
+         <th class=\"num\">Completed</th><th>Last active</th></tr>\n",
+    );
+    for agent in activity {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            escape_xml(agent.agent_id),
+            agent.events,
+            agent.tasks,
+            agent.completed,
+            agent.last_active.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+    html.push_str("</table>\n</section>\n");
+}
+
+/// Entry point for `rotd report`: Markdown to `out` or stdout, or the HTML
+/// dashboard into the `html` directory
+pub fn run(
+    out: Option<&Path>,
+    html: Option<&Path>,
+    since: DateTime<Utc>,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let data = ReportData::load(since)?;
+    if let Some(dir) = html {
+        return write_dashboard(&data, dir, dry_run, is_agent_mode);
+    }
+    let content = to_markdown(&data);
+
+    let Some(out) = out else {
+        if is_agent_mode {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "success",
+                    "action": "report",
+                    "format": "md",
+                    "content": content,
+                })
+            );
+        } else {
+            print!("{}", content);
+        }
+        return Ok(());
+    };
+
+    if !dry_run {
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(out, &content)
+            .with_context(|| format!("Failed to write {}", out.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "report",
+                "format": "md",
+                "out": out.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} report to {}",
+            if dry_run { "Would write" } else { "✓ Wrote" },
+            out.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+fn write_dashboard(data: &ReportData, dir: &Path, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    let path = dir.join(DASHBOARD_FILE);
+    if !dry_run {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, to_html(data))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "report",
+                "format": "html",
+                "out": path.display().to_string(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        println!(
+            "{} dashboard to {}",
+            if dry_run { "Would write" } else { "✓ Wrote" },
+            path.display().to_string().cyan()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        time.parse().unwrap()
+    }
+
+    fn sample_data() -> ReportData {
+        let task = |id: &str, status: &str, phase: Option<&str>, tags: &[&str]| -> TaskEntry {
+            serde_json::from_value(json!({
+                "id": id,
+                "title": format!("Task {}", id),
+                "status": status,
+                "phase": phase,
+                "tags": tags,
+            }))
+            .unwrap()
+        };
+        let score = |task_id: &str, score: u32, date: &str| -> PSSScore {
+            serde_json::from_value(json!({
+                "task_id": task_id,
+                "score": score,
+                "timestamp": format!("{}T10:00:00Z", date),
+                "criteria": {},
+            }))
+            .unwrap()
+        };
+        let event = |task_id: &str, agent_id: &str, status: &str, time: &str| {
+            let mut event = TaskHistoryEvent::new(
+                task_id.to_string(),
+                agent_id.to_string(),
+                status.to_string(),
+            );
+            event.timestamp = at(time);
+            event
+        };
+        ReportData {
+            project: "demo <app>".to_string(),
+            generated_at: at("2026-01-31T12:00:00Z"),
+            since: at("2026-01-24T12:00:00Z"),
+            tasks: vec![
+                task("1.1", "complete", Some("1"), &["api"]),
+                task("1.2", "in_progress", Some("1"), &["api", "ui"]),
+                task("2.1", "blocked", None, &[]),
+                task("2.2", "pending", Some("2"), &["ui"]),
+            ],
+            scores: vec![
+                score("1.2", 5, "2026-01-29"),
+                score("1.1", 8, "2026-01-30"),
+                score("1.2", 4, "2026-01-30"),
+            ],
+            coverage: Some(CoverageHistory {
+                floor: 70.0,
+                ratchet_threshold: 5.0,
+                history: ["2026-01-20", "2026-01-25", "2026-01-30"]
+                    .iter()
+                    .zip([65.0, 70.0, 76.5])
+                    .map(|(date, coverage)| {
+                        serde_json::from_value(json!({
+                            "task_id": "1.1",
+                            "coverage": coverage,
+                            "timestamp": format!("{}T09:00:00Z", date),
+                            "triggered_ratchet": coverage > 75.0,
+                        }))
+                        .unwrap()
+                    })
+                    .collect(),
+            }),
+            lessons: Vec::new(),
+            audit: vec![
+                audit::parse_log_line("[2026-01-30 09:00:00 UTC] [ERROR] pss_low 1.2 - Score below threshold")
+                    .unwrap(),
+                audit::parse_log_line("[2026-01-29 09:00:00 UTC] [INFO] task_update 1.1 - Updated")
+                    .unwrap(),
+            ],
+            history: vec![
+                event("1.1", "agent-a", "in_progress", "2026-01-28T09:00:00Z"),
+                event("1.1", "agent-a", "complete", "2026-01-29T09:00:00Z"),
+                event("1.2", "agent-a", "in_progress", "2026-01-29T10:00:00Z"),
+                event("2.1", "agent-b", "blocked", "2026-01-30T09:00:00Z"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let md = to_markdown(&sample_data());
+        assert!(md.starts_with("# demo <app> Status Report\n"));
+        assert!(md.contains("4 tasks: 1 pending, 1 in progress, 1 blocked, 1 complete."));
+        assert!(md.contains("| 1 | 0 | 1 | 0 | 0 | 1 | 2 |"));
+        // Unphased tasks come after the named phases
+        assert!(md.find("| 2 |").unwrap() < md.find("| (none) |").unwrap());
+        // Tasks count toward each of their tags
+        assert!(md.contains("| api | 0 | 1 | 0 | 0 | 1 | 2 |"));
+        assert!(md.contains("| ui | 1 | 1 | 0 | 0 | 0 | 2 |"));
+        assert!(md.contains("### Blocked\n\n- **2.1** Task 2.1"));
+        assert!(md.contains("average 6.0/10, lowest 4, highest 8; 1 of 2 at or above 6."));
+        assert!(md.contains("- **1.2** 4/10"));
+        assert!(md.contains("Floor 70.0%, latest 76.5% (1.1), +6.5 points this period."));
+        assert!(!md.contains("65.0%"));
+        assert!(md.contains("No lessons logged this period."));
+        assert!(md.contains("2 entries: 1 error, 1 info."));
+        assert!(md.contains("**ERROR** pss_low (1.2): Score below threshold"));
+        assert!(!md.contains("task_update (1.1)"));
+    }
+
+    #[test]
+    fn test_dashboard() {
+        let data = sample_data();
+        assert_eq!(
+            daily_average_scores(&data.scores)
+                .iter()
+                .map(|(_, avg)| *avg)
+                .collect::<Vec<_>>(),
+            vec![5.0, 6.0]
+        );
+        assert_eq!(
+            agent_activity(&data.history),
+            vec![
+                AgentActivity {
+                    agent_id: "agent-b",
+                    events: 1,
+                    tasks: 1,
+                    completed: 0,
+                    last_active: at("2026-01-30T09:00:00Z"),
+                },
+                AgentActivity {
+                    agent_id: "agent-a",
+                    events: 3,
+                    tasks: 2,
+                    completed: 1,
+                    last_active: at("2026-01-29T10:00:00Z"),
+                },
+            ]
+        );
+
+        let html = to_html(&data);
+        assert!(html.contains("<title>demo &lt;app&gt; Dashboard</title>"));
+        assert!(html.contains("<h3>Blocked (1)</h3>\n<div class=\"card blocked\"><span class=\"id\">2.1</span>"));
+        // Coverage and PSS charts, each with a point per entry
+        assert_eq!(html.matches("<polyline").count(), 2);
+        assert_eq!(html.matches("<circle").count(), 5);
+        assert!(html.contains("<td class=\"num low\">4/10</td>"));
+        // Self-contained: nothing loaded from elsewhere
+        assert!(!html.contains("<script") && !html.contains("href="));
+        assert!(line_chart(&[], 10.0, None, "").contains("No data recorded."));
+    }
+}
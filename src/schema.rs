@@ -1,10 +1,59 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// `_schema` versions this binary writes and understands. Records written
+/// before stamping was introduced read as the first version.
+pub const TASK_SCHEMA: &str = "task.v1";
+pub const TEST_SUMMARY_SCHEMA: &str = "test_summary.v1";
+pub const LESSON_SCHEMA: &str = "lesson.v1";
+pub const PSS_SCORE_SCHEMA: &str = "pss_score.v1";
+pub const SESSION_STATE_SCHEMA: &str = "session_state.v1";
+pub const TASK_HISTORY_SCHEMA: &str = "task_history.v1";
+pub const SESSION_LOG_SCHEMA: &str = "session_log.v1";
+
+/// Artifacts stamped with a `_schema` version
+pub trait Versioned {
+    /// The version this binary writes
+    const SCHEMA: &'static str;
+
+    fn schema(&self) -> &str;
+
+    /// Why this record may not be fully understood, if it may not be
+    fn schema_issue(&self) -> Option<String> {
+        schema_issue(self.schema(), Self::SCHEMA)
+    }
+}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+fn split_schema(schema: &str) -> Option<(&str, u32)> {
+    let (name, version) = schema.rsplit_once(".v")?;
+    Some((name, version.parse().ok()?))
+}
+
+/// Compare a `name.vN` stamp against the version this binary supports
+pub fn schema_issue(found: &str, supported: &str) -> Option<String> {
+    let (name, version) = split_schema(supported)?;
+    match split_schema(found) {
+        Some((found_name, _)) if found_name != name => Some(format!(
+            "Schema {} is not a {} record (expected {})",
+            found, name, supported
+        )),
+        Some((_, found_version)) if found_version > version => Some(format!(
+            "Written with newer schema {}; this rotd understands up to {}. Run 'rotd upgrade'",
+            found, supported
+        )),
+        Some(_) => None,
+        None => Some(format!(
+            "Unrecognized schema version '{}' (expected {})",
+            found, supported
+        )),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct TaskEntry {
     pub id: String,
     pub title: String,
@@ -20,9 +69,21 @@ pub struct TaskEntry {
     pub created: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
     pub completed: Option<DateTime<Utc>>,
+    /// Pull request opened for this task by `rotd github pr`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_url: Option<String>,
+    /// Person or agent responsible for the task, independent of who holds
+    /// its claim in the coordination registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Free-form labels, independent of phase and priority
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(rename = "_schema", default = "default_task_schema")]
+    pub schema: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskStatus {
     Pending,
@@ -32,6 +93,27 @@ pub enum TaskStatus {
     Scaffolded,
 }
 
+/// Status names as written in tasks.jsonl
+pub const TASK_STATUSES: &[&str] = &["pending", "in_progress", "complete", "blocked", "scaffolded"];
+
+impl TaskStatus {
+    /// Parse a status name as written in tasks.jsonl
+    pub fn parse(name: &str) -> Option<TaskStatus> {
+        serde_json::from_value(serde_json::Value::from(name)).ok()
+    }
+
+    /// Name as written in tasks.jsonl
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Complete => "complete",
+            TaskStatus::Blocked => "blocked",
+            TaskStatus::Scaffolded => "scaffolded",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum RegistryStatus {
@@ -56,7 +138,7 @@ pub struct ActiveWorkRegistry {
     pub tasks: Vec<RegistryEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
     Urgent,
@@ -66,7 +148,15 @@ pub enum Priority {
     Deferred,
 }
 
+/// Priority names as written in tasks.jsonl
+pub const PRIORITIES: &[&str] = &["urgent", "high", "medium", "low", "deferred"];
+
 impl Priority {
+    /// Parse a priority name as written in tasks.jsonl
+    pub fn parse(name: &str) -> Option<Priority> {
+        serde_json::from_value(serde_json::Value::from(name)).ok()
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             Priority::Urgent => "urgent",
@@ -88,7 +178,7 @@ impl Priority {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TestSummary {
     pub task_id: String,
     pub status: String,
@@ -100,9 +190,11 @@ pub struct TestSummary {
     pub verified_by: String,
     pub timestamp: DateTime<Utc>,
     pub notes: Option<String>,
+    #[serde(rename = "_schema", default = "default_test_summary_schema")]
+    pub schema: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LessonLearned {
     pub id: String,
     pub hash: Option<String>,
@@ -112,53 +204,155 @@ pub struct LessonLearned {
     pub remediation: String,
     pub tags: Vec<String>,
     pub timestamp: Option<DateTime<Utc>>,
+    /// Set when secrets were redacted from the lesson as it was logged
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+    #[serde(rename = "_schema", default = "default_lesson_schema")]
+    pub schema: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PSSScore {
     pub task_id: String,
     pub score: u32,
     pub timestamp: DateTime<Utc>,
     pub criteria: HashMap<String, CriterionScore>,
+    #[serde(rename = "_schema", default = "default_pss_score_schema")]
+    pub schema: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CriterionScore {
     pub score: u32,
     pub rationale: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CoverageHistory {
     pub floor: f64,
     pub ratchet_threshold: f64,
     pub history: Vec<CoverageEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CoverageEntry {
     pub task_id: String,
     pub coverage: f64,
     pub timestamp: DateTime<Utc>,
     pub triggered_ratchet: bool,
+    /// Tool whose report supplied the percentage; absent when it was passed in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Floor after this entry; absent in entries recorded before it was kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SessionState {
     pub session_id: String,
     pub timestamp: DateTime<Utc>,
     pub current_task: Option<String>,
     pub status: String,
     pub deltas: Option<serde_json::Value>,
+    #[serde(rename = "_schema", default = "default_session_state_schema")]
+    pub schema: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A session starting or ending, as recorded in sessions.jsonl
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct SessionLogEntry {
+    pub session_id: String,
+    pub agent_id: String,
+    /// `start` or `end`
+    pub event: String,
+    pub timestamp: DateTime<Utc>,
+    pub current_task: Option<String>,
+    /// Session status after the event: `active` on start, the end status on end
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// What changed during the session, on end events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deltas: Option<serde_json::Value>,
+    #[serde(rename = "_schema", default = "default_session_log_schema")]
+    pub schema: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuditEntry {
     pub timestamp: DateTime<Utc>,
     pub task_id: Option<String>,
     pub rule: String,
     pub severity: String,
     pub message: String,
+    /// Whether secrets were redacted from the message
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+}
+
+/// Metadata written into a lock file by the process currently holding it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub hostname: String,
+    pub since: DateTime<Utc>,
+}
+
+impl Versioned for TaskEntry {
+    const SCHEMA: &'static str = TASK_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+}
+
+impl Versioned for TestSummary {
+    const SCHEMA: &'static str = TEST_SUMMARY_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+}
+
+impl Versioned for LessonLearned {
+    const SCHEMA: &'static str = LESSON_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+}
+
+impl Versioned for PSSScore {
+    const SCHEMA: &'static str = PSS_SCORE_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+}
+
+impl Versioned for SessionState {
+    const SCHEMA: &'static str = SESSION_STATE_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+}
+
+impl Versioned for SessionLogEntry {
+    const SCHEMA: &'static str = SESSION_LOG_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
+}
+
+impl Versioned for TaskHistoryEvent {
+    const SCHEMA: &'static str = TASK_HISTORY_SCHEMA;
+
+    fn schema(&self) -> &str {
+        &self.schema
+    }
 }
 
 // Validation functions
@@ -216,9 +410,23 @@ pub struct UpdateManifest {
     pub date: String,
     pub changes: Vec<ChangeEntry>,
     pub previous_version: String,
+    /// Methodology files written by the update, used to detect local edits
+    /// on the next one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<AppliedFile>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedFile {
+    /// Path relative to the `.rotd` directory
+    pub path: String,
+    /// SHA-256 of the content shipped in the bundle
+    pub sha256: String,
+    /// `added`, `updated`, `unchanged`, or `conflict`
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChangeEntry {
     pub change_type: String,
     pub component: String,
@@ -227,16 +435,6 @@ pub struct ChangeEntry {
     pub migration_required: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UpdateHistoryEntry {
-    pub version: String,
-    pub updated_at: DateTime<Utc>,
-    pub updated_by: String,
-    pub status: String,
-    pub changes_applied: Vec<String>,
-    pub migration_notes: Option<String>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidationReport {
     pub overall_status: String,
@@ -253,7 +451,7 @@ pub struct ValidationResult {
 }
 
 // Primer-related structures
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ProjectPrimer {
     pub name: String,
     pub scope: String,
@@ -267,18 +465,18 @@ pub struct ProjectPrimer {
     pub key_concepts: Vec<String>,
     pub preferred_agents: Option<Vec<String>>,
     pub suggested_starting_points: Vec<String>,
-    pub major_components: Option<HashMap<String, ComponentInfo>>,
+    pub major_components: Option<BTreeMap<String, ComponentInfo>>,
     pub update_triggers: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ComponentInfo {
     pub description: String,
     pub files: Vec<String>,
 }
 
 // Task History structures
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct TaskHistoryEvent {
     pub timestamp: DateTime<Utc>,
     pub task_id: String,
@@ -316,7 +514,7 @@ impl TaskHistoryEvent {
             capability: None,
             comment: None,
             pss_delta: None,
-            schema: "task_history.v1".to_string(),
+            schema: TASK_HISTORY_SCHEMA.to_string(),
         }
     }
 
@@ -336,6 +534,27 @@ impl TaskHistoryEvent {
     }
 }
 
+/// Settings for this machine only, read from `config.local.jsonc` in the
+/// machine-local directory. Anything that decides where credentials or
+/// traffic go lives here rather than in the committed config.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LocalConfig {
+    /// URL of a `rotd serve --coord` instance that coordination commands
+    /// go through instead of local files (`ROTD_COORD_SERVER` takes precedence)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coord_server: Option<String>,
+    /// Minisign public key (base64) trusted to sign release archives when
+    /// the binary pins none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_public_key: Option<String>,
+    /// Proxy URL for GitHub requests (overrides `HTTPS_PROXY`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// PEM bundle of extra root certificates for GitHub requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<String>,
+}
+
 // ROTD Configuration
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RotdConfig {
@@ -345,6 +564,203 @@ pub struct RotdConfig {
     pub history_compress_closed: bool,
     #[serde(default = "default_history_total_cap_mib")]
     pub history_total_cap_mib: u64,
+    /// Record a task history event on every task update (default true)
+    #[serde(default = "default_history_enabled")]
+    pub history_enabled: bool,
+    /// Set `updated_at` on every task write and keep `completed` in step
+    /// with the status (default true); turn off to manage both by hand
+    #[serde(default = "default_manage_timestamps")]
+    pub manage_timestamps: bool,
+    /// Release channel for upgrades: `stable` (default) or `beta`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_channel: Option<String>,
+    /// Reject unknown fields in agent input and report them in `validate`
+    /// (same as `--strict-parse`)
+    #[serde(default)]
+    pub strict_parse: bool,
+    /// Per-check overrides for `rotd check`, keyed by check ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub checks: HashMap<String, CheckSettings>,
+    /// Minimum health percentage for `rotd check --ci` to pass (default 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci_threshold: Option<f64>,
+    /// Time-limited exceptions for specific `rotd check` findings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waivers: Vec<CheckWaiver>,
+    /// Hours after which a session with in-progress tasks counts as stale
+    #[serde(default = "default_stale_session_hours")]
+    pub stale_session_hours: u64,
+    /// Language profile chosen at `rotd init --profile`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Command PSS runs to check that the project compiles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_command: Option<String>,
+    /// Build or typecheck commands that must all pass, for projects needing
+    /// more than one; takes precedence over `compile_command`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compile_checks: Vec<CompileCheck>,
+    /// Seconds a compile check result is reused across runs while sources
+    /// and HEAD are unchanged; 0 disables the cache (default 300)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_cache_ttl_secs: Option<u64>,
+    /// Stub markers, file extensions, and directories for stub detection;
+    /// the built-in Rust/TypeScript set when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stub_patterns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stub_extensions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stub_dirs: Vec<String>,
+    /// Gitignore-style globs for paths stub detection skips, applied after
+    /// the project's `.gitignore`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stub_ignore: Vec<String>,
+    /// Test directories recorded in new primers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_dirs: Vec<String>,
+    /// Command that produces coverage for the ratchet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_command: Option<String>,
+    /// Report `coverage_command` writes, read by `ratchet-coverage --run`;
+    /// well-known report files are tried when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_report: Option<String>,
+    /// Replace a task's previous score in pss_scores.jsonl instead of
+    /// appending another (default false)
+    #[serde(default)]
+    pub pss_replace_scores: bool,
+    /// Coverage history entries kept after each ratchet; unset keeps all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_keep_entries: Option<usize>,
+    /// Days of coverage history kept after each ratchet; unset keeps all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_keep_days: Option<u64>,
+    /// Link each line appended to tasks.jsonl and the audit log to the
+    /// one before it by hash, for `rotd verify-integrity` (default false)
+    #[serde(default)]
+    pub hash_chain: bool,
+    /// Replace secrets in lessons, audit messages, and coordination
+    /// messages with `[REDACTED]` as they are written (default true)
+    #[serde(default = "default_redact_secrets")]
+    pub redact_secrets: bool,
+    /// Regexes for project-specific secrets, on top of the built-in token,
+    /// key, and connection string patterns; a `secret` group limits what is
+    /// replaced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact_patterns: Vec<String>,
+    /// Factor weights for `rotd prioritize`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prioritize: Option<PrioritizeSettings>,
+    /// Remote that `rotd sync push/pull` keeps `.rotd/` in step with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<SyncSettings>,
+    /// Statuses each status may move to, replacing the built-in rules for
+    /// the statuses listed, e.g. `{"complete": ["in_progress"]}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub status_transitions: HashMap<String, Vec<String>>,
+}
+
+/// How `rotd prioritize` turns task attributes into a 0-100 `priority_score`.
+/// Each factor is scaled to 0-1 and the score is their weighted average.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrioritizeSettings {
+    /// Weight of the priority level (urgent 1 through deferred 0)
+    pub priority_weight: f64,
+    /// Weight of time since creation, reaching 1 at `age_cap_days`
+    pub age_weight: f64,
+    /// Weight of how many incomplete tasks wait on this one, reaching 1 at
+    /// `fan_out_cap`
+    pub fan_out_weight: f64,
+    /// Weight of the task's phase from `phase_weights`
+    pub phase_weight: f64,
+    pub age_cap_days: f64,
+    pub fan_out_cap: usize,
+    /// 0-1 value per phase; phases not listed (and tasks without one) get 0.5
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub phase_weights: HashMap<String, f64>,
+}
+
+impl Default for PrioritizeSettings {
+    fn default() -> Self {
+        Self {
+            priority_weight: 0.4,
+            age_weight: 0.2,
+            fan_out_weight: 0.3,
+            phase_weight: 0.1,
+            age_cap_days: 30.0,
+            fan_out_cap: 5,
+            phase_weights: HashMap::new(),
+        }
+    }
+}
+
+/// Where `rotd sync` stores `.rotd/`, e.g.
+/// `{"backend": "s3", "bucket": "team-state"}` or
+/// `{"backend": "git", "remote": "git@host:org/state.git"}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum SyncSettings {
+    /// An S3-compatible bucket. Credentials come from `AWS_ACCESS_KEY_ID`,
+    /// `AWS_SECRET_ACCESS_KEY`, and `AWS_SESSION_TOKEN`, never from config.
+    S3 {
+        bucket: String,
+        /// Key prefix the files are stored under (default "rotd")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix: Option<String>,
+        /// Endpoint for non-AWS stores such as MinIO or R2 (default
+        /// `https://s3.<region>.amazonaws.com`); buckets are addressed by path
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+        /// Region used for signing (default `AWS_REGION`, else us-east-1)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        region: Option<String>,
+    },
+    /// A branch of a git remote holding nothing but `.rotd/` files
+    Git {
+        /// Remote URL or path
+        remote: String,
+        /// Branch the files are committed to (default "rotd-state")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckSettings {
+    #[serde(default = "default_check_enabled")]
+    pub enabled: bool,
+    /// `critical`, `error`, or `warning`; warnings never fail a check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompileCheck {
+    /// Program and arguments, split with shell quoting rules but run
+    /// without a shell
+    pub command: String,
+    /// Working directory, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// Seconds before the command is killed and counted as failing
+    /// (default 600)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckWaiver {
+    /// Check ID the waiver applies to
+    pub check: String,
+    /// Only waive findings whose message or file contains this text;
+    /// every finding of the check when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<String>,
+    pub reason: String,
+    /// Last day (UTC) the waiver applies
+    pub expires: NaiveDate,
 }
 
 impl Default for RotdConfig {
@@ -353,6 +769,34 @@ impl Default for RotdConfig {
             history_max_size_mib: default_history_max_size_mib(),
             history_compress_closed: default_history_compress_closed(),
             history_total_cap_mib: default_history_total_cap_mib(),
+            history_enabled: default_history_enabled(),
+            manage_timestamps: default_manage_timestamps(),
+            release_channel: None,
+            strict_parse: false,
+            checks: HashMap::new(),
+            ci_threshold: None,
+            waivers: Vec::new(),
+            stale_session_hours: default_stale_session_hours(),
+            profile: None,
+            compile_command: None,
+            compile_checks: Vec::new(),
+            compile_cache_ttl_secs: None,
+            stub_patterns: Vec::new(),
+            stub_extensions: Vec::new(),
+            stub_dirs: Vec::new(),
+            stub_ignore: Vec::new(),
+            test_dirs: Vec::new(),
+            coverage_command: None,
+            coverage_report: None,
+            pss_replace_scores: false,
+            coverage_keep_entries: None,
+            coverage_keep_days: None,
+            hash_chain: false,
+            redact_secrets: true,
+            redact_patterns: Vec::new(),
+            prioritize: None,
+            sync: None,
+            status_transitions: HashMap::new(),
         }
     }
 }
@@ -360,3 +804,35 @@ impl Default for RotdConfig {
 fn default_history_max_size_mib() -> u64 { 1 }
 fn default_history_compress_closed() -> bool { true }
 fn default_history_total_cap_mib() -> u64 { 100 }
+fn default_history_enabled() -> bool { true }
+fn default_manage_timestamps() -> bool { true }
+fn default_check_enabled() -> bool { true }
+fn default_redact_secrets() -> bool { true }
+fn default_stale_session_hours() -> u64 { 24 }
+fn default_task_schema() -> String { TASK_SCHEMA.to_string() }
+fn default_test_summary_schema() -> String { TEST_SUMMARY_SCHEMA.to_string() }
+fn default_lesson_schema() -> String { LESSON_SCHEMA.to_string() }
+fn default_pss_score_schema() -> String { PSS_SCORE_SCHEMA.to_string() }
+fn default_session_state_schema() -> String { SESSION_STATE_SCHEMA.to_string() }
+fn default_session_log_schema() -> String { SESSION_LOG_SCHEMA.to_string() }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_issue() {
+        assert_eq!(schema_issue("task.v1", TASK_SCHEMA), None);
+        assert_eq!(schema_issue("task.v0", TASK_SCHEMA), None);
+        assert!(schema_issue("task.v2", TASK_SCHEMA)
+            .unwrap()
+            .contains("newer schema task.v2"));
+        assert!(schema_issue("lesson.v1", TASK_SCHEMA).is_some());
+        assert!(schema_issue("task", TASK_SCHEMA).is_some());
+
+        // Unstamped records read as the current version
+        let task: TaskEntry =
+            serde_json::from_str(r#"{"id":"1","title":"t","status":"pending"}"#).unwrap();
+        assert_eq!(task.schema, TASK_SCHEMA);
+    }
+}
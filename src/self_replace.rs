@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sibling path used while staging a new executable, e.g. `rotd.new`
+fn staged_path(exe: &Path) -> PathBuf {
+    sibling(exe, "new")
+}
+
+/// Where a running executable is parked on Windows until the next start
+pub fn parked_path(exe: &Path) -> PathBuf {
+    sibling(exe, "old")
+}
+
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let name = exe
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "rotd".to_string());
+    exe.with_file_name(format!("{}.{}", name, suffix))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Move `target` aside to `parked`, move `staged` into place, and put the
+/// original back if the second step fails. Windows lets a running
+/// executable be renamed but not overwritten or deleted, so this is how
+/// the binary replaces itself there.
+fn swap_in(target: &Path, staged: &Path, parked: &Path) -> Result<()> {
+    if parked.exists() {
+        fs::remove_file(parked)
+            .with_context(|| format!("Failed to remove stale {}", parked.display()))?;
+    }
+    fs::rename(target, parked)
+        .with_context(|| format!("Failed to move {} aside", target.display()))?;
+
+    if let Err(e) = fs::rename(staged, target) {
+        let _ = fs::rename(parked, target);
+        return Err(anyhow::Error::new(e)
+            .context(format!("Failed to move new binary to {}", target.display())));
+    }
+
+    Ok(())
+}
+
+/// Replace the executable at `exe` with `binary`.
+/// The new binary is staged next to the target so the final rename never
+/// crosses filesystems. On Unix the rename replaces the running file
+/// atomically; on Windows the running file is parked as `<exe>.old` and
+/// removed by `cleanup_parked` on the next run.
+pub fn replace_executable(exe: &Path, binary: &[u8]) -> Result<()> {
+    let staged = staged_path(exe);
+    fs::write(&staged, binary).with_context(|| format!("Failed to write {}", staged.display()))?;
+    make_executable(&staged)?;
+
+    let result = if cfg!(windows) {
+        swap_in(exe, &staged, &parked_path(exe))
+    } else {
+        fs::rename(&staged, exe).with_context(|| format!("Failed to replace {}", exe.display()))
+    };
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result
+}
+
+/// Remove an executable parked by a previous Windows upgrade, if any
+pub fn cleanup_parked() {
+    if let Ok(exe) = std::env::current_exe() {
+        let parked = parked_path(&exe);
+        if parked.exists() {
+            let _ = fs::remove_file(parked);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_in_restores_on_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exe = dir.path().join("rotd.exe");
+        let parked = parked_path(&exe);
+        fs::write(&exe, b"old").unwrap();
+        fs::write(staged_path(&exe), b"new").unwrap();
+        fs::write(&parked, b"stale").unwrap();
+
+        swap_in(&exe, &staged_path(&exe), &parked).unwrap();
+        assert_eq!(fs::read(&exe).unwrap(), b"new");
+        assert_eq!(fs::read(&parked).unwrap(), b"old");
+
+        // No staged file: the original must be put back
+        assert!(swap_in(&exe, &staged_path(&exe), &parked).is_err());
+        assert_eq!(fs::read(&exe).unwrap(), b"new");
+    }
+
+    #[test]
+    fn test_replace_executable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exe = dir.path().join("rotd");
+        fs::write(&exe, b"old").unwrap();
+
+        replace_executable(&exe, b"new").unwrap();
+        assert_eq!(fs::read(&exe).unwrap(), b"new");
+        assert!(!staged_path(&exe).exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&exe).unwrap().permissions().mode();
+            assert_eq!(mode & 0o755, 0o755);
+        }
+        #[cfg(windows)]
+        assert_eq!(fs::read(parked_path(&exe)).unwrap(), b"old");
+    }
+}
@@ -0,0 +1,528 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::SessionCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{append_jsonl, read_json, read_jsonl, write_json};
+use crate::history::get_agent_id;
+use crate::schema::{
+    LessonLearned, PSSScore, SESSION_LOG_SCHEMA, SESSION_STATE_SCHEMA, SessionLogEntry,
+    SessionState, TaskEntry, TaskHistoryEvent, TaskStatus, TestSummary,
+};
+
+/// Status of a session that has started and not yet ended
+pub const ACTIVE: &str = "active";
+
+/// Statuses a session can end with
+pub const END_STATUSES: [&str; 4] = ["complete", "handoff", "blocked", "paused"];
+
+pub fn handle_command(cmd: SessionCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        SessionCommands::Start { task, force } => {
+            cmd_start(task.as_deref(), force, dry_run, is_agent_mode)
+        }
+        SessionCommands::End { status, note } => {
+            cmd_end(&status, note.as_deref(), dry_run, is_agent_mode)
+        }
+        SessionCommands::Show { log, limit, deltas } => {
+            cmd_show(log, limit, deltas, is_agent_mode)
+        }
+    }
+}
+
+/// ID for a session starting now, e.g. `session-20260131091500`
+pub fn new_session_id() -> String {
+    format!("session-{}", Utc::now().format("%Y%m%d%H%M%S"))
+}
+
+/// The session in session_state.json, if there is one
+pub fn current() -> Option<SessionState> {
+    read_json(&crate::common::session_state_path()).ok()
+}
+
+/// Session state to replace a lost or unreadable session_state.json: the
+/// last logged session event, or with no log a session on the task most
+/// recently moved along among those in progress
+pub fn rebuild_state(
+    log: &[SessionLogEntry],
+    history: &[TaskHistoryEvent],
+    tasks: &[TaskEntry],
+    now: DateTime<Utc>,
+) -> SessionState {
+    let in_progress: Vec<&str> = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TaskStatus::InProgress))
+        .map(|t| t.id.as_str())
+        .collect();
+    let recent_task = history
+        .iter()
+        .filter(|e| in_progress.contains(&e.task_id.as_str()))
+        .max_by_key(|e| e.timestamp)
+        .map(|e| e.task_id.clone())
+        .or_else(|| in_progress.last().map(|id| id.to_string()));
+
+    match log.iter().max_by_key(|e| e.timestamp) {
+        Some(last) => SessionState {
+            session_id: last.session_id.clone(),
+            timestamp: last.timestamp,
+            current_task: last.current_task.clone().or(recent_task),
+            status: last.status.clone(),
+            deltas: last.deltas.clone(),
+            schema: SESSION_STATE_SCHEMA.to_string(),
+        },
+        None => SessionState {
+            session_id: new_session_id(),
+            timestamp: history.iter().map(|e| e.timestamp).max().unwrap_or(now),
+            status: if recent_task.is_some() { ACTIVE } else { "initialized" }.to_string(),
+            current_task: recent_task,
+            deltas: None,
+            schema: SESSION_STATE_SCHEMA.to_string(),
+        },
+    }
+}
+
+/// What changed in the project during a session
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionDeltas {
+    /// Tasks updated, with the status each was left in
+    pub tasks_touched: BTreeMap<String, String>,
+    /// Tasks whose test summary was written
+    pub summaries_added: Vec<String>,
+    pub scores_recorded: Vec<ScoreDelta>,
+    /// IDs of lessons logged
+    pub lessons_logged: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScoreDelta {
+    pub task_id: String,
+    pub score: u32,
+}
+
+impl SessionDeltas {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Deltas from the records stamped at or after `since`. Tasks count as
+/// touched from either their log entries or their history events.
+pub fn collect_deltas(
+    since: DateTime<Utc>,
+    tasks: &[TaskEntry],
+    history: &[TaskHistoryEvent],
+    summaries: &[TestSummary],
+    scores: &[PSSScore],
+    lessons: &[LessonLearned],
+) -> SessionDeltas {
+    let mut updates: Vec<(DateTime<Utc>, &str, &str)> = tasks
+        .iter()
+        .filter_map(|t| Some((t.updated_at?, t.id.as_str(), t.status.as_str())))
+        .chain(
+            history
+                .iter()
+                .map(|e| (e.timestamp, e.task_id.as_str(), e.status.as_str())),
+        )
+        .filter(|(time, _, _)| *time >= since)
+        .collect();
+    updates.sort();
+
+    SessionDeltas {
+        tasks_touched: updates
+            .into_iter()
+            .map(|(_, id, status)| (id.to_string(), status.to_string()))
+            .collect(),
+        summaries_added: summaries
+            .iter()
+            .filter(|s| s.timestamp >= since)
+            .map(|s| s.task_id.clone())
+            .collect(),
+        scores_recorded: scores
+            .iter()
+            .filter(|s| s.timestamp >= since)
+            .map(|s| ScoreDelta {
+                task_id: s.task_id.clone(),
+                score: s.score,
+            })
+            .collect(),
+        lessons_logged: lessons
+            .iter()
+            .filter(|l| l.timestamp.is_some_and(|t| t >= since))
+            .map(|l| l.id.clone())
+            .collect(),
+    }
+}
+
+/// Deltas from the project's artifacts since `since`
+pub fn deltas_since(since: DateTime<Utc>) -> Result<SessionDeltas> {
+    let summaries: Vec<TestSummary> = crate::output::test_summary_files()
+        .into_iter()
+        .filter_map(|(path, _)| read_json(&path).ok())
+        .collect();
+    Ok(collect_deltas(
+        since,
+        &read_jsonl::<TaskEntry>(&crate::common::tasks_path())?,
+        &crate::history::read_all_history()?,
+        &summaries,
+        &read_jsonl::<PSSScore>(&crate::common::pss_scores_path())?,
+        &read_jsonl::<LessonLearned>(&crate::common::lessons_path())?,
+    ))
+}
+
+/// Deltas of a session: recorded at its end, or so far while it is active
+pub fn session_deltas(session: &SessionState) -> Result<Option<SessionDeltas>> {
+    if session.status == ACTIVE {
+        return deltas_since(session.timestamp).map(Some);
+    }
+    Ok(session
+        .deltas
+        .clone()
+        .and_then(|deltas| serde_json::from_value(deltas).ok()))
+}
+
+fn log_event(session: &SessionState, event: &str, note: Option<&str>) -> Result<SessionLogEntry> {
+    let entry = SessionLogEntry {
+        session_id: session.session_id.clone(),
+        agent_id: get_agent_id(),
+        event: event.to_string(),
+        timestamp: session.timestamp,
+        current_task: session.current_task.clone(),
+        status: session.status.clone(),
+        note: note.map(str::to_string),
+        deltas: session.deltas.clone(),
+        schema: SESSION_LOG_SCHEMA.to_string(),
+    };
+    append_jsonl(&crate::common::sessions_path(), &entry)?;
+    Ok(entry)
+}
+
+/// End `session` with `status`, recording what changed since it started
+/// in session_state.json and the log
+fn end(session: &mut SessionState, status: &str, note: Option<&str>) -> Result<SessionLogEntry> {
+    session.deltas = Some(serde_json::to_value(deltas_since(session.timestamp)?)?);
+    session.status = status.to_string();
+    session.timestamp = Utc::now();
+    write_json(&crate::common::session_state_path(), &*session)?;
+    log_event(session, "end", note)
+}
+
+fn cmd_start(task: Option<&str>, force: bool, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let previous = current().filter(|s| s.status == ACTIVE);
+    if let (Some(previous), false) = (&previous, force) {
+        return Err(anyhow::anyhow!(
+            "Session {} is still active. Run 'rotd session end' first, or pass --force to end it as abandoned",
+            previous.session_id
+        ));
+    }
+
+    let mut session = SessionState {
+        session_id: new_session_id(),
+        timestamp: Utc::now(),
+        // A session picks up the previous one's task unless told otherwise
+        current_task: task
+            .map(str::to_string)
+            .or_else(|| current().and_then(|s| s.current_task)),
+        status: ACTIVE.to_string(),
+        deltas: None,
+        schema: SESSION_STATE_SCHEMA.to_string(),
+    };
+
+    if !dry_run {
+        if let Some(mut previous) = previous {
+            end(&mut previous, "abandoned", Some("Superseded by rotd session start --force"))?;
+        }
+        session.timestamp = Utc::now();
+        write_json(&crate::common::session_state_path(), &session)?;
+        log_event(&session, "start", None)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "session_start",
+                "dry_run": dry_run,
+                "session": session,
+            })
+        );
+    } else {
+        println!(
+            "{} session {}",
+            if dry_run { "Would start" } else { "✓ Started" },
+            session.session_id.cyan()
+        );
+        if let Some(task) = &session.current_task {
+            println!("   Current task: {}", task);
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_end(status: &str, note: Option<&str>, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let Some(mut session) = current().filter(|s| s.status == ACTIVE) else {
+        return Err(anyhow::anyhow!(
+            "No active session. Run 'rotd session start' first"
+        ));
+    };
+
+    if dry_run {
+        session.deltas = Some(serde_json::to_value(deltas_since(session.timestamp)?)?);
+        session.status = status.to_string();
+    } else {
+        end(&mut session, status, note)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "session_end",
+                "dry_run": dry_run,
+                "session": session,
+                "note": note,
+            })
+        );
+    } else {
+        println!(
+            "{} session {} as {}",
+            if dry_run { "Would end" } else { "✓ Ended" },
+            session.session_id.cyan(),
+            status.bold()
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_show(log: bool, limit: usize, show_deltas: bool, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let session = current();
+    let deltas = match &session {
+        Some(session) if show_deltas || is_agent_mode => session_deltas(session)?,
+        _ => None,
+    };
+    let entries = if log {
+        let mut entries: Vec<SessionLogEntry> = read_jsonl(&crate::common::sessions_path())?;
+        entries.reverse();
+        entries.truncate(limit);
+        entries
+    } else {
+        Vec::new()
+    };
+
+    if is_agent_mode {
+        let mut result = serde_json::json!({
+            "status": "success",
+            "session": session,
+            "deltas": deltas,
+        });
+        if log {
+            result["log"] = serde_json::to_value(&entries)?;
+        }
+        println!("{}", result);
+        return Ok(());
+    }
+
+    match &session {
+        Some(session) => {
+            println!("{}", format!("Session {}", session.session_id).cyan().bold());
+            let status = if session.status == ACTIVE {
+                session.status.green()
+            } else {
+                session.status.normal()
+            };
+            println!("  Status:       {}", status);
+            println!(
+                "  Current task: {}",
+                session.current_task.as_deref().unwrap_or("-")
+            );
+            println!("  Since:        {}", session.timestamp);
+        }
+        None => println!("No session state recorded."),
+    }
+
+    if show_deltas {
+        print_deltas(session.as_ref(), deltas.as_ref());
+    }
+
+    if log {
+        println!("\n{}", "Session Log".cyan().bold());
+        if entries.is_empty() {
+            println!("  No sessions logged yet");
+        }
+        for entry in &entries {
+            println!(
+                "  {}  {:<5}  {}  {}  {}{}",
+                entry.timestamp.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+                entry.event,
+                entry.session_id,
+                entry.agent_id,
+                entry.status,
+                entry
+                    .current_task
+                    .as_ref()
+                    .map_or(String::new(), |t| format!(" (task {})", t))
+            );
+            if let Some(note) = &entry.note {
+                println!("  {:>16}  {}", "", note.italic());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_deltas(session: Option<&SessionState>, deltas: Option<&SessionDeltas>) {
+    let heading = match session {
+        Some(s) if s.status == ACTIVE => "Changes So Far",
+        _ => "Changes",
+    };
+    println!("\n{}", heading.cyan().bold());
+    let Some(deltas) = deltas.filter(|d| !d.is_empty()) else {
+        println!("  None recorded");
+        return;
+    };
+
+    if !deltas.tasks_touched.is_empty() {
+        println!("  Tasks:");
+        for (id, status) in &deltas.tasks_touched {
+            println!("    {} ({})", id, status);
+        }
+    }
+    if !deltas.summaries_added.is_empty() {
+        println!("  Test summaries: {}", deltas.summaries_added.join(", "));
+    }
+    if !deltas.scores_recorded.is_empty() {
+        let scores: Vec<String> = deltas
+            .scores_recorded
+            .iter()
+            .map(|s| format!("{} = {}", s.task_id, s.score))
+            .collect();
+        println!("  PSS scores: {}", scores.join(", "));
+    }
+    if !deltas.lessons_logged.is_empty() {
+        println!("  Lessons: {}", deltas.lessons_logged.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collect_deltas() {
+        let since: DateTime<Utc> = "2026-01-31T09:00:00Z".parse().unwrap();
+        let task = |id: &str, status: &str, time: &str| -> TaskEntry {
+            serde_json::from_value(json!({
+                "id": id,
+                "title": "Task",
+                "status": status,
+                "updated_at": time,
+            }))
+            .unwrap()
+        };
+        let tasks = vec![
+            task("6.1", "pending", "2026-01-31T08:00:00Z"),
+            task("6.2", "in_progress", "2026-01-31T09:30:00Z"),
+            task("6.2", "complete", "2026-01-31T10:00:00Z"),
+        ];
+        let mut event = TaskHistoryEvent::new(
+            "6.3".to_string(),
+            "agent-a".to_string(),
+            "blocked".to_string(),
+        );
+        event.timestamp = "2026-01-31T09:15:00Z".parse().unwrap();
+        let scores: Vec<PSSScore> = ["08:59:00", "09:45:00"]
+            .iter()
+            .map(|time| {
+                serde_json::from_value(json!({
+                    "task_id": "6.2",
+                    "score": 7,
+                    "timestamp": format!("2026-01-31T{}Z", time),
+                    "criteria": {},
+                }))
+                .unwrap()
+            })
+            .collect();
+
+        let deltas = collect_deltas(since, &tasks, &[event], &[], &scores, &[]);
+        assert_eq!(
+            deltas.tasks_touched,
+            BTreeMap::from([
+                ("6.2".to_string(), "complete".to_string()),
+                ("6.3".to_string(), "blocked".to_string()),
+            ])
+        );
+        assert_eq!(
+            deltas.scores_recorded,
+            vec![ScoreDelta {
+                task_id: "6.2".to_string(),
+                score: 7
+            }]
+        );
+        assert!(deltas.summaries_added.is_empty());
+        assert!(collect_deltas(since, &[], &[], &[], &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_rebuild_state() {
+        let now: DateTime<Utc> = "2026-02-01T12:00:00Z".parse().unwrap();
+        let tasks: Vec<TaskEntry> = [("6.1", "in_progress"), ("6.2", "in_progress"), ("6.3", "complete")]
+            .iter()
+            .map(|(id, status)| {
+                serde_json::from_value(json!({"id": id, "title": "Task", "status": status})).unwrap()
+            })
+            .collect();
+        let event = |task_id: &str, time: &str| {
+            let mut event = TaskHistoryEvent::new(
+                task_id.to_string(),
+                "agent-a".to_string(),
+                "in_progress".to_string(),
+            );
+            event.timestamp = time.parse().unwrap();
+            event
+        };
+        let history = vec![
+            event("6.2", "2026-01-31T09:00:00Z"),
+            event("6.1", "2026-01-31T10:00:00Z"),
+            event("6.3", "2026-01-31T11:00:00Z"),
+        ];
+
+        // Without a session log: the latest in-progress task from history
+        let state = rebuild_state(&[], &history, &tasks, now);
+        assert_eq!(state.current_task.as_deref(), Some("6.1"));
+        assert_eq!(state.status, ACTIVE);
+        assert_eq!(state.timestamp, "2026-01-31T11:00:00Z".parse::<DateTime<Utc>>().unwrap());
+
+        // The last logged event wins when there is a log
+        let logged: SessionLogEntry = serde_json::from_value(json!({
+            "session_id": "session-20260131080000",
+            "agent_id": "agent-a",
+            "event": "end",
+            "timestamp": "2026-01-31T12:00:00Z",
+            "current_task": null,
+            "status": "paused",
+        }))
+        .unwrap();
+        let state = rebuild_state(&[logged], &history, &tasks, now);
+        assert_eq!(state.session_id, "session-20260131080000");
+        assert_eq!(state.status, "paused");
+        assert_eq!(state.current_task.as_deref(), Some("6.1"));
+
+        let state = rebuild_state(&[], &[], &[], now);
+        assert_eq!(state.status, "initialized");
+        assert_eq!(state.timestamp, now);
+    }
+}
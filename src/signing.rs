@@ -0,0 +1,589 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::KeysCommands;
+use crate::common::check_rotd_initialized;
+use crate::integrity::{AGENT_FIELD, PREV_FIELD};
+use crate::output::Finding;
+
+/// Field holding an entry's signature
+pub const SIGNATURE_FIELD: &str = "_sig";
+
+/// An agent's signing identity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentProfile {
+    /// Ed25519 public key, base64
+    pub public_key: String,
+    /// The agent's private key file, relative to the project root; defaults
+    /// to `keys/<agent>.key` in the machine-local part of `.rotd/`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_file: Option<String>,
+    pub created: DateTime<Utc>,
+    /// Keys replaced by `keys generate --force`, which still verify the
+    /// entries written while they were active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retired: Vec<RetiredKey>,
+}
+
+/// A key an agent used to sign with, and when it was in use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetiredKey {
+    /// Ed25519 public key, base64
+    pub public_key: String,
+    pub created: DateTime<Utc>,
+    pub retired: DateTime<Utc>,
+}
+
+/// Profiles by agent ID, stored in `agents.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AgentProfiles {
+    #[serde(default)]
+    pub agents: BTreeMap<String, AgentProfile>,
+}
+
+/// What an entry's `_sig` holds
+#[derive(Debug, Serialize, Deserialize)]
+struct EntrySignature {
+    agent: String,
+    /// Ed25519 signature, base64
+    signature: String,
+}
+
+pub fn handle_command(cmd: KeysCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        KeysCommands::Generate { agent_id, force } => {
+            cmd_generate(agent_id, force, dry_run, is_agent_mode)
+        }
+        KeysCommands::List => cmd_list(is_agent_mode),
+    }
+}
+
+pub fn load_profiles() -> Result<AgentProfiles> {
+    let path = crate::common::agents_path();
+    if !path.exists() {
+        return Ok(AgentProfiles::default());
+    }
+    crate::fs_ops::read_json(&path)
+}
+
+impl AgentProfile {
+    fn key_path(&self, agent: &str) -> PathBuf {
+        self.key_file
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| crate::common::keys_path().join(format!("{}.key", agent)))
+    }
+
+    fn verifying_key(&self) -> Result<VerifyingKey> {
+        decode_public_key(&self.public_key)
+    }
+
+    /// The public keys an entry written at `at` may be signed with: the one
+    /// active then, or every key the agent has had when the time is unknown
+    fn keys_active_at(&self, at: Option<DateTime<Utc>>) -> Vec<&str> {
+        let current = std::iter::once((self.public_key.as_str(), self.created, None));
+        let retired = self
+            .retired
+            .iter()
+            .map(|k| (k.public_key.as_str(), k.created, Some(k.retired)));
+        current
+            .chain(retired)
+            .filter(|(_, created, retired)| match at {
+                Some(at) => *created <= at && retired.is_none_or(|retired| at < retired),
+                None => true,
+            })
+            .map(|(key, _, _)| key)
+            .collect()
+    }
+
+    /// When the agent's first key was registered
+    fn first_registered(&self) -> DateTime<Utc> {
+        self.retired
+            .iter()
+            .map(|k| k.created)
+            .fold(self.created, DateTime::min)
+    }
+}
+
+fn decode_public_key(public_key: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = BASE64
+        .decode(public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .context("public_key is not a base64 Ed25519 key")?;
+    VerifyingKey::from_bytes(&bytes).context("public_key is not a valid Ed25519 key")
+}
+
+fn read_signing_key(path: &Path) -> Result<SigningKey> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read signing key {}", path.display()))?;
+    let bytes: [u8; 32] = BASE64
+        .decode(content.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .with_context(|| format!("{} is not a base64 Ed25519 key", path.display()))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+/// The current agent and its signing key, if its profile registers one.
+/// Fails when the key is registered but missing or not the one registered,
+/// rather than writing unsigned entries.
+fn current_key() -> Result<Option<(String, SigningKey)>> {
+    let agent = crate::history::get_agent_id();
+    let profiles = load_profiles()?;
+    let Some(profile) = profiles.agents.get(&agent) else {
+        return Ok(None);
+    };
+    let key = read_signing_key(&profile.key_path(&agent)).with_context(|| {
+        format!("Agent {} has a registered signing key but it can't be used", agent)
+    })?;
+    if key.verifying_key() != profile.verifying_key()? {
+        anyhow::bail!(
+            "{} does not match the public key registered for {}",
+            profile.key_path(&agent).display(),
+            agent
+        );
+    }
+    Ok(Some((agent, key)))
+}
+
+/// What gets signed for a record: the agent ID, then the record as JSON
+/// with sorted keys and without its signature or hash chain link
+fn signed_message(agent: &str, record: &serde_json::Value) -> String {
+    let mut record = record.clone();
+    if let Some(map) = record.as_object_mut() {
+        for field in [SIGNATURE_FIELD, PREV_FIELD, AGENT_FIELD] {
+            map.remove(field);
+        }
+    }
+    format!("{}\n{}", agent, record)
+}
+
+fn signature(agent: &str, key: &SigningKey, record: &serde_json::Value) -> EntrySignature {
+    EntrySignature {
+        agent: agent.to_string(),
+        signature: BASE64.encode(key.sign(signed_message(agent, record).as_bytes()).to_bytes()),
+    }
+}
+
+/// Sign task lines about to be appended to tasks.jsonl with the current
+/// agent's key. Other files, and agents without a key, are left as is.
+pub fn sign_lines(path: &Path, lines: Vec<String>) -> Result<Vec<String>> {
+    if path != crate::common::tasks_path() {
+        return Ok(lines);
+    }
+    let Some((agent, key)) = current_key()? else {
+        return Ok(lines);
+    };
+    lines
+        .into_iter()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(&line)?;
+            let signature = serde_json::to_string(&signature(&agent, &key, &record))?;
+            crate::integrity::append_fields(&line, &[(SIGNATURE_FIELD, signature)])
+        })
+        .collect()
+}
+
+/// Add the current agent's signature to a document, such as a test
+/// summary, when it has a key
+pub fn sign_document(document: &mut serde_json::Value) -> Result<()> {
+    let Some((agent, key)) = current_key()? else {
+        return Ok(());
+    };
+    let signature = serde_json::to_value(signature(&agent, &key, document))?;
+    if let Some(map) = document.as_object_mut() {
+        map.insert(SIGNATURE_FIELD.to_string(), signature);
+    }
+    Ok(())
+}
+
+/// When a record was written, for deciding whether it should be signed
+fn written_at(record: &serde_json::Value) -> Option<DateTime<Utc>> {
+    let value = record.get("updated_at").or_else(|| record.get("timestamp"))?;
+    serde_json::from_value(value.clone()).ok()
+}
+
+/// What's wrong with a record's signature, if anything. With `required_since`,
+/// records written since then must be signed, and so must records whose
+/// write time is missing or unreadable.
+fn signature_problem(
+    record: &serde_json::Value,
+    profiles: &AgentProfiles,
+    required_since: Option<DateTime<Utc>>,
+) -> Option<String> {
+    let Some(sig) = record.get(SIGNATURE_FIELD) else {
+        let since = required_since?;
+        return match written_at(record) {
+            None => Some(
+                "Unsigned, with no readable write time, while signing keys are registered"
+                    .to_string(),
+            ),
+            Some(at) if at >= since => {
+                Some("Unsigned, but written after signing keys were registered".to_string())
+            }
+            Some(_) => None,
+        };
+    };
+    let Ok(sig) = serde_json::from_value::<EntrySignature>(sig.clone()) else {
+        return Some(format!("Malformed {}", SIGNATURE_FIELD));
+    };
+    if let Some(writer) = record.get(AGENT_FIELD).and_then(|a| a.as_str()) {
+        if writer != sig.agent {
+            return Some(format!("Written by {} but signed by {}", writer, sig.agent));
+        }
+    }
+    let Some(profile) = profiles.agents.get(&sig.agent) else {
+        return Some(format!("Signed by {}, who has no registered key", sig.agent));
+    };
+    let written = written_at(record);
+    let keys = profile.keys_active_at(written);
+    if keys.is_empty() {
+        return Some(format!(
+            "Signed by {}, who had no key at {}",
+            sig.agent,
+            written.map(|at| at.to_rfc3339()).unwrap_or_default()
+        ));
+    }
+    let bytes: Option<[u8; 64]> = BASE64
+        .decode(&sig.signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok());
+    let Some(bytes) = bytes else {
+        return Some(format!(
+            "Signature by {} does not verify: signature is not base64 Ed25519",
+            sig.agent
+        ));
+    };
+    let message = signed_message(&sig.agent, record);
+    let mut error = None;
+    for key in keys {
+        match decode_public_key(key).and_then(|key| {
+            key.verify(message.as_bytes(), &Signature::from_bytes(&bytes))
+                .context("entry was changed after signing, or signed with another key")
+        }) {
+            Ok(()) => return None,
+            Err(e) => error = Some(e),
+        }
+    }
+    error.map(|e| format!("Signature by {} does not verify: {}", sig.agent, e))
+}
+
+/// Tasks whose first entry in tasks.jsonl comes after a signed entry
+fn signed_era_tasks() -> HashSet<String> {
+    let mut seen = HashSet::new();
+    let mut era = HashSet::new();
+    let mut signed = false;
+    for (_, _, record) in crate::json_schema::artifact_records("tasks") {
+        let Some(id) = record.get("id").and_then(|id| id.as_str()) else {
+            continue;
+        };
+        if seen.insert(id.to_string()) && signed {
+            era.insert(id.to_string());
+        }
+        signed |= record.get(SIGNATURE_FIELD).is_some();
+    }
+    era
+}
+
+/// Signature problems in tasks.jsonl or the test summaries. Every signature
+/// must verify against its agent's registered key; in strict mode, entries
+/// written since the first key was registered, undated entries, and entries
+/// appended after a signed one must also be signed.
+pub fn signature_findings(schema_name: &str, strict: bool) -> Vec<Finding> {
+    if !matches!(schema_name, "tasks" | "test_summaries") {
+        return Vec::new();
+    }
+    let profiles = match load_profiles() {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            let path = crate::common::agents_path();
+            return vec![Finding::new("invalid_agent_profiles", format!("{:#}", e)).at(&path, None)];
+        }
+    };
+    let required_since = if strict {
+        profiles.agents.values().map(AgentProfile::first_registered).min()
+    } else {
+        None
+    };
+
+    // A record's own timestamps can be backdated, so where it sits counts
+    // too: nothing appended after a signed entry may be unsigned, and
+    // neither may the summary of a task first recorded after one
+    let mut signed_files: HashSet<PathBuf> = HashSet::new();
+    let signed_era_tasks = match (required_since, schema_name) {
+        (Some(_), "test_summaries") => signed_era_tasks(),
+        _ => HashSet::new(),
+    };
+    crate::json_schema::artifact_records(schema_name)
+        .into_iter()
+        .filter_map(|(path, line, record)| {
+            let signed = record.get(SIGNATURE_FIELD).is_some();
+            let follows_signed = line.is_some() && signed_files.contains(&path);
+            if signed && line.is_some() {
+                signed_files.insert(path.clone());
+            }
+            let task_id = record.get("task_id").and_then(|id| id.as_str());
+            let problem = match signature_problem(&record, &profiles, required_since) {
+                Some(problem) => problem,
+                None if signed || required_since.is_none() => return None,
+                None if follows_signed => "Unsigned, but appended after signed entries".to_string(),
+                None if task_id.is_some_and(|id| signed_era_tasks.contains(id)) => {
+                    "Unsigned, but its task was recorded after signed entries".to_string()
+                }
+                None => return None,
+            };
+            let id = record.get("id").or_else(|| record.get("task_id"));
+            let message = match id.and_then(|id| id.as_str()) {
+                Some(id) => format!("Task {}: {}", id, problem),
+                None => problem,
+            };
+            Some(Finding::new("signature", message).at(&path, line))
+        })
+        .collect()
+}
+
+fn cmd_generate(
+    agent: Option<String>,
+    force: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+    let agent = agent.unwrap_or_else(crate::history::get_agent_id);
+    let mut profiles = load_profiles()?;
+    if profiles.agents.contains_key(&agent) && !force {
+        anyhow::bail!(
+            "Agent {} already has a signing key (use --force to replace it)",
+            agent
+        );
+    }
+
+    let key = SigningKey::generate(&mut rand_core::OsRng);
+    let now = Utc::now();
+    // The replaced key stays on record so its entries keep verifying
+    let retired = match profiles.agents.get(&agent) {
+        Some(previous) => {
+            let mut retired = previous.retired.clone();
+            retired.push(RetiredKey {
+                public_key: previous.public_key.clone(),
+                created: previous.created,
+                retired: now,
+            });
+            retired
+        }
+        None => Vec::new(),
+    };
+    let profile = AgentProfile {
+        public_key: BASE64.encode(key.verifying_key().as_bytes()),
+        key_file: None,
+        created: now,
+        retired,
+    };
+    let key_path = profile.key_path(&agent);
+
+    if !dry_run {
+        if let Some(parent) = key_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&key_path, BASE64.encode(key.to_bytes()) + "\n")
+            .with_context(|| format!("Failed to write {}", key_path.display()))?;
+        restrict_permissions(&key_path)?;
+        profiles.agents.insert(agent.clone(), profile.clone());
+        crate::fs_ops::write_json(&crate::common::agents_path(), &profiles)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "keys_generate",
+                "agent": agent,
+                "public_key": profile.public_key,
+                "key_file": key_path,
+                "retired_keys": profile.retired.len(),
+                "dry_run": dry_run,
+            })
+        );
+    } else {
+        let verb = if dry_run { "Would generate" } else { "✓ Generated" };
+        println!("{} signing key for {}", verb, agent);
+        println!("  Public key: {}", profile.public_key);
+        println!("  Private key: {} (keep it off version control)", key_path.display());
+        if !profile.retired.is_empty() {
+            println!("  Previous key retired; entries it signed still verify");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = std::fs::metadata(path)?.permissions();
+    perms.set_mode(0o600);
+    std::fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn cmd_list(is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    let profiles = load_profiles()?;
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "keys_list",
+                "agents": profiles.agents,
+            })
+        );
+        return Ok(());
+    }
+
+    if profiles.agents.is_empty() {
+        println!("No signing keys registered (rotd keys generate <AGENT_ID>)");
+        return Ok(());
+    }
+    for (agent, profile) in &profiles.agents {
+        let local = if profile.key_path(agent).exists() {
+            "key on this machine"
+        } else {
+            "no key on this machine"
+        };
+        println!(
+            "{}  {}  registered {} ({})",
+            agent,
+            profile.public_key,
+            profile.created.format("%Y-%m-%d"),
+            local
+        );
+        for retired in &profile.retired {
+            println!(
+                "  retired {}  {} to {}",
+                retired.public_key,
+                retired.created.format("%Y-%m-%d"),
+                retired.retired.format("%Y-%m-%d")
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_problems() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let created = "2026-01-01T00:00:00Z".parse().unwrap();
+        let profiles = AgentProfiles {
+            agents: BTreeMap::from([(
+                "agent-1".to_string(),
+                AgentProfile {
+                    public_key: BASE64.encode(key.verifying_key().as_bytes()),
+                    key_file: None,
+                    created,
+                    retired: Vec::new(),
+                },
+            )]),
+        };
+
+        let line = r#"{"id":"1.1","title":"Parser","status":"in_progress","updated_at":"2026-02-01T00:00:00Z"}"#;
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        let sig = serde_json::to_string(&signature("agent-1", &key, &record)).unwrap();
+        let signed = crate::integrity::append_fields(line, &[(SIGNATURE_FIELD, sig)]).unwrap();
+        let problem = |line: &str, required: Option<DateTime<Utc>>| {
+            signature_problem(&serde_json::from_str(line).unwrap(), &profiles, required)
+        };
+
+        assert_eq!(problem(&signed, Some(created)), None);
+        // A hash chain link added afterwards doesn't disturb the signature
+        let linked = crate::integrity::append_fields(
+            &signed,
+            &[(PREV_FIELD, "\"00\"".to_string()), (AGENT_FIELD, "\"agent-1\"".to_string())],
+        )
+        .unwrap();
+        assert_eq!(problem(&linked, None), None);
+
+        let edited = signed.replace("in_progress", "complete");
+        assert!(problem(&edited, None).unwrap().contains("does not verify"));
+        let spoofed = signed.replace(r#""agent":"agent-1""#, r#""agent":"agent-2""#);
+        assert!(problem(&spoofed, None).unwrap().contains("no registered key"));
+        let relabelled = crate::integrity::append_fields(
+            &signed,
+            &[(AGENT_FIELD, "\"agent-2\"".to_string())],
+        )
+        .unwrap();
+        assert!(problem(&relabelled, None).unwrap().contains("signed by agent-1"));
+
+        // Unsigned entries only matter in strict mode, and only once keys exist
+        assert_eq!(problem(line, None), None);
+        assert!(problem(line, Some(created)).unwrap().contains("Unsigned"));
+        assert_eq!(problem(line, Some("2026-03-01T00:00:00Z".parse().unwrap())), None);
+        // Without a readable write time there is no telling, so it must be signed
+        let undated = r#"{"id":"1.1","title":"Parser","status":"in_progress"}"#;
+        assert!(problem(undated, Some(created)).unwrap().contains("no readable write time"));
+        let garbled = line.replace("2026-02-01T00:00:00Z", "yesterday");
+        assert!(problem(&garbled, Some(created)).unwrap().contains("no readable write time"));
+        assert_eq!(problem(undated, None), None);
+    }
+
+    #[test]
+    fn test_retired_keys_verify_their_own_window() {
+        let old_key = SigningKey::from_bytes(&[7; 32]);
+        let new_key = SigningKey::from_bytes(&[9; 32]);
+        let encode = |key: &SigningKey| BASE64.encode(key.verifying_key().as_bytes());
+        let profiles = AgentProfiles {
+            agents: BTreeMap::from([(
+                "agent-1".to_string(),
+                AgentProfile {
+                    public_key: encode(&new_key),
+                    key_file: None,
+                    created: "2026-03-01T00:00:00Z".parse().unwrap(),
+                    retired: vec![RetiredKey {
+                        public_key: encode(&old_key),
+                        created: "2026-01-01T00:00:00Z".parse().unwrap(),
+                        retired: "2026-03-01T00:00:00Z".parse().unwrap(),
+                    }],
+                },
+            )]),
+        };
+        let problem = |key: &SigningKey, updated_at: Option<&str>| {
+            let mut record = serde_json::json!({"id": "1.1", "title": "Parser", "status": "pending"});
+            if let Some(at) = updated_at {
+                record["updated_at"] = serde_json::json!(at);
+            }
+            let sig = signature("agent-1", key, &record);
+            record[SIGNATURE_FIELD] = serde_json::to_value(sig).unwrap();
+            signature_problem(&record, &profiles, None)
+        };
+
+        // Each key verifies what was written while it was active
+        assert_eq!(problem(&old_key, Some("2026-02-01T00:00:00Z")), None);
+        assert_eq!(problem(&new_key, Some("2026-04-01T00:00:00Z")), None);
+        // ...and nothing outside that window
+        assert!(problem(&old_key, Some("2026-04-01T00:00:00Z")).unwrap().contains("does not verify"));
+        assert!(problem(&new_key, Some("2026-02-01T00:00:00Z")).unwrap().contains("does not verify"));
+        assert!(problem(&new_key, Some("2025-12-01T00:00:00Z")).unwrap().contains("had no key at"));
+        // Undated entries may carry any of the agent's keys
+        assert_eq!(problem(&old_key, None), None);
+
+        let first = profiles.agents["agent-1"].first_registered();
+        assert_eq!(first, "2026-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap());
+    }
+}
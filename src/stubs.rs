@@ -0,0 +1,139 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::common::check_rotd_initialized;
+
+/// A stub marker found by PSS stub detection, with the lines around it
+#[derive(Debug, Serialize)]
+pub struct StubHit {
+    pub file: PathBuf,
+    pub line: usize,
+    pub pattern: String,
+    pub snippet: Vec<SnippetLine>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SnippetLine {
+    pub line: usize,
+    pub text: String,
+}
+
+/// Lines `line - context ..= line + context` (1-based) of `content`,
+/// clipped to the file
+pub fn snippet(content: &str, line: usize, context: usize) -> Vec<SnippetLine> {
+    let first = line.saturating_sub(context).max(1);
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i + 1, text))
+        .skip(first - 1)
+        .take_while(|(n, _)| *n <= line + context)
+        .map(|(line, text)| SnippetLine {
+            line,
+            text: text.to_string(),
+        })
+        .collect()
+}
+
+/// Every location `pss::find_stubs` reports, with `context` lines of
+/// surrounding source
+pub fn find(context: usize) -> Vec<StubHit> {
+    let mut contents: BTreeMap<PathBuf, String> = BTreeMap::new();
+    crate::pss::find_stubs()
+        .into_iter()
+        .map(|(file, line, pattern)| {
+            let content = contents
+                .entry(file.clone())
+                .or_insert_with(|| std::fs::read_to_string(&file).unwrap_or_default());
+            StubHit {
+                snippet: snippet(content, line, context),
+                file,
+                line,
+                pattern,
+            }
+        })
+        .collect()
+}
+
+/// `rotd stubs`
+pub fn cmd_stubs(context: usize, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let hits = find(context);
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "stubs",
+                "count": hits.len(),
+                "stubs": hits,
+            })
+        );
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("{} No stubs detected", "✓".green());
+        return Ok(());
+    }
+
+    let width = hits
+        .iter()
+        .flat_map(|hit| hit.snippet.last())
+        .map(|s| s.line.to_string().len())
+        .max()
+        .unwrap_or(1);
+    for hit in &hits {
+        println!(
+            "{}:{} {}",
+            hit.file.display().to_string().cyan(),
+            hit.line,
+            hit.pattern.yellow()
+        );
+        for line in &hit.snippet {
+            let number = format!("{:>width$}", line.line, width = width);
+            if line.line == hit.line {
+                println!("  {} {} {}", ">".red().bold(), number.bold(), line.text);
+            } else {
+                println!("    {} {}", number.dimmed(), line.text.dimmed());
+            }
+        }
+        println!();
+    }
+    println!(
+        "{} stub(s) remaining; PSS stub_free fails until they are removed",
+        hits.len()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet() {
+        let content = "fn a() {\n    panic!()\n}\n\nfn b() {}\n";
+        let lines = |snippet: Vec<SnippetLine>| -> Vec<usize> {
+            snippet.iter().map(|s| s.line).collect()
+        };
+
+        assert_eq!(lines(snippet(content, 2, 1)), vec![1, 2, 3]);
+        // Clipped at the start and end of the file
+        assert_eq!(lines(snippet(content, 1, 2)), vec![1, 2, 3]);
+        assert_eq!(lines(snippet(content, 5, 2)), vec![3, 4, 5]);
+        assert_eq!(
+            snippet(content, 2, 0),
+            vec![SnippetLine {
+                line: 2,
+                text: "    panic!()".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,1016 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::TaskCommands;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{append_tasks, latest_tasks, read_json, safe_update_task, write_json};
+use crate::git::run_git;
+use crate::schema::{SESSION_STATE_SCHEMA, SessionState, TaskEntry, TaskStatus};
+
+pub fn handle_command(cmd: TaskCommands, is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    match cmd {
+        TaskCommands::Start {
+            task_id,
+            branch,
+            force,
+        } => cmd_start(&task_id, branch, force, dry_run, is_agent_mode),
+        TaskCommands::Assign {
+            task_id,
+            assignee,
+            clear,
+        } => cmd_assign(&task_id, assignee, clear, dry_run, is_agent_mode),
+        TaskCommands::Tag {
+            task_id,
+            tags,
+            remove,
+        } => cmd_tag(&task_id, &tags, remove, dry_run, is_agent_mode),
+        TaskCommands::SetStatus {
+            ids,
+            filters,
+            status,
+            reason,
+            force,
+        } => cmd_set_status(
+            &ids,
+            &filters,
+            &status,
+            reason,
+            force,
+            dry_run,
+            is_agent_mode,
+        ),
+        TaskCommands::New {
+            task_id,
+            title,
+            template,
+            vars,
+            priority,
+            phase,
+        } => {
+            let task = new_task(&task_id, &title, template.as_deref(), &vars, priority, phase)?;
+            cmd_new(&task, dry_run, is_agent_mode)
+        }
+    }
+}
+
+/// Fields a `--filter key=value` can test
+pub const FILTER_KEYS: &[&str] = &["status", "phase", "priority", "assignee", "tag"];
+
+/// Parse `key=value` filters, rejecting unknown keys
+pub fn parse_filters(filters: &[String]) -> Result<Vec<(&str, &str)>> {
+    filters
+        .iter()
+        .map(|filter| {
+            let (key, value) = filter
+                .split_once('=')
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .ok_or_else(|| anyhow::anyhow!("Filter '{}' is not key=value", filter))?;
+            if !FILTER_KEYS.contains(&key) {
+                anyhow::bail!(
+                    "Unknown filter key '{}' (expected one of: {})",
+                    key,
+                    FILTER_KEYS.join(", ")
+                );
+            }
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Whether a task passes every filter from `parse_filters`
+pub fn matches_filters(task: &TaskEntry, filters: &[(&str, &str)]) -> bool {
+    filters.iter().all(|&(key, value)| match key {
+        "status" => task.status.as_str() == value,
+        "phase" => task.phase.as_deref() == Some(value),
+        "priority" => task.priority.as_ref().is_some_and(|p| p.as_str() == value),
+        "assignee" => task.assignee.as_deref() == Some(value),
+        "tag" => task.tags.iter().any(|t| t == value),
+        _ => false,
+    })
+}
+
+/// Whether a task carries every one of `tags`
+pub fn has_tags(task: &TaskEntry, tags: &[String]) -> bool {
+    tags.iter().all(|tag| task.tags.contains(tag))
+}
+
+/// `prev` with the top-level fields of `fields` replacing its own; a
+/// `null` clears an optional field
+pub fn apply_fields(prev: &TaskEntry, fields: &serde_json::Value) -> Result<TaskEntry> {
+    let fields = fields
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("Expected a JSON object"))?;
+    let mut merged = serde_json::to_value(prev)?;
+    if let Some(entry) = merged.as_object_mut() {
+        entry.extend(fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    Ok(serde_json::from_value(merged)?)
+}
+
+/// `prev` with a patch document applied: an object is an RFC 7386 merge
+/// patch and an array an RFC 6902 JSON Patch. The task ID cannot change.
+pub fn apply_patch(prev: &TaskEntry, patch: &serde_json::Value) -> Result<TaskEntry> {
+    let mut doc = serde_json::to_value(prev)?;
+    match patch {
+        serde_json::Value::Object(_) => json_patch::merge(&mut doc, patch),
+        serde_json::Value::Array(_) => {
+            let operations: json_patch::Patch = serde_json::from_value(patch.clone())?;
+            json_patch::patch(&mut doc, &operations)?;
+        }
+        _ => anyhow::bail!("A patch must be a JSON object (merge patch) or array (JSON Patch)"),
+    }
+    let task: TaskEntry = serde_json::from_value(doc)?;
+    if task.id != prev.id {
+        anyhow::bail!("A patch cannot change the task ID ({} -> {})", prev.id, task.id);
+    }
+    Ok(task)
+}
+
+/// Statuses a task may move to by default. Work passes through
+/// in_progress before it is complete, and complete tasks are not reopened.
+fn default_transitions(from: &TaskStatus) -> &'static [&'static str] {
+    match from {
+        TaskStatus::Pending => &["in_progress", "blocked", "scaffolded"],
+        TaskStatus::Scaffolded => &["pending", "in_progress", "blocked"],
+        TaskStatus::InProgress => &["pending", "blocked", "scaffolded", "complete"],
+        TaskStatus::Blocked => &["pending", "in_progress"],
+        TaskStatus::Complete => &[],
+    }
+}
+
+/// Statuses `from` may move to, from `status_transitions` in the config
+/// when it lists `from`, otherwise the built-in rules
+pub fn allowed_transitions(
+    from: &TaskStatus,
+    overrides: &std::collections::HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    match overrides.get(from.as_str()) {
+        Some(allowed) => allowed.clone(),
+        None => default_transitions(from).iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Reject a status change the transition rules do not allow. New tasks and
+/// unchanged statuses always pass.
+pub fn check_transition(prev: Option<&TaskEntry>, task: &TaskEntry) -> Result<()> {
+    let Some(prev) = prev.filter(|p| p.status != task.status) else {
+        return Ok(());
+    };
+    let overrides = crate::history::load_config()
+        .map(|c| c.status_transitions)
+        .unwrap_or_default();
+    let allowed = allowed_transitions(&prev.status, &overrides);
+    if allowed.iter().any(|s| s == task.status.as_str()) {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Task {} cannot move from {} to {} (allowed: {})",
+        task.id,
+        prev.status.as_str(),
+        task.status.as_str(),
+        if allowed.is_empty() {
+            "none".to_string()
+        } else {
+            allowed.join(", ")
+        }
+    )
+}
+
+/// Record a transition that broke the rules but was forced through
+pub fn log_forced_transition(prev: &TaskEntry, task: &TaskEntry) -> Result<()> {
+    crate::audit::log_warning(
+        Some(&task.id),
+        TRANSITION_OVERRIDE_RULE,
+        &forced_transition_message(&prev.status, &task.status),
+    )
+}
+
+/// Audit rule under which forced transitions are recorded
+pub const TRANSITION_OVERRIDE_RULE: &str = "TRANSITION_OVERRIDE";
+
+/// Audit message for a transition forced from `from` to `to`
+pub fn forced_transition_message(from: &TaskStatus, to: &TaskStatus) -> String {
+    format!(
+        "Status {} -> {} forced past transition rules",
+        from.as_str(),
+        to.as_str()
+    )
+}
+
+/// Open work for one assignee
+#[derive(Debug, Default, Serialize)]
+pub struct Workload {
+    pub assignee: String,
+    /// Incomplete tasks, of which some are in progress or blocked
+    pub open: usize,
+    pub in_progress: usize,
+    pub blocked: usize,
+}
+
+/// Incomplete tasks per assignee, by name; unassigned tasks are left out
+pub fn workload(tasks: &[TaskEntry]) -> Vec<Workload> {
+    let mut by_assignee: BTreeMap<&str, Workload> = BTreeMap::new();
+    for task in tasks.iter().filter(|t| t.status != TaskStatus::Complete) {
+        let Some(assignee) = task.assignee.as_deref() else {
+            continue;
+        };
+        let load = by_assignee.entry(assignee).or_insert_with(|| Workload {
+            assignee: assignee.to_string(),
+            ..Default::default()
+        });
+        load.open += 1;
+        match task.status {
+            TaskStatus::InProgress => load.in_progress += 1,
+            TaskStatus::Blocked => load.blocked += 1,
+            _ => {}
+        }
+    }
+    by_assignee.into_values().collect()
+}
+
+/// Branch name for a task, e.g. `task/6.2-add-parser-cache`
+pub fn branch_name(task: &TaskEntry) -> String {
+    let slug = |s: &str| {
+        s.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>()
+            .split('-')
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+
+    let title: String = slug(&task.title)
+        .split('-')
+        .take(6)
+        .collect::<Vec<_>>()
+        .join("-");
+    let id = slug(&task.id).trim_matches('.').to_string();
+
+    if title.is_empty() {
+        format!("task/{}", id)
+    } else {
+        format!("task/{}-{}", id, title)
+    }
+}
+
+/// Shell-style match of a task ID or path against a pattern where `*` is
+/// any run of characters and `?` any single one
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was, and how much of the text it had consumed
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Tasks matching any of `patterns` (IDs or globs), in pattern order and
+/// then task order, each listed once; plus the patterns nothing matched
+pub fn resolve<'a>(
+    patterns: &[String],
+    tasks: &'a [TaskEntry],
+) -> (Vec<&'a TaskEntry>, Vec<String>) {
+    let mut found: Vec<&TaskEntry> = Vec::new();
+    let mut unmatched = Vec::new();
+    for pattern in patterns {
+        let matches: Vec<&TaskEntry> = tasks
+            .iter()
+            .filter(|t| glob_match(pattern, &t.id))
+            .collect();
+        if matches.is_empty() {
+            unmatched.push(pattern.clone());
+        }
+        for task in matches {
+            if !found.iter().any(|f| f.id == task.id) {
+                found.push(task);
+            }
+        }
+    }
+    (found, unmatched)
+}
+
+/// A new pending task, from a template when one is named; explicit
+/// priority and phase override the template's
+fn new_task(
+    task_id: &str,
+    title: &str,
+    template: Option<&str>,
+    vars: &[String],
+    priority: Option<String>,
+    phase: Option<String>,
+) -> Result<TaskEntry> {
+    check_rotd_initialized()?;
+
+    if latest_tasks()?.iter().any(|t| t.id == task_id) {
+        anyhow::bail!("Task {} already exists", task_id);
+    }
+    let vars = vars
+        .iter()
+        .map(|var| {
+            var.split_once('=')
+                .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+                .ok_or_else(|| anyhow::anyhow!("Variable '{}' is not NAME=VALUE", var))
+        })
+        .collect::<Result<BTreeMap<String, String>>>()?;
+
+    let template = match template {
+        Some(name) => crate::template::load(name)?,
+        None => crate::template::TaskTemplate::default(),
+    };
+    let mut task = template.instantiate(task_id, title, &vars)?;
+    if let Some(priority) = priority {
+        task.priority = crate::schema::Priority::parse(&priority);
+    }
+    if phase.is_some() {
+        task.phase = phase;
+    }
+    task.validate()?;
+    Ok(task)
+}
+
+fn cmd_new(task: &TaskEntry, dry_run: bool, is_agent_mode: bool) -> Result<()> {
+    if !dry_run {
+        safe_update_task(task, false)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if dry_run { "dry_run" } else { "success" },
+                "action": "task_new",
+                "task": task,
+            })
+        );
+    } else {
+        println!(
+            "{} task {} {}",
+            if dry_run { "Would create" } else { "✓ Created" },
+            task.id.bold(),
+            task.title
+        );
+        if let Some(tests) = &task.tests {
+            println!("  Tests: {}", tests.join(", "));
+        }
+        if !task.tags.is_empty() {
+            println!("  Tags:  {}", task.tags.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_assign(
+    task_id: &str,
+    assignee: Option<String>,
+    clear: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let assignee = if clear {
+        None
+    } else {
+        // Defaults to whoever is running the command
+        Some(assignee.unwrap_or_else(crate::history::get_agent_id))
+    };
+    let tasks = latest_tasks()?;
+    let task = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+    let previous = task.assignee.clone();
+
+    let changed = previous != assignee;
+    if changed && !dry_run {
+        let mut updated = task.clone();
+        updated.assignee = assignee.clone();
+        updated.update_timestamp();
+        safe_update_task(&updated, false)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if dry_run { "dry_run" } else { "success" },
+                "action": "task_assign",
+                "task_id": task_id,
+                "assignee": assignee,
+                "previous": previous,
+                "changed": changed,
+            })
+        );
+    } else if !changed {
+        println!("Task {} is already {}", task_id, describe_assignee(&assignee));
+    } else {
+        println!(
+            "{} task {} {}",
+            if dry_run { "Would update" } else { "✓ Updated" },
+            task_id.bold(),
+            describe_assignee(&assignee)
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_tag(
+    task_id: &str,
+    tags: &[String],
+    remove: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks = latest_tasks()?;
+    let task = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+
+    let mut updated = task.clone();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if remove {
+            updated.tags.retain(|t| t != tag);
+        } else if !updated.tags.iter().any(|t| t == tag) {
+            updated.tags.push(tag.to_string());
+        }
+    }
+
+    let changed = updated.tags != task.tags;
+    if changed && !dry_run {
+        updated.update_timestamp();
+        safe_update_task(&updated, false)?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if dry_run { "dry_run" } else { "success" },
+                "action": "task_tag",
+                "task_id": task_id,
+                "tags": updated.tags,
+                "changed": changed,
+            })
+        );
+    } else if !changed {
+        println!("Task {} tags unchanged", task_id);
+    } else {
+        println!(
+            "{} task {} tags: {}",
+            if dry_run { "Would update" } else { "✓ Updated" },
+            task_id.bold(),
+            if updated.tags.is_empty() {
+                "none".to_string()
+            } else {
+                updated.tags.join(", ")
+            }
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_set_status(
+    ids: &[String],
+    filters: &[String],
+    status: &str,
+    reason: Option<String>,
+    force: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let new_status = TaskStatus::parse(status)
+        .ok_or_else(|| anyhow::anyhow!("Unknown status: {}", status))?;
+    let filters = parse_filters(filters)?;
+    let tasks = latest_tasks()?;
+
+    // IDs and globs narrow the set first; without them filters apply to every task
+    let selected: Vec<&TaskEntry> = if ids.is_empty() {
+        tasks.iter().collect()
+    } else {
+        let (found, unmatched) = resolve(ids, &tasks);
+        if !unmatched.is_empty() {
+            anyhow::bail!("No tasks match: {}", unmatched.join(", "));
+        }
+        found
+    };
+    let selected: Vec<&TaskEntry> = selected
+        .into_iter()
+        .filter(|t| matches_filters(t, &filters))
+        .collect();
+    let (unchanged, to_update): (Vec<&TaskEntry>, Vec<&TaskEntry>) =
+        selected.into_iter().partition(|t| t.status == new_status);
+
+    // The write path stamps updated_at and completed
+    let updated: Vec<TaskEntry> = to_update
+        .iter()
+        .map(|task| {
+            let mut task = (*task).clone();
+            task.status = new_status.clone();
+            task
+        })
+        .collect();
+
+    // Nothing is written unless every change is allowed or forced
+    let mut forced = Vec::new();
+    for (task, prev) in updated.iter().zip(&to_update) {
+        if let Err(e) = check_transition(Some(prev), task) {
+            if !force {
+                return Err(e.context("Pass --force to override the transition rules"));
+            }
+            forced.push((*prev, task));
+        }
+    }
+
+    if !dry_run && !updated.is_empty() {
+        append_tasks(&updated, reason.as_deref())?;
+        for (prev, task) in &forced {
+            log_forced_transition(prev, task)?;
+        }
+        for (task, prev) in updated.iter().zip(&to_update) {
+            let mut message = format!(
+                "Status {} -> {} via bulk update",
+                prev.status.as_str(),
+                status
+            );
+            if let Some(reason) = &reason {
+                message.push_str(&format!(": {}", reason));
+            }
+            crate::audit::log_info(Some(&task.id), "TASK_UPDATE", &message)?;
+        }
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if dry_run { "dry_run" } else { "success" },
+                "action": "task_set_status",
+                "new_status": status,
+                "updated": updated.iter().map(|t| &t.id).collect::<Vec<_>>(),
+                "unchanged": unchanged.iter().map(|t| &t.id).collect::<Vec<_>>(),
+                "reason": reason,
+            })
+        );
+        return Ok(());
+    }
+
+    if updated.is_empty() && unchanged.is_empty() {
+        println!("No tasks matched");
+        return Ok(());
+    }
+    for (task, prev) in updated.iter().zip(&to_update) {
+        println!(
+            "  {} {} {} → {}",
+            "•".cyan(),
+            task.id.bold(),
+            prev.status.as_str().dimmed(),
+            status
+        );
+    }
+    println!(
+        "{} {} task(s) to {}{}",
+        if dry_run { "Would set" } else { "✓ Set" },
+        updated.len(),
+        status,
+        if unchanged.is_empty() {
+            String::new()
+        } else {
+            format!("; {} already {}", unchanged.len(), status)
+        }
+    );
+
+    Ok(())
+}
+
+fn describe_assignee(assignee: &Option<String>) -> String {
+    match assignee {
+        Some(name) => format!("assigned to {}", name),
+        None => "unassigned".to_string(),
+    }
+}
+
+fn cmd_start(
+    task_id: &str,
+    create_branch: bool,
+    force: bool,
+    dry_run: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let tasks = latest_tasks()?;
+    let task = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+
+    let mut updated = task.clone();
+    updated.status = TaskStatus::InProgress;
+    updated.updated_at = Some(Utc::now());
+
+    // Validate everything up front so a refusal leaves no partial state
+    let forced = match check_transition(Some(task), &updated) {
+        Ok(()) => false,
+        Err(_) if force => true,
+        Err(e) => return Err(e.context("Use --force to start it anyway")),
+    };
+    if !force {
+        let unmet: Vec<&str> = task
+            .depends_on
+            .iter()
+            .flatten()
+            .filter(|dep| {
+                !tasks
+                    .iter()
+                    .any(|t| &t.id == *dep && matches!(t.status, TaskStatus::Complete))
+            })
+            .map(|s| s.as_str())
+            .collect();
+        if !unmet.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Task {} has incomplete dependencies: {}. Use --force to start anyway.",
+                task_id,
+                unmet.join(", ")
+            ));
+        }
+    }
+
+    let branch = if create_branch {
+        let name = branch_name(task);
+        run_git(&["check-ref-format", "--branch", &name])
+            .with_context(|| format!("Invalid branch name {}", name))?;
+        Some(name)
+    } else {
+        None
+    };
+
+    let agent_id = crate::history::get_agent_id();
+
+    if dry_run {
+        if is_agent_mode {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "dry_run",
+                    "task_id": task_id,
+                    "branch": branch,
+                    "agent_id": agent_id,
+                })
+            );
+        } else {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+            println!("  Would set task {} to in_progress", task_id);
+            println!(
+                "  Would claim {} in the coordination registry (if listed)",
+                task_id
+            );
+            println!("  Would set session current_task to {}", task_id);
+            if let Some(name) = &branch {
+                println!("  Would switch to branch {}", name);
+            }
+        }
+        return Ok(());
+    }
+
+    let coord = crate::coord_server::client()?;
+    let claimed = match &coord {
+        Some(client) => client.claim_task(task_id, &agent_id)?,
+        None => crate::coord::claim_task(task_id, &agent_id)?,
+    }
+    .is_some();
+    // Any failure from here releases the claim, so the task isn't left held
+    // by an agent that never started it
+    let rollback = |e: anyhow::Error| -> anyhow::Error {
+        if !claimed {
+            return e;
+        }
+        let unclaimed = match &coord {
+            Some(client) => client.unclaim(task_id, &agent_id),
+            None => crate::coord::unclaim_task(task_id, &agent_id),
+        };
+        match unclaimed {
+            Ok(()) => e,
+            Err(unclaim) => e.context(format!(
+                "Task {} is still claimed by {}; releasing it failed: {}",
+                task_id, agent_id, unclaim
+            )),
+        }
+    };
+
+    if let Some(name) = &branch {
+        let exists = run_git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", name),
+        ])
+        .is_ok();
+        let switched = if exists {
+            run_git(&["switch", name])
+        } else {
+            run_git(&["switch", "-c", name])
+        };
+        if let Err(e) = switched {
+            return Err(rollback(e.context(format!("Failed to switch to branch {}", name))));
+        }
+    }
+
+    // The task log append also records the status change in task history
+    safe_update_task(&updated, false).map_err(rollback)?;
+    if forced {
+        log_forced_transition(task, &updated)?;
+    }
+
+    let session_path = crate::common::session_state_path();
+    let mut session = read_json::<SessionState>(&session_path).unwrap_or_else(|_| SessionState {
+        session_id: crate::session::new_session_id(),
+        timestamp: Utc::now(),
+        current_task: None,
+        status: "active".to_string(),
+        deltas: None,
+        schema: SESSION_STATE_SCHEMA.to_string(),
+    });
+    session.current_task = Some(task_id.to_string());
+    session.timestamp = Utc::now();
+    session.status = "active".to_string();
+    write_json(&session_path, &session)
+        .with_context(|| format!("Failed to make {} the current task", task_id))
+        .map_err(rollback)?;
+
+    // The task has started by now, so an unreadable primer only loses the hint
+    let primer_scopes = crate::primer::relevant_scopes(&updated).unwrap_or_default();
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "task_start",
+                "task_id": task_id,
+                "claimed": claimed,
+                "branch": branch,
+                "agent_id": agent_id,
+                "primer_scopes": primer_scopes,
+            })
+        );
+    } else {
+        println!(
+            "{} Started task {}: {}",
+            "✓".green(),
+            task_id.bold(),
+            updated.title
+        );
+        if claimed {
+            println!("  Claimed in coordination registry as {}", agent_id);
+        }
+        if let Some(name) = &branch {
+            println!("  On branch {}", name.cyan());
+        }
+        let scoped: Vec<&String> = primer_scopes
+            .iter()
+            .filter(|s| *s != crate::primer::ROOT_SCOPE)
+            .collect();
+        if !scoped.is_empty() {
+            println!(
+                "  Relevant primers: {} (rotd primer parse --task {})",
+                scoped
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                task_id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_name() {
+        let task: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "6.2",
+            "title": "Add parser cache: LRU + eviction (phase 2) for big files",
+            "status": "pending",
+        }))
+        .unwrap();
+        assert_eq!(
+            branch_name(&task),
+            "task/6.2-add-parser-cache-lru-eviction-phase"
+        );
+    }
+
+    #[test]
+    fn test_has_tags() {
+        let task: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "6.2",
+            "title": "Parser cache",
+            "status": "pending",
+            "tags": ["api", "perf"],
+        }))
+        .unwrap();
+        let tags = |tags: &[&str]| tags.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        assert!(has_tags(&task, &tags(&[])));
+        assert!(has_tags(&task, &tags(&["api"])));
+        assert!(has_tags(&task, &tags(&["perf", "api"])));
+        // Every requested tag must be present
+        assert!(!has_tags(&task, &tags(&["api", "ui"])));
+        assert!(!has_tags(&task, &tags(&["API"])));
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert!(glob_match("6.*", "6.1"));
+        assert!(glob_match("6.*", "6.1.2"));
+        assert!(!glob_match("6.*", "16.1"));
+        assert!(glob_match("?.1", "6.1"));
+        assert!(glob_match("*a*b", "xaab"));
+        assert!(!glob_match("6.1", "6.10"));
+
+        let tasks: Vec<TaskEntry> = ["6.1", "6.2", "7.1"]
+            .iter()
+            .map(|id| {
+                serde_json::from_value(serde_json::json!({
+                    "id": id,
+                    "title": "Task",
+                    "status": "pending",
+                }))
+                .unwrap()
+            })
+            .collect();
+        let patterns: Vec<String> = ["7.1", "6.*", "6.2", "8.*"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let (found, unmatched) = resolve(&patterns, &tasks);
+        let ids: Vec<&str> = found.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["7.1", "6.1", "6.2"]);
+        assert_eq!(unmatched, vec!["8.*"]);
+    }
+
+    #[test]
+    fn test_workload() {
+        let task = |id: &str, status: &str, assignee: Option<&str>| -> TaskEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "title": id, "status": status, "assignee": assignee,
+            }))
+            .unwrap()
+        };
+        let tasks = vec![
+            task("1", "in_progress", Some("bo")),
+            task("2", "blocked", Some("bo")),
+            task("3", "complete", Some("ana")),
+            task("4", "pending", Some("ana")),
+            task("5", "pending", None),
+        ];
+
+        let loads = workload(&tasks);
+        let summary: Vec<(&str, usize, usize, usize)> = loads
+            .iter()
+            .map(|w| (w.assignee.as_str(), w.open, w.in_progress, w.blocked))
+            .collect();
+        assert_eq!(summary, vec![("ana", 1, 0, 0), ("bo", 2, 1, 1)]);
+    }
+
+    #[test]
+    fn test_filters() {
+        let task: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "6.1",
+            "title": "Parser",
+            "status": "in_progress",
+            "phase": "2",
+            "priority": "high",
+            "tags": ["api", "core"],
+        }))
+        .unwrap();
+        let filters = vec!["status=in_progress".to_string(), "tag = api".to_string()];
+        assert!(matches_filters(&task, &parse_filters(&filters).unwrap()));
+        let filters = vec!["phase=2".to_string(), "assignee=bob".to_string()];
+        assert!(!matches_filters(&task, &parse_filters(&filters).unwrap()));
+        assert!(parse_filters(&["owner=bob".to_string()]).is_err());
+        assert!(parse_filters(&["blocked".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_allowed_transitions() {
+        let defaults = std::collections::HashMap::new();
+        assert_eq!(
+            allowed_transitions(&TaskStatus::Blocked, &defaults),
+            vec!["pending", "in_progress"]
+        );
+        assert!(allowed_transitions(&TaskStatus::Complete, &defaults).is_empty());
+
+        // Configured rules replace the built-in ones only for the statuses listed
+        let overrides = std::collections::HashMap::from([(
+            "complete".to_string(),
+            vec!["in_progress".to_string()],
+        )]);
+        assert_eq!(
+            allowed_transitions(&TaskStatus::Complete, &overrides),
+            vec!["in_progress"]
+        );
+        assert!(!allowed_transitions(&TaskStatus::Pending, &overrides).contains(&"complete".to_string()));
+    }
+
+    #[test]
+    fn test_apply_fields() {
+        let prev: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "6.2",
+            "title": "Parser",
+            "status": "in_progress",
+            "description": "Recursive descent",
+            "tags": ["core"],
+        }))
+        .unwrap();
+
+        let task = apply_fields(&prev, &serde_json::json!({"id": "6.2", "status": "complete"})).unwrap();
+        assert_eq!(task.status, TaskStatus::Complete);
+        assert_eq!(task.title, "Parser");
+        assert_eq!(task.tags, vec!["core"]);
+
+        let task = apply_fields(&prev, &serde_json::json!({"description": null})).unwrap();
+        assert_eq!(task.description, None);
+        assert!(apply_fields(&prev, &serde_json::json!({"status": "done"})).is_err());
+        assert!(apply_fields(&prev, &serde_json::json!(["status"])).is_err());
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let prev: TaskEntry = serde_json::from_value(serde_json::json!({
+            "id": "6.2",
+            "title": "Parser",
+            "status": "in_progress",
+            "description": "Recursive descent",
+            "tests": ["test_parse"],
+        }))
+        .unwrap();
+
+        let merge = serde_json::json!({"description": null, "priority": "high"});
+        let task = apply_patch(&prev, &merge).unwrap();
+        assert_eq!(task.description, None);
+        assert_eq!(task.priority, Some(crate::schema::Priority::High));
+
+        let ops = serde_json::json!([
+            {"op": "add", "path": "/tests/-", "value": "test_errors"},
+            {"op": "replace", "path": "/status", "value": "complete"},
+        ]);
+        let task = apply_patch(&prev, &ops).unwrap();
+        assert_eq!(
+            task.tests,
+            Some(vec!["test_parse".to_string(), "test_errors".to_string()])
+        );
+        assert_eq!(task.status, TaskStatus::Complete);
+
+        // A failed test op rejects the whole patch
+        let ops = serde_json::json!([{"op": "test", "path": "/status", "value": "pending"}]);
+        assert!(apply_patch(&prev, &ops).is_err());
+        assert!(apply_patch(&prev, &serde_json::json!({"id": "6.3"})).is_err());
+        assert!(apply_patch(&prev, &serde_json::json!("status")).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::schema::{Priority, TASK_SCHEMA, TaskEntry, TaskStatus};
+
+/// Extensions tried, in order, for `.rotd/templates/<name>`
+const TEMPLATE_EXTENSIONS: &[&str] = &["json", "jsonc"];
+
+/// Defaults for a recurring shape of task, stored as
+/// `.rotd/templates/<name>.json`. Text fields may use `{{variable}}`
+/// placeholders; `id`, `title`, and `date` are always defined.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskTemplate {
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tests: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+    pub phase: Option<String>,
+    /// Default values for variables, overridden by `--var`
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+}
+
+fn template_file(name: &str) -> Option<PathBuf> {
+    TEMPLATE_EXTENSIONS
+        .iter()
+        .map(|ext| crate::common::templates_path().join(format!("{}.{}", name, ext)))
+        .find(|path| path.is_file())
+}
+
+/// Names of the task templates in `.rotd/templates/`
+pub fn available() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(crate::common::templates_path()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| TEMPLATE_EXTENSIONS.contains(&e))
+        })
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+pub fn load(name: &str) -> Result<TaskTemplate> {
+    let Some(path) = template_file(name) else {
+        let names = available();
+        return Err(anyhow::anyhow!(
+            "No template named '{}' in {}{}",
+            name,
+            crate::common::templates_path().display(),
+            if names.is_empty() {
+                String::new()
+            } else {
+                format!(" (available: {})", names.join(", "))
+            }
+        ));
+    };
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    crate::history::parse_jsonc(&content)
+        .with_context(|| format!("Invalid template {}", path.display()))
+}
+
+/// Replace `{{name}}` placeholders, collecting names with no value
+fn render(text: &str, vars: &BTreeMap<String, String>, missing: &mut Vec<String>) -> String {
+    let placeholder = Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").unwrap();
+    placeholder
+        .replace_all(text, |caps: &regex::Captures| match vars.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => {
+                missing.push(caps[1].to_string());
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+impl TaskTemplate {
+    /// A pending task built from the template, with placeholders filled from
+    /// the built-in variables, the template's defaults, then `vars`
+    pub fn instantiate(
+        &self,
+        id: &str,
+        title: &str,
+        vars: &BTreeMap<String, String>,
+    ) -> Result<TaskEntry> {
+        let now = Utc::now();
+        let mut values = self.vars.clone();
+        values.insert("id".to_string(), id.to_string());
+        values.insert("title".to_string(), title.to_string());
+        values.insert("date".to_string(), now.format("%Y-%m-%d").to_string());
+        values.extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let mut missing = Vec::new();
+        let mut render_all = |items: &[String]| -> Vec<String> {
+            items.iter().map(|s| render(s, &values, &mut missing)).collect()
+        };
+        let tests = render_all(&self.tests);
+        let tags = render_all(&self.tags);
+        let description = self
+            .description
+            .as_ref()
+            .map(|d| render(d, &values, &mut missing));
+        let phase = self.phase.as_ref().map(|p| render(p, &values, &mut missing));
+        if !missing.is_empty() {
+            missing.sort();
+            missing.dedup();
+            anyhow::bail!(
+                "Template needs a value for: {} (pass --var NAME=VALUE)",
+                missing.join(", ")
+            );
+        }
+
+        Ok(TaskEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            status: TaskStatus::Pending,
+            tests: (!tests.is_empty()).then_some(tests),
+            description,
+            summary_file: None,
+            origin: None,
+            phase,
+            depends_on: None,
+            priority: self.priority.clone(),
+            priority_score: None,
+            created: Some(now),
+            updated_at: Some(now),
+            completed: None,
+            pr_url: None,
+            assignee: None,
+            tags,
+            schema: TASK_SCHEMA.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instantiate() {
+        let template: TaskTemplate = crate::history::parse_jsonc(
+            r#"{
+                // Regression fix with a test that reproduces it
+                "description": "Fix {{ component }}: {{title}}\nReported in {{issue}}",
+                "tests": ["test_{{component}}_regression"],
+                "tags": ["bug", "{{component}}"],
+                "priority": "high",
+                "vars": {"component": "core"},
+            }"#,
+        )
+        .unwrap();
+
+        let vars = BTreeMap::from([("issue".to_string(), "#42".to_string())]);
+        let task = template.instantiate("6.3", "Crash on empty input", &vars).unwrap();
+        assert_eq!(
+            task.description.as_deref(),
+            Some("Fix core: Crash on empty input\nReported in #42")
+        );
+        assert_eq!(task.tests, Some(vec!["test_core_regression".to_string()]));
+        assert_eq!(task.tags, vec!["bug", "core"]);
+        assert_eq!(task.priority, Some(Priority::High));
+
+        // --var overrides the template's default
+        let vars = BTreeMap::from([
+            ("issue".to_string(), "#7".to_string()),
+            ("component".to_string(), "cli".to_string()),
+        ]);
+        let task = template.instantiate("6.4", "Bad flag", &vars).unwrap();
+        assert_eq!(task.tags, vec!["bug", "cli"]);
+
+        let err = template
+            .instantiate("6.5", "No issue", &BTreeMap::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("needs a value for: issue"));
+    }
+}
@@ -0,0 +1,220 @@
+use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::audit;
+use crate::common::check_rotd_initialized;
+use crate::fs_ops::{latest_tasks, safe_update_task};
+use crate::schema::{Priority, TASK_SCHEMA, TaskEntry, TaskStatus};
+
+/// A TODO or FIXME comment found in the source tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoComment {
+    pub file: PathBuf,
+    pub line: usize,
+    /// `TODO` or `FIXME`
+    pub kind: String,
+    pub text: String,
+}
+
+impl TodoComment {
+    /// Stable across rescans: derived from the file, marker, and comment text
+    /// rather than the line number, so moving a comment doesn't duplicate it
+    pub fn task_id(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.file.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(self.kind.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(self.text.as_bytes());
+        let digest = format!("{:x}", hasher.finalize());
+        format!("todo-{}", &digest[..8])
+    }
+
+    pub fn to_task(&self) -> TaskEntry {
+        let location = format!("{}:{}", self.file.display(), self.line);
+        let title = if self.text.is_empty() {
+            format!("{} in {}", self.kind, location)
+        } else {
+            self.text.clone()
+        };
+        let now = Utc::now();
+
+        TaskEntry {
+            id: self.task_id(),
+            title,
+            status: TaskStatus::Pending,
+            tests: None,
+            description: Some(format!("{} comment at {}", self.kind, location)),
+            summary_file: None,
+            origin: Some(location),
+            phase: None,
+            depends_on: None,
+            priority: Some(if self.kind == "FIXME" {
+                Priority::High
+            } else {
+                Priority::Medium
+            }),
+            priority_score: None,
+            created: Some(now),
+            updated_at: Some(now),
+            completed: None,
+            pr_url: None,
+            assignee: None,
+            tags: Vec::new(),
+            schema: TASK_SCHEMA.to_string(),
+        }
+    }
+}
+
+fn comment_regex() -> Regex {
+    // A marker after a line or block comment opener (`//`, `#`, `--`, `/*`,
+    // or a leading `*`), with an optional `(owner)` and colon
+    Regex::new(r"(?://+|#+|--|/\*+|^\s*\*)\s*(TODO|FIXME)\b(?:\([^)]*\))?:?\s*(.*)$").unwrap()
+}
+
+/// TODO/FIXME comments in one file's content
+pub fn scan_content(file: &Path, content: &str) -> Vec<TodoComment> {
+    let regex = comment_regex();
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line_num, line)| {
+            let captures = regex.captures(line)?;
+            let text = captures[2].trim().trim_end_matches("*/").trim_end();
+            Some(TodoComment {
+                file: file.to_path_buf(),
+                line: line_num + 1,
+                kind: captures[1].to_string(),
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// TODO/FIXME comments in the files covered by stub detection
+pub fn scan() -> Result<Vec<TodoComment>> {
+    let config = crate::history::load_config()?;
+    let mut found = Vec::new();
+    for path in crate::pss::source_files(&config) {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            found.extend(scan_content(&path, &content));
+        }
+    }
+    Ok(found)
+}
+
+/// Add a pending task for each comment not seen by an earlier scan.
+/// Returns the new tasks and the IDs of comments that already had one.
+pub fn import(comments: &[TodoComment], dry_run: bool) -> Result<(Vec<TaskEntry>, Vec<String>)> {
+    let mut known: Vec<String> = latest_tasks()?.into_iter().map(|t| t.id).collect();
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+
+    for comment in comments {
+        let task = comment.to_task();
+        if known.contains(&task.id) {
+            skipped.push(task.id);
+            continue;
+        }
+        if !dry_run {
+            safe_update_task(&task, false)?;
+        }
+        known.push(task.id.clone());
+        created.push(task);
+    }
+
+    Ok((created, skipped))
+}
+
+/// `rotd scan-todos`, also run by `rotd init --scan-todos`
+pub fn cmd_scan(is_agent_mode: bool, dry_run: bool) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let comments = scan()?;
+    let (created, skipped) = import(&comments, dry_run)?;
+
+    if !dry_run && !created.is_empty() {
+        audit::log_info(
+            None,
+            "SCAN_TODOS",
+            &format!("Created {} task(s) from TODO/FIXME comments", created.len()),
+        )?;
+    }
+
+    if is_agent_mode {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "success",
+                "action": "scan_todos",
+                "dry_run": dry_run,
+                "found": comments.len(),
+                "created": created.iter().map(|t| &t.id).collect::<Vec<_>>(),
+                "skipped": skipped,
+            })
+        );
+    } else {
+        if dry_run {
+            println!(
+                "{}",
+                "DRY RUN MODE - No changes will be made".yellow().bold()
+            );
+        }
+        println!("Found {} TODO/FIXME comment(s)", comments.len());
+        for task in &created {
+            println!(
+                "  {} {} {} ({})",
+                "+".green(),
+                task.id.bold(),
+                task.title,
+                task.origin.as_deref().unwrap_or_default()
+            );
+        }
+        if !skipped.is_empty() {
+            println!(
+                "  {} {} already tracked",
+                "•".yellow(),
+                skipped.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_content() {
+        let content = "fn main() {\n    // TODO: handle errors\n    let todo_list = 1; # not a TODO marker\n    /* FIXME(ana): off by one */\n    # TODO\n}\n";
+        let found = scan_content(Path::new("src/main.rs"), content);
+
+        let summary: Vec<(usize, &str, &str)> = found
+            .iter()
+            .map(|c| (c.line, c.kind.as_str(), c.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, "TODO", "handle errors"),
+                (4, "FIXME", "off by one"),
+                (5, "TODO", ""),
+            ]
+        );
+
+        // Moving a comment keeps its task ID
+        let moved = TodoComment {
+            line: 40,
+            ..found[0].clone()
+        };
+        assert_eq!(moved.task_id(), found[0].task_id());
+        assert_eq!(found[2].to_task().title, "TODO in src/main.rs:5");
+        assert_eq!(found[1].to_task().priority, Some(Priority::High));
+    }
+}
@@ -0,0 +1,630 @@
+use anyhow::Result;
+use colored::Colorize;
+use console::{Alignment, Key, Term, measure_text_width, pad_str, truncate_str};
+use std::io::IsTerminal;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::audit;
+use crate::common::check_rotd_initialized;
+use crate::coord::{self, WorkRegistry, WorkRegistryTask};
+use crate::fs_ops::{latest_tasks, read_json};
+use crate::history::{describe_event, get_agent_id, read_task_history};
+use crate::schema::{AuditEntry, CoverageHistory, TaskEntry, TaskHistoryEvent};
+
+/// Audit entries loaded for the audit pane
+const AUDIT_ENTRIES: usize = 50;
+
+/// Characters of the coverage sparkline, lowest first
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+const KEY_HELP: &str =
+    "↑↓/jk move  Tab switch pane  Enter details  c claim  r release  a approve  Space refresh  q quit";
+
+/// Project data the dashboard shows, read fresh on every refresh
+#[derive(Default)]
+pub struct Snapshot {
+    pub tasks: Vec<TaskEntry>,
+    pub registry: Vec<WorkRegistryTask>,
+    /// Newest first
+    pub audit: Vec<AuditEntry>,
+    pub coverage: Option<CoverageHistory>,
+}
+
+impl Snapshot {
+    /// Read the project's artifacts; anything missing or unreadable shows
+    /// as an empty pane
+    pub fn load() -> Self {
+        let registry_path = crate::common::coordination_path().join("active_work_registry.json");
+        let mut audit = audit::read_entries().unwrap_or_default();
+        audit.truncate(AUDIT_ENTRIES);
+        Self {
+            tasks: latest_tasks().unwrap_or_default(),
+            registry: read_json::<WorkRegistry>(&registry_path)
+                .map(|r| r.tasks)
+                .unwrap_or_default(),
+            audit,
+            coverage: read_json(&crate::common::coverage_history_path()).ok(),
+        }
+    }
+}
+
+/// The list pane keys move through
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pane {
+    Tasks,
+    Registry,
+}
+
+/// What the event loop does after a key press
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    Open(String),
+    Claim(String),
+    Release(String),
+    Approve(String),
+}
+
+pub struct Dashboard {
+    pub snapshot: Snapshot,
+    pub focus: Pane,
+    /// Selected row in the tasks and registry panes
+    pub selected: [usize; 2],
+    /// Task being drilled into, with its history
+    pub detail: Option<(String, Vec<TaskHistoryEvent>)>,
+    /// Outcome of the last action, shown above the key help
+    pub message: Option<String>,
+}
+
+impl Dashboard {
+    pub fn new(snapshot: Snapshot) -> Self {
+        Self {
+            snapshot,
+            focus: Pane::Tasks,
+            selected: [0, 0],
+            detail: None,
+            message: None,
+        }
+    }
+
+    fn pane_len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Tasks => self.snapshot.tasks.len(),
+            Pane::Registry => self.snapshot.registry.len(),
+        }
+    }
+
+    /// Swap in fresh data, keeping selections in range
+    pub fn refresh(&mut self, snapshot: Snapshot) {
+        self.snapshot = snapshot;
+        for pane in [Pane::Tasks, Pane::Registry] {
+            let len = self.pane_len(pane);
+            let selected = &mut self.selected[pane as usize];
+            *selected = (*selected).min(len.saturating_sub(1));
+        }
+    }
+
+    /// ID of the task selected in the focused pane
+    pub fn selected_id(&self) -> Option<&str> {
+        let i = self.selected[self.focus as usize];
+        match self.focus {
+            Pane::Tasks => self.snapshot.tasks.get(i).map(|t| t.id.as_str()),
+            Pane::Registry => self.snapshot.registry.get(i).map(|t| t.id.as_str()),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> Action {
+        if self.detail.is_some() {
+            return match key {
+                Key::Char('q') | Key::CtrlC => Action::Quit,
+                Key::Escape | Key::Enter | Key::Backspace => {
+                    self.detail = None;
+                    Action::None
+                }
+                _ => Action::None,
+            };
+        }
+
+        let selected_id = self.selected_id().map(str::to_string);
+        let len = self.pane_len(self.focus);
+        let selected = &mut self.selected[self.focus as usize];
+        match key {
+            Key::Char('q') | Key::Escape | Key::CtrlC => Action::Quit,
+            Key::Char(' ') => Action::Refresh,
+            Key::Tab | Key::BackTab => {
+                self.focus = match self.focus {
+                    Pane::Tasks => Pane::Registry,
+                    Pane::Registry => Pane::Tasks,
+                };
+                Action::None
+            }
+            Key::ArrowUp | Key::Char('k') => {
+                *selected = selected.saturating_sub(1);
+                Action::None
+            }
+            Key::ArrowDown | Key::Char('j') => {
+                *selected = (*selected + 1).min(len.saturating_sub(1));
+                Action::None
+            }
+            Key::Enter => selected_id.map_or(Action::None, Action::Open),
+            Key::Char('c') => selected_id.map_or(Action::None, Action::Claim),
+            Key::Char('r') => selected_id.map_or(Action::None, Action::Release),
+            Key::Char('a') => selected_id.map_or(Action::None, Action::Approve),
+            _ => Action::None,
+        }
+    }
+
+    /// Screen contents for a terminal of `width` x `height`
+    pub fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let body = height.saturating_sub(2);
+        let mut lines = match &self.detail {
+            Some((id, history)) => boxed(
+                &format!("Task {}", id),
+                &self.detail_lines(id, history),
+                None,
+                false,
+                width,
+                body,
+            ),
+            None => self.render_panes(width, body),
+        };
+
+        let message = self.message.as_deref().unwrap_or("");
+        lines.push(pad_str(message, width, Alignment::Left, Some("…")).to_string());
+        let help = match self.detail {
+            Some(_) => "Esc back  q quit",
+            None => KEY_HELP,
+        };
+        lines.push(pad_str(help, width, Alignment::Left, Some("…")).dimmed().to_string());
+        lines
+    }
+
+    fn render_panes(&self, width: usize, height: usize) -> Vec<String> {
+        let left = width / 2;
+        let right = width - left;
+        let top = height * 3 / 5;
+        let bottom = height - top;
+
+        let tasks: Vec<String> = self
+            .snapshot
+            .tasks
+            .iter()
+            .map(|t| format!("{:<8} {:<11} {}", t.id, t.status.as_str(), t.title))
+            .collect();
+        let registry: Vec<String> = self
+            .snapshot
+            .registry
+            .iter()
+            .map(|t| {
+                format!(
+                    "{:<8} {:<9} {:<12} {}",
+                    t.id,
+                    t.status.as_str(),
+                    t.claimed_by.as_deref().unwrap_or("-"),
+                    t.title
+                )
+            })
+            .collect();
+        let audit: Vec<String> = self
+            .snapshot
+            .audit
+            .iter()
+            .map(|e| {
+                format!(
+                    "{} {:<8} {} {}: {}",
+                    e.timestamp.format("%m-%d %H:%M"),
+                    e.severity.to_uppercase(),
+                    e.rule,
+                    e.task_id.as_deref().unwrap_or("GLOBAL"),
+                    e.message
+                )
+            })
+            .collect();
+
+        let selection = |pane: Pane| Some(self.selected[pane as usize]);
+        let mut lines = side_by_side(vec![
+            boxed(
+                &format!("Tasks ({})", tasks.len()),
+                &tasks,
+                selection(Pane::Tasks),
+                self.focus == Pane::Tasks,
+                left,
+                top,
+            ),
+            boxed(
+                &format!("Coordination ({})", registry.len()),
+                &registry,
+                selection(Pane::Registry),
+                self.focus == Pane::Registry,
+                right,
+                top,
+            ),
+        ]);
+        lines.extend(side_by_side(vec![
+            boxed("Recent Audit", &audit, None, false, left, bottom),
+            boxed(
+                "Coverage",
+                &coverage_lines(self.snapshot.coverage.as_ref(), right.saturating_sub(2)),
+                None,
+                false,
+                right,
+                bottom,
+            ),
+        ]));
+        lines
+    }
+
+    fn detail_lines(&self, id: &str, history: &[TaskHistoryEvent]) -> Vec<String> {
+        let mut lines = Vec::new();
+        match self.snapshot.tasks.iter().find(|t| t.id == id) {
+            Some(task) => {
+                lines.push(format!("Title:    {}", task.title));
+                lines.push(format!("Status:   {}", task.status.as_str()));
+                if let Some(priority) = &task.priority {
+                    lines.push(format!("Priority: {}", priority.as_str()));
+                }
+                if let Some(phase) = &task.phase {
+                    lines.push(format!("Phase:    {}", phase));
+                }
+                if let Some(deps) = task.depends_on.as_ref().filter(|d| !d.is_empty()) {
+                    lines.push(format!("Depends:  {}", deps.join(", ")));
+                }
+                if let Some(description) = &task.description {
+                    lines.push(String::new());
+                    lines.extend(description.lines().map(str::to_string));
+                }
+            }
+            None => lines.push("Not in tasks.jsonl".to_string()),
+        }
+
+        if let Some(entry) = self.snapshot.registry.iter().find(|t| t.id == id) {
+            lines.push(String::new());
+            lines.push(format!(
+                "Registry: {} (claimed by {})",
+                entry.status.as_str(),
+                entry.claimed_by.as_deref().unwrap_or("nobody")
+            ));
+            if let Some(reason) = &entry.blocked_reason {
+                lines.push(format!("Blocked:  {}", reason));
+            }
+        }
+
+        lines.push(String::new());
+        lines.push("History:".to_string());
+        if history.is_empty() {
+            lines.push("  No history recorded".to_string());
+        }
+        for event in history.iter().rev() {
+            lines.push(format!(
+                "  {} {}",
+                event.timestamp.format("%Y-%m-%d %H:%M"),
+                describe_event(event)
+            ));
+        }
+        lines
+    }
+}
+
+/// Sparkline of coverage values, scaled from 0 to 100%
+pub fn sparkline(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(|v| {
+            let level = (v.clamp(0.0, 100.0) / 100.0 * (SPARKS.len() - 1) as f64).round();
+            SPARKS[level as usize]
+        })
+        .collect()
+}
+
+fn coverage_lines(coverage: Option<&CoverageHistory>, width: usize) -> Vec<String> {
+    let Some(coverage) = coverage else {
+        return vec!["No coverage history recorded".to_string()];
+    };
+    let values: Vec<f64> = coverage.history.iter().map(|e| e.coverage).collect();
+    let mut lines = vec![format!(
+        "Floor {:.1}%  Latest {}",
+        coverage.floor,
+        values
+            .last()
+            .map_or("-".to_string(), |v| format!("{:.1}%", v))
+    )];
+    // The most recent entries that fit
+    lines.push(sparkline(&values[values.len().saturating_sub(width)..]));
+    lines.extend(coverage.history.iter().rev().map(|e| {
+        format!(
+            "{} {:>5.1}% {}{}",
+            e.timestamp.format("%Y-%m-%d"),
+            e.coverage,
+            e.task_id,
+            if e.triggered_ratchet { " ratchet" } else { "" }
+        )
+    }));
+    lines
+}
+
+/// `lines` in a titled box of `width` x `height`, scrolled so the selected
+/// row stays visible; the selection is highlighted when `focused`
+pub fn boxed(
+    title: &str,
+    lines: &[String],
+    selected: Option<usize>,
+    focused: bool,
+    width: usize,
+    height: usize,
+) -> Vec<String> {
+    if width < 2 || height < 2 {
+        return vec![" ".repeat(width); height];
+    }
+    let inner_width = width - 2;
+    let inner_height = height - 2;
+
+    let title = truncate_str(&format!(" {} ", title), inner_width, "…").to_string();
+    let rule = "─".repeat(inner_width - measure_text_width(&title));
+    let title = if focused {
+        title.bold().to_string()
+    } else {
+        title
+    };
+    let mut out = vec![format!("┌{}{}┐", title, rule)];
+
+    let offset = selected.map_or(0, |s| (s + 1).saturating_sub(inner_height));
+    for row in 0..inner_height {
+        let i = offset + row;
+        let text = lines.get(i).map(String::as_str).unwrap_or("");
+        let cell = pad_str(text, inner_width, Alignment::Left, Some("…")).to_string();
+        let cell = match selected {
+            Some(s) if s == i && i < lines.len() && focused => cell.reversed().to_string(),
+            Some(s) if s == i && i < lines.len() => cell.bold().to_string(),
+            _ => cell,
+        };
+        out.push(format!("│{}│", cell));
+    }
+    out.push(format!("└{}┘", "─".repeat(inner_width)));
+    out
+}
+
+/// Boxes of equal height joined row by row, left to right
+pub fn side_by_side(columns: Vec<Vec<String>>) -> Vec<String> {
+    let height = columns.iter().map(Vec::len).min().unwrap_or(0);
+    (0..height)
+        .map(|row| columns.iter().map(|c| c[row].as_str()).collect())
+        .collect()
+}
+
+fn apply(action: &Action, agent_id: &str) -> Result<String> {
+    match action {
+        Action::Claim(id) => {
+            let claimed = match crate::coord_server::client()? {
+                Some(client) => client.claim_task(id, agent_id)?,
+                None => coord::claim_task(id, agent_id)?,
+            };
+            match claimed {
+                Some(_) => Ok(format!("Claimed task {} as {}", id, agent_id)),
+                None => Ok(format!("Task {} is not in the coordination registry", id)),
+            }
+        }
+        Action::Release(id) => {
+            match crate::coord_server::client()? {
+                Some(client) => client.release(id, agent_id)?,
+                None => coord::release_task(id, agent_id)?,
+            }
+            Ok(format!("Released task {}", id))
+        }
+        Action::Approve(id) => {
+            coord::approve_task(id, agent_id)?;
+            Ok(format!("Approved task {}", id))
+        }
+        _ => Ok(String::new()),
+    }
+}
+
+fn draw(term: &Term, dashboard: &Dashboard) -> Result<()> {
+    let (rows, cols) = term.size();
+    let lines = dashboard.render(cols as usize, rows as usize);
+    term.move_cursor_to(0, 0)?;
+    // Raw mode may be on while the key reader waits, so return explicitly
+    term.write_str(&lines.join("\r\n"))?;
+    Ok(())
+}
+
+/// Terminal mode saved when the dashboard starts, so it can be restored even
+/// while the key reader has the terminal in raw mode
+#[cfg(unix)]
+#[derive(Clone, Copy)]
+struct SavedMode(libc::termios);
+
+#[cfg(unix)]
+impl SavedMode {
+    fn save() -> Option<Self> {
+        let tty = std::fs::File::open("/dev/tty").ok()?;
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+        let rc = unsafe { libc::tcgetattr(std::os::fd::AsRawFd::as_raw_fd(&tty), &mut termios) };
+        (rc == 0).then_some(SavedMode(termios))
+    }
+
+    fn restore(&self) {
+        if let Ok(tty) = std::fs::File::open("/dev/tty") {
+            unsafe {
+                libc::tcsetattr(std::os::fd::AsRawFd::as_raw_fd(&tty), libc::TCSANOW, &self.0);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+#[derive(Clone, Copy)]
+struct SavedMode;
+
+#[cfg(not(unix))]
+impl SavedMode {
+    fn save() -> Option<Self> {
+        None
+    }
+
+    fn restore(&self) {}
+}
+
+/// Puts the terminal back the way `run` found it on every exit, including
+/// errors and panics (release builds abort, so a panic hook covers those)
+struct TerminalGuard {
+    term: Term,
+    mode: Option<SavedMode>,
+}
+
+impl TerminalGuard {
+    fn new(term: Term) -> Self {
+        let mode = SavedMode::save();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if let Some(mode) = mode {
+                mode.restore();
+            }
+            let _ = Term::stdout().show_cursor();
+            previous(info);
+        }));
+        TerminalGuard { term, mode }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = self.term.clear_screen();
+        let _ = self.term.show_cursor();
+        if let Some(mode) = &self.mode {
+            mode.restore();
+        }
+    }
+}
+
+/// Entry point for `rotd tui`: redraws every `interval` seconds and on each
+/// key press until quit
+pub fn run(interval: u64, is_agent_mode: bool) -> Result<()> {
+    check_rotd_initialized()?;
+    if is_agent_mode || !std::io::stdout().is_terminal() {
+        return Err(anyhow::anyhow!(
+            "rotd tui needs an interactive terminal; use 'rotd coord ls' or 'rotd report' instead"
+        ));
+    }
+
+    let agent_id = get_agent_id();
+    let term = Term::stdout();
+    let (keys, key_presses) = mpsc::channel();
+    let (resume, resumed) = mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        let term = Term::stdout();
+        // Wait for each key to be handled before reading the next, so no
+        // read is left pending in raw mode after quitting
+        while let Ok(key) = term.read_key() {
+            if keys.send(key).is_err() || resumed.recv().is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut dashboard = Dashboard::new(Snapshot::load());
+    let _guard = TerminalGuard::new(term.clone());
+    term.hide_cursor()?;
+    term.clear_screen()?;
+    (|| -> Result<()> {
+        loop {
+            draw(&term, &dashboard)?;
+            let key = match key_presses.recv_timeout(Duration::from_secs(interval.max(1))) {
+                Ok(key) => key,
+                Err(RecvTimeoutError::Timeout) => {
+                    dashboard.refresh(Snapshot::load());
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            };
+
+            match dashboard.handle_key(key) {
+                Action::Quit => return Ok(()),
+                Action::None => {}
+                Action::Refresh => dashboard.message = Some("Refreshed".to_string()),
+                Action::Open(id) => {
+                    let history = read_task_history(&id).unwrap_or_default();
+                    dashboard.detail = Some((id, history));
+                }
+                action => {
+                    dashboard.message = Some(match apply(&action, &agent_id) {
+                        Ok(message) => message,
+                        Err(e) => format!("Error: {}", e),
+                    });
+                }
+            }
+            dashboard.refresh(Snapshot::load());
+            term.clear_screen()?;
+            let _ = resume.send(());
+        }
+    })()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Dashboard {
+        let task = |id: &str, status: &str| -> TaskEntry {
+            serde_json::from_value(json!({"id": id, "title": format!("Task {}", id), "status": status}))
+                .unwrap()
+        };
+        let registry: WorkRegistry = serde_json::from_value(json!({"tasks": [{
+            "id": "1.2", "title": "Task 1.2", "status": "review", "priority": "high",
+            "claimed_by": "agent-a", "claimed_at": null, "completed_at": null,
+            "blocked_reason": null, "reviewer_id": null, "capability": null, "skill_level": null
+        }]}))
+        .unwrap();
+        Dashboard::new(Snapshot {
+            tasks: vec![task("1.1", "complete"), task("1.2", "in_progress")],
+            registry: registry.tasks,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_keys_and_actions() {
+        let mut dashboard = sample();
+        assert_eq!(dashboard.selected_id(), Some("1.1"));
+        dashboard.handle_key(Key::ArrowDown);
+        dashboard.handle_key(Key::Char('j'));
+        assert_eq!(dashboard.selected_id(), Some("1.2"));
+        assert_eq!(dashboard.handle_key(Key::Char('c')), Action::Claim("1.2".to_string()));
+
+        dashboard.handle_key(Key::Tab);
+        assert_eq!(dashboard.focus, Pane::Registry);
+        assert_eq!(dashboard.handle_key(Key::Char('a')), Action::Approve("1.2".to_string()));
+        assert_eq!(dashboard.handle_key(Key::Enter), Action::Open("1.2".to_string()));
+
+        dashboard.detail = Some(("1.2".to_string(), Vec::new()));
+        // Keys other than back and quit do nothing in the detail view
+        assert_eq!(dashboard.handle_key(Key::Char('c')), Action::None);
+        assert_eq!(dashboard.handle_key(Key::Escape), Action::None);
+        assert!(dashboard.detail.is_none());
+        assert_eq!(dashboard.handle_key(Key::Char('q')), Action::Quit);
+
+        // A refresh with fewer tasks pulls the selection back into range
+        dashboard.focus = Pane::Tasks;
+        dashboard.refresh(Snapshot::default());
+        assert_eq!(dashboard.selected, [0, 0]);
+        assert_eq!(dashboard.selected_id(), None);
+    }
+
+    #[test]
+    fn test_render() {
+        colored::control::set_override(false);
+        let dashboard = sample();
+        let lines = dashboard.render(80, 20);
+        assert_eq!(lines.len(), 20);
+        assert!(lines.iter().all(|l| measure_text_width(l) == 80));
+        assert!(lines[0].starts_with("┌ Tasks (2) ─"));
+        assert!(lines[0].contains("┌ Coordination (1) ─"));
+        assert!(lines[1].contains("1.1      complete    Task 1.1"));
+        assert!(lines[1].contains("1.2      review    agent-a"));
+        assert!(lines.iter().any(|l| l.contains("No coverage history recorded")));
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+    }
+}
@@ -0,0 +1,856 @@
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::coord::{DependencyMap, LockMetadata, QuotaTracker, WorkRegistry, WorkStatus};
+use crate::output::{Finding, Level, Outcome, parse_jsonl_lines};
+use crate::schema::{
+    CoverageHistory, LessonLearned, PSSScore, ProjectPrimer, SessionState, TaskEntry, TestSummary,
+    ValidationResult, Versioned,
+};
+
+/// Values accepted by `validate --schema`
+pub const SCHEMA_TYPES: [&str; 8] = [
+    "tasks",
+    "lessons",
+    "pss_scores",
+    "test_summaries",
+    "session_state",
+    "coverage_history",
+    "primer",
+    "coordination",
+];
+
+/// Findings for one artifact plus how many records were examined
+#[derive(Debug, Clone)]
+pub struct ArtifactReport {
+    pub outcome: Outcome,
+    pub items_checked: u32,
+}
+
+impl ArtifactReport {
+    fn new(id: &'static str, findings: Vec<Finding>, items_checked: usize) -> Self {
+        Self {
+            outcome: Outcome { id, findings },
+            items_checked: items_checked as u32,
+        }
+    }
+
+    /// The legacy `ValidationResult` shape used by `rotd --agent validate`
+    pub fn to_result(&self) -> ValidationResult {
+        let describe = |f: &Finding| match f.line {
+            Some(line) => format!("Line {}: {}", line, f.message),
+            None => f.message.clone(),
+        };
+        let errors: Vec<String> = self.outcome.errors().map(describe).collect();
+        let warnings: Vec<String> = self.outcome.warnings().map(describe).collect();
+        ValidationResult {
+            status: if errors.is_empty() {
+                "passed"
+            } else {
+                "failed"
+            }
+            .to_string(),
+            errors,
+            warnings,
+            items_checked: self.items_checked,
+        }
+    }
+}
+
+/// Schemas to run: all of them unless one was named
+pub fn select(all: bool, schema_type: Option<&str>) -> Result<Vec<&'static str>> {
+    match schema_type {
+        Some(name) if !all => SCHEMA_TYPES
+            .iter()
+            .find(|s| **s == name)
+            .map(|s| vec![*s])
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown schema type: {}. Expected one of: {}",
+                    name,
+                    SCHEMA_TYPES.join(", ")
+                )
+            }),
+        _ => Ok(SCHEMA_TYPES.to_vec()),
+    }
+}
+
+/// Validate one artifact by schema name (see `SCHEMA_TYPES`). Strict mode
+/// also applies any JSON Schema overlay for it; strict parsing reports
+/// fields the artifact type does not define.
+pub fn validate_schema(name: &str, strict: bool, strict_parse: bool) -> ArtifactReport {
+    let mut report = match name {
+        "tasks" => validate_tasks(strict),
+        "lessons" => validate_lessons(strict),
+        "pss_scores" => validate_pss_scores(strict),
+        "test_summaries" => validate_test_summaries(strict),
+        "session_state" => validate_session_state(strict),
+        "coverage_history" => validate_coverage_history(strict),
+        "primer" => validate_primer(strict),
+        "coordination" => validate_coordination(strict),
+        other => {
+            return ArtifactReport::new(
+                "schema",
+                vec![Finding::new(
+                    "unknown_schema",
+                    format!("Unknown schema type: {}", other),
+                )],
+                0,
+            );
+        }
+    };
+    report
+        .outcome
+        .findings
+        .extend(crate::signing::signature_findings(name, strict));
+    // Team-specific rules layered on top of the built-in ones
+    if strict {
+        report
+            .outcome
+            .findings
+            .extend(crate::json_schema::overlay_findings(name));
+    }
+    if strict_parse {
+        report
+            .outcome
+            .findings
+            .extend(crate::json_schema::unknown_field_findings(name));
+    }
+    report
+}
+
+/// Run the selected validations
+pub fn run(
+    all: bool,
+    schema_type: Option<&str>,
+    strict: bool,
+    strict_parse: bool,
+) -> Result<Vec<ArtifactReport>> {
+    Ok(select(all, schema_type)?
+        .into_iter()
+        .map(|name| validate_schema(name, strict, strict_parse))
+        .collect())
+}
+
+fn task_ids() -> HashSet<String> {
+    let (tasks, _) = parse_jsonl_lines::<TaskEntry>(&crate::common::tasks_path());
+    tasks.into_iter().map(|(_, t)| t.id).collect()
+}
+
+fn in_percent_range(value: f64) -> bool {
+    (0.0..=100.0).contains(&value)
+}
+
+/// A record stamped with a `_schema` this binary does not understand is a
+/// warning, or an error in strict mode
+fn schema_finding<T: Versioned>(record: &T, strict: bool, context: &str) -> Option<Finding> {
+    let issue = record.schema_issue()?;
+    let mut finding = Finding::new("schema_version", format!("{}{}", context, issue));
+    if !strict {
+        finding.level = Level::Warning;
+    }
+    Some(finding)
+}
+
+/// Read and deserialize a JSON (or JSONC) file; `None` if it does not exist
+fn read_document<T>(path: &Path, jsonc: bool) -> Option<Result<T, Finding>>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    let content = std::fs::read_to_string(path).ok()?;
+    let content = if jsonc {
+        let content = crate::history::remove_jsonc_comments(&content);
+        crate::history::remove_trailing_commas(&content)
+    } else {
+        content
+    };
+    Some(serde_json::from_str(&content).map_err(|e| {
+        Finding::new("invalid_json", format!("Invalid JSON: {}", e)).at(path, Some(e.line()))
+    }))
+}
+
+fn validate_tasks(strict: bool) -> ArtifactReport {
+    let path = crate::common::tasks_path();
+    let (tasks, mut findings) = parse_jsonl_lines::<TaskEntry>(&path);
+    let checked = tasks.len() + findings.len();
+
+    for (line, task) in &tasks {
+        let context = format!("Task {}: ", task.id);
+        findings.extend(schema_finding(task, strict, &context).map(|f| f.at(&path, Some(*line))));
+        for issue in crate::agent::task_issues(task, strict) {
+            findings.push(
+                Finding::new("invalid_task", format!("Task {}: {}", task.id, issue))
+                    .at(&path, Some(*line)),
+            );
+        }
+    }
+
+    // Every status change in the log must follow the transition rules,
+    // unless the audit log shows it was forced
+    if strict {
+        let overrides = crate::history::load_config()
+            .map(|c| c.status_transitions)
+            .unwrap_or_default();
+        let forced: HashSet<(String, String)> = crate::audit::read_entries()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|e| e.rule == crate::task::TRANSITION_OVERRIDE_RULE)
+            .filter_map(|e| Some((e.task_id?, e.message)))
+            .collect();
+        for (line, prev, task) in illegal_transitions(&tasks, &overrides) {
+            let message =
+                crate::task::forced_transition_message(&prev.status, &task.status);
+            let was_forced = forced.contains(&(task.id.clone(), message));
+            let mut finding = Finding::new(
+                "illegal_transition",
+                format!(
+                    "Task {}: status {} -> {} is not an allowed transition{}",
+                    task.id,
+                    prev.status.as_str(),
+                    task.status.as_str(),
+                    if was_forced { " (forced; see audit log)" } else { "" }
+                ),
+            )
+            .at(&path, Some(line));
+            if was_forced {
+                finding.level = Level::Warning;
+            }
+            findings.push(finding);
+        }
+    }
+
+    ArtifactReport::new("tasks", findings, checked)
+}
+
+/// Status changes in the task log, in order, that `allowed_transitions`
+/// forbids: the line of the change with the entries before and after it
+fn illegal_transitions<'a>(
+    tasks: &'a [(usize, TaskEntry)],
+    overrides: &HashMap<String, Vec<String>>,
+) -> Vec<(usize, &'a TaskEntry, &'a TaskEntry)> {
+    let mut latest: HashMap<&str, &TaskEntry> = HashMap::new();
+    let mut illegal = Vec::new();
+    for (line, task) in tasks {
+        if let Some(prev) = latest.insert(task.id.as_str(), task) {
+            if prev.status != task.status
+                && !crate::task::allowed_transitions(&prev.status, overrides)
+                    .iter()
+                    .any(|s| s == task.status.as_str())
+            {
+                illegal.push((*line, prev, task));
+            }
+        }
+    }
+    illegal
+}
+
+fn validate_lessons(strict: bool) -> ArtifactReport {
+    let path = crate::common::lessons_path();
+    let (lessons, mut findings) = parse_jsonl_lines::<LessonLearned>(&path);
+    let checked = lessons.len() + findings.len();
+
+    for (line, lesson) in &lessons {
+        let context = format!("Lesson {}: ", lesson.id);
+        findings.extend(schema_finding(lesson, strict, &context).map(|f| f.at(&path, Some(*line))));
+        let mut issues = Vec::new();
+        if let Err(e) = lesson.validate() {
+            issues.push(e.to_string());
+        }
+        if lesson.trigger.is_empty() {
+            issues.push("Trigger must list at least one symptom".to_string());
+        }
+        if strict && lesson.tags.is_empty() {
+            issues.push("Missing tags".to_string());
+        }
+        if strict && lesson.timestamp.is_none() {
+            issues.push("Missing timestamp".to_string());
+        }
+        for issue in issues {
+            findings.push(
+                Finding::new("invalid_lesson", format!("Lesson {}: {}", lesson.id, issue))
+                    .at(&path, Some(*line)),
+            );
+        }
+    }
+
+    ArtifactReport::new("lessons", findings, checked)
+}
+
+fn validate_pss_scores(strict: bool) -> ArtifactReport {
+    let path = crate::common::pss_scores_path();
+    let (scores, mut findings) = parse_jsonl_lines::<PSSScore>(&path);
+    let checked = scores.len() + findings.len();
+    let known_tasks = if strict { task_ids() } else { HashSet::new() };
+
+    for (line, score) in &scores {
+        let context = format!("Score for task {}: ", score.task_id);
+        findings.extend(schema_finding(score, strict, &context).map(|f| f.at(&path, Some(*line))));
+        let mut issues = Vec::new();
+        if score.task_id.is_empty() {
+            issues.push("Task ID cannot be empty".to_string());
+        }
+        if score.score > 10 {
+            issues.push(format!("Score must be between 0-10, got {}", score.score));
+        }
+        let total: u32 = score.criteria.values().map(|c| c.score).sum();
+        if !score.criteria.is_empty() && total != score.score {
+            issues.push(format!(
+                "Score {} does not match its criteria, which sum to {}",
+                score.score, total
+            ));
+        }
+        let mut over: Vec<&str> = score
+            .criteria
+            .iter()
+            .filter(|(_, c)| c.score > 1)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        over.sort();
+        for name in over {
+            issues.push(format!("Criterion {} must score 0 or 1", name));
+        }
+        if strict && !known_tasks.contains(&score.task_id) {
+            issues.push("Task is not in tasks.jsonl".to_string());
+        }
+        for issue in issues {
+            findings.push(
+                Finding::new(
+                    "invalid_pss_score",
+                    format!("Score for task {}: {}", score.task_id, issue),
+                )
+                .at(&path, Some(*line)),
+            );
+        }
+    }
+
+    ArtifactReport::new("pss_scores", findings, checked)
+}
+
+fn validate_test_summaries(strict: bool) -> ArtifactReport {
+    let dir = crate::common::test_summaries_path();
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    let known_tasks = if strict { task_ids() } else { HashSet::new() };
+    let mut findings = Vec::new();
+
+    for path in &paths {
+        let summary: TestSummary = match read_document(path, false) {
+            Some(Ok(summary)) => summary,
+            Some(Err(finding)) => {
+                findings.push(finding);
+                continue;
+            }
+            None => continue,
+        };
+
+        findings.extend(schema_finding(&summary, strict, "").map(|f| f.at(path, None)));
+        let mut issues = Vec::new();
+        if let Err(e) = summary.validate() {
+            issues.push(e.to_string());
+        }
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        if stem != summary.task_id {
+            issues.push(format!(
+                "File is named {}.json but is for task {}",
+                stem, summary.task_id
+            ));
+        }
+        if let Some(coverage) = summary.coverage {
+            if !in_percent_range(coverage) {
+                issues.push(format!("Coverage must be between 0-100, got {}", coverage));
+            }
+        }
+        if strict && !known_tasks.contains(&summary.task_id) {
+            issues.push("Task is not in tasks.jsonl".to_string());
+        }
+        for issue in issues {
+            findings.push(Finding::new("invalid_test_summary", issue).at(path, None));
+        }
+    }
+
+    ArtifactReport::new("test_summaries", findings, paths.len())
+}
+
+fn validate_session_state(strict: bool) -> ArtifactReport {
+    let path = crate::common::session_state_path();
+    let state: SessionState = match read_document(&path, false) {
+        Some(Ok(state)) => state,
+        Some(Err(finding)) => return ArtifactReport::new("session_state", vec![finding], 1),
+        None => return ArtifactReport::new("session_state", Vec::new(), 0),
+    };
+
+    let mut issues = Vec::new();
+    if state.session_id.is_empty() {
+        issues.push("Session ID cannot be empty".to_string());
+    }
+    if state.status.is_empty() {
+        issues.push("Status cannot be empty".to_string());
+    }
+    if let Some(task_id) = &state.current_task {
+        if strict && !task_ids().contains(task_id) {
+            issues.push(format!("Current task {} is not in tasks.jsonl", task_id));
+        }
+    }
+
+    let mut findings: Vec<Finding> = issues
+        .into_iter()
+        .map(|issue| Finding::new("invalid_session_state", issue).at(&path, None))
+        .collect();
+    findings.extend(schema_finding(&state, strict, "").map(|f| f.at(&path, None)));
+    ArtifactReport::new("session_state", findings, 1)
+}
+
+fn validate_coverage_history(strict: bool) -> ArtifactReport {
+    let path = crate::common::coverage_history_path();
+    let history: CoverageHistory = match read_document(&path, false) {
+        Some(Ok(history)) => history,
+        Some(Err(finding)) => return ArtifactReport::new("coverage_history", vec![finding], 1),
+        None => return ArtifactReport::new("coverage_history", Vec::new(), 0),
+    };
+
+    let mut issues = Vec::new();
+    if !in_percent_range(history.floor) {
+        issues.push(format!(
+            "Floor must be between 0-100, got {}",
+            history.floor
+        ));
+    }
+    if history.ratchet_threshold < 0.0 {
+        issues.push(format!(
+            "Ratchet threshold cannot be negative, got {}",
+            history.ratchet_threshold
+        ));
+    }
+    for (i, entry) in history.history.iter().enumerate() {
+        if !in_percent_range(entry.coverage) {
+            issues.push(format!(
+                "Entry {} (task {}): coverage must be between 0-100, got {}",
+                i + 1,
+                entry.task_id,
+                entry.coverage
+            ));
+        }
+        if strict && i > 0 && entry.timestamp < history.history[i - 1].timestamp {
+            issues.push(format!(
+                "Entry {} (task {}) is older than the entry before it",
+                i + 1,
+                entry.task_id
+            ));
+        }
+    }
+
+    let findings = issues
+        .into_iter()
+        .map(|issue| Finding::new("invalid_coverage_history", issue).at(&path, None))
+        .collect();
+    ArtifactReport::new("coverage_history", findings, 1 + history.history.len())
+}
+
+fn validate_primer(strict: bool) -> ArtifactReport {
+    let path = crate::common::primer_path();
+    let primer: ProjectPrimer = match read_document(&path, true) {
+        Some(Ok(primer)) => primer,
+        Some(Err(finding)) => return ArtifactReport::new("primer", vec![finding], 1),
+        None => return ArtifactReport::new("primer", Vec::new(), 0),
+    };
+
+    let mut issues = Vec::new();
+    if primer.name.is_empty() {
+        issues.push("Name cannot be empty".to_string());
+    }
+    if primer.scope.is_empty() {
+        issues.push("Scope cannot be empty".to_string());
+    }
+    if strict {
+        // Placeholders left by `rotd primer init` and `rotd update`
+        let value = serde_json::to_value(&primer).unwrap_or_default();
+        let mut placeholders: Vec<String> = value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(_, v)| v.to_string().contains("TODO"))
+            .map(|(k, _)| k.clone())
+            .collect();
+        placeholders.sort();
+        if !placeholders.is_empty() {
+            issues.push(format!(
+                "Placeholder values remain in: {}",
+                placeholders.join(", ")
+            ));
+        }
+    }
+
+    let findings = issues
+        .into_iter()
+        .map(|issue| Finding::new("invalid_primer", issue).at(&path, None))
+        .collect();
+    ArtifactReport::new("primer", findings, 1)
+}
+
+/// Dependency cycles, each reported once starting from its smallest task ID
+fn dependency_cycles(deps: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    fn visit(
+        id: &str,
+        deps: &HashMap<String, Vec<String>>,
+        stack: &mut Vec<String>,
+        done: &mut HashSet<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(start) = stack.iter().position(|s| s == id) {
+            let mut cycle = stack[start..].to_vec();
+            let min = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap_or(0);
+            cycle.rotate_left(min);
+            if !cycles.contains(&cycle) {
+                cycles.push(cycle);
+            }
+            return;
+        }
+        if done.contains(id) {
+            return;
+        }
+        stack.push(id.to_string());
+        for next in deps.get(id).into_iter().flatten() {
+            visit(next, deps, stack, done, cycles);
+        }
+        stack.pop();
+        done.insert(id.to_string());
+    }
+
+    let mut ids: Vec<&String> = deps.keys().collect();
+    ids.sort();
+    let mut cycles = Vec::new();
+    let mut done = HashSet::new();
+    for id in ids {
+        visit(id, deps, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+}
+
+/// Registry, dependency map, quota, and lock files under `coordination/`
+fn validate_coordination(strict: bool) -> ArtifactReport {
+    let dir = crate::common::coordination_path();
+    let mut findings = Vec::new();
+    let mut checked = 0;
+
+    // Registry
+    let registry_path = dir.join(crate::common::ACTIVE_WORK_REGISTRY_FILE);
+    let registry: Option<WorkRegistry> = match read_document(&registry_path, false) {
+        Some(Ok(registry)) => Some(registry),
+        Some(Err(finding)) => {
+            findings.push(finding);
+            None
+        }
+        None => None,
+    };
+    let mut registry_ids = HashSet::new();
+    let mut claims: HashMap<&str, &str> = HashMap::new();
+    if let Some(registry) = &registry {
+        checked += 1;
+        for task in &registry.tasks {
+            let mut issues = Vec::new();
+            if !registry_ids.insert(task.id.as_str()) {
+                issues.push("Listed more than once".to_string());
+            }
+            match (&task.status, &task.claimed_by) {
+                (WorkStatus::Claimed, None) => {
+                    issues.push("Claimed but has no claimed_by".to_string())
+                }
+                (WorkStatus::Unclaimed, Some(agent)) => {
+                    issues.push(format!("Unclaimed but claimed_by is {}", agent))
+                }
+                (WorkStatus::Claimed, Some(agent)) => {
+                    claims.insert(task.id.as_str(), agent.as_str());
+                }
+                _ => {}
+            }
+            if strict && task.status == WorkStatus::Done && task.completed_at.is_none() {
+                issues.push("Done but has no completed_at".to_string());
+            }
+            if strict && task.status == WorkStatus::Blocked && task.blocked_reason.is_none() {
+                issues.push("Blocked but has no blocked_reason".to_string());
+            }
+            for issue in issues {
+                findings.push(
+                    Finding::new("invalid_registry", format!("Task {}: {}", task.id, issue))
+                        .at(&registry_path, None),
+                );
+            }
+        }
+    }
+
+    // Dependency map
+    let deps_path = dir.join("dependency_map.json");
+    match read_document::<DependencyMap>(&deps_path, false) {
+        Some(Ok(map)) => {
+            checked += 1;
+            let mut ids: Vec<&String> = map.deps.keys().collect();
+            ids.sort();
+            for id in ids {
+                let mut issues = Vec::new();
+                if registry.is_some() && !registry_ids.contains(id.as_str()) {
+                    issues.push("Not in the active work registry".to_string());
+                }
+                for dep in &map.deps[id] {
+                    if dep == id {
+                        issues.push("Depends on itself".to_string());
+                    } else if registry.is_some() && !registry_ids.contains(dep.as_str()) {
+                        issues.push(format!("Depends on unknown task {}", dep));
+                    }
+                }
+                for issue in issues {
+                    findings.push(
+                        Finding::new("dangling_dependency", format!("Task {}: {}", id, issue))
+                            .at(&deps_path, None),
+                    );
+                }
+            }
+            for cycle in dependency_cycles(&map.deps) {
+                if cycle.len() > 1 {
+                    findings.push(
+                        Finding::new(
+                            "dependency_cycle",
+                            format!("Dependency cycle: {} -> {}", cycle.join(" -> "), cycle[0]),
+                        )
+                        .at(&deps_path, None),
+                    );
+                }
+            }
+        }
+        Some(Err(finding)) => findings.push(finding),
+        None => {}
+    }
+
+    // Quota
+    let quota_path = dir.join("quota.json");
+    match read_document::<QuotaTracker>(&quota_path, false) {
+        Some(Ok(quota)) => {
+            checked += 1;
+            if quota.last_reset > chrono::Utc::now() {
+                findings.push(
+                    Finding::new("invalid_quota", "last_reset is in the future")
+                        .at(&quota_path, None),
+                );
+            }
+            if quota.tokens_used > 0 && quota.requests == 0 {
+                findings.push(
+                    Finding::new(
+                        "invalid_quota",
+                        format!("{} tokens used across 0 requests", quota.tokens_used),
+                    )
+                    .at(&quota_path, None),
+                );
+            }
+        }
+        Some(Err(finding)) => findings.push(finding),
+        None => {}
+    }
+
+    // Task claim locks: agent_locks/<task_id>.<agent_id>.lock
+    let mut locked = HashSet::new();
+    let mut lock_files: Vec<_> = std::fs::read_dir(dir.join("agent_locks"))
+        .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+        .unwrap_or_default();
+    lock_files.sort();
+    for path in &lock_files {
+        checked += 1;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let stem = match name.strip_suffix(".lock") {
+            Some(stem) => stem,
+            None => {
+                findings.push(
+                    Finding::new("invalid_lock", format!("{} is not a .lock file", name))
+                        .at(path, None),
+                );
+                continue;
+            }
+        };
+        let metadata = match read_document::<LockMetadata>(path, false) {
+            Some(Ok(metadata)) => metadata,
+            Some(Err(_)) | None => {
+                findings.push(
+                    Finding::new(
+                        "invalid_lock",
+                        format!("{} has no valid holder metadata", name),
+                    )
+                    .at(path, None),
+                );
+                continue;
+            }
+        };
+        // Task IDs contain dots, so the holder recorded inside the lock
+        // decides where the task ID ends
+        let task_id = match stem.strip_suffix(&format!(".{}", metadata.holder)) {
+            Some(task_id) if !task_id.is_empty() => task_id,
+            _ => {
+                findings.push(
+                    Finding::new(
+                        "invalid_lock",
+                        format!(
+                            "{} is held by {} but not named <task_id>.{}.lock",
+                            name, metadata.holder, metadata.holder
+                        ),
+                    )
+                    .at(path, None),
+                );
+                continue;
+            }
+        };
+        locked.insert(task_id.to_string());
+        if registry.is_some() {
+            match claims.get(task_id) {
+                Some(agent) if *agent == metadata.holder => {}
+                Some(agent) => findings.push(
+                    Finding::new(
+                        "invalid_lock",
+                        format!(
+                            "Task {} is locked by {} but claimed by {} in the registry",
+                            task_id, metadata.holder, agent
+                        ),
+                    )
+                    .at(path, None),
+                ),
+                None => findings.push(
+                    Finding::new(
+                        "invalid_lock",
+                        format!(
+                            "Task {} is locked by {} but not claimed in the registry",
+                            task_id, metadata.holder
+                        ),
+                    )
+                    .at(path, None),
+                ),
+            }
+        }
+    }
+    let mut unlocked: Vec<(&&str, &&str)> = claims
+        .iter()
+        .filter(|(id, _)| !locked.contains(**id))
+        .collect();
+    unlocked.sort();
+    for (id, agent) in unlocked {
+        findings.push(
+            Finding::new(
+                "invalid_lock",
+                format!("Task {} is claimed by {} but has no lock file", id, agent),
+            )
+            .at(&registry_path, None),
+        );
+    }
+
+    // Process locks under .lock/: empty until first acquired, then holder metadata
+    let mut process_locks: Vec<_> = std::fs::read_dir(dir.join(".lock"))
+        .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+        .unwrap_or_default();
+    process_locks.sort();
+    for path in &process_locks {
+        let content = std::fs::read_to_string(path).unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+        checked += 1;
+        match crate::fs_ops::read_lock_holder(path) {
+            Some(holder) if crate::fs_ops::holder_is_dead(&holder) => findings.push(Finding {
+                level: Level::Warning,
+                ..Finding::new(
+                    "stale_lock",
+                    format!(
+                        "Held by dead process {} since {}; it will be broken on next use",
+                        holder.pid,
+                        holder.since.to_rfc3339()
+                    ),
+                )
+                .at(path, None)
+            }),
+            Some(_) => {}
+            None => findings.push(
+                Finding::new("invalid_lock", "Lock file has unreadable holder metadata")
+                    .at(path, None),
+            ),
+        }
+    }
+
+    ArtifactReport::new("coordination", findings, checked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_illegal_transitions() {
+        let tasks: Vec<(usize, TaskEntry)> = [
+            ("1.1", "pending"),
+            ("1.2", "pending"),
+            ("1.1", "in_progress"),
+            ("1.1", "in_progress"),
+            ("1.2", "complete"),
+            ("1.1", "complete"),
+            ("1.1", "pending"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, (id, status))| {
+            let task = serde_json::from_value(serde_json::json!({
+                "id": id, "title": "Task", "status": status
+            }))
+            .unwrap();
+            (index + 1, task)
+        })
+        .collect();
+
+        let describe = |illegal: Vec<(usize, &TaskEntry, &TaskEntry)>| -> Vec<String> {
+            illegal
+                .into_iter()
+                .map(|(line, prev, task)| {
+                    format!("{}:{} {}->{}", line, task.id, prev.status.as_str(), task.status.as_str())
+                })
+                .collect()
+        };
+        assert_eq!(
+            describe(illegal_transitions(&tasks, &HashMap::new())),
+            vec!["5:1.2 pending->complete", "7:1.1 complete->pending"]
+        );
+
+        // Config overrides replace the built-in rules for the listed statuses
+        let overrides: HashMap<String, Vec<String>> =
+            [("pending".to_string(), vec!["complete".to_string()])].into();
+        assert_eq!(
+            describe(illegal_transitions(&tasks, &overrides)),
+            vec!["3:1.1 pending->in_progress", "7:1.1 complete->pending"]
+        );
+    }
+
+    #[test]
+    fn test_dependency_cycles() {
+        let deps: HashMap<String, Vec<String>> = [
+            ("b", vec!["c"]),
+            ("c", vec!["a"]),
+            ("a", vec!["b", "d"]),
+            ("d", vec![]),
+            ("e", vec!["e"]),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.into_iter().map(str::to_string).collect()))
+        .collect();
+
+        assert_eq!(
+            dependency_cycles(&deps),
+            vec![vec!["a", "b", "c"], vec!["e"]]
+        );
+    }
+}
@@ -0,0 +1,535 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::common::{self, check_rotd_initialized};
+
+/// A single debounced change to a ROTD artifact
+#[derive(Debug, Serialize, Clone)]
+pub struct WatchEvent {
+    pub event: String,
+    pub change: String,
+    pub path: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Map a path inside `.rotd/` to the event name emitted for it.
+/// Lock files and unknown artifacts are ignored.
+pub fn classify(path: &Path) -> Option<&'static str> {
+    let rel = path
+        .components()
+        .skip_while(|c| c.as_os_str() != common::ROTD_DIR)
+        .skip(1)
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+
+    if rel.iter().any(|c| c == ".lock") || rel.last().is_some_and(|f| f.ends_with(".lock")) {
+        return None;
+    }
+
+    let mut parts: Vec<&str> = rel.iter().map(|s| s.as_str()).collect();
+    // The split layout nests artifacts one level deeper
+    if matches!(parts.first(), Some(&common::SHARED_DIR) | Some(&common::LOCAL_DIR)) {
+        parts.remove(0);
+    }
+    match parts.as_slice() {
+        [common::TASKS_FILE] => Some("task_updated"),
+        [common::LESSONS_FILE] => Some("lesson_logged"),
+        [common::PSS_SCORES_FILE] => Some("score_recorded"),
+        [common::SESSION_STATE_FILE] => Some("session_changed"),
+        [common::COVERAGE_HISTORY_FILE] => Some("coverage_changed"),
+        [common::AUDIT_LOG_FILE] => Some("audit_logged"),
+        [common::BUCKLE_STATE_FILE] => Some("buckle_state_changed"),
+        [common::TEST_SUMMARIES_DIR, f] if f.ends_with(".json") => Some("summary_added"),
+        [common::TASK_HISTORY_DIR, f] if f.ends_with(".jsonl") => Some("history_appended"),
+        [common::COORDINATION_DIR, common::ACTIVE_WORK_REGISTRY_FILE] => Some("registry_changed"),
+        [common::COORDINATION_DIR, "coordination.log"] => Some("coordination_message"),
+        [common::COORDINATION_DIR, "heartbeat", _] => Some("heartbeat"),
+        _ => None,
+    }
+}
+
+fn change_name(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("created"),
+        EventKind::Modify(_) => Some("modified"),
+        EventKind::Remove(_) => Some("removed"),
+        _ => None,
+    }
+}
+
+/// Watch `roots` recursively and invoke `on_batch` with debounced events.
+/// Runs until the watcher channel closes or `on_batch` returns an error.
+pub fn run_watcher<C, F>(
+    roots: &[PathBuf],
+    debounce: Duration,
+    classifier: C,
+    mut on_batch: F,
+) -> Result<()>
+where
+    C: Fn(&Path) -> Option<&'static str>,
+    F: FnMut(Vec<WatchEvent>) -> Result<()>,
+{
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| anyhow::anyhow!("Failed to start filesystem watcher: {}", e))?;
+
+    for root in roots {
+        watcher
+            .watch(root, RecursiveMode::Recursive)
+            .map_err(|e| anyhow::anyhow!("Failed to watch {}: {}", root.display(), e))?;
+    }
+
+    let mut pending = Debouncer::default();
+
+    loop {
+        match rx.recv_timeout(debounce) {
+            Ok(Ok(event)) => {
+                let Some(change) = change_name(&event.kind) else {
+                    continue;
+                };
+                for path in &event.paths {
+                    if let Some(name) = classifier(path) {
+                        pending.record(name, change, relative_display(path), Instant::now());
+                    }
+                }
+            }
+            Ok(Err(e)) => return Err(anyhow::anyhow!("Watcher error: {}", e)),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+
+        let batch = pending.take_quiet(Instant::now(), debounce);
+        if !batch.is_empty() {
+            on_batch(batch)?;
+        }
+    }
+}
+
+/// Coalesces bursts of notifications for the same file (one write usually
+/// produces several modify events) into a single event, emitted once the
+/// file has been quiet for the debounce interval
+#[derive(Default)]
+struct Debouncer {
+    pending: HashMap<(String, String), (WatchEvent, Instant)>,
+}
+
+impl Debouncer {
+    fn record(&mut self, name: &str, change: &str, path: String, now: Instant) {
+        let key = (name.to_string(), path.clone());
+        let entry = self.pending.entry(key).or_insert_with(|| {
+            (
+                WatchEvent {
+                    event: name.to_string(),
+                    change: change.to_string(),
+                    path,
+                    timestamp: Utc::now(),
+                },
+                now,
+            )
+        });
+        // Every notification restarts the quiet period
+        entry.1 = now;
+        // A removal wins over earlier modifications in the same burst
+        if change == "removed" {
+            entry.0.change = change.to_string();
+        }
+    }
+
+    /// Remove and return, oldest first, the events whose file has seen no
+    /// notification for `debounce`
+    fn take_quiet(&mut self, now: Instant, debounce: Duration) -> Vec<WatchEvent> {
+        let ready: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, seen))| now.duration_since(*seen) >= debounce)
+            .map(|(k, _)| k.clone())
+            .collect();
+        let mut batch: Vec<WatchEvent> = ready
+            .into_iter()
+            .filter_map(|k| self.pending.remove(&k).map(|(e, _)| e))
+            .collect();
+        batch.sort_by_key(|e| e.timestamp);
+        batch
+    }
+}
+
+fn relative_display(path: &Path) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    path.strip_prefix(&cwd)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Event name for changes to project source files
+pub const SOURCE_CHANGED: &str = "source_changed";
+
+/// Events that never trigger a re-check: the check itself doesn't depend on
+/// them, and some change constantly while agents work
+const NO_RECHECK: [&str; 3] = ["audit_logged", "heartbeat", "coordination_message"];
+
+/// Failing findings and stub locations from one run of `rotd check`, each
+/// as a one-line description
+#[derive(Debug, Default)]
+pub struct CheckSnapshot {
+    pub passed: usize,
+    pub total: usize,
+    pub health: f64,
+    pub findings: BTreeSet<String>,
+    pub stubs: BTreeSet<String>,
+}
+
+impl CheckSnapshot {
+    /// Run the configured checks, plus stub detection with `stubs`
+    pub fn take(stubs: bool) -> Result<Self> {
+        let run = crate::checks::run()?;
+        let findings = run
+            .failed()
+            .flat_map(|r| r.outcome.findings.iter().map(move |f| (r.outcome.id, f)))
+            .map(|(check, f)| {
+                let location = match (&f.file, f.line) {
+                    (Some(file), Some(line)) => format!(" ({}:{})", relative_display(file), line),
+                    (Some(file), None) => format!(" ({})", relative_display(file)),
+                    _ => String::new(),
+                };
+                format!("{}: {}{}", check, f.message, location)
+            })
+            .collect();
+        let stubs = if stubs {
+            crate::pss::find_stubs()
+                .into_iter()
+                .map(|(file, line, pattern)| {
+                    format!("{}:{} {}", relative_display(&file), line, pattern)
+                })
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+        Ok(Self {
+            passed: run.passed(),
+            total: run.results.len(),
+            health: (run.health_percentage() * 10.0).round() / 10.0,
+            findings,
+            stubs,
+        })
+    }
+}
+
+/// What changed between two check runs
+#[derive(Debug, Serialize, PartialEq)]
+pub struct CheckDelta {
+    pub event: &'static str,
+    pub passed: usize,
+    pub total: usize,
+    pub health: f64,
+    pub new_findings: Vec<String>,
+    pub resolved_findings: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new_stubs: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_stubs: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CheckDelta {
+    pub fn between(previous: &CheckSnapshot, current: &CheckSnapshot) -> Self {
+        let added = |old: &BTreeSet<String>, new: &BTreeSet<String>| -> Vec<String> {
+            new.difference(old).cloned().collect()
+        };
+        Self {
+            event: "check",
+            passed: current.passed,
+            total: current.total,
+            health: current.health,
+            new_findings: added(&previous.findings, &current.findings),
+            resolved_findings: added(&current.findings, &previous.findings),
+            new_stubs: added(&previous.stubs, &current.stubs),
+            removed_stubs: added(&current.stubs, &previous.stubs),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn is_unchanged(&self) -> bool {
+        self.new_findings.is_empty()
+            && self.resolved_findings.is_empty()
+            && self.new_stubs.is_empty()
+            && self.removed_stubs.is_empty()
+    }
+
+    fn print_text(&self) {
+        use colored::Colorize;
+
+        let summary = format!(
+            "[{}] check: {}/{} passed ({:.1}%)",
+            self.timestamp.format("%H:%M:%S"),
+            self.passed,
+            self.total,
+            self.health
+        );
+        let summary = if self.passed == self.total {
+            summary.green()
+        } else {
+            summary.yellow()
+        };
+        if self.is_unchanged() {
+            println!("{} {}", summary, "no change".dimmed());
+            return;
+        }
+        println!("{}", summary);
+        for finding in &self.new_findings {
+            println!("  {} {}", "+".red(), finding);
+        }
+        for finding in &self.resolved_findings {
+            println!("  {} {}", "-".green(), finding);
+        }
+        for stub in &self.new_stubs {
+            println!("  {} stub {}", "+".red(), stub);
+        }
+        for stub in &self.removed_stubs {
+            println!("  {} stub {}", "-".green(), stub);
+        }
+    }
+}
+
+/// Classifier for `--check`: project source files under `roots`, then
+/// `.rotd` artifacts
+fn classify_with_sources(roots: &[PathBuf], path: &Path) -> Option<&'static str> {
+    let in_source = roots.iter().any(|root| {
+        path.strip_prefix(root).is_ok_and(|rel| {
+            !rel.components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+        })
+    });
+    if in_source {
+        Some(SOURCE_CHANGED)
+    } else {
+        classify(path)
+    }
+}
+
+/// Re-run `rotd check` whenever source files or `.rotd` artifacts change,
+/// printing what changed since the previous run
+fn watch_check(json: bool, debounce: Duration, stubs: bool, rotd_dir: PathBuf) -> Result<()> {
+    let config = crate::history::load_config().unwrap_or_default();
+    let source_roots: Vec<PathBuf> = crate::pss::source_dirs(&config)
+        .iter()
+        .filter_map(|dir| std::fs::canonicalize(dir).ok())
+        .collect();
+
+    if !json {
+        use colored::Colorize;
+        let mut watched: Vec<String> = source_roots.iter().map(|r| relative_display(r)).collect();
+        watched.push(relative_display(&rotd_dir));
+        println!(
+            "{}",
+            format!(
+                "Re-running check on changes to {} (Ctrl-C to stop)...",
+                watched.join(", ")
+            )
+            .cyan()
+        );
+    }
+
+    let emit = |delta: &CheckDelta| -> Result<()> {
+        if json {
+            println!("{}", serde_json::to_string(delta)?);
+        } else {
+            delta.print_text();
+        }
+        use std::io::Write;
+        std::io::stdout().flush()?;
+        Ok(())
+    };
+
+    // The first run reports everything as new
+    let mut previous = CheckSnapshot::take(stubs)?;
+    emit(&CheckDelta::between(&CheckSnapshot::default(), &previous))?;
+
+    let mut roots = source_roots.clone();
+    roots.push(rotd_dir);
+    run_watcher(
+        &roots,
+        debounce,
+        |path| classify_with_sources(&source_roots, path),
+        |batch| {
+            if batch.iter().all(|e| NO_RECHECK.contains(&e.event.as_str())) {
+                return Ok(());
+            }
+            let current = CheckSnapshot::take(stubs)?;
+            emit(&CheckDelta::between(&previous, &current))?;
+            previous = current;
+            Ok(())
+        },
+    )
+}
+
+/// Stream `.rotd` artifact changes to stdout, or with `check` re-run
+/// `rotd check` as they and the project's source change
+pub fn watch(
+    format: &str,
+    debounce_ms: u64,
+    check: bool,
+    stubs: bool,
+    is_agent_mode: bool,
+) -> Result<()> {
+    check_rotd_initialized()?;
+
+    let json = is_agent_mode || format == "json";
+    let rotd_dir = std::fs::canonicalize(common::rotd_path())?;
+    if check {
+        return watch_check(json, Duration::from_millis(debounce_ms), stubs, rotd_dir);
+    }
+
+    if !json {
+        use colored::Colorize;
+        println!(
+            "{}",
+            format!(
+                "Watching {} for changes (Ctrl-C to stop)...",
+                rotd_dir.display()
+            )
+            .cyan()
+        );
+    }
+
+    run_watcher(
+        &[rotd_dir],
+        Duration::from_millis(debounce_ms),
+        classify,
+        |batch| {
+            for event in batch {
+                if json {
+                    println!("{}", serde_json::to_string(&event)?);
+                } else {
+                    println!(
+                        "[{}] {:<22} {:<9} {}",
+                        event.timestamp.format("%H:%M:%S"),
+                        event.event,
+                        event.change,
+                        event.path
+                    );
+                }
+            }
+            use std::io::Write;
+            std::io::stdout().flush()?;
+            Ok(())
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_artifacts() {
+        let root = Path::new("/project/.rotd");
+        assert_eq!(classify(&root.join("tasks.jsonl")), Some("task_updated"));
+        assert_eq!(
+            classify(&root.join("test_summaries/6.2.json")),
+            Some("summary_added")
+        );
+        assert_eq!(
+            classify(&root.join("coordination/active_work_registry.json")),
+            Some("registry_changed")
+        );
+        assert_eq!(
+            classify(&root.join("buckle_state.json")),
+            Some("buckle_state_changed")
+        );
+        assert_eq!(classify(&root.join(".lock/tasks.jsonl.lock")), None);
+        assert_eq!(
+            classify(&root.join("coordination/.lock/registry.lock")),
+            None
+        );
+        assert_eq!(
+            classify(&root.join("shared/tasks.jsonl")),
+            Some("task_updated")
+        );
+        assert_eq!(
+            classify(&root.join("local/coordination/heartbeat/agent-1.beat")),
+            Some("heartbeat")
+        );
+        assert_eq!(classify(&root.join("something_else.txt")), None);
+    }
+
+    #[test]
+    fn test_debounce_waits_for_quiet() {
+        let debounce = Duration::from_millis(200);
+        let start = Instant::now();
+        let mut pending = Debouncer::default();
+        pending.record("task_updated", "modified", "tasks.jsonl".into(), start);
+        pending.record(
+            "task_updated",
+            "modified",
+            "tasks.jsonl".into(),
+            start + Duration::from_millis(150),
+        );
+
+        // The second write restarted the window
+        assert!(pending.take_quiet(start + debounce, debounce).is_empty());
+
+        pending.record(
+            "task_updated",
+            "removed",
+            "tasks.jsonl".into(),
+            start + Duration::from_millis(300),
+        );
+        assert!(pending
+            .take_quiet(start + Duration::from_millis(450), debounce)
+            .is_empty());
+
+        let batch = pending.take_quiet(start + Duration::from_millis(500), debounce);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].change, "removed");
+        assert!(pending
+            .take_quiet(start + Duration::from_secs(1), debounce)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_check_delta() {
+        let set = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let previous = CheckSnapshot {
+            passed: 8,
+            total: 10,
+            health: 80.0,
+            findings: set(&["stale_session: Session is stale", "invalid_jsonl: bad line"]),
+            stubs: set(&["src/lib.rs:3 todo!()"]),
+        };
+        let current = CheckSnapshot {
+            passed: 9,
+            total: 10,
+            health: 90.0,
+            findings: set(&["invalid_jsonl: bad line", "missing_test_summaries: 6.2"]),
+            stubs: set(&["src/lib.rs:3 todo!()"]),
+        };
+
+        let delta = CheckDelta::between(&previous, &current);
+        assert_eq!(delta.new_findings, vec!["missing_test_summaries: 6.2"]);
+        assert_eq!(delta.resolved_findings, vec!["stale_session: Session is stale"]);
+        assert!(delta.new_stubs.is_empty() && delta.removed_stubs.is_empty());
+        assert!(!delta.is_unchanged());
+        assert!(CheckDelta::between(&current, &current).is_unchanged());
+
+        let roots = [PathBuf::from("/project/src")];
+        assert_eq!(
+            classify_with_sources(&roots, Path::new("/project/src/main.rs")),
+            Some(SOURCE_CHANGED)
+        );
+        assert_eq!(
+            classify_with_sources(&roots, Path::new("/project/src/.cache/x")),
+            None
+        );
+        assert_eq!(
+            classify_with_sources(&roots, Path::new("/project/.rotd/tasks.jsonl")),
+            Some("task_updated")
+        );
+    }
+}
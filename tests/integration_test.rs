@@ -11,10 +11,20 @@ fn test_version_command() {
         .stdout(predicate::str::contains("rotd"));
 }
 
+#[test]
+fn test_version_build_info() {
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.args(["--agent", "version", "--build-info"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"git_sha\""))
+        .stdout(predicate::str::contains("\"rustc_version\":\"rustc "));
+}
+
 #[test]
 fn test_agent_info_command() {
     let mut cmd = Command::cargo_bin("rotd").unwrap();
-    cmd.args(&["agent", "info"])
+    cmd.args(["agent", "info"])
         .assert()
         .success()
         .stdout(predicate::str::contains("rotd_cli"));
@@ -37,6 +47,204 @@ fn test_init_command() {
     assert!(temp_dir.path().join(".rotd/session_state.json").exists());
 }
 
+#[test]
+fn test_init_git_split_layout() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--force", "--git"])
+        .assert()
+        .success();
+
+    let rotd = temp_dir.path().join(".rotd");
+    assert!(rotd.join("shared/tasks.jsonl").exists());
+    assert!(rotd.join("local/session_state.json").exists());
+    assert!(!rotd.join("tasks.jsonl").exists());
+    let gitignore = std::fs::read_to_string(rotd.join(".gitignore")).unwrap();
+    assert!(gitignore.lines().any(|l| l == "local/"));
+
+    // Commands resolve paths through the split layout
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"passed\":8"));
+}
+
+#[test]
+fn test_validate_github_annotations() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    let tasks = temp_dir.path().join(".rotd/tasks.jsonl");
+    let mut content = std::fs::read_to_string(&tasks).unwrap();
+    content.push_str("{\"id\": broken\n");
+    std::fs::write(&tasks, content).unwrap();
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["validate", "--output", "github"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "::error file=.rotd/tasks.jsonl,line=2,title=invalid_json::",
+        ));
+}
+
+#[test]
+fn test_validate_all_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    // A fresh project validates cleanly
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "validate", "--all"])
+        .assert()
+        .success();
+
+    let scores = temp_dir.path().join(".rotd/pss_scores.jsonl");
+    std::fs::write(
+        &scores,
+        r#"{"task_id":"1.1","score":3,"timestamp":"2025-01-01T00:00:00Z","criteria":{"a":{"score":1,"rationale":""}}}"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "validate", "--schema", "pss_scores"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Line 1: Score for task 1.1: Score 3 does not match its criteria, which sum to 1",
+        ));
+}
+
+#[test]
+fn test_validate_schema_overlay() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    let schemas = temp_dir.path().join(".rotd/schemas");
+    std::fs::create_dir_all(&schemas).unwrap();
+    std::fs::write(
+        schemas.join("coverage_history.schema.json"),
+        r#"{"required":["owner"],"properties":{"owner":{"type":"string"}}}"#,
+    )
+    .unwrap();
+
+    // Overlays only apply in strict mode
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "validate", "--schema", "coverage_history"])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "validate", "--schema", "coverage_history", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            r#"coverage_history.schema.json (root): \"owner\" is a required property"#,
+        ));
+}
+
+#[test]
+fn test_init_non_interactive() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--yes", "--profile", "rust"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "rotd-init status=success layout=flat profile=rust",
+        ));
+
+    // Without a TTY an existing project is a conflict rather than a prompt
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .arg("init")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Use --force to overwrite"));
+}
+
+#[test]
+fn test_check_fixes_dangling_dependencies() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    let tasks = temp_dir.path().join(".rotd/tasks.jsonl");
+    let mut content = std::fs::read_to_string(&tasks).unwrap();
+    content.push_str(
+        "{\"id\":\"2.1\",\"title\":\"Parser\",\"status\":\"pending\",\"depends_on\":[\"init\",\"9.9\"]}\n",
+    );
+    std::fs::write(&tasks, content).unwrap();
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["check", "--verbose"])
+        .assert()
+        .stdout(predicate::str::contains("Task 2.1 depends on unknown task 9.9"));
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["check", "--fix"])
+        .assert()
+        .stdout(predicate::str::contains(
+            "Removed dangling dependencies from task(s) 2.1",
+        ));
+
+    let content = std::fs::read_to_string(&tasks).unwrap();
+    let last = content.lines().last().unwrap();
+    assert!(last.contains("\"depends_on\":[\"init\"]"));
+}
+
+#[test]
+fn test_check_ci_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    // The init task is complete without a test summary, so 8 of 9 checks pass
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["check", "--ci"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("rotd-check status=fail health=88.9"));
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["check", "--ci", "--threshold", "75"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rotd-check status=pass"));
+}
+
 #[test]
 fn test_check_command_without_init() {
     let temp_dir = TempDir::new().unwrap();
@@ -65,12 +273,355 @@ fn test_agent_update_task_dry_run() {
     // Test update task with dry run
     let mut cmd = Command::cargo_bin("rotd").unwrap();
     cmd.current_dir(&temp_dir)
-        .args(&["agent", "update-task", "--dry-run"])
+        .args(["agent", "update-task", "--dry-run"])
         .write_stdin(r#"{"id":"test","title":"Test task","status":"pending"}"#)
         .assert()
         .success();
 }
 
+#[test]
+fn test_agent_update_task_records_history() {
+    let temp_dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    for (status, comment) in [("pending", "Created"), ("in_progress", "Picked up")] {
+        Command::cargo_bin("rotd")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .env("ROTD_AGENT_ID", "agent-a")
+            .args(["agent", "update-task", "--comment", comment])
+            .write_stdin(format!(
+                r#"{{"id":"6.1","title":"Test task","status":"{}"}}"#,
+                status
+            ))
+            .assert()
+            .success();
+    }
+
+    let history =
+        std::fs::read_to_string(temp_dir.path().join(".rotd/task_history/6.1.jsonl")).unwrap();
+    let events: Vec<serde_json::Value> = history
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1]["agent_id"], "agent-a");
+    assert_eq!(events[1]["prev_status"], "pending");
+    assert_eq!(events[1]["status"], "in_progress");
+    assert_eq!(events[1]["comment"], "Picked up");
+}
+
+#[test]
+fn test_agent_update_task_transitions_and_partial_updates() {
+    let temp_dir = TempDir::new().unwrap();
+    let update = |status: &str, extra: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir)
+            .args(["agent", "update-task"])
+            .args(extra)
+            .write_stdin(format!(
+                r#"{{"id":"6.1","title":"Test task","status":"{}","priority":"medium"}}"#,
+                status
+            ));
+        cmd
+    };
+
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    update("pending", &[]).assert().success();
+    // Skipping in_progress is rejected until explicitly overridden
+    update("complete", &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid_transition"));
+    update("complete", &["--allow-transition"]).assert().success();
+
+    // Only the fields sent are changed
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["agent", "update-task"])
+        .write_stdin(r#"{"id":"6.1","description":"Done by hand"}"#)
+        .assert()
+        .success();
+    let tasks = std::fs::read_to_string(temp_dir.path().join(".rotd/tasks.jsonl")).unwrap();
+    let latest: serde_json::Value = serde_json::from_str(tasks.lines().last().unwrap()).unwrap();
+    assert_eq!(latest["title"], "Test task");
+    assert_eq!(latest["status"], "complete");
+    assert_eq!(latest["description"], "Done by hand");
+
+    let audit = std::fs::read_to_string(temp_dir.path().join(".rotd/audit.log")).unwrap();
+    assert!(audit.contains("[WARNING] TRANSITION_OVERRIDE 6.1 - Status pending -> complete"));
+
+    // Starting goes through the same rules and audit trail
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+    rotd(&["task", "start", "6.1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot move from complete to in_progress"));
+    rotd(&["task", "start", "6.1", "--force"]).assert().success();
+    let audit = std::fs::read_to_string(temp_dir.path().join(".rotd/audit.log")).unwrap();
+    assert!(audit.contains("TRANSITION_OVERRIDE 6.1 - Status complete -> in_progress"));
+
+    // Strict validation walks the log: forced changes warn, others fail
+    rotd(&["validate", "--schema", "tasks", "--strict"])
+        .assert()
+        .success();
+    let tasks_path = temp_dir.path().join(".rotd/tasks.jsonl");
+    let mut tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    tasks.push_str("{\"id\":\"6.1\",\"title\":\"Test task\",\"status\":\"scaffolded\",\"priority\":\"medium\"}\n");
+    tasks.push_str("{\"id\":\"6.1\",\"title\":\"Test task\",\"status\":\"complete\",\"priority\":\"medium\"}\n");
+    std::fs::write(&tasks_path, tasks).unwrap();
+    rotd(&["--agent", "validate", "--schema", "tasks", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Task 6.1: status scaffolded -> complete is not an allowed transition",
+        ));
+}
+
+#[test]
+fn test_agent_update_task_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    let batch = r#"[
+        {"id":"7.1","title":"Lexer","status":"pending"},
+        {"id":"7.2","status":"pending"},
+        {"id":"7.3","title":"Parser","status":"pending","depends_on":["7.1"]}
+    ]"#;
+    let task_lines = || {
+        std::fs::read_to_string(temp_dir.path().join(".rotd/tasks.jsonl"))
+            .unwrap()
+            .lines()
+            .count()
+    };
+
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+    let before = task_lines();
+
+    // Strict mode writes nothing when any entry is bad
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["agent", "update-task", "--strict"])
+        .write_stdin(batch)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("batch_rejected"));
+    assert_eq!(task_lines(), before);
+
+    let output = Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["agent", "update-task"])
+        .write_stdin(batch)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let response: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(response["updated"], 2);
+    assert_eq!(response["failed"], 1);
+    assert_eq!(response["results"][1]["task_id"], "7.2");
+    assert_eq!(response["results"][1]["error"]["error"], "invalid_json");
+    assert_eq!(task_lines(), before + 2);
+}
+
+#[test]
+fn test_agent_log_lesson_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    let lesson = |id: &str, remediation: &str| {
+        format!(
+            r#"{{"id":"{}","trigger":["cargo test"],"context":{{}},"diagnosis":"Flaky timing","remediation":"{}","tags":["tests"]}}"#,
+            id, remediation
+        )
+    };
+    let log = |payload: String| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir)
+            .args(["agent", "log-lesson"])
+            .write_stdin(payload);
+        cmd
+    };
+
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+
+    // A lesson without a remediation rejects the whole batch
+    log(format!("[{},{}]", lesson("l1", "Use a fake clock"), lesson("l2", "")))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("batch_rejected"));
+    assert!(!temp_dir.path().join(".rotd/lessons_learned.jsonl").exists());
+
+    log(format!("[{},{}]", lesson("l1", "Use a fake clock"), lesson("l2", "Retry once")))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""lesson_ids":["l1","l2"]"#));
+    let logged = std::fs::read_to_string(temp_dir.path().join(".rotd/lessons_learned.jsonl")).unwrap();
+    assert_eq!(logged.lines().count(), 2);
+}
+
+#[test]
+fn test_pss_latest_and_replace_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+    let scores = || {
+        std::fs::read_to_string(temp_dir.path().join(".rotd/pss_scores.jsonl")).unwrap_or_default()
+    };
+
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"in_progress"}"#)
+        .assert()
+        .success();
+    rotd(&["--agent", "pss", "latest", "1.1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no_score"));
+
+    rotd(&["score", "1.1"]).assert().success();
+    rotd(&["score", "1.1"]).assert().success();
+    assert_eq!(scores().lines().count(), 2);
+
+    let config = temp_dir.path().join(".rotd/config.jsonc");
+    std::fs::write(&config, r#"{ "pss_replace_scores": true }"#).unwrap();
+    rotd(&["score", "1.1"]).assert().success();
+    assert_eq!(scores().lines().count(), 1);
+
+    rotd(&["--agent", "pss", "latest", "1.1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""task_id":"1.1""#));
+}
+
+#[test]
+fn test_check_fix_scaffolds_and_reports() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"in_progress"}"#)
+        .assert()
+        .success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","status":"complete"}"#)
+        .assert()
+        .success();
+    let session = temp_dir.path().join(".rotd/session_state.json");
+    std::fs::write(&session, "{ not json").unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+    std::fs::write(temp_dir.path().join("src/lib.rs"), "// #[rotd_stub]\n").unwrap();
+
+    // A dry run previews each change without writing anything
+    rotd(&["--agent", "--dry-run", "check", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""path":".rotd/session_state.json""#))
+        .stdout(predicate::str::contains(r#"-{ not json"#));
+    assert!(!temp_dir.path().join(".rotd/test_summaries/1.1.json").exists());
+    assert!(!temp_dir.path().join(".rotd/backups").exists());
+
+    rotd(&["--agent", "check", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scaffolded_test_summaries"))
+        .stdout(predicate::str::contains("regenerated_session_state"))
+        .stdout(predicate::str::contains(r#"{"issue":"stubs_remaining","reason":"#));
+
+    let summary =
+        std::fs::read_to_string(temp_dir.path().join(".rotd/test_summaries/1.1.json")).unwrap();
+    assert!(summary.contains(r#""status": "unverified""#));
+    let session_state = std::fs::read_to_string(&session).unwrap();
+    assert!(session_state.contains("session_id"));
+    let backups: Vec<_> = std::fs::read_dir(temp_dir.path().join(".rotd/backups"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(
+        std::fs::read_to_string(backups[0].join("session_state.json")).unwrap(),
+        "{ not json"
+    );
+
+    // Placeholders don't earn test points
+    rotd(&["--agent", "pss", "explain", "1.1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""criterion":"history_maintained""#));
+}
+
+#[test]
+fn test_session_lifecycle() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir)
+            .env("ROTD_AGENT_ID", "agent-a")
+            .args(args);
+        cmd
+    };
+
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["session", "start", "--task", "6.1"]).assert().success();
+    rotd(&["session", "start"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("still active"));
+    rotd(&["session", "end", "--status", "handoff", "--note", "Parser half done"])
+        .assert()
+        .success();
+    rotd(&["--agent", "session", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""status":"handoff""#))
+        .stdout(predicate::str::contains(r#""current_task":"6.1""#));
+
+    let log = std::fs::read_to_string(temp_dir.path().join(".rotd/sessions.jsonl")).unwrap();
+    let events: Vec<serde_json::Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["event"], "start");
+    assert_eq!(events[1]["event"], "end");
+    assert_eq!(events[1]["agent_id"], "agent-a");
+    assert_eq!(events[1]["note"], "Parser half done");
+}
+
 #[test]
 fn test_agent_update_task_invalid_json() {
     let temp_dir = TempDir::new().unwrap();
@@ -87,7 +638,7 @@ fn test_agent_update_task_invalid_json() {
     // Test with invalid JSON
     let mut cmd = Command::cargo_bin("rotd").unwrap();
     cmd.current_dir(&temp_dir)
-        .args(&["agent", "update-task"])
+        .args(["agent", "update-task"])
         .write_stdin("invalid json")
         .assert()
         .failure()
@@ -101,7 +652,7 @@ fn test_agent_mode_flag() {
     // Test agent mode with init
     let mut cmd = Command::cargo_bin("rotd").unwrap();
     cmd.current_dir(&temp_dir)
-        .args(&["--agent", "init", "--force"])
+        .args(["--agent", "init", "--force"])
         .assert()
         .success()
         .stdout(predicate::str::contains(r#""action":"init""#));
@@ -110,8 +661,995 @@ fn test_agent_mode_flag() {
 #[test]
 fn test_completions_command() {
     let mut cmd = Command::cargo_bin("rotd").unwrap();
-    cmd.args(&["completions", "bash"])
+    cmd.args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rotd __complete --"));
+}
+
+#[test]
+fn test_hooks_install_chains_existing_hook() {
+    let temp_dir = TempDir::new().unwrap();
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&temp_dir)
+        .status()
+        .unwrap();
+
+    let hooks_dir = temp_dir.path().join(".git/hooks");
+    std::fs::create_dir_all(&hooks_dir).unwrap();
+    std::fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\necho existing\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "hooks", "install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"chained\""));
+
+    let installed = std::fs::read_to_string(hooks_dir.join("pre-commit")).unwrap();
+    assert!(installed.contains("rotd-managed-hook"));
+    assert!(installed.contains("check --ci"));
+    assert!(hooks_dir.join("pre-commit.rotd-chained").exists());
+    assert!(hooks_dir.join("pre-push").exists());
+
+    let mut cmd = Command::cargo_bin("rotd").unwrap();
+    cmd.current_dir(&temp_dir)
+        .args(["--agent", "hooks", "uninstall"])
+        .assert()
+        .success();
+
+    let restored = std::fs::read_to_string(hooks_dir.join("pre-commit")).unwrap();
+    assert!(restored.contains("echo existing"));
+    assert!(!hooks_dir.join("pre-commit.rotd-chained").exists());
+    assert!(!hooks_dir.join("pre-push").exists());
+}
+
+#[test]
+fn test_verify_integrity_detects_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+
+    rotd(&["init", "--force"]).assert().success();
+    let config = temp_dir.path().join(".rotd/config.jsonc");
+    std::fs::write(&config, r#"{ "hash_chain": true }"#).unwrap();
+    for status in ["pending", "in_progress"] {
+        rotd(&["agent", "update-task"])
+            .env("ROTD_AGENT_ID", "agent-1")
+            .write_stdin(format!(r#"{{"id":"1.1","title":"Parser","status":"{}"}}"#, status))
+            .assert()
+            .success();
+    }
+
+    let tasks_path = temp_dir.path().join(".rotd/tasks.jsonl");
+    let tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    assert!(tasks.contains(r#""_agent":"agent-1""#));
+    rotd(&["verify-integrity"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("chain intact"));
+
+    std::fs::write(&tasks_path, tasks.replacen("Parser", "Lexer", 1)).unwrap();
+    rotd(&["--agent", "verify-integrity"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""status":"tampered""#))
+        .stdout(predicate::str::contains(r#""line":3"#));
+}
+
+#[test]
+fn test_signed_entries_verify_in_validate() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args).env("ROTD_AGENT_ID", "agent-1");
+        cmd
+    };
+
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["--agent", "keys", "generate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""agent":"agent-1""#));
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"in_progress","priority":"high"}"#)
+        .assert()
+        .success();
+
+    let tasks_path = temp_dir.path().join(".rotd/tasks.jsonl");
+    let tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    assert!(tasks.contains(r#""_sig":{"agent":"agent-1""#));
+    rotd(&["validate", "--all", "--strict"]).assert().success();
+
+    // Replacing the key retires the old one instead of orphaning its entries
+    rotd(&["--agent", "keys", "generate", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""retired_keys":1"#));
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.2","title":"Lexer","status":"pending","priority":"high"}"#)
+        .assert()
+        .success();
+    rotd(&["validate", "--all", "--strict"]).assert().success();
+
+    // Backdating or dropping the write time doesn't excuse a missing signature
+    let tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    std::fs::write(
+        &tasks_path,
+        format!(
+            "{}{}\n",
+            tasks,
+            r#"{"id":"1.3","title":"Forged","status":"pending","updated_at":"2020-01-01T00:00:00Z"}"#
+        ),
+    )
+    .unwrap();
+    rotd(&["--agent", "validate", "--schema", "tasks", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Unsigned, but appended after signed entries"));
+    std::fs::write(
+        &tasks_path,
+        format!("{}{}\n", tasks, r#"{"id":"1.3","title":"Forged","status":"pending"}"#),
+    )
+    .unwrap();
+    rotd(&["--agent", "validate", "--schema", "tasks", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("no readable write time"));
+    std::fs::write(&tasks_path, &tasks).unwrap();
+    let summaries = temp_dir.path().join(".rotd/test_summaries");
+    std::fs::create_dir_all(&summaries).unwrap();
+    std::fs::write(
+        summaries.join("1.2.json"),
+        r#"{"task_id":"1.2","status":"passing","total_tests":1,"passed":1,"failed":0,"warnings":null,"coverage":null,"verified_by":"cargo test","timestamp":"2020-01-01T00:00:00Z","notes":null}"#,
+    )
+    .unwrap();
+    rotd(&["--agent", "validate", "--schema", "test_summaries", "--strict"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("its task was recorded after signed entries"));
+    std::fs::remove_file(summaries.join("1.2.json")).unwrap();
+
+    // Rewriting a signed entry is caught
+    let tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    std::fs::write(&tasks_path, tasks.replace("Parser", "Lexer")).unwrap();
+    rotd(&["--agent", "validate", "--schema", "tasks"])
+        .assert()
+        .stdout(predicate::str::contains("does not verify"));
+}
+
+#[test]
+fn test_export_and_import_bundle() {
+    let source = TempDir::new().unwrap();
+    let target = TempDir::new().unwrap();
+    let rotd = |dir: &TempDir, args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(dir).args(args);
+        cmd
+    };
+
+    rotd(&source, &["init", "--force"]).assert().success();
+    rotd(&source, &["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"in_progress"}"#)
+        .assert()
+        .success();
+    std::fs::create_dir_all(source.path().join(".rotd/coordination/heartbeat")).unwrap();
+    std::fs::write(source.path().join(".rotd/coordination/heartbeat/agent-1.beat"), "0").unwrap();
+
+    let bundle = source.path().join("handoff.tar.gz");
+    rotd(&source, &["--agent", "export", bundle.to_str().unwrap()])
         .assert()
         .success()
-        .stdout(predicate::str::contains("Completions generated"));
+        .stdout(predicate::str::contains(r#""task.v1":"#));
+
+    rotd(&target, &["import", "bundle", bundle.to_str().unwrap()])
+        .assert()
+        .success();
+    let tasks = std::fs::read_to_string(target.path().join(".rotd/tasks.jsonl")).unwrap();
+    assert!(tasks.contains("Parser"));
+    assert!(!target.path().join(".rotd/coordination/heartbeat").exists());
+
+    // Existing files are only replaced with --force, after a backup
+    rotd(&target, &["import", "bundle", bundle.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    rotd(&target, &["--agent", "import", "bundle", bundle.to_str().unwrap(), "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("import-"));
+}
+
+#[test]
+fn test_sync_merges_logs_through_git_remote() {
+    let remote = TempDir::new().unwrap();
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let status = std::process::Command::new("git")
+        .args(["init", "-q", "--bare"])
+        .arg(remote.path())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let rotd = |dir: &TempDir, args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(dir).args(args);
+        cmd
+    };
+    let update = |dir: &TempDir, id: &str| {
+        rotd(dir, &["agent", "update-task"])
+            .write_stdin(format!(r#"{{"id":"{}","title":"Task {}","status":"pending"}}"#, id, id))
+            .assert()
+            .success();
+    };
+    for dir in [&first, &second] {
+        rotd(dir, &["init", "--force"]).assert().success();
+        let config = format!(
+            r#"{{ "sync": {{ "backend": "git", "remote": "{}" }} }}"#,
+            remote.path().display().to_string().replace('\\', "/")
+        );
+        std::fs::write(dir.path().join(".rotd/config.jsonc"), config).unwrap();
+    }
+
+    update(&first, "1.1");
+    rotd(&first, &["sync", "push"]).assert().success();
+    // The fresh project has its own init files, so the first pull replaces them
+    rotd(&second, &["sync", "pull", "--force"]).assert().success();
+
+    // Both sides add a task; the second push unions tasks.jsonl
+    update(&second, "2.1");
+    rotd(&second, &["sync", "push"]).assert().success();
+    update(&first, "3.1");
+    rotd(&first, &["--agent", "sync", "push"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""merged":["audit.log","tasks.jsonl"]"#));
+    rotd(&second, &["sync", "pull"]).assert().success();
+    let tasks = std::fs::read_to_string(second.path().join(".rotd/tasks.jsonl")).unwrap();
+    for id in ["1.1", "2.1", "3.1"] {
+        assert!(tasks.contains(&format!(r#""id":"{}""#, id)), "{}", id);
+    }
+
+    // Other files changed on both sides stop the sync
+    std::fs::write(first.path().join(".rotd/coverage_history.json"), "[]").unwrap();
+    rotd(&first, &["sync", "push"]).assert().success();
+    std::fs::write(second.path().join(".rotd/coverage_history.json"), "[ ]").unwrap();
+    rotd(&second, &["--agent", "sync", "push"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""conflicts":["coverage_history.json"]"#));
+    rotd(&second, &["sync", "push", "--force"]).assert().success();
+}
+
+#[test]
+fn test_coord_commands_through_server() {
+    use std::io::BufRead;
+
+    let server_dir = TempDir::new().unwrap();
+    let client_dir = TempDir::new().unwrap();
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&server_dir)
+        .args(["init", "--force"])
+        .assert()
+        .success();
+    let coordination = server_dir.path().join(".rotd/coordination");
+    std::fs::create_dir_all(&coordination).unwrap();
+    std::fs::write(
+        coordination.join("active_work_registry.json"),
+        r#"{"tasks":[{"id":"1.1","title":"Parser","status":"unclaimed","priority":"high",
+            "claimed_by":null,"claimed_at":null,"completed_at":null,"blocked_reason":null,
+            "reviewer_id":null,"capability":null,"skill_level":null},
+            {"id":"1.2","title":"Lexer","status":"unclaimed","priority":"low",
+            "claimed_by":null,"claimed_at":null,"completed_at":null,"blocked_reason":null,
+            "reviewer_id":null,"capability":null,"skill_level":null}]}"#,
+    )
+    .unwrap();
+
+    // Kill the server even when an assertion fails
+    struct Server(std::process::Child);
+    impl Drop for Server {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+    let mut server = Server(
+        std::process::Command::new(assert_cmd::cargo::cargo_bin("rotd"))
+            .current_dir(&server_dir)
+            .args(["--agent", "serve", "--coord", "--bind", "127.0.0.1:0"])
+            .env("ROTD_COORD_TOKEN", "s3cret")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap(),
+    );
+    let mut banner = String::new();
+    std::io::BufReader::new(server.0.stdout.take().unwrap())
+        .read_line(&mut banner)
+        .unwrap();
+    let banner: serde_json::Value = serde_json::from_str(&banner).unwrap();
+    let url = format!("http://{}", banner["bind"].as_str().unwrap());
+
+    let rotd = |args: &[&str], token: &str| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&client_dir)
+            .args(args)
+            .env("ROTD_AGENT_ID", "agent-1")
+            .env("ROTD_COORD_SERVER", &url)
+            .env("ROTD_COORD_TOKEN", token);
+        cmd
+    };
+
+    rotd(&["--agent", "coord", "beat"], "wrong")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("401"));
+    rotd(&["--agent", "coord", "claim"], "s3cret")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""claimed_by":"agent-1""#));
+    rotd(&["--agent", "coord", "beat"], "s3cret").assert().success();
+    rotd(&["--agent", "coord", "quota", "--add", "250"], "s3cret")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""tokens_used":250"#));
+    rotd(&["--agent", "coord", "release", "1.1"], "s3cret")
+        .env("ROTD_AGENT_ID", "agent-2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not claimed by this agent"));
+    rotd(&["--agent", "coord", "release", "1.1"], "s3cret")
+        .assert()
+        .success();
+
+    // The state lives with the server; the client has no .rotd at all
+    let registry =
+        std::fs::read_to_string(coordination.join("active_work_registry.json")).unwrap();
+    assert!(registry.contains(r#""done""#));
+    assert!(coordination.join("heartbeat/agent-1.beat").exists());
+    assert!(!client_dir.path().join(".rotd").exists());
+    rotd(&["--agent", "coord", "ls"], "s3cret")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""id":"1.2""#));
+    rotd(&["--agent", "coord", "board"], "s3cret")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""done":1"#));
+
+    // task start claims on the server, and hands the claim back there on failure
+    rotd(&["init", "--force"], "s3cret").assert().success();
+    rotd(&["agent", "update-task"], "s3cret")
+        .write_stdin(r#"{"id":"1.2","title":"Lexer","status":"pending","priority":"low"}"#)
+        .assert()
+        .success();
+    let session = client_dir.path().join(".rotd/session_state.json");
+    std::fs::remove_file(&session).unwrap();
+    std::fs::create_dir(&session).unwrap();
+    rotd(&["task", "start", "1.2"], "s3cret").assert().failure();
+    let registry =
+        std::fs::read_to_string(coordination.join("active_work_registry.json")).unwrap();
+    assert_eq!(registry.matches(r#""unclaimed""#).count(), 1);
+    std::fs::remove_dir(&session).unwrap();
+    rotd(&["task", "start", "1.2"], "s3cret").assert().success();
+    let registry =
+        std::fs::read_to_string(coordination.join("active_work_registry.json")).unwrap();
+    assert!(!registry.contains(r#""unclaimed""#));
+    assert!(!client_dir.path().join(".rotd/coordination/active_work_registry.json").exists());
+
+    // Only the environment or the machine-local config name the server;
+    // a committed config can't redirect the token
+    std::fs::write(
+        client_dir.path().join(".rotd/config.jsonc"),
+        format!(r#"{{"coord_server": "{}"}}"#, url),
+    )
+    .unwrap();
+    rotd(&["--agent", "coord", "ls"], "s3cret")
+        .env_remove("ROTD_COORD_SERVER")
+        .assert()
+        .failure();
+    std::fs::write(
+        client_dir.path().join(".rotd/config.local.jsonc"),
+        format!(r#"{{"coord_server": "{}"}}"#, url),
+    )
+    .unwrap();
+    rotd(&["--agent", "coord", "ls"], "s3cret")
+        .env_remove("ROTD_COORD_SERVER")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""claimed_by":"agent-1""#));
+
+    // Oversized bodies are refused before they are read
+    let mut stream = std::net::TcpStream::connect(banner["bind"].as_str().unwrap()).unwrap();
+    std::io::Write::write_all(
+        &mut stream,
+        b"POST /coord/beat HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer s3cret\r\n\
+          Content-Type: application/json\r\nContent-Length: 10000000\r\nConnection: close\r\n\r\n",
+    )
+    .unwrap();
+    let mut status = String::new();
+    std::io::BufReader::new(stream).read_line(&mut status).unwrap();
+    assert!(status.contains("413"), "{}", status);
+
+    // Without a token the server only listens on loopback
+    Command::cargo_bin("rotd")
+        .unwrap()
+        .current_dir(&server_dir)
+        .args(["serve", "--coord", "--bind", "0.0.0.0:0"])
+        .env_remove("ROTD_COORD_TOKEN")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("without ROTD_COORD_TOKEN"));
+}
+
+#[test]
+fn test_sync_keeps_same_id_lines_and_refuses_machine_local_files() {
+    let remote = TempDir::new().unwrap();
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    };
+    git(remote.path(), &["init", "-q", "--bare"]);
+    let rotd = |dir: &TempDir, args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(dir).args(args);
+        cmd
+    };
+    let remote_url = remote.path().display().to_string().replace('\\', "/");
+    for dir in [&first, &second] {
+        rotd(dir, &["init", "--force"]).assert().success();
+        let config = format!(r#"{{ "sync": {{ "backend": "git", "remote": "{}" }} }}"#, remote_url);
+        std::fs::write(dir.path().join(".rotd/config.jsonc"), config).unwrap();
+    }
+    let append = |dir: &TempDir, line: &str| {
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(".rotd/tasks.jsonl"))
+            .unwrap();
+        writeln!(file, "{}", line).unwrap();
+    };
+
+    append(&first, r#"{"id":"1.1","title":"Parser","status":"pending"}"#);
+    rotd(&first, &["sync", "push"]).assert().success();
+    rotd(&second, &["sync", "pull", "--force"]).assert().success();
+
+    // Both sides log another status for the same task, with no updated_at
+    append(&first, r#"{"id":"1.1","title":"Parser","status":"in_progress"}"#);
+    append(&second, r#"{"id":"1.1","title":"Parser","status":"blocked"}"#);
+    rotd(&first, &["sync", "push"]).assert().success();
+    rotd(&second, &["sync", "pull"]).assert().success();
+    let tasks = std::fs::read_to_string(second.path().join(".rotd/tasks.jsonl")).unwrap();
+    for status in ["pending", "in_progress", "blocked"] {
+        assert!(tasks.contains(&format!(r#""status":"{}""#, status)), "{}", status);
+    }
+
+    // A remote carrying a signing key is refused before anything is written
+    let checkout = TempDir::new().unwrap();
+    git(checkout.path(), &["clone", "-q", "--branch", "rotd-state", &remote_url, "."]);
+    std::fs::create_dir_all(checkout.path().join("keys")).unwrap();
+    std::fs::write(checkout.path().join("keys/agent-1.key"), "planted").unwrap();
+    git(checkout.path(), &["add", "-A"]);
+    git(
+        checkout.path(),
+        &["-c", "user.name=x", "-c", "user.email=x@x", "commit", "-q", "-m", "plant"],
+    );
+    git(checkout.path(), &["push", "-q", "origin", "rotd-state"]);
+    rotd(&second, &["sync", "pull", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("machine-local file keys/agent-1.key"));
+    assert!(!second.path().join(".rotd/keys").exists());
+}
+
+#[test]
+fn test_redaction_fails_closed() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    let config = temp_dir.path().join(".rotd/config.jsonc");
+    let lessons = temp_dir.path().join(".rotd/lessons_learned.jsonl");
+    let lesson = |id: &str| {
+        format!(
+            r#"{{"id":"{}","trigger":["curl"],"context":{{}},"diagnosis":"used sk-abcdefghijklmnopqrstuvwx","remediation":"rotate","tags":[]}}"#,
+            id
+        )
+    };
+
+    // An unreadable config still redacts with the built-in patterns
+    std::fs::write(&config, "{ \"redact_patterns\": [").unwrap();
+    rotd(&["agent", "log-lesson"])
+        .write_stdin(lesson("broken-config"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("redacting with built-in patterns only"));
+
+    // A bad custom pattern is reported and the others still apply
+    std::fs::write(
+        &config,
+        r#"{"redact_patterns": ["(unclosed", "\\bACME-\\d{6}\\b"]}"#,
+    )
+    .unwrap();
+    rotd(&["agent", "log-lesson"])
+        .write_stdin(lesson("bad-pattern").replace("rotate", "rotate ACME-123456"))
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("skipping Invalid redact_patterns entry: (unclosed"));
+
+    let content = std::fs::read_to_string(&lessons).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    assert!(!content.contains("sk-abcdefghijklmnopqrstuvwx"));
+    assert!(!content.contains("ACME-123456"));
+    assert!(content.contains("rotate [REDACTED]"));
+}
+
+#[test]
+fn test_task_start_releases_claim_on_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args).env("ROTD_AGENT_ID", "agent-1");
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"pending","priority":"high"}"#)
+        .assert()
+        .success();
+    let coordination = temp_dir.path().join(".rotd/coordination");
+    std::fs::create_dir_all(&coordination).unwrap();
+    let registry_path = coordination.join("active_work_registry.json");
+    std::fs::write(
+        &registry_path,
+        r#"{"tasks":[{"id":"1.1","title":"Parser","status":"unclaimed","priority":"high",
+            "claimed_by":null,"claimed_at":null,"completed_at":null,"blocked_reason":null,
+            "reviewer_id":null,"capability":null,"skill_level":null}]}"#,
+    )
+    .unwrap();
+
+    // The session can't be written once the claim is taken
+    let session = temp_dir.path().join(".rotd/session_state.json");
+    let _ = std::fs::remove_file(&session);
+    std::fs::create_dir(&session).unwrap();
+    rotd(&["task", "start", "1.1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to make 1.1 the current task"));
+    let registry: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&registry_path).unwrap()).unwrap();
+    assert_eq!(registry["tasks"][0]["status"], "unclaimed");
+    assert!(registry["tasks"][0]["claimed_by"].is_null());
+
+    std::fs::remove_dir(&session).unwrap();
+    rotd(&["task", "start", "1.1"]).assert().success();
+    let registry: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&registry_path).unwrap()).unwrap();
+    assert_eq!(registry["tasks"][0]["claimed_by"], "agent-1");
+}
+
+#[test]
+fn test_github_pr_reports_missing_remote_and_token() {
+    let temp_dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .current_dir(&temp_dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    };
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir)
+            .args(args)
+            .env_remove("ROTD_GITHUB_TOKEN")
+            .env_remove("GITHUB_TOKEN");
+        cmd
+    };
+    git(&["init", "-q", "-b", "feature"]);
+    git(&[
+        "-c", "user.name=x", "-c", "user.email=x@x", "commit", "-q", "--allow-empty", "-m", "start",
+    ]);
+    git(&["remote", "add", "origin", "git@gitlab.com:acme/app.git"]);
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"in_progress","priority":"high","description":"Parse primers"}"#)
+        .assert()
+        .success();
+
+    // A non-GitHub origin can't name the repository
+    rotd(&["github", "pr", "1.1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not determine the GitHub repository"));
+
+    // The dry run needs no token and shows the body it would send
+    rotd(&["--agent", "github", "pr", "1.1", "--repo", "acme/app", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""head":"feature""#))
+        .stdout(predicate::str::contains("Parse primers"))
+        .stdout(predicate::str::contains("ROTD-Task: 1.1"));
+
+    rotd(&["github", "pr", "1.1", "--repo", "acme/app"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("GITHUB_TOKEN is required"));
+}
+
+#[test]
+fn test_check_fix_archives_orphans() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","title":"Parser","status":"in_progress","priority":"high"}"#)
+        .assert()
+        .success();
+
+    let rotd_dir = temp_dir.path().join(".rotd");
+    let summary = |id: &str| {
+        format!(
+            r#"{{"task_id":"{}","status":"passing","total_tests":3,"passed":3,"failed":0,"warnings":null,"coverage":null,"verified_by":"cargo test","timestamp":"2025-07-01T10:00:00Z","notes":null}}"#,
+            id
+        )
+    };
+    let score = |id: &str| {
+        format!(
+            r#"{{"task_id":"{}","score":6,"timestamp":"2025-07-01T10:00:00Z","criteria":{{}}}}"#,
+            id
+        )
+    };
+    std::fs::create_dir_all(rotd_dir.join("test_summaries")).unwrap();
+    std::fs::write(rotd_dir.join("test_summaries/1.1.json"), summary("1.1")).unwrap();
+    std::fs::write(rotd_dir.join("test_summaries/9.9.json"), summary("9.9")).unwrap();
+    std::fs::write(
+        rotd_dir.join("pss_scores.jsonl"),
+        format!("{}\n{}\n{}\n", score("1.1"), score("9.9"), score("1.1")),
+    )
+    .unwrap();
+
+    rotd(&["--agent", "check"])
+        .assert()
+        .stdout(predicate::str::contains(r#""id":"orphaned_test_summaries","passed":false"#))
+        .stdout(predicate::str::contains(r#""id":"orphaned_pss_scores","passed":false"#));
+    rotd(&["--agent", "check", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("archived_orphaned_test_summaries"))
+        .stdout(predicate::str::contains("archived_orphaned_pss_scores"));
+
+    // Known tasks keep their artifacts; only the orphans move
+    assert!(rotd_dir.join("test_summaries/1.1.json").exists());
+    assert!(!rotd_dir.join("test_summaries/9.9.json").exists());
+    assert_eq!(
+        std::fs::read_to_string(rotd_dir.join("archive/test_summaries/9.9.json")).unwrap(),
+        summary("9.9")
+    );
+    assert_eq!(
+        std::fs::read_to_string(rotd_dir.join("pss_scores.jsonl")).unwrap(),
+        format!("{}\n{}\n", score("1.1"), score("1.1"))
+    );
+    assert_eq!(
+        std::fs::read_to_string(rotd_dir.join("archive/pss_scores.jsonl")).unwrap(),
+        format!("{}\n", score("9.9"))
+    );
+
+    rotd(&["--agent", "check"])
+        .assert()
+        .stdout(predicate::str::contains(r#""id":"orphaned_test_summaries","passed":true"#))
+        .stdout(predicate::str::contains(r#""id":"orphaned_pss_scores","passed":true"#));
+}
+
+#[test]
+fn test_check_fix_relinks_repairs_and_signs_placeholders() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args).env("ROTD_AGENT_ID", "agent-1");
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    let rotd_dir = temp_dir.path().join(".rotd");
+    std::fs::write(rotd_dir.join("config.jsonc"), r#"{ "hash_chain": true }"#).unwrap();
+    for task in [
+        r#"{"id":"1.1","title":"Parser","status":"in_progress","priority":"high"}"#,
+        r#"{"id":"1.2","title":"Lexer","status":"pending","priority":"high"}"#,
+    ] {
+        rotd(&["agent", "update-task"]).write_stdin(task).assert().success();
+    }
+    rotd(&["keys", "generate"]).assert().success();
+    rotd(&["agent", "update-task"])
+        .write_stdin(r#"{"id":"1.1","status":"complete"}"#)
+        .assert()
+        .success();
+
+    // A hand edit in the middle of the chain leaves a key and a value unquoted
+    let tasks_path = rotd_dir.join("tasks.jsonl");
+    let tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    let edited: Vec<String> = tasks
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            if value["id"] != "1.2" {
+                return line.to_string();
+            }
+            format!(
+                r#"{{id:"1.2","title":"Lexer","status":pending,"priority":high,"updated_at":{},"_prev":{},"_agent":"agent-1"}}"#,
+                value["updated_at"],
+                value["_prev"]
+            )
+        })
+        .collect();
+    std::fs::write(&tasks_path, edited.join("\n") + "\n").unwrap();
+    rotd(&["verify-integrity"]).assert().failure();
+
+    rotd(&["--agent", "check", "--fix"])
+        .assert()
+        .stdout(predicate::str::contains("fixed_jsonl_format"))
+        .stdout(predicate::str::contains("scaffolded_test_summaries"));
+
+    // The repaired line keeps its agent, and the lines after it link to it
+    let tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    let repaired = tasks.lines().find(|l| l.contains(r#""id":"1.2""#)).unwrap();
+    assert!(repaired.ends_with(r#","_agent":"agent-1"}"#));
+    rotd(&["verify-integrity"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("chain intact"));
+
+    // The placeholder is signed, so strict validation accepts it
+    let summary = std::fs::read_to_string(rotd_dir.join("test_summaries/1.1.json")).unwrap();
+    assert!(summary.contains(r#""_sig""#));
+    rotd(&["validate", "--all", "--strict"]).assert().success();
+}
+
+#[test]
+fn test_check_fix_dry_run_writes_nothing_and_backs_up() {
+    type Files = std::collections::BTreeMap<std::path::PathBuf, Vec<u8>>;
+    fn snapshot(dir: &std::path::Path, files: &mut Files) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                snapshot(&path, files);
+            } else {
+                files.insert(path.clone(), std::fs::read(&path).unwrap());
+            }
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    let rotd_dir = temp_dir.path().join(".rotd");
+    let tasks_path = rotd_dir.join("tasks.jsonl");
+    let session_path = rotd_dir.join("session_state.json");
+    let mut tasks = std::fs::read_to_string(&tasks_path).unwrap();
+    tasks.push_str("{id:\"2.1\",\"title\":\"Lexer\",\"status\":\"pending\",}\n");
+    std::fs::write(&tasks_path, &tasks).unwrap();
+    std::fs::write(&session_path, "{ not json").unwrap();
+
+    let mut before = std::collections::BTreeMap::new();
+    snapshot(&rotd_dir, &mut before);
+    rotd(&["--agent", "--dry-run", "check", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""path":".rotd/tasks.jsonl""#))
+        .stdout(predicate::str::contains(r#""path":".rotd/session_state.json""#));
+    let mut after = std::collections::BTreeMap::new();
+    snapshot(&rotd_dir, &mut after);
+    assert_eq!(before, after, "a dry run must not touch .rotd");
+
+    rotd(&["--agent", "check", "--fix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fixed_jsonl_format"))
+        .stdout(predicate::str::contains("regenerated_session_state"));
+    assert!(std::fs::read_to_string(&tasks_path)
+        .unwrap()
+        .contains(r#"{"id":"2.1","status":"pending","title":"Lexer"}"#));
+
+    // One backup directory, named by timestamp, holding every file as it was
+    let backups: Vec<_> = std::fs::read_dir(rotd_dir.join("backups"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(backups.len(), 1);
+    let name = backups[0].file_name().unwrap().to_str().unwrap();
+    let stamp = name.strip_prefix("fix-").unwrap();
+    assert!(stamp.len() == 14 && stamp.chars().all(|c| c.is_ascii_digit()), "{}", name);
+    assert_eq!(std::fs::read_to_string(backups[0].join("tasks.jsonl")).unwrap(), tasks);
+    assert_eq!(
+        std::fs::read_to_string(backups[0].join("session_state.json")).unwrap(),
+        "{ not json"
+    );
+}
+
+#[test]
+fn test_tags_filter_tasks_and_claims() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir)
+            .args(args)
+            .env("ROTD_AGENT_ID", "agent-1")
+            .env_remove("ROTD_COORD_SERVER");
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    for (id, title) in [("1.1", "Parser"), ("1.2", "Lexer"), ("1.3", "Docs")] {
+        rotd(&["agent", "update-task"])
+            .write_stdin(format!(
+                r#"{{"id":"{}","title":"{}","status":"pending","priority":"high"}}"#,
+                id, title
+            ))
+            .assert()
+            .success();
+    }
+    rotd(&["task", "tag", "1.1", "api"]).assert().success();
+    rotd(&["task", "tag", "1.2", "api", "ui"]).assert().success();
+    rotd(&["task", "tag", "1.3", "ui"]).assert().success();
+    rotd(&["--agent", "task", "tag", "1.3", "ui", "--remove"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""tags":[]"#));
+
+    rotd(&["show-task", "--tag", "api", "--tag", "ui"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Lexer"))
+        .stdout(predicate::str::contains("Parser").not())
+        .stdout(predicate::str::contains("Docs").not());
+
+    let coordination = temp_dir.path().join(".rotd/coordination");
+    std::fs::create_dir_all(&coordination).unwrap();
+    let registry_task = |id: &str, title: &str| {
+        format!(
+            r#"{{"id":"{}","title":"{}","status":"unclaimed","priority":"high",
+            "claimed_by":null,"claimed_at":null,"completed_at":null,"blocked_reason":null,
+            "reviewer_id":null,"capability":null,"skill_level":null}}"#,
+            id, title
+        )
+    };
+    std::fs::write(
+        coordination.join("active_work_registry.json"),
+        format!(
+            r#"{{"tasks":[{},{},{}]}}"#,
+            registry_task("1.1", "Parser"),
+            registry_task("1.3", "Docs"),
+            registry_task("1.2", "Lexer")
+        ),
+    )
+    .unwrap();
+
+    // Only a task carrying every requested tag is claimed
+    rotd(&["--agent", "coord", "claim", "--tag", "api", "--tag", "ui"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""id":"1.2""#));
+    rotd(&["--agent", "coord", "claim", "--tag", "ui"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no_eligible_task"));
+    rotd(&["--agent", "coord", "claim", "--tag", "api"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""id":"1.1""#));
+}
+
+#[test]
+fn test_agent_append_summary_from_stdin() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir).args(args);
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+    let summary = r#"{"task_id":"1.1","status":"passing","total_tests":4,"passed":4,"failed":0,"warnings":null,"coverage":82.5,"verified_by":"cargo test","timestamp":"2025-07-01T10:00:00Z","notes":null}"#;
+
+    rotd(&["agent", "append-summary", "--dry-run"])
+        .write_stdin(summary)
+        .assert()
+        .success();
+    let summary_path = temp_dir.path().join(".rotd/test_summaries/1.1.json");
+    assert!(!summary_path.exists());
+
+    rotd(&["agent", "append-summary"])
+        .write_stdin(summary)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""action":"append_summary","task_id":"1.1""#));
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!(written["passed"], 4);
+    assert_eq!(written["coverage"], 82.5);
+
+    rotd(&["agent", "append-summary"])
+        .write_stdin("not json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("read_failed"));
+}
+
+#[test]
+fn test_github_sync_plans_status_pushes() {
+    let temp_dir = TempDir::new().unwrap();
+    let rotd = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("rotd").unwrap();
+        cmd.current_dir(&temp_dir)
+            .args(args)
+            .env_remove("ROTD_GITHUB_TOKEN")
+            .env_remove("GITHUB_TOKEN");
+        cmd
+    };
+    rotd(&["init", "--force"]).assert().success();
+
+    rotd(&["github", "sync", "--no-pull", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No repository configured"));
+
+    for (id, status, origin) in [
+        ("1.1", "in_progress", "https://github.com/acme/app/issues/7"),
+        ("1.2", "complete", "https://github.com/acme/app/issues/8"),
+        ("1.3", "in_progress", "https://github.com/other/repo/issues/9"),
+    ] {
+        rotd(&["agent", "update-task"])
+            .write_stdin(format!(
+                r#"{{"id":"{}","title":"Task {}","status":"{}","priority":"high","origin":"{}"}}"#,
+                id, id, status, origin
+            ))
+            .assert()
+            .success();
+    }
+    let integration_path = temp_dir.path().join(".rotd/integrations/github.json");
+    std::fs::create_dir_all(integration_path.parent().unwrap()).unwrap();
+    let integration = r#"{"repo":"acme/app","label":null,"issues":{"1.2":{"number":8,
+        "url":"https://github.com/acme/app/issues/8","last_status":"complete","synced_at":null}}}"#;
+    std::fs::write(&integration_path, integration).unwrap();
+
+    // Only the changed task linked to this repository is pushed
+    let output = rotd(&["--agent", "github", "sync", "--no-pull", "--dry-run"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let result: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(result["repo"], "acme/app");
+    assert_eq!(
+        result["pushed"],
+        serde_json::json!([{"task_id": "1.1", "issue": 7, "status": "in_progress", "closed": false}])
+    );
+    assert_eq!(result["imported"], serde_json::json!([]));
+    assert_eq!(std::fs::read_to_string(&integration_path).unwrap(), integration);
+
+    rotd(&["github", "sync", "--no-pull"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("GITHUB_TOKEN is required to update issues"));
+    assert_eq!(std::fs::read_to_string(&integration_path).unwrap(), integration);
 }